@@ -49,6 +49,8 @@ module TestAccount::TableTestData {
 
         Table::add(&mut t.u8_table, 1, 1);
         Table::add(&mut t.u64_table, 1, 1);
+        Table::add(&mut t.u64_table, 2, 2);
+        Table::add(&mut t.u64_table, 3, 3);
         Table::add(&mut t.u128_table, 1, 1);
         Table::add(&mut t.bool_table, true, true);
         Table::add(&mut t.string_table, str, copy str);
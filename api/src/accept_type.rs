@@ -0,0 +1,34 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_api_types::mime_types;
+
+use warp::{http::header::ACCEPT, Filter, Rejection};
+
+/// The encoding of the response body, negotiated with the `Accept` header of the request
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AcceptType {
+    Json,
+    Bcs,
+}
+
+impl AcceptType {
+    fn parse(accept: Option<String>) -> Self {
+        let accepts_bcs = accept.map_or(false, |accept| {
+            accept
+                .split(',')
+                .filter_map(|media_range| media_range.split(';').next())
+                .any(|mime_type| mime_type.trim() == mime_types::BCS)
+        });
+        if accepts_bcs {
+            AcceptType::Bcs
+        } else {
+            AcceptType::Json
+        }
+    }
+}
+
+/// Responds with BCS only when the client accepts `application/x-bcs`, JSON otherwise
+pub(crate) fn accept_type() -> impl Filter<Extract = (AcceptType,), Error = Rejection> + Clone {
+    warp::header::optional::<String>(ACCEPT.as_str()).map(AcceptType::parse)
+}
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    accept_type::{accept_type, AcceptType},
     context::Context,
     failpoint::fail_point,
     metrics::metrics,
     param::{AddressParam, LedgerVersionParam, MoveIdentifierParam, MoveStructTagParam},
+    proofs::{self, WithProofParam},
+    quota::client_quota,
     version::Version,
 };
 
@@ -27,14 +30,16 @@ use move_deps::move_core_types::{
     move_resource::MoveStructType,
     value::MoveValue,
 };
-use std::convert::TryInto;
+use std::{collections::BTreeMap, convert::TryInto};
 use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
 
-// GET /accounts/<address>
+// GET /accounts/<address>?with_proof=<bool>
 pub fn get_account(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("accounts" / AddressParam)
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(warp::query::<WithProofParam>())
+        .and(accept_type())
         .and_then(handle_get_account)
         .with(metrics("get_account"))
         .boxed()
@@ -44,10 +49,11 @@ pub fn get_account(context: Context) -> BoxedFilter<(impl Reply,)> {
 pub fn get_account_resources(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("accounts" / AddressParam / "resources")
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
         .and(warp::query::<Version>())
         .map(|address, ctx, version: Version| (version.version, address, ctx))
         .untuple_one()
+        .and(accept_type())
         .and_then(handle_get_account_resources)
         .with(metrics("get_account_resources"))
         .boxed()
@@ -57,10 +63,11 @@ pub fn get_account_resources(context: Context) -> BoxedFilter<(impl Reply,)> {
 pub fn get_account_modules(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("accounts" / AddressParam / "modules")
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
         .and(warp::query::<Version>())
         .map(|address, ctx, version: Version| (version.version, address, ctx))
         .untuple_one()
+        .and(accept_type())
         .and_then(handle_get_account_modules)
         .with(metrics("get_account_modules"))
         .boxed()
@@ -69,27 +76,31 @@ pub fn get_account_modules(context: Context) -> BoxedFilter<(impl Reply,)> {
 async fn handle_get_account(
     address: AddressParam,
     context: Context,
+    query: WithProofParam,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_account")?;
-    Ok(Account::new(None, address, context)?.account()?)
+    Ok(Account::new(None, address, context)?.account(query.with_proof, accept_type)?)
 }
 
 async fn handle_get_account_resources(
     ledger_version: Option<LedgerVersionParam>,
     address: AddressParam,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_account_resources")?;
-    Ok(Account::new(ledger_version, address, context)?.resources()?)
+    Ok(Account::new(ledger_version, address, context)?.resources(accept_type)?)
 }
 
 async fn handle_get_account_modules(
     ledger_version: Option<LedgerVersionParam>,
     address: AddressParam,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_account_modules")?;
-    Ok(Account::new(ledger_version, address, context)?.modules()?)
+    Ok(Account::new(ledger_version, address, context)?.modules(accept_type)?)
 }
 
 pub(crate) struct Account {
@@ -126,39 +137,64 @@ impl Account {
         })
     }
 
-    pub fn account(self) -> Result<impl Reply, Error> {
+    pub fn account(self, with_proof: bool, accept_type: AcceptType) -> Result<impl Reply, Error> {
+        if with_proof && accept_type == AcceptType::Bcs {
+            return Err(proofs::with_proof_not_supported_for_bcs());
+        }
         let state_key = StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
             self.address.into(),
             AccountResource::struct_tag(),
         )));
 
-        let state_value = self
+        let bytes = self
             .context
-            .get_state_value(&state_key, self.ledger_version)?;
-
-        let account_resource: AccountResource = state_value
-            .map(|bytes| bcs::from_bytes(&bytes))
-            .transpose()
-            .map_err(anyhow::Error::from)?
+            .get_state_value(&state_key, self.ledger_version)?
             .ok_or_else(|| self.resource_not_found(&AccountResource::struct_tag()))?;
+        if accept_type == AcceptType::Bcs {
+            return Ok(Response::new_raw_bcs(self.latest_ledger_info, bytes));
+        }
+
+        let account_resource: AccountResource =
+            bcs::from_bytes(&bytes).map_err(anyhow::Error::from)?;
 
         let account: AccountData = account_resource.into();
 
+        if with_proof {
+            return proofs::with_proof(
+                &self.context,
+                self.latest_ledger_info,
+                account,
+                &state_key,
+                self.ledger_version,
+            );
+        }
         Response::new(self.latest_ledger_info, &account)
     }
 
-    pub fn resources(self) -> Result<impl Reply, Error> {
+    pub fn resources(self, accept_type: AcceptType) -> Result<impl Reply, Error> {
+        let account_state = self.account_state()?;
+        if accept_type == AcceptType::Bcs {
+            let resources: BTreeMap<StructTag, Vec<u8>> = account_state
+                .get_resources()
+                .map(|(struct_tag, bytes)| (struct_tag, bytes.to_vec()))
+                .collect();
+            return Response::new_bcs(self.latest_ledger_info, &resources);
+        }
         let resources = self
             .context
             .move_resolver()?
             .as_converter()
-            .try_into_resources(self.account_state()?.get_resources())?;
+            .try_into_resources(account_state.get_resources())?;
         Response::new(self.latest_ledger_info, &resources)
     }
 
-    pub fn modules(self) -> Result<impl Reply, Error> {
-        let modules = self
-            .account_state()?
+    pub fn modules(self, accept_type: AcceptType) -> Result<impl Reply, Error> {
+        let account_state = self.account_state()?;
+        if accept_type == AcceptType::Bcs {
+            let modules: Vec<Vec<u8>> = account_state.into_modules().collect();
+            return Response::new_bcs(self.latest_ledger_info, &modules);
+        }
+        let modules = account_state
             .into_modules()
             .map(MoveModuleBytecode::new)
             .map(|m| m.try_parse_abi())
@@ -1,6 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::quota::{ClientPermit, ClientQuotas};
 use aptos_api_types::{Error, LedgerInfo, TransactionOnChainData};
 use aptos_config::config::ApiConfig;
 use aptos_crypto::HashValue;
@@ -12,14 +13,17 @@ use aptos_types::{
     contract_event::ContractEvent,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
+    state_proof::StateProof,
     transaction::{SignedTransaction, TransactionWithProof},
 };
 use storage_interface::{DbReader, Order};
 
-use anyhow::{ensure, format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use aptos_state_view::StateView;
 use aptos_types::{
-    state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix},
+    state_store::{
+        state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValueWithProof,
+    },
     transaction::Version,
 };
 use aptos_vm::data_cache::{IntoMoveResolver, RemoteStorageOwned};
@@ -37,6 +41,9 @@ pub struct Context {
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     api_config: ApiConfig,
+    client_quotas: Arc<ClientQuotas>,
+    // The quota permit of the request served with this context, if any
+    permit: Option<Arc<ClientPermit>>,
 }
 
 impl Context {
@@ -45,13 +52,16 @@ impl Context {
         db: Arc<dyn DbReader>,
         mp_sender: MempoolClientSender,
         api_config: ApiConfig,
-    ) -> Self {
-        Self {
+    ) -> Result<Self> {
+        let client_quotas = Arc::new(ClientQuotas::new(api_config.auth.as_ref())?);
+        Ok(Self {
             chain_id,
             db,
             mp_sender,
             api_config,
-        }
+            client_quotas,
+            permit: None,
+        })
     }
 
     pub fn move_resolver(&self) -> Result<RemoteStorageOwned<DbStateView>> {
@@ -72,6 +82,24 @@ impl Context {
         self.api_config.content_length_limit()
     }
 
+    pub fn max_view_gas_amount(&self) -> u64 {
+        self.api_config.max_view_gas_amount()
+    }
+
+    pub fn max_submit_transaction_batch_size(&self) -> usize {
+        self.api_config.max_submit_transaction_batch_size()
+    }
+
+    pub(crate) fn client_quotas(&self) -> &ClientQuotas {
+        &self.client_quotas
+    }
+
+    /// Holds `permit` until this context and its clones are dropped, i.e. the request is served
+    pub(crate) fn with_permit(mut self, permit: ClientPermit) -> Self {
+        self.permit = Some(Arc::new(permit));
+        self
+    }
+
     pub fn filter(self) -> impl Filter<Extract = (Context,), Error = Infallible> + Clone {
         warp::any().map(move || self.clone())
     }
@@ -86,6 +114,22 @@ impl Context {
         callback.await?
     }
 
+    /// Submits `txns` to mempool in one request, returning their statuses in the same order
+    pub async fn submit_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Result<Vec<SubmissionStatus>> {
+        let (req_sender, callback) = oneshot::channel();
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::SubmitTransactionBatch(
+                txns, req_sender,
+            ))
+            .await?;
+
+        callback.await?
+    }
+
     pub fn get_latest_ledger_info(&self) -> Result<LedgerInfo, Error> {
         Ok(LedgerInfo::new(
             &self.chain_id(),
@@ -103,6 +147,46 @@ impl Context {
             .get_state_value(state_key)
     }
 
+    /// Returns the state value at `version`, with the proofs relative to the ledger info at
+    /// `ledger_version`. Returns `None` if `version` isn't a state checkpoint, as the state is only
+    /// authenticated at checkpoints.
+    pub fn get_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        version: u64,
+        ledger_version: u64,
+    ) -> Result<Option<StateValueWithProof>> {
+        let transaction_info_with_proof = self
+            .db
+            .get_transaction_by_version(version, ledger_version, false)?
+            .proof;
+        if transaction_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let (state_value, proof) = self
+            .db
+            .get_state_value_with_proof_by_version(state_key, version)?;
+        Ok(Some(StateValueWithProof::new(
+            version,
+            state_value,
+            proof,
+            transaction_info_with_proof,
+        )))
+    }
+
+    pub fn get_state_proof(
+        &self,
+        known_version: u64,
+        ledger_info: LedgerInfoWithSignatures,
+    ) -> Result<StateProof> {
+        self.db
+            .get_state_proof_with_ledger_info(known_version, ledger_info)
+    }
+
     pub fn get_account_state(
         &self,
         address: AccountAddress,
@@ -115,6 +199,45 @@ impl Context {
         )
     }
 
+    /// Returns up to `limit` items of the table `handle` at `version`, as their raw key and value
+    /// in the order of the keys, starting from the raw key `start` when set. Also returns the raw
+    /// key of the first item of the next page, unless this page is the last one. As the number of
+    /// keys visited per page is bounded, a page may hold fewer than `limit` items.
+    pub fn get_table_items(
+        &self,
+        handle: u128,
+        start: Option<Vec<u8>>,
+        limit: usize,
+        version: u64,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>)> {
+        let start_key = start.map(|key| StateKey::table_item(handle, key));
+        let (values, next_key) = self.db.get_state_values_by_key_prefix_paginated(
+            &StateKeyPrefix::table_items(handle),
+            start_key.as_ref(),
+            limit,
+            version,
+        )?;
+        let items = values
+            .into_iter()
+            .map(
+                |(state_key, state_value)| match (state_key, state_value.maybe_bytes) {
+                    (StateKey::TableItem { key, .. }, Some(bytes)) => Ok((key, bytes)),
+                    (state_key, _) => Err(format_err!(
+                        "unexpected state value of {:?} in table {}",
+                        state_key,
+                        handle
+                    )),
+                },
+            )
+            .collect::<Result<_>>()?;
+        let next = match next_key {
+            Some(StateKey::TableItem { key, .. }) => Some(key),
+            Some(state_key) => bail!("unexpected next key {:?} of table {}", state_key, handle),
+            None => None,
+        };
+        Ok((items, next))
+    }
+
     pub fn get_block_timestamp(&self, version: u64) -> Result<u64> {
         self.db.get_block_timestamp(version)
     }
@@ -220,6 +343,15 @@ impl Context {
         )?)
     }
 
+    pub fn get_transaction_with_proof(
+        &self,
+        version: u64,
+        ledger_version: u64,
+    ) -> Result<TransactionWithProof> {
+        self.db
+            .get_transaction_by_version(version, ledger_version, true)
+    }
+
     pub fn get_accumulator_root_hash(&self, version: u64) -> Result<HashValue> {
         self.db.get_accumulator_root_hash(version)
     }
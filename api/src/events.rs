@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    accept_type::{accept_type, AcceptType},
     accounts::Account,
     context::Context,
     failpoint::fail_point,
     metrics::metrics,
     page::Page,
     param::{AddressParam, EventKeyParam, MoveIdentifierParam, MoveStructTagParam},
+    quota::client_quota,
 };
 
 use aptos_api_types::{AsConverter, Error, LedgerInfo, Response};
@@ -21,7 +23,8 @@ pub fn get_events_by_event_key(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("events" / EventKeyParam)
         .and(warp::get())
         .and(warp::query::<Page>())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_get_events_by_event_key)
         .with(metrics("get_events_by_event_key"))
         .boxed()
@@ -32,7 +35,8 @@ pub fn get_events_by_event_handle(context: Context) -> BoxedFilter<(impl Reply,)
     warp::path!("accounts" / AddressParam / "events" / MoveStructTagParam / MoveIdentifierParam)
         .and(warp::get())
         .and(warp::query::<Page>())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_get_events_by_event_handle)
         .with(metrics("get_events_by_event_handle"))
         .boxed()
@@ -42,9 +46,10 @@ async fn handle_get_events_by_event_key(
     event_key: EventKeyParam,
     page: Page,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_events_by_event_key")?;
-    Ok(Events::new(event_key.parse("event key")?.into(), context)?.list(page)?)
+    Ok(Events::new(event_key.parse("event key")?.into(), context)?.list(page, accept_type)?)
 }
 
 async fn handle_get_events_by_event_handle(
@@ -53,11 +58,12 @@ async fn handle_get_events_by_event_handle(
     field_name: MoveIdentifierParam,
     page: Page,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_events_by_event_handle")?;
     let key =
         Account::new(None, address, context.clone())?.find_event_key(struct_tag, field_name)?;
-    Ok(Events::new(key, context)?.list(page)?)
+    Ok(Events::new(key, context)?.list(page, accept_type)?)
 }
 
 struct Events {
@@ -76,13 +82,16 @@ impl Events {
         })
     }
 
-    pub fn list(self, page: Page) -> Result<impl Reply, Error> {
+    pub fn list(self, page: Page, accept_type: AcceptType) -> Result<impl Reply, Error> {
         let contract_events = self.context.get_events(
             &self.key,
             page.start(0, u64::MAX)?,
             page.limit()?,
             self.ledger_info.version(),
         )?;
+        if accept_type == AcceptType::Bcs {
+            return Response::new_bcs(self.ledger_info, &contract_events);
+        }
 
         let resolver = self.context.move_resolver()?;
         let events = resolver.as_converter().try_into_events(&contract_events)?;
@@ -2,22 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    accept_type::{accept_type, AcceptType},
     accounts,
     context::Context,
     events,
     failpoint::fail_point,
     log,
     metrics::{metrics, status_metrics},
-    state, transactions,
+    proofs,
+    quota::{client_quota, QuotaExceeded, X_API_KEY},
+    state, transactions, view,
 };
-use aptos_api_types::{Error, Response};
+use aptos_api_types::{Error, LedgerInfo, Response};
 
 use std::convert::Infallible;
 use warp::{
     body::BodyDeserializeError,
     cors::CorsForbidden,
     filters::BoxedFilter,
-    http::{header, HeaderValue, StatusCode},
+    http::{header, HeaderName, HeaderValue, StatusCode},
     reject::{LengthRequired, MethodNotAllowed, PayloadTooLarge, UnsupportedMediaType},
     reply, Filter, Rejection, Reply,
 };
@@ -36,18 +39,28 @@ pub fn routes(context: Context) -> impl Filter<Extract = impl Reply, Error = Inf
         .or(transactions::get_account_transactions(context.clone()))
         .or(transactions::submit_bcs_transactions(context.clone()))
         .or(transactions::submit_json_transactions(context.clone()))
+        .or(transactions::submit_bcs_transaction_batch(context.clone()))
+        .or(transactions::submit_json_transaction_batch(context.clone()))
         .or(transactions::create_signing_message(context.clone()))
         .or(events::get_events_by_event_key(context.clone()))
         .or(events::get_events_by_event_handle(context.clone()))
         .or(state::get_account_resource(context.clone()))
         .or(state::get_account_module(context.clone()))
         .or(state::get_table_item(context.clone()))
+        .or(state::get_table_items(context.clone()))
+        .or(view::view_function(context.clone()))
+        .or(proofs::get_state_proof(context.clone()))
+        .or(proofs::get_state_value_with_proof(context.clone()))
+        .or(proofs::get_transaction_with_proof(context.clone()))
         .or(context.health_check_route().with(metrics("health_check")))
         .with(
             warp::cors()
                 .allow_any_origin()
                 .allow_methods(vec!["POST", "GET"])
-                .allow_headers(vec![header::CONTENT_TYPE]),
+                .allow_headers(vec![
+                    header::CONTENT_TYPE,
+                    HeaderName::from_static(X_API_KEY),
+                ]),
         )
         .recover(handle_rejection)
         .with(log::logger())
@@ -74,21 +87,40 @@ pub fn openapi_spec() -> BoxedFilter<(impl Reply,)> {
 pub fn index(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path::end()
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_index)
         .with(metrics("get_ledger_info"))
         .boxed()
 }
 
-pub async fn handle_index(context: Context) -> Result<impl Reply, Rejection> {
+pub(crate) async fn handle_index(
+    context: Context,
+    accept_type: AcceptType,
+) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_index")?;
-    let info = context.get_latest_ledger_info()?;
-    Ok(Response::new(info.clone(), &info)?)
+    Ok(ledger_info(context, accept_type)?)
+}
+
+/// The latest ledger info, as the signed `LedgerInfoWithSignatures` when BCS is accepted
+fn ledger_info(context: Context, accept_type: AcceptType) -> Result<Response, Error> {
+    match accept_type {
+        AcceptType::Json => {
+            let info = context.get_latest_ledger_info()?;
+            Response::new(info.clone(), &info)
+        }
+        AcceptType::Bcs => {
+            let ledger_info_with_sigs = context.get_latest_ledger_info_with_signatures()?;
+            let info = LedgerInfo::new(&context.chain_id(), &ledger_info_with_sigs);
+            Response::new_bcs(info, &ledger_info_with_sigs)
+        }
+    }
 }
 
 async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
     let code;
     let body;
+    let mut retry_after = None;
 
     if err.is_not_found() {
         code = StatusCode::NOT_FOUND;
@@ -111,6 +143,10 @@ async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
     } else if let Some(cause) = err.find::<UnsupportedMediaType>() {
         code = StatusCode::UNSUPPORTED_MEDIA_TYPE;
         body = reply::json(&Error::new(code, cause.to_string()));
+    } else if let Some(cause) = err.find::<QuotaExceeded>() {
+        code = StatusCode::TOO_MANY_REQUESTS;
+        body = reply::json(&Error::new(code, cause.to_string()));
+        retry_after = Some(cause.retry_after_secs());
     } else if let Some(cause) = err.find::<MethodNotAllowed>() {
         code = StatusCode::METHOD_NOT_ALLOWED;
         body = reply::json(&Error::new(code, cause.to_string()));
@@ -121,6 +157,10 @@ async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
     let mut rep = reply::with_status(body, code).into_response();
     rep.headers_mut()
         .insert("access-control-allow-origin", HeaderValue::from_static("*"));
+    if let Some(secs) = retry_after {
+        rep.headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
     Ok(rep)
 }
 
@@ -1,6 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod accept_type;
 mod accounts;
 pub mod context;
 mod events;
@@ -10,10 +11,13 @@ pub(crate) mod log;
 mod metrics;
 mod page;
 pub mod param;
+mod proofs;
+mod quota;
 pub mod runtime;
 mod state;
 mod transactions;
 pub(crate) mod version;
+mod view;
 
 mod failpoint;
 #[cfg(any(test))]
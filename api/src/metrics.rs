@@ -1,7 +1,9 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_metrics_core::{register_histogram_vec, HistogramVec};
+use aptos_metrics_core::{
+    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec,
+};
 
 use once_cell::sync::Lazy;
 use warp::log::{custom, Info, Log};
@@ -24,6 +26,15 @@ static RESPONSE_STATUS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+static CLIENT_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_client_requests",
+        "API requests grouped by client (API key name or anonymous) and quota result",
+        &["client", "result"]
+    )
+    .unwrap()
+});
+
 // Record metrics by method, operation_id and status.
 // The operation_id is the id for the request handler.
 // Should use same `operationId` defined in `openapi.yaml` whenever possible.
@@ -51,3 +62,9 @@ pub fn status_metrics() -> Log<impl Fn(Info) + Copy> {
     };
     custom(func)
 }
+
+// Count the requests of a client by quota result: accepted, rate_limited, concurrency_limited or
+// unauthorized.
+pub fn client_requests(client: &str, result: &str) {
+    CLIENT_REQUESTS.with_label_values(&[client, result]).inc();
+}
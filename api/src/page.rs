@@ -36,18 +36,21 @@ impl Page {
     }
 
     pub fn limit(&self) -> Result<u16, Error> {
-        let limit = self
-            .limit
-            .clone()
-            .map(|v| v.parse("limit"))
-            .unwrap_or_else(|| Ok(NonZeroU16::new(DEFAULT_PAGE_SIZE).unwrap()))?
-            .get();
-        if limit > MAX_PAGE_SIZE {
-            return Err(Error::invalid_param(
-                "limit",
-                format!("{}, exceed limit {}", limit, MAX_PAGE_SIZE),
-            ));
-        }
-        Ok(limit)
+        parse_limit(self.limit.clone())
+    }
+}
+
+/// Parses the `limit` query param of a paginated endpoint, `DEFAULT_PAGE_SIZE` when unset
+pub(crate) fn parse_limit(limit: Option<Param<NonZeroU16>>) -> Result<u16, Error> {
+    let limit = limit
+        .map(|v| v.parse("limit"))
+        .unwrap_or_else(|| Ok(NonZeroU16::new(DEFAULT_PAGE_SIZE).unwrap()))?
+        .get();
+    if limit > MAX_PAGE_SIZE {
+        return Err(Error::invalid_param(
+            "limit",
+            format!("{}, exceed limit {}", limit, MAX_PAGE_SIZE),
+        ));
     }
+    Ok(limit)
 }
@@ -0,0 +1,223 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Endpoints returning on-chain data along with the proofs needed to verify it, for clients which
+//! don't trust the node they query. Their responses are BCS encoded; the JSON endpoints reading a
+//! single state value embed the same proofs in their response with `with_proof=true`.
+
+use crate::{
+    context::Context,
+    failpoint::fail_point,
+    metrics::metrics,
+    param::{LedgerVersionParam, TransactionVersionParam},
+    quota::client_quota,
+};
+use aptos_api_types::{Error, LedgerInfo, Response, StateValueProof, TransactionId, WithProof};
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, state_store::state_key::StateKey};
+use serde::{Deserialize, Serialize};
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+/// Query parameter of the JSON endpoints reading a single state value, which return the proof of
+/// the value along with it when `with_proof=true`
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct WithProofParam {
+    #[serde(default)]
+    pub(crate) with_proof: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct KnownVersion {
+    known_version: Option<LedgerVersionParam>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct LedgerVersion {
+    ledger_version: Option<LedgerVersionParam>,
+}
+
+// GET /state_proof?known_version={u64}
+pub fn get_state_proof(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("state_proof")
+        .and(warp::get())
+        .and(warp::query::<KnownVersion>())
+        .and(client_quota(context))
+        .and_then(handle_get_state_proof)
+        .with(metrics("get_state_proof"))
+        .boxed()
+}
+
+// GET /state_values/{hex encoded BCS state key}/proof?ledger_version={u64}
+pub fn get_state_value_with_proof(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("state_values" / String / "proof")
+        .and(warp::get())
+        .and(warp::query::<LedgerVersion>())
+        .and(client_quota(context))
+        .and_then(handle_get_state_value_with_proof)
+        .with(metrics("get_state_value_with_proof"))
+        .boxed()
+}
+
+// GET /transactions/{version}/proof?ledger_version={u64}
+pub fn get_transaction_with_proof(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("transactions" / TransactionVersionParam / "proof")
+        .and(warp::get())
+        .and(warp::query::<LedgerVersion>())
+        .and(client_quota(context))
+        .and_then(handle_get_transaction_with_proof)
+        .with(metrics("get_transaction_with_proof"))
+        .boxed()
+}
+
+async fn handle_get_state_proof(
+    query: KnownVersion,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_get_state_proof")?;
+    Ok(Proofs::new(context)?.state_proof(query.known_version)?)
+}
+
+async fn handle_get_state_value_with_proof(
+    state_key: String,
+    query: LedgerVersion,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_get_state_value_with_proof")?;
+    Ok(Proofs::new(context)?.state_value(parse_state_key(&state_key)?, query.ledger_version)?)
+}
+
+async fn handle_get_transaction_with_proof(
+    version: TransactionVersionParam,
+    query: LedgerVersion,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_get_transaction_with_proof")?;
+    Ok(Proofs::new(context)?
+        .transaction(version.parse("transaction version")?, query.ledger_version)?)
+}
+
+/// Returns `data` read from `state_key` at `version`, along with the proof of the state value
+/// relative to the latest ledger info
+pub(crate) fn with_proof<T: Serialize>(
+    context: &Context,
+    ledger_info: LedgerInfo,
+    data: T,
+    state_key: &StateKey,
+    version: u64,
+) -> Result<Response, Error> {
+    let ledger_info_with_sigs = context.get_latest_ledger_info_with_signatures()?;
+    let state_value_with_proof = context
+        .get_state_value_with_proof(
+            state_key,
+            version,
+            ledger_info_with_sigs.ledger_info().version(),
+        )?
+        .ok_or_else(|| not_a_state_checkpoint(version))?;
+    let proof = StateValueProof::new(state_key, &state_value_with_proof, &ledger_info_with_sigs)?;
+    Response::new(ledger_info, &WithProof { data, proof })
+}
+
+/// `with_proof=true` embeds the proof in a JSON response, BCS clients get raw values with proofs
+/// from `/state_values/{state_key}/proof` instead
+pub(crate) fn with_proof_not_supported_for_bcs() -> Error {
+    Error::bad_request(
+        "with_proof is not supported for BCS responses, use /state_values/{state_key}/proof instead",
+    )
+}
+
+fn not_a_state_checkpoint(version: u64) -> Error {
+    Error::bad_request(format!(
+        "version {} is not a state checkpoint, proofs are only available at state checkpoints",
+        version
+    ))
+}
+
+/// Parses a state key given as its hex encoded BCS serialization
+fn parse_state_key(state_key: &str) -> Result<StateKey, Error> {
+    hex::decode(state_key.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bcs::from_bytes(&bytes).ok())
+        .ok_or_else(|| Error::invalid_param("state key", state_key))
+}
+
+struct Proofs {
+    ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ledger_info: LedgerInfo,
+    context: Context,
+}
+
+impl Proofs {
+    fn new(context: Context) -> Result<Self, Error> {
+        let ledger_info_with_sigs = context.get_latest_ledger_info_with_signatures()?;
+        let ledger_info = LedgerInfo::new(&context.chain_id(), &ledger_info_with_sigs);
+        Ok(Self {
+            ledger_info_with_sigs,
+            ledger_info,
+            context,
+        })
+    }
+
+    /// Returns the latest ledger info with signatures, and the epoch change proof from the epoch
+    /// of `known_version` (genesis by default)
+    pub fn state_proof(self, known_version: Option<LedgerVersionParam>) -> Result<Response, Error> {
+        let known_version = self.version(known_version, "known version", 0)?;
+        let state_proof = self
+            .context
+            .get_state_proof(known_version, self.ledger_info_with_sigs)?;
+        Response::new_bcs(self.ledger_info, &state_proof)
+    }
+
+    /// Returns the state value at `ledger_version` (the latest by default), with the proofs
+    /// relative to the ledger info at that version
+    pub fn state_value(
+        self,
+        state_key: StateKey,
+        ledger_version: Option<LedgerVersionParam>,
+    ) -> Result<Response, Error> {
+        let ledger_version =
+            self.version(ledger_version, "ledger version", self.ledger_info.version())?;
+        let state_value_with_proof = self
+            .context
+            .get_state_value_with_proof(&state_key, ledger_version, ledger_version)?
+            .ok_or_else(|| not_a_state_checkpoint(ledger_version))?;
+        Response::new_bcs(self.ledger_info, &state_value_with_proof)
+    }
+
+    /// Returns the transaction at `version` and its events, with the proof relative to the ledger
+    /// info at `ledger_version` (the latest by default)
+    pub fn transaction(
+        self,
+        version: u64,
+        ledger_version: Option<LedgerVersionParam>,
+    ) -> Result<Response, Error> {
+        let ledger_version =
+            self.version(ledger_version, "ledger version", self.ledger_info.version())?;
+        if version > ledger_version {
+            return Err(Error::not_found(
+                "transaction",
+                TransactionId::Version(version),
+                ledger_version,
+            ));
+        }
+        let transaction_with_proof = self
+            .context
+            .get_transaction_with_proof(version, ledger_version)?;
+        Response::new_bcs(self.ledger_info, &transaction_with_proof)
+    }
+
+    fn version(
+        &self,
+        version: Option<LedgerVersionParam>,
+        name: &str,
+        default: u64,
+    ) -> Result<u64, Error> {
+        let version = version.map(|v| v.parse(name)).unwrap_or(Ok(default))?;
+        if version > self.ledger_info.version() {
+            return Err(Error::not_found(
+                "ledger",
+                TransactionId::Version(version),
+                self.ledger_info.version(),
+            ));
+        }
+        Ok(version)
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{context::Context, metrics::client_requests};
+use aptos_api_types::Error;
+use aptos_config::config::{ApiAuthConfig, ApiQuota};
+use aptos_infallible::Mutex;
+use aptos_rate_limiter::rate_limit::{Bucket, SharedBucket};
+
+use anyhow::{ensure, Result};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use warp::{
+    http::StatusCode,
+    reject::{self, Reject},
+    Filter, Rejection,
+};
+
+pub const X_API_KEY: &str = "x-api-key";
+
+const ANONYMOUS: &str = "anonymous";
+
+/// Request quotas of the API clients, identified by the API key of their requests.
+/// Requests are not limited when the API config has no `auth` section.
+pub(crate) struct ClientQuotas {
+    anonymous: Option<Client>,
+    api_keys: HashMap<String, Client>,
+    enabled: bool,
+}
+
+impl ClientQuotas {
+    pub fn new(config: Option<&ApiAuthConfig>) -> Result<Self> {
+        let config = match config {
+            Some(config) => config,
+            None => {
+                return Ok(Self {
+                    anonymous: None,
+                    api_keys: HashMap::new(),
+                    enabled: false,
+                })
+            }
+        };
+
+        let mut api_keys = HashMap::new();
+        for api_key in config.api_keys.load()? {
+            ensure!(!api_key.key.is_empty(), "API key {} is empty", api_key.name);
+            ensure!(
+                !api_keys.contains_key(&api_key.key),
+                "API key {} is defined more than once",
+                api_key.name
+            );
+            let client = Client::new(api_key.name, api_key.quota.unwrap_or(config.default_quota))?;
+            api_keys.insert(api_key.key, client);
+        }
+        let anonymous = if config.require_api_key {
+            None
+        } else {
+            Some(Client::new(ANONYMOUS.to_owned(), config.anonymous_quota)?)
+        };
+        Ok(Self {
+            anonymous,
+            api_keys,
+            enabled: true,
+        })
+    }
+
+    /// Admits a request of the client owning `api_key`, or of an anonymous one without key.
+    /// The returned permit counts in the concurrent requests of the client until it's dropped.
+    fn acquire(&self, api_key: Option<&str>) -> Result<ClientPermit, Rejection> {
+        if !self.enabled {
+            return Ok(ClientPermit(None));
+        }
+        let client = match api_key {
+            Some(api_key) => self.api_keys.get(api_key).ok_or_else(|| {
+                client_requests("unknown", "unauthorized");
+                unauthorized("invalid API key")
+            })?,
+            None => self.anonymous.as_ref().ok_or_else(|| {
+                client_requests(ANONYMOUS, "unauthorized");
+                unauthorized(&format!("the {} header is required", X_API_KEY))
+            })?,
+        };
+        client.acquire()
+    }
+}
+
+/// Extracts the context of an API request, or rejects the request when the client it's from has
+/// no quota left. API routes use it in place of `Context::filter` once the path and method of the
+/// request matched, so that requests to other routes, such as the health check, and unmatched
+/// requests don't count in the quota. The permit is held by the context until the request is
+/// served.
+pub(crate) fn client_quota(
+    context: Context,
+) -> impl Filter<Extract = (Context,), Error = Rejection> + Clone {
+    warp::header::optional::<String>(X_API_KEY)
+        .and(context.filter())
+        .and_then(|api_key: Option<String>, context: Context| async move {
+            let permit = context.client_quotas().acquire(api_key.as_deref())?;
+            Ok::<_, Rejection>(context.with_permit(permit))
+        })
+}
+
+/// Held by a request for as long as it's served
+pub(crate) struct ClientPermit(Option<OwnedSemaphorePermit>);
+
+struct Client {
+    name: String,
+    requests: SharedBucket,
+    concurrent_requests: Arc<Semaphore>,
+}
+
+impl Client {
+    fn new(name: String, quota: ApiQuota) -> Result<Self> {
+        ensure!(
+            quota.requests_per_second > 0 && quota.max_concurrent_requests > 0,
+            "quota of API client {} must allow at least one request, got {:?}",
+            name,
+            quota
+        );
+        let requests = Bucket::new(
+            "api".to_owned(),
+            name.clone(),
+            name.clone(),
+            quota.requests_per_second,
+            quota.requests_per_second,
+            quota.requests_per_second,
+            None,
+        );
+        Ok(Self {
+            name,
+            requests: Arc::new(Mutex::new(requests)),
+            concurrent_requests: Arc::new(Semaphore::new(quota.max_concurrent_requests)),
+        })
+    }
+
+    fn acquire(&self) -> Result<ClientPermit, Rejection> {
+        // Check the concurrent requests first, so that no token is spent on a rejected request
+        let permit = self
+            .concurrent_requests
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| {
+                client_requests(&self.name, "concurrency_limited");
+                reject::custom(QuotaExceeded::new(
+                    "too many concurrent requests",
+                    Duration::from_secs(1),
+                ))
+            })?;
+        self.requests
+            .lock()
+            .acquire_all_tokens(1)
+            .map_err(|refill_time| {
+                client_requests(&self.name, "rate_limited");
+                let retry_after = refill_time.map_or(Duration::from_secs(1), |time| {
+                    time.saturating_duration_since(Instant::now())
+                });
+                reject::custom(QuotaExceeded::new("too many requests", retry_after))
+            })?;
+        client_requests(&self.name, "accepted");
+        Ok(ClientPermit(Some(permit)))
+    }
+}
+
+fn unauthorized(message: &str) -> Rejection {
+    reject::custom(Error::new(StatusCode::UNAUTHORIZED, message.to_owned()))
+}
+
+/// Rejection of a request over the quota of its client, answered with `429 Too Many Requests`
+#[derive(Debug)]
+pub(crate) struct QuotaExceeded {
+    message: &'static str,
+    retry_after: Duration,
+}
+
+impl QuotaExceeded {
+    fn new(message: &'static str, retry_after: Duration) -> Self {
+        Self {
+            message,
+            retry_after,
+        }
+    }
+
+    /// The number of seconds to wait before retrying, for the `Retry-After` header
+    pub fn retry_after_secs(&self) -> u64 {
+        // `Retry-After` is in whole seconds, round up so that the retry isn't rejected again
+        let secs = self.retry_after.as_secs();
+        if self.retry_after.subsec_nanos() > 0 || secs == 0 {
+            secs + 1
+        } else {
+            secs
+        }
+    }
+}
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, retry after {} seconds",
+            self.message,
+            self.retry_after_secs()
+        )
+    }
+}
+
+impl Reject for QuotaExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_quota_exceeded(result: Result<ClientPermit, Rejection>) -> bool {
+        matches!(result, Err(rejection) if rejection.find::<QuotaExceeded>().is_some())
+    }
+
+    #[test]
+    fn test_concurrent_requests_quota() {
+        let client = Client::new(
+            "test".to_owned(),
+            ApiQuota {
+                requests_per_second: 2,
+                max_concurrent_requests: 1,
+            },
+        )
+        .unwrap();
+
+        let permit = client.acquire().unwrap();
+        assert!(is_quota_exceeded(client.acquire()));
+
+        // The rejected request spent no token, so a request fits once the first one is served
+        drop(permit);
+        let permit = client.acquire().unwrap();
+        drop(permit);
+        assert!(is_quota_exceeded(client.acquire()));
+    }
+
+    #[test]
+    fn test_disabled_quotas() {
+        let quotas = ClientQuotas::new(None).unwrap();
+        let permits = (0..100)
+            .map(|_| quotas.acquire(Some("unknown")))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(permits.iter().all(|permit| permit.0.is_none()));
+    }
+}
@@ -31,9 +31,9 @@ pub fn bootstrap(
 
     let api_config = config.api.clone();
     let api = WebServer::from(api_config.clone());
+    let context = Context::new(chain_id, db, mp_sender, api_config)?;
 
     runtime.spawn(async move {
-        let context = Context::new(chain_id, db, mp_sender, api_config);
         let routes = index::routes(context);
         api.serve(routes).await;
     });
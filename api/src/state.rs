@@ -2,53 +2,69 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    accept_type::{accept_type, AcceptType},
     context::Context,
     failpoint::fail_point,
     metrics::metrics,
+    page::parse_limit,
     param::{
-        AddressParam, LedgerVersionParam, MoveIdentifierParam, MoveStructTagParam, TableHandleParam,
+        AddressParam, HexEncodedBytesParam, LedgerVersionParam, MoveIdentifierParam,
+        MoveStructTagParam, Param, TableHandleParam,
     },
+    proofs::{self, WithProofParam},
+    quota::client_quota,
     version::Version,
 };
 use anyhow::anyhow;
 use aptos_api_types::{
-    AsConverter, Error, LedgerInfo, MoveModuleBytecode, Response, TableItemRequest, TransactionId,
+    AsConverter, Error, HexEncodedBytes, LedgerInfo, MoveModuleBytecode, Response, TableItem,
+    TableItemRequest, TransactionId,
 };
 use aptos_state_view::StateView;
-use aptos_types::{access_path::AccessPath, state_store::state_key::StateKey};
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::{TableInfo, TableInfosResource},
+    state_store::state_key::StateKey,
+};
 use aptos_vm::data_cache::AsMoveResolver;
 use move_deps::move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
-    language_storage::{ModuleId, ResourceKey, StructTag},
+    language_storage::{ModuleId, ResourceKey, StructTag, CORE_CODE_ADDRESS},
+    move_resource::MoveResource,
 };
-use std::convert::TryInto;
+use serde::{Deserialize, Serialize};
+use std::{convert::TryInto, num::NonZeroU16};
 use storage_interface::state_view::DbStateView;
 use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
 
-// GET /accounts/<address>/resource/<resource_type>
+// GET /accounts/<address>/resource/<resource_type>?with_proof=<bool>
 pub fn get_account_resource(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("accounts" / AddressParam / "resource" / MoveStructTagParam)
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
         .and(warp::query::<Version>())
         .map(|address, struct_tag, ctx, version: Version| {
             (version.version, address, struct_tag, ctx)
         })
         .untuple_one()
+        .and(warp::query::<WithProofParam>())
+        .and(accept_type())
         .and_then(handle_get_account_resource)
         .with(metrics("get_account_resource"))
         .boxed()
 }
 
-// GET /state/module/<address>/<module_name>
+// GET /state/module/<address>/<module_name>?with_proof=<bool>
 pub fn get_account_module(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("accounts" / AddressParam / "module" / MoveIdentifierParam)
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
         .and(warp::query::<Version>())
         .map(|address, name, ctx, version: Version| (version.version, address, name, ctx))
         .untuple_one()
+        .and(warp::query::<WithProofParam>())
+        .and(accept_type())
         .and_then(handle_get_account_module)
         .with(metrics("get_account_module"))
         .boxed()
@@ -62,7 +78,7 @@ pub fn get_table_item(context: Context) -> BoxedFilter<(impl Reply,)> {
             context.content_length_limit(),
         ))
         .and(warp::body::json::<TableItemRequest>())
-        .and(context.filter())
+        .and(client_quota(context))
         .and(warp::query::<Version>())
         .map(|handle, body, ctx, version: Version| (version.version, handle, body, ctx))
         .untuple_one()
@@ -71,11 +87,34 @@ pub fn get_table_item(context: Context) -> BoxedFilter<(impl Reply,)> {
         .boxed()
 }
 
+// GET /tables/<table_handle>/items?start=<raw key>&limit=<limit>
+pub fn get_table_items(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("tables" / TableHandleParam / "items")
+        .and(warp::get())
+        .and(client_quota(context))
+        .and(warp::query::<Version>())
+        .map(|handle, ctx, version: Version| (version.version, handle, ctx))
+        .untuple_one()
+        .and(warp::query::<TableItemsQuery>())
+        .and_then(handle_get_table_items)
+        .with(metrics("get_table_items"))
+        .boxed()
+}
+
+/// The page of table items to list
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct TableItemsQuery {
+    start: Option<HexEncodedBytesParam>,
+    limit: Option<Param<NonZeroU16>>,
+}
+
 async fn handle_get_account_resource(
     ledger_version: Option<LedgerVersionParam>,
     address: AddressParam,
     struct_tag: MoveStructTagParam,
     context: Context,
+    query: WithProofParam,
+    accept_type: AcceptType,
 ) -> anyhow::Result<impl Reply, Rejection> {
     fail_point("endpoint_query_resource")?;
     let struct_tag = struct_tag.parse("struct tag")?;
@@ -85,6 +124,8 @@ async fn handle_get_account_resource(
             .clone()
             .try_into()
             .map_err(|_| Error::invalid_param("resource_type", struct_tag))?,
+        query.with_proof,
+        accept_type,
     )?)
 }
 
@@ -93,11 +134,15 @@ async fn handle_get_account_module(
     address: AddressParam,
     name: MoveIdentifierParam,
     context: Context,
+    query: WithProofParam,
+    accept_type: AcceptType,
 ) -> anyhow::Result<impl Reply, Rejection> {
     fail_point("endpoint_get_account_module")?;
     Ok(State::new(ledger_version, context)?.module(
         address.parse("account address")?.into(),
         name.parse("module name")?,
+        query.with_proof,
+        accept_type,
     )?)
 }
 
@@ -111,10 +156,30 @@ async fn handle_get_table_item(
     Ok(State::new(ledger_version, context)?.table_item(handle.parse("table handle")?, body)?)
 }
 
+async fn handle_get_table_items(
+    ledger_version: Option<LedgerVersionParam>,
+    handle: TableHandleParam,
+    context: Context,
+    query: TableItemsQuery,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_get_table_items")?;
+    let start = query
+        .start
+        .map(|start| start.parse("start"))
+        .transpose()?
+        .map(Vec::from);
+    Ok(State::new(ledger_version, context)?.table_items(
+        handle.parse("table handle")?,
+        start,
+        parse_limit(query.limit)?,
+    )?)
+}
+
 pub(crate) struct State {
     state_view: DbStateView,
     ledger_version: aptos_types::transaction::Version,
     latest_ledger_info: LedgerInfo,
+    context: Context,
 }
 
 impl State {
@@ -141,6 +206,7 @@ impl State {
             state_view,
             ledger_version,
             latest_ledger_info,
+            context,
         })
     }
 
@@ -148,6 +214,8 @@ impl State {
         self,
         address: AccountAddress,
         struct_tag: StructTag,
+        with_proof: bool,
+        accept_type: AcceptType,
     ) -> Result<impl Reply, Error> {
         let resource_key = ResourceKey::new(address, struct_tag.clone());
         let access_path = AccessPath::resource_access_path(resource_key.clone());
@@ -156,16 +224,25 @@ impl State {
             .state_view
             .get_state_value(&state_key)?
             .ok_or_else(|| Error::not_found("Resource", resource_key, self.ledger_version))?;
+        if accept_type == AcceptType::Bcs {
+            return self.respond_bcs(bytes, with_proof);
+        }
 
         let resource = self
             .state_view
             .as_move_resolver()
             .as_converter()
             .try_into_resource(&struct_tag, &bytes)?;
-        Response::new(self.latest_ledger_info, &resource)
+        self.respond(resource, &state_key, with_proof)
     }
 
-    pub fn module(self, address: AccountAddress, name: Identifier) -> Result<impl Reply, Error> {
+    pub fn module(
+        self,
+        address: AccountAddress,
+        name: Identifier,
+        with_proof: bool,
+        accept_type: AcceptType,
+    ) -> Result<impl Reply, Error> {
         let module_id = ModuleId::new(address, name);
         let access_path = AccessPath::code_access_path(module_id.clone());
         let state_key = StateKey::AccessPath(access_path);
@@ -173,11 +250,14 @@ impl State {
             .state_view
             .get_state_value(&state_key)?
             .ok_or_else(|| Error::not_found("Module", module_id, self.ledger_version))?;
+        if accept_type == AcceptType::Bcs {
+            return self.respond_bcs(bytes, with_proof);
+        }
 
         let module = MoveModuleBytecode::new(bytes)
             .try_parse_abi()
             .map_err(Error::internal)?;
-        Response::new(self.latest_ledger_info, &module)
+        self.respond(module, &state_key, with_proof)
     }
 
     pub fn table_item(self, handle: u128, body: TableItemRequest) -> Result<impl Reply, Error> {
@@ -209,4 +289,90 @@ impl State {
         let move_value = converter.try_into_move_value(&value_type, &bytes)?;
         Response::new(self.latest_ledger_info, &move_value)
     }
+
+    /// Lists the items of the table `handle` by key, starting from the raw key `start`, decoded
+    /// with the key and value types recorded when the table was created. When there are more
+    /// items, the response cursor is the raw key to start the next page from.
+    pub fn table_items(
+        self,
+        handle: u128,
+        start: Option<Vec<u8>>,
+        limit: u16,
+    ) -> Result<impl Reply, Error> {
+        let info = self.table_info(handle)?;
+        let key_type = info.key_type().map_err(Error::internal)?;
+        let value_type = info.value_type().map_err(Error::internal)?;
+
+        let (items, next) =
+            self.context
+                .get_table_items(handle, start, limit as usize, self.ledger_version)?;
+        let cursor = next.map(|key| HexEncodedBytes::from(key).to_string());
+
+        let resolver = self.state_view.as_move_resolver();
+        let converter = resolver.as_converter();
+        let items = items
+            .into_iter()
+            .map(|(key, value)| {
+                Ok(TableItem {
+                    key: converter.try_into_move_value(&key_type, &key)?,
+                    value: converter.try_into_move_value(&value_type, &value)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Response::new(self.latest_ledger_info, &items)?.with_cursor(cursor))
+    }
+
+    /// Returns the key and value types of the table `handle`, which are recorded only for the
+    /// tables created after genesis
+    fn table_info(&self, handle: u128) -> Result<TableInfo, Error> {
+        let infos_key = StateKey::AccessPath(AccessPath::new(
+            CORE_CODE_ADDRESS,
+            TableInfosResource::resource_path(),
+        ));
+        let infos: TableInfosResource = self
+            .state_view
+            .get_state_value(&infos_key)?
+            .map(|bytes| bcs::from_bytes(&bytes))
+            .transpose()
+            .map_err(|err| Error::internal(err.into()))?
+            .ok_or_else(|| {
+                Error::not_found("Table infos", CORE_CODE_ADDRESS, self.ledger_version)
+            })?;
+        let info_key = infos.info_key(handle).map_err(Error::internal)?;
+        let bytes = self.state_view.get_state_value(&info_key)?.ok_or_else(|| {
+            Error::not_found(
+                "Table info",
+                format!("table handle {}", handle),
+                self.ledger_version,
+            )
+        })?;
+        bcs::from_bytes(&bytes).map_err(|err| Error::internal(err.into()))
+    }
+
+    fn respond<T: Serialize>(
+        self,
+        data: T,
+        state_key: &StateKey,
+        with_proof: bool,
+    ) -> Result<Response, Error> {
+        if with_proof {
+            proofs::with_proof(
+                &self.context,
+                self.latest_ledger_info,
+                data,
+                state_key,
+                self.ledger_version,
+            )
+        } else {
+            Response::new(self.latest_ledger_info, &data)
+        }
+    }
+
+    /// Responds with the raw state value, which is the BCS encoded resource or module bytecode
+    fn respond_bcs(self, bytes: Vec<u8>, with_proof: bool) -> Result<Response, Error> {
+        if with_proof {
+            return Err(proofs::with_proof_not_supported_for_bcs());
+        }
+        Ok(Response::new_raw_bcs(self.latest_ledger_info, bytes))
+    }
 }
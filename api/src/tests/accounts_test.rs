@@ -5,7 +5,12 @@ use crate::{
     current_function_name,
     tests::{find_value, new_test_context},
 };
+use aptos_api_types::mime_types;
+use aptos_types::account_config::AccountResource;
+use move_deps::move_core_types::{language_storage::StructTag, move_resource::MoveStructType};
 use serde_json::json;
+use std::collections::BTreeMap;
+use warp::http::header::ACCEPT;
 
 #[tokio::test]
 async fn test_get_account_resources_returns_empty_array_for_account_has_no_resources() {
@@ -204,6 +209,57 @@ async fn test_get_core_account_data_not_found() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test]
+async fn test_get_account_bcs() {
+    let context = new_test_context(current_function_name!());
+    let account: AccountResource = context.get_bcs("/accounts/0xA550C18").await;
+    let resp = context.get("/accounts/0xA550C18").await;
+    assert_eq!(
+        account.sequence_number().to_string(),
+        resp["sequence_number"].as_str().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_get_account_with_proof_bcs() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path("/accounts/0xA550C18?with_proof=true")
+                .header(ACCEPT, mime_types::BCS),
+        )
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
+#[tokio::test]
+async fn test_get_account_resources_bcs() {
+    let context = new_test_context(current_function_name!());
+    let resources: BTreeMap<StructTag, Vec<u8>> =
+        context.get_bcs(&account_resources("0xA550C18")).await;
+    let resp = context.get(&account_resources("0xA550C18")).await;
+    assert_eq!(resources.len(), resp.as_array().unwrap().len());
+
+    let account: AccountResource =
+        bcs::from_bytes(&resources[&AccountResource::struct_tag()]).unwrap();
+    assert_eq!(account.address().to_hex_literal(), "0xa550c18");
+}
+
+#[tokio::test]
+async fn test_get_account_modules_bcs() {
+    let context = new_test_context(current_function_name!());
+    let modules: Vec<Vec<u8>> = context.get_bcs(&account_modules("0x1")).await;
+    let resp = context.get(&account_modules("0x1")).await;
+    let resp = resp.as_array().unwrap();
+    assert_eq!(modules.len(), resp.len());
+    for (bytecode, json) in modules.iter().zip(resp) {
+        assert_eq!(json["bytecode"], format!("0x{}", hex::encode(bytecode)));
+    }
+}
+
 fn account_resources(address: &str) -> String {
     format!("/accounts/{}/resources", address)
 }
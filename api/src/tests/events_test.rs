@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{current_function_name, tests::new_test_context};
+use aptos_types::contract_event::ContractEvent;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 
 static EVENT_KEY: &str =
@@ -16,6 +17,24 @@ async fn test_get_events() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test]
+async fn test_get_events_bcs() {
+    let context = new_test_context(current_function_name!());
+    let path = format!("/events/{}", EVENT_KEY);
+
+    let events: Vec<ContractEvent> = context.get_bcs(&path).await;
+    let resp = context.get(&path).await;
+    let resp = resp.as_array().unwrap();
+    assert!(!events.is_empty());
+    assert_eq!(events.len(), resp.len());
+    for (event, json) in events.iter().zip(resp) {
+        assert_eq!(
+            event.sequence_number().to_string(),
+            json["sequence_number"].as_str().unwrap()
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_get_events_filter_by_start_sequence_number() {
     let mut context = new_test_context(current_function_name!());
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{current_function_name, tests::new_test_context};
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
 use serde_json::json;
 
 #[tokio::test]
@@ -11,6 +12,19 @@ async fn test_get_ledger_info() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test]
+async fn test_get_ledger_info_bcs() {
+    let context = new_test_context(current_function_name!());
+    let ledger_info: LedgerInfoWithSignatures = context.get_bcs("/").await;
+    assert_eq!(
+        ledger_info,
+        context
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_returns_not_found_for_the_invalid_path() {
     let mut context = new_test_context(current_function_name!());
@@ -7,13 +7,16 @@ mod events_test;
 mod golden_output;
 mod index_test;
 mod invalid_post_request_test;
+mod proofs_test;
+mod quota_test;
 mod state_test;
 mod string_resource_test;
 mod test_context;
 mod transactions_test;
+mod view_test;
 
 use serde_json::Value;
-pub use test_context::{new_test_context, TestContext};
+pub use test_context::{new_test_context, new_test_context_with_config, TestContext};
 
 pub fn find_value(val: &Value, filter: for<'r> fn(&'r &Value) -> bool) -> Value {
     let resources = val
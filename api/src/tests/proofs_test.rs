@@ -0,0 +1,241 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    current_function_name,
+    tests::{new_test_context, TestContext},
+};
+use aptos_api_types::StateValueProof;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{aptos_root_address, AccountResource},
+    state_proof::StateProof,
+    state_store::{state_key::StateKey, state_value::StateValueWithProof},
+    transaction::TransactionWithProof,
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use move_deps::move_core_types::{language_storage::ResourceKey, move_resource::MoveStructType};
+
+#[tokio::test]
+async fn test_get_state_proof_from_genesis_waypoint() {
+    let context = new_test_context(current_function_name!());
+    let state_proof: StateProof = context.get_bcs("/state_proof").await;
+
+    let trusted_state = TrustedState::from_epoch_waypoint(context.genesis_waypoint);
+    match trusted_state.verify_and_ratchet(&state_proof).unwrap() {
+        TrustedStateChange::Epoch { new_state, .. } => {
+            assert_eq!(new_state.version(), 0);
+            assert!(!new_state.is_epoch_waypoint());
+        }
+        _ => panic!("expected the genesis epoch change"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_value_with_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let ledger_info = context
+        .context
+        .get_latest_ledger_info_with_signatures()
+        .unwrap();
+    let version = ledger_info.ledger_info().version();
+    for (address, exists) in [
+        (aptos_root_address(), true),
+        (account.address(), true),
+        (AccountAddress::random(), false),
+    ] {
+        let state_key = account_state_key(address);
+        let state_value_with_proof: StateValueWithProof = context
+            .get_bcs(&format!(
+                "/state_values/{}/proof",
+                hex::encode(bcs::to_bytes(&state_key).unwrap())
+            ))
+            .await;
+        assert_eq!(state_value_with_proof.state_value.is_some(), exists);
+        state_value_with_proof
+            .verify(ledger_info.ledger_info(), version, &state_key)
+            .unwrap();
+
+        // The proof doesn't hold for another key
+        assert!(state_value_with_proof
+            .verify(
+                ledger_info.ledger_info(),
+                version,
+                &account_state_key(AccountAddress::random())
+            )
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_value_with_proof_at_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let state_key = account_state_key(account.address());
+    let state_value_with_proof: StateValueWithProof = context
+        .get_bcs(&format!(
+            "/state_values/{}/proof?ledger_version=0",
+            hex::encode(bcs::to_bytes(&state_key).unwrap())
+        ))
+        .await;
+    assert_eq!(state_value_with_proof.version, 0);
+    assert!(state_value_with_proof.state_value.is_none());
+}
+
+#[tokio::test]
+async fn test_get_state_value_with_proof_by_invalid_state_key() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .get("/state_values/0xzz/proof")
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
+#[tokio::test]
+async fn test_get_transaction_with_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let ledger_info = context
+        .context
+        .get_latest_ledger_info_with_signatures()
+        .unwrap();
+    // The block metadata transaction is at version 1, the user transaction right after it
+    let transaction_with_proof: TransactionWithProof =
+        context.get_bcs("/transactions/2/proof").await;
+    transaction_with_proof
+        .verify(ledger_info.ledger_info(), 2)
+        .unwrap();
+    transaction_with_proof
+        .verify_user_txn(
+            ledger_info.ledger_info(),
+            2,
+            txn.sender(),
+            txn.sequence_number(),
+        )
+        .unwrap();
+    assert!(transaction_with_proof
+        .verify(ledger_info.ledger_info(), 1)
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_get_transaction_with_proof_beyond_ledger_version() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(404)
+        .get("/transactions/1/proof")
+        .await;
+    assert_eq!(resp["code"], 404);
+}
+
+#[tokio::test]
+async fn test_get_account_with_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let resp = context
+        .get(&format!(
+            "/accounts/{}?with_proof=true",
+            account.address().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(
+        resp["data"],
+        context
+            .get(&format!("/accounts/{}", account.address().to_hex_literal()))
+            .await
+    );
+
+    let proof: StateValueProof = serde_json::from_value(resp["proof"].clone()).unwrap();
+    let bytes = proof
+        .verify(
+            &latest_waypoint(&context),
+            &account_state_key(account.address()),
+        )
+        .unwrap()
+        .unwrap();
+    let account_resource: AccountResource = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        account_resource.sequence_number().to_string(),
+        resp["data"]["sequence_number"].as_str().unwrap()
+    );
+
+    // The proof doesn't hold for another key
+    assert!(proof
+        .verify(
+            &latest_waypoint(&context),
+            &account_state_key(aptos_root_address())
+        )
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_get_account_resource_with_proof() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get(&format!(
+            "/accounts/{}/resource/0x1::Account::Account?with_proof=true",
+            aptos_root_address().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(resp["data"]["type"], "0x1::Account::Account");
+
+    let proof: StateValueProof = serde_json::from_value(resp["proof"].clone()).unwrap();
+    assert!(proof
+        .verify(
+            &latest_waypoint(&context),
+            &account_state_key(aptos_root_address())
+        )
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_get_account_resource_with_proof_not_at_state_checkpoint() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    // Version 2 is the user transaction, the block's state checkpoint is at version 3
+    let resp = context
+        .expect_status_code(400)
+        .get(&format!(
+            "/accounts/{}/resource/0x1::Account::Account?version=2&with_proof=true",
+            aptos_root_address().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
+fn latest_waypoint(context: &TestContext) -> Waypoint {
+    Waypoint::new_any(
+        context
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .unwrap()
+            .ledger_info(),
+    )
+}
+
+fn account_state_key(address: AccountAddress) -> StateKey {
+    StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
+        address,
+        AccountResource::struct_tag(),
+    )))
+}
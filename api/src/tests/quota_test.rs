@@ -0,0 +1,151 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    current_function_name,
+    quota::X_API_KEY,
+    tests::{new_test_context_with_config, TestContext},
+};
+use aptos_api_types::mime_types::BCS_SIGNED_TRANSACTION;
+use aptos_config::config::{ApiAuthConfig, ApiConfig, ApiKey, ApiKeysConfig, ApiQuota};
+use warp::http::{
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    StatusCode,
+};
+
+const API_KEY: &str = "e0b7b0e6d1b14f6c";
+
+#[tokio::test]
+async fn test_request_with_api_key() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    let resp = context.execute(get_index(Some(API_KEY))).await;
+    assert_eq!(resp["chain_id"], 4);
+}
+
+#[tokio::test]
+async fn test_request_with_unknown_api_key() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    let resp = context
+        .expect_status_code(401)
+        .execute(get_index(Some("unknown")))
+        .await;
+    assert_eq!(resp["code"], 401);
+}
+
+#[tokio::test]
+async fn test_request_without_required_api_key() {
+    let context = new_quota_test_context(current_function_name!(), true);
+    let resp = context
+        .expect_status_code(401)
+        .execute(get_index(None))
+        .await;
+    assert_eq!(resp["code"], 401);
+}
+
+#[tokio::test]
+async fn test_api_key_requests_per_second_quota() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    for _ in 0..2 {
+        context.execute(get_index(Some(API_KEY))).await;
+    }
+
+    let resp = context.reply(get_index(Some(API_KEY))).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()[RETRY_AFTER], "1");
+
+    // Anonymous requests have a quota of their own
+    context.execute(get_index(None)).await;
+}
+
+#[tokio::test]
+async fn test_anonymous_requests_per_second_quota() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    context.execute(get_index(None)).await;
+
+    let resp = context
+        .expect_status_code(429)
+        .execute(get_index(None))
+        .await;
+    assert_eq!(resp["code"], 429);
+
+    context.execute(get_index(Some(API_KEY))).await;
+}
+
+#[tokio::test]
+async fn test_health_check_and_spec_without_api_key() {
+    let context = new_quota_test_context(current_function_name!(), true);
+    for path in ["/-/healthy", "/openapi.yaml", "/spec.html"] {
+        for _ in 0..3 {
+            let resp = context
+                .reply(warp::test::request().method("GET").path(path))
+                .await;
+            assert_eq!(resp.status(), StatusCode::OK, "{}", path);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_unmatched_requests_are_not_counted() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    for _ in 0..3 {
+        let resp = context
+            .reply(warp::test::request().method("GET").path("/unknown"))
+            .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    // The single anonymous request per second is still available
+    context.execute(get_index(None)).await;
+}
+
+#[tokio::test]
+async fn test_bcs_submission_is_counted_once() {
+    let context = new_quota_test_context(current_function_name!(), false);
+    // Rejected by the BCS route, which makes the JSON one try the request too
+    let resp = context
+        .expect_status_code(400)
+        .execute(
+            warp::test::request()
+                .method("POST")
+                .path("/transactions")
+                .header(CONTENT_TYPE, BCS_SIGNED_TRANSACTION)
+                .header(X_API_KEY, API_KEY)
+                .body("invalid"),
+        )
+        .await;
+    assert_eq!(resp["code"], 400);
+
+    // The second request per second of the API key is still available
+    context.execute(get_index(Some(API_KEY))).await;
+}
+
+fn new_quota_test_context(test_name: &'static str, require_api_key: bool) -> TestContext {
+    let api_config = ApiConfig {
+        auth: Some(ApiAuthConfig {
+            api_keys: ApiKeysConfig::FromConfig(vec![ApiKey {
+                name: "test".to_owned(),
+                key: API_KEY.to_owned(),
+                quota: Some(ApiQuota {
+                    requests_per_second: 2,
+                    max_concurrent_requests: 2,
+                }),
+            }]),
+            default_quota: ApiQuota::api_key(),
+            anonymous_quota: ApiQuota {
+                requests_per_second: 1,
+                max_concurrent_requests: 1,
+            },
+            require_api_key,
+        }),
+        ..ApiConfig::default()
+    };
+    new_test_context_with_config(test_name, api_config)
+}
+
+fn get_index(api_key: Option<&str>) -> warp::test::RequestBuilder {
+    let req = warp::test::request().method("GET").path("/");
+    match api_key {
+        Some(api_key) => req.header(X_API_KEY, api_key),
+        None => req,
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
     current_function_name,
     tests::{new_test_context, TestContext},
 };
-use aptos_sdk::types::LocalAccount;
+use aptos_api_types::X_APTOS_CURSOR;
+use aptos_sdk::types::{account_config::AccountResource, LocalAccount};
 use move_deps::{move_core_types::account_address::AccountAddress, move_package::BuildConfig};
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -176,6 +177,94 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test]
+async fn test_get_table_items() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let mut account = ctx.gen_account();
+    let acc = &mut account;
+    let txn = ctx.create_user_account(acc);
+    ctx.commit_block(&vec![txn.clone()]).await;
+    make_test_tables(ctx, acc).await;
+
+    let tt = ctx
+        .api_get_account_resource(
+            acc,
+            format!(
+                "{}::TableTestData::TestTables",
+                acc.address().to_hex_literal()
+            ),
+        )
+        .await["data"]
+        .to_owned();
+
+    let u64_table = get_table_items(&tt["u64_table"]);
+    let resp = ctx.get(&u64_table).await;
+    assert_eq!(
+        resp,
+        json!([
+            {"key": "1", "value": "1"},
+            {"key": "2", "value": "2"},
+            {"key": "3", "value": "3"},
+        ])
+    );
+
+    let resp = ctx
+        .reply(warp::test::request().path(&format!("{}?limit=2", u64_table)))
+        .await;
+    let items: Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(
+        items,
+        json!([{"key": "1", "value": "1"}, {"key": "2", "value": "2"}])
+    );
+    let cursor = resp.headers()[X_APTOS_CURSOR].to_str().unwrap().to_owned();
+
+    let resp = ctx
+        .reply(warp::test::request().path(&format!("{}?limit=2&start={}", u64_table, cursor)))
+        .await;
+    let items: Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(items, json!([{"key": "3", "value": "3"}]));
+    assert!(resp.headers().get(X_APTOS_CURSOR).is_none());
+
+    let resp = ctx.get(&get_table_items(&tt["string_table"])).await;
+    assert_eq!(resp, json!([{"key": "abc", "value": "abc"}]));
+}
+
+#[tokio::test]
+async fn test_get_table_items_of_unknown_table() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(404)
+        .get("/tables/12345/items")
+        .await;
+    assert_eq!(resp["code"], 404);
+}
+
+#[tokio::test]
+async fn test_get_account_resource_bcs() {
+    let context = new_test_context(current_function_name!());
+    let account: AccountResource = context
+        .get_bcs(&get_account_resource("0xA550C18", "0x1::Account::Account"))
+        .await;
+    let resp = context
+        .get(&get_account_resource("0xA550C18", "0x1::Account::Account"))
+        .await;
+    assert_eq!(
+        account.sequence_number().to_string(),
+        resp["data"]["sequence_number"].as_str().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_get_account_module_bcs() {
+    let context = new_test_context(current_function_name!());
+    let bytecode = context
+        .get_bcs_bytes(&get_account_module("0x1", "GUID"))
+        .await;
+    let resp = context.get(&get_account_module("0x1", "GUID")).await;
+    assert_eq!(resp["bytecode"], format!("0x{}", hex::encode(&bytecode)));
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -188,6 +277,10 @@ fn get_table_item(handle: u128) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn get_table_items(table: &Value) -> String {
+    format!("/tables/{}/items", table["handle"].as_str().unwrap())
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
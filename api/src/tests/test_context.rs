@@ -27,6 +27,7 @@ use aptos_types::{
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::{Transaction, TransactionStatus},
+    waypoint::Waypoint,
 };
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
@@ -40,13 +41,18 @@ use storage_interface::DbReaderWriter;
 use crate::tests::golden_output::GoldenOutputs;
 use executor::block_executor::BlockExecutor;
 use rand::SeedableRng;
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use std::{boxed::Box, collections::BTreeMap, iter::once, sync::Arc};
 use storage_interface::state_view::DbStateView;
 use vm_validator::vm_validator::VMValidator;
-use warp::http::header::CONTENT_TYPE;
+use warp::http::header::{ACCEPT, CONTENT_TYPE};
 
 pub fn new_test_context(test_name: &'static str) -> TestContext {
+    new_test_context_with_config(test_name, ApiConfig::default())
+}
+
+pub fn new_test_context_with_config(test_name: &'static str, api_config: ApiConfig) -> TestContext {
     let tmp_dir = TempPath::new();
     tmp_dir.create_as_dir().unwrap();
 
@@ -71,14 +77,16 @@ pub fn new_test_context(test_name: &'static str) -> TestContext {
             ChainId::test(),
             db.clone(),
             mempool.ac_client.clone(),
-            ApiConfig::default(),
-        ),
+            api_config,
+        )
+        .unwrap(),
         rng,
         root_keys,
         validator_owner,
         Box::new(BlockExecutor::<AptosVM>::new(db_rw)),
         mempool,
         db,
+        genesis_waypoint,
         test_name,
     )
 }
@@ -89,6 +97,7 @@ pub struct TestContext {
     pub validator_owner: AccountAddress,
     pub mempool: Arc<MockSharedMempool>,
     pub db: Arc<AptosDB>,
+    pub genesis_waypoint: Waypoint,
     rng: rand::rngs::StdRng,
     root_keys: Arc<RootKeys>,
     executor: Arc<dyn BlockExecutorTrait>,
@@ -107,6 +116,7 @@ impl TestContext {
         executor: Box<dyn BlockExecutorTrait>,
         mempool: MockSharedMempool,
         db: Arc<AptosDB>,
+        genesis_waypoint: Waypoint,
         test_name: &'static str,
     ) -> Self {
         Self {
@@ -118,6 +128,7 @@ impl TestContext {
             mempool: Arc::new(mempool),
             expect_status_code: 200,
             db,
+            genesis_waypoint,
             test_name,
             golden_output: None,
             fake_time: 0,
@@ -363,6 +374,24 @@ impl TestContext {
         .await
     }
 
+    pub async fn get_bcs<T: DeserializeOwned>(&self, path: &str) -> T {
+        bcs::from_bytes(&self.get_bcs_bytes(path).await).expect("response body is BCS")
+    }
+
+    pub async fn get_bcs_bytes(&self, path: &str) -> Bytes {
+        let resp = self
+            .reply(
+                warp::test::request()
+                    .method("GET")
+                    .path(path)
+                    .header(ACCEPT, mime_types::BCS),
+            )
+            .await;
+        assert_eq!(resp.status(), 200, "{:?}", resp.body());
+        assert_eq!(resp.headers()[CONTENT_TYPE], mime_types::BCS);
+        resp.into_body()
+    }
+
     pub async fn reply(&self, req: warp::test::RequestBuilder) -> Response<Bytes> {
         req.reply(&index::routes(self.context.clone())).await
     }
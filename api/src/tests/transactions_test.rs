@@ -6,7 +6,9 @@ use crate::{
     tests::{assert_json, new_test_context, pretty, TestContext},
 };
 
-use aptos_api_types::HexEncodedBytes;
+use aptos_api_types::{
+    HexEncodedBytes, TransactionData, TransactionOnChainData, TransactionSubmissionStatus,
+};
 use aptos_crypto::{
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
     PrivateKey, SigningKey, Uniform,
@@ -17,7 +19,7 @@ use aptos_types::{
     account_address::AccountAddress,
     transaction::{
         authenticator::{AuthenticationKey, TransactionAuthenticator},
-        ChangeSet, Script, ScriptFunction, SignedTransaction,
+        ChangeSet, Script, ScriptFunction, SignedTransaction, Transaction,
     },
     utility_coin::TEST_COIN_TYPE,
     write_set::{WriteOp, WriteSetMut},
@@ -283,6 +285,81 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test]
+async fn test_post_bcs_format_transaction_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let invalid_txn = context.create_invalid_signature_transaction();
+    let body = bcs::to_bytes(&vec![txn.clone(), invalid_txn.clone()]).unwrap();
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions/batch", &body)
+        .await;
+
+    let statuses: Vec<TransactionSubmissionStatus> = serde_json::from_value(resp).unwrap();
+    assert_eq!(statuses.len(), 2);
+    assert!(statuses[0].is_accepted());
+    assert_eq!(
+        statuses[0].hash.to_string(),
+        txn.committed_hash().to_hex_literal()
+    );
+    assert_eq!(
+        statuses[1].hash.to_string(),
+        invalid_txn.committed_hash().to_hex_literal()
+    );
+    assert_eq!(statuses[1].mempool_status, "VmError");
+    assert_eq!(statuses[1].vm_status.as_deref(), Some("INVALID_SIGNATURE"));
+}
+
+#[tokio::test]
+async fn test_post_transaction_batch_rejected_by_mempool() {
+    let mut context = new_test_context(current_function_name!());
+    let account1 = context.gen_account();
+    let account2 = context.gen_account();
+    let txn1 = context.create_user_account(&account1);
+    let txn2 = context.create_user_account(&account2);
+
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn(
+            "/transactions/batch",
+            &bcs::to_bytes(&vec![txn1, txn2]).unwrap(),
+        )
+        .await;
+    assert_eq!(resp[0]["mempool_status"], "Accepted");
+    assert_eq!(resp[1]["mempool_status"], "InvalidUpdate");
+    assert_eq!(resp[1]["message"], "Transaction already in mempool");
+}
+
+#[tokio::test]
+async fn test_post_json_format_transaction_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", &bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    let resp = context
+        .expect_status_code(202)
+        .post("/transactions/batch", json!([pending_txn.clone()]))
+        .await;
+    assert_eq!(resp[0]["hash"], pending_txn["hash"]);
+    assert_eq!(resp[0]["mempool_status"], "Accepted");
+}
+
+#[tokio::test]
+async fn test_post_empty_transaction_batch() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post("/transactions/batch", json!([]))
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_multi_agent_signed_transaction() {
@@ -499,6 +576,53 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test]
+async fn test_get_transactions_bcs() {
+    let context = new_test_context(current_function_name!());
+    let txns: Vec<TransactionOnChainData> = context.get_bcs("/transactions").await;
+    assert_eq!(txns.len(), 1);
+    assert_eq!(txns[0].version, 0);
+    assert!(matches!(
+        txns[0].transaction,
+        Transaction::GenesisTransaction(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_transaction_by_version_bcs() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn.clone()]).await;
+
+    match context.get_bcs("/transactions/2").await {
+        TransactionData::OnChain(data) => {
+            assert_eq!(data.version, 2);
+            assert_eq!(data.transaction, Transaction::UserTransaction(txn));
+            assert!(data.info.status().is_success());
+        }
+        TransactionData::Pending(_) => panic!("expected a committed transaction"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_pending_transaction_by_hash_bcs() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let path = format!("/transactions/{}", pending_txn["hash"].as_str().unwrap());
+    match context.get_bcs(&path).await {
+        TransactionData::Pending(pending) => assert_eq!(*pending, txn),
+        TransactionData::OnChain(_) => panic!("expected a pending transaction"),
+    }
+}
+
 #[tokio::test]
 async fn test_signing_message_with_script_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -0,0 +1,106 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{current_function_name, tests::new_test_context};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_view_function() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::get_sequence_number",
+                "type_arguments": [],
+                "arguments": ["0xA550C18"],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!(["0"]));
+}
+
+#[tokio::test]
+async fn test_view_generic_function() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Coin::is_coin_initialized",
+                "type_arguments": ["0x1::TestCoin::TestCoin"],
+                "arguments": [],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!([true]));
+}
+
+#[tokio::test]
+async fn test_view_function_at_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let request = json!({
+        "function": "0x1::Account::exists_at",
+        "type_arguments": [],
+        "arguments": [account.address().to_hex_literal()],
+    });
+    let resp = context.post("/view", request.clone()).await;
+    assert_eq!(resp, json!([true]));
+    let resp = context.post("/view?version=0", request).await;
+    assert_eq!(resp, json!([false]));
+}
+
+#[tokio::test]
+async fn test_view_private_function() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::create_account_unchecked",
+                "type_arguments": [],
+                "arguments": ["0x1"],
+            }),
+        )
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
+#[tokio::test]
+async fn test_view_function_taking_signer() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::initialize",
+                "type_arguments": [],
+                "arguments": [],
+            }),
+        )
+        .await;
+    assert_eq!(resp["code"], 400);
+}
+
+#[tokio::test]
+async fn test_view_function_with_invalid_arguments() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::get_sequence_number",
+                "type_arguments": [],
+                "arguments": [],
+            }),
+        )
+        .await;
+    assert_eq!(resp["code"], 400);
+}
@@ -2,16 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    accept_type::{accept_type, AcceptType},
     context::Context,
     failpoint::fail_point,
     metrics::metrics,
     page::Page,
     param::{AddressParam, TransactionIdParam},
+    quota::client_quota,
 };
 
 use aptos_api_types::{
-    mime_types::BCS_SIGNED_TRANSACTION, AsConverter, Error, LedgerInfo, Response, Transaction,
-    TransactionData, TransactionId, TransactionOnChainData, TransactionSigningMessage,
+    mime_types::{BCS_SIGNED_TRANSACTION, JSON},
+    AsConverter, Error, LedgerInfo, Response, Transaction, TransactionData, TransactionId,
+    TransactionOnChainData, TransactionSigningMessage, TransactionSubmissionStatus,
     UserCreateSigningMessageRequest, UserTransactionRequest,
 };
 use aptos_crypto::signing_message;
@@ -31,7 +34,8 @@ use warp::{
 pub fn get_transaction(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("transactions" / TransactionIdParam)
         .and(warp::get())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_get_transaction)
         .with(metrics("get_transaction"))
         .boxed()
@@ -42,7 +46,8 @@ pub fn get_transactions(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("transactions")
         .and(warp::get())
         .and(warp::query::<Page>())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_get_transactions)
         .with(metrics("get_transactions"))
         .boxed()
@@ -53,7 +58,8 @@ pub fn get_account_transactions(context: Context) -> BoxedFilter<(impl Reply,)>
     warp::path!("accounts" / AddressParam / "transactions")
         .and(warp::get())
         .and(warp::query::<Page>())
-        .and(context.filter())
+        .and(client_quota(context))
+        .and(accept_type())
         .and_then(handle_get_account_transactions)
         .with(metrics("get_account_transactions"))
         .boxed()
@@ -66,8 +72,9 @@ pub fn submit_json_transactions(context: Context) -> BoxedFilter<(impl Reply,)>
         .and(warp::body::content_length_limit(
             context.content_length_limit(),
         ))
+        .and(json_content_type())
+        .and(client_quota(context))
         .and(warp::body::json::<UserTransactionRequest>())
-        .and(context.filter())
         .and_then(handle_submit_json_transactions)
         .with(metrics("submit_json_transactions"))
         .boxed()
@@ -80,6 +87,8 @@ pub fn submit_bcs_transactions(context: Context) -> BoxedFilter<(impl Reply,)> {
     // When the content-type is invalid (not json / bcs signed txn), `submit_json_transactions`
     // route will emit correct rejection (UnsupportedMediaType) which will be handled by recover
     // handler, the invalid header error should be ignored.
+    // The content-type is checked before the client quota, so that a request is only counted by
+    // the route it matches, and the quota before the body, which isn't read when it's exceeded.
     warp::path!("transactions")
         .and(warp::post())
         .and(warp::body::content_length_limit(
@@ -89,13 +98,48 @@ pub fn submit_bcs_transactions(context: Context) -> BoxedFilter<(impl Reply,)> {
             CONTENT_TYPE.as_str(),
             BCS_SIGNED_TRANSACTION,
         ))
+        .and(client_quota(context))
         .and(warp::body::bytes())
-        .and(context.filter())
         .and_then(handle_submit_bcs_transactions)
         .with(metrics("submit_bcs_transactions"))
         .boxed()
 }
 
+// POST /transactions/batch with JSON
+pub fn submit_json_transaction_batch(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("transactions" / "batch")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            context.content_length_limit(),
+        ))
+        .and(json_content_type())
+        .and(client_quota(context))
+        .and(warp::body::json::<Vec<UserTransactionRequest>>())
+        .and_then(handle_submit_json_transaction_batch)
+        .with(metrics("submit_json_transaction_batch"))
+        .boxed()
+}
+
+// POST /transactions/batch with BCS
+pub fn submit_bcs_transaction_batch(context: Context) -> BoxedFilter<(impl Reply,)> {
+    // Same content-type handling as `submit_bcs_transactions`, the body being a BCS serialized
+    // `Vec<SignedTransaction>`.
+    warp::path!("transactions" / "batch")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            context.content_length_limit(),
+        ))
+        .and(warp::header::exact(
+            CONTENT_TYPE.as_str(),
+            BCS_SIGNED_TRANSACTION,
+        ))
+        .and(client_quota(context))
+        .and(warp::body::bytes())
+        .and_then(handle_submit_bcs_transaction_batch)
+        .with(metrics("submit_bcs_transaction_batch"))
+        .boxed()
+}
+
 // POST /transactions/signing_message
 pub fn create_signing_message(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("transactions" / "signing_message")
@@ -103,40 +147,72 @@ pub fn create_signing_message(context: Context) -> BoxedFilter<(impl Reply,)> {
         .and(warp::body::content_length_limit(
             context.content_length_limit(),
         ))
+        .and(json_content_type())
+        .and(client_quota(context))
         .and(warp::body::json::<UserCreateSigningMessageRequest>())
-        .and(context.filter())
         .and_then(handle_create_signing_message)
         .with(metrics("create_signing_message"))
         .boxed()
 }
 
+/// Matches the requests with a JSON body, or without a content-type like `warp::body::json`. The
+/// latter checks it only when reading the body, after the client quota is taken, so a request of
+/// another route would be counted twice.
+fn json_content_type() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>(CONTENT_TYPE.as_str())
+        .and_then(|content_type: Option<String>| async move {
+            let is_json = content_type.map_or(true, |content_type| {
+                content_type
+                    .split(';')
+                    .next()
+                    .map_or(false, |mime| mime.trim().eq_ignore_ascii_case(JSON))
+            });
+            if is_json {
+                Ok(())
+            } else {
+                // Same rejection as `warp::body::json`, which can't be built outside of warp
+                Err(warp::reject::custom(Error::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    "The request's content-type is not supported".to_owned(),
+                )))
+            }
+        })
+        .untuple_one()
+}
+
 async fn handle_get_transaction(
     id: TransactionIdParam,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_transaction")?;
     Ok(Transactions::new(context)?
-        .get_transaction(id.parse("transaction hash or version")?)
+        .get_transaction(id.parse("transaction hash or version")?, accept_type)
         .await?)
 }
 
-async fn handle_get_transactions(page: Page, context: Context) -> Result<impl Reply, Rejection> {
+async fn handle_get_transactions(
+    page: Page,
+    context: Context,
+    accept_type: AcceptType,
+) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_transactions")?;
-    Ok(Transactions::new(context)?.list(page)?)
+    Ok(Transactions::new(context)?.list(page, accept_type)?)
 }
 
 async fn handle_get_account_transactions(
     address: AddressParam,
     page: Page,
     context: Context,
+    accept_type: AcceptType,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_get_account_transactions")?;
-    Ok(Transactions::new(context)?.list_by_account(address, page)?)
+    Ok(Transactions::new(context)?.list_by_account(address, page, accept_type)?)
 }
 
 async fn handle_submit_json_transactions(
-    body: UserTransactionRequest,
     context: Context,
+    body: UserTransactionRequest,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_submit_json_transactions")?;
     Ok(Transactions::new(context)?
@@ -145,8 +221,8 @@ async fn handle_submit_json_transactions(
 }
 
 async fn handle_submit_bcs_transactions(
-    body: bytes::Bytes,
     context: Context,
+    body: bytes::Bytes,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_submit_bcs_transactions")?;
     let txn = bcs::from_bytes(&body)
@@ -154,9 +230,29 @@ async fn handle_submit_bcs_transactions(
     Ok(Transactions::new(context)?.create(txn).await?)
 }
 
+async fn handle_submit_json_transaction_batch(
+    context: Context,
+    body: Vec<UserTransactionRequest>,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_submit_json_transaction_batch")?;
+    Ok(Transactions::new(context)?
+        .create_batch_from_requests(body)
+        .await?)
+}
+
+async fn handle_submit_bcs_transaction_batch(
+    context: Context,
+    body: bytes::Bytes,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_submit_bcs_transaction_batch")?;
+    let txns = bcs::from_bytes(&body)
+        .map_err(|err| Error::invalid_request_body(format!("deserialize error: {}", err)))?;
+    Ok(Transactions::new(context)?.create_batch(txns).await?)
+}
+
 async fn handle_create_signing_message(
-    body: UserCreateSigningMessageRequest,
     context: Context,
+    body: UserCreateSigningMessageRequest,
 ) -> Result<impl Reply, Rejection> {
     fail_point("endpoint_create_signing_message")?;
     Ok(Transactions::new(context)?.signing_message(body)?)
@@ -216,7 +312,60 @@ impl Transactions {
         }
     }
 
-    pub fn list(self, page: Page) -> Result<impl Reply, Error> {
+    pub async fn create_batch_from_requests(
+        self,
+        reqs: Vec<UserTransactionRequest>,
+    ) -> Result<impl Reply, Error> {
+        self.check_batch_size(reqs.len())?;
+        let txns = {
+            let resolver = self.context.move_resolver()?;
+            let converter = resolver.as_converter();
+            reqs.into_iter()
+                .enumerate()
+                .map(|(index, req)| {
+                    converter
+                        .try_into_signed_transaction(req, self.context.chain_id())
+                        .map_err(|e| {
+                            Error::invalid_request_body(format!(
+                                "failed to create SignedTransaction from UserTransactionRequest at index {}: {}",
+                                index, e
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>, Error>>()?
+        };
+        self.create_batch(txns).await
+    }
+
+    /// Submits all `txns` to mempool in one request. Each transaction is accepted or rejected on
+    /// its own, their statuses are returned in the order of the batch.
+    pub async fn create_batch(self, txns: Vec<SignedTransaction>) -> Result<impl Reply, Error> {
+        self.check_batch_size(txns.len())?;
+        let hashes: Vec<_> = txns
+            .iter()
+            .map(|txn| txn.clone().committed_hash())
+            .collect();
+        let statuses: Vec<_> = hashes
+            .into_iter()
+            .zip(self.context.submit_transactions(txns).await?)
+            .map(|(hash, status)| TransactionSubmissionStatus::new(hash, status))
+            .collect();
+        let resp = Response::new(self.ledger_info, &statuses)?;
+        Ok(reply::with_status(resp, StatusCode::ACCEPTED))
+    }
+
+    fn check_batch_size(&self, size: usize) -> Result<(), Error> {
+        let max_size = self.context.max_submit_transaction_batch_size();
+        if size == 0 || size > max_size {
+            return Err(Error::invalid_request_body(format!(
+                "batch of {} transactions, expected between 1 and {}",
+                size, max_size
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn list(self, page: Page, accept_type: AcceptType) -> Result<impl Reply, Error> {
         let ledger_version = self.ledger_info.version();
         let limit = page.limit()?;
         let last_page_start = if ledger_version > (limit as u64) {
@@ -230,20 +379,32 @@ impl Transactions {
             .context
             .get_transactions(start_version, limit, ledger_version)?;
 
-        self.render_transactions(data)
+        self.render_transactions(data, accept_type)
     }
 
-    pub fn list_by_account(self, address: AddressParam, page: Page) -> Result<impl Reply, Error> {
+    pub fn list_by_account(
+        self,
+        address: AddressParam,
+        page: Page,
+        accept_type: AcceptType,
+    ) -> Result<impl Reply, Error> {
         let data = self.context.get_account_transactions(
             address.parse("account address")?.into(),
             page.start(0, u64::MAX)?,
             page.limit()?,
             self.ledger_info.version(),
         )?;
-        self.render_transactions(data)
+        self.render_transactions(data, accept_type)
     }
 
-    fn render_transactions(self, data: Vec<TransactionOnChainData>) -> Result<impl Reply, Error> {
+    fn render_transactions(
+        self,
+        data: Vec<TransactionOnChainData>,
+        accept_type: AcceptType,
+    ) -> Result<impl Reply, Error> {
+        if accept_type == AcceptType::Bcs {
+            return Response::new_bcs(self.ledger_info, &data);
+        }
         if data.is_empty() {
             let txns: Vec<Transaction> = vec![];
             return Response::new(self.ledger_info, &txns);
@@ -265,13 +426,22 @@ impl Transactions {
         Response::new(self.ledger_info, &txns)
     }
 
-    pub async fn get_transaction(self, id: TransactionId) -> Result<impl Reply, Error> {
+    pub async fn get_transaction(
+        self,
+        id: TransactionId,
+        accept_type: AcceptType,
+    ) -> Result<impl Reply, Error> {
         let txn_data = match id.clone() {
             TransactionId::Hash(hash) => self.get_by_hash(hash.into()).await?,
             TransactionId::Version(version) => self.get_by_version(version)?,
         }
         .ok_or_else(|| self.transaction_not_found(id))?;
 
+        // BCS clients tell on-chain and pending transactions apart by the `TransactionData` variant
+        if accept_type == AcceptType::Bcs {
+            return Response::new_bcs(self.ledger_info, &txn_data);
+        }
+
         let resolver = self.context.move_resolver()?;
         let txn = match txn_data {
             TransactionData::OnChain(txn) => {
@@ -0,0 +1,92 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    context::Context, failpoint::fail_point, metrics::metrics, param::LedgerVersionParam,
+    quota::client_quota, version::Version,
+};
+use aptos_api_types::{AsConverter, Error, Response, TransactionId, ViewFunction, ViewRequest};
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+// POST /view
+pub fn view_function(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("view")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            context.content_length_limit(),
+        ))
+        .and(warp::body::json::<ViewRequest>())
+        .and(client_quota(context))
+        .and(warp::query::<Version>())
+        .map(|body, ctx, version: Version| (version.version, body, ctx))
+        .untuple_one()
+        .and_then(handle_view_function)
+        .with(metrics("view_function"))
+        .boxed()
+}
+
+async fn handle_view_function(
+    ledger_version: Option<LedgerVersionParam>,
+    request: ViewRequest,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_view_function")?;
+    Ok(view(ledger_version, request, context)?)
+}
+
+/// Executes the function of `request` over the state at `ledger_version` (the latest by default)
+/// and returns its return values. Nothing is committed: the function runs in a session which is
+/// dropped once it returns, with its gas capped by the API config.
+fn view(
+    ledger_version: Option<LedgerVersionParam>,
+    request: ViewRequest,
+    context: Context,
+) -> Result<impl Reply, Error> {
+    let latest_ledger_info = context.get_latest_ledger_info()?;
+    let ledger_version = ledger_version
+        .map(|v| v.parse("ledger version"))
+        .unwrap_or_else(|| Ok(latest_ledger_info.version()))?;
+    if ledger_version > latest_ledger_info.version() {
+        return Err(Error::not_found(
+            "ledger",
+            TransactionId::Version(ledger_version),
+            latest_ledger_info.version(),
+        ));
+    }
+
+    let state_view = context.state_view_at_version(ledger_version)?;
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter();
+    let ViewFunction {
+        module,
+        function,
+        type_arguments,
+        arguments,
+        return_types,
+    } = converter
+        .try_into_view_function(request)
+        .map_err(Error::bad_request)?;
+
+    let return_values = AptosVM::execute_view_function(
+        &state_view,
+        &module,
+        &function,
+        type_arguments,
+        arguments,
+        context.max_view_gas_amount(),
+    )
+    .map_err(|status| {
+        Error::bad_request(format!(
+            "execution of {}::{} failed: {:?}",
+            module, function, status
+        ))
+    })?;
+
+    let values = return_types
+        .iter()
+        .zip(return_values.iter())
+        .map(|(typ, bytes)| converter.try_into_move_value(typ, bytes))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Response::new(latest_ledger_info, &values)
+}
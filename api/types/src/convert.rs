@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    move_types::MoveFunctionVisibility,
     transaction::{ModuleBundlePayload, StateCheckpointTransaction},
     Bytecode, DirectWriteSet, Event, HexEncodedBytes, MoveFunction, MoveModuleBytecode,
-    MoveResource, MoveScriptBytecode, MoveValue, ScriptFunctionId, ScriptFunctionPayload,
+    MoveResource, MoveScriptBytecode, MoveType, MoveValue, ScriptFunctionId, ScriptFunctionPayload,
     ScriptPayload, ScriptWriteSet, Transaction, TransactionInfo, TransactionOnChainData,
-    TransactionPayload, UserTransactionRequest, WriteSet, WriteSetChange, WriteSetPayload,
+    TransactionPayload, UserTransactionRequest, ViewFunction, ViewRequest, WriteSet,
+    WriteSetChange, WriteSetPayload,
 };
 use anyhow::{bail, ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -421,6 +423,67 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    /// Resolves the function called by `request` and converts its type arguments and JSON
+    /// arguments. Only public and script functions which don't take a signer can be viewed.
+    pub fn try_into_view_function(&self, request: ViewRequest) -> Result<ViewFunction> {
+        let ViewRequest {
+            function,
+            type_arguments,
+            arguments,
+        } = request;
+
+        let module: ModuleId = function.module.clone().into();
+        let code = self.inner.get_module(&module)? as Rc<dyn Bytecode>;
+        let mut func = code
+            .find_function(function.name.as_ident_str())
+            .filter(|func| {
+                matches!(
+                    func.visibility,
+                    MoveFunctionVisibility::Public | MoveFunctionVisibility::Script
+                )
+            })
+            .ok_or_else(|| format_err!("could not find public function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        ensure!(
+            !func.params.iter().any(MoveType::is_signer),
+            "function {} takes a signer, it can't be viewed",
+            function
+        );
+
+        func.params = func
+            .params
+            .iter()
+            .map(|t| t.instantiate(&type_arguments))
+            .collect::<Result<_>>()?;
+        let return_types = func
+            .return_
+            .iter()
+            .map(|t| TypeTag::try_from(t.instantiate(&type_arguments)?))
+            .collect::<Result<_>>()?;
+        let arguments = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(ViewFunction {
+            module,
+            function: function.name,
+            type_arguments: type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            arguments,
+            return_types,
+        })
+    }
+
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
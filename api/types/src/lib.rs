@@ -11,9 +11,11 @@ mod hash;
 mod ledger_info;
 pub mod mime_types;
 mod move_types;
+mod proof;
 mod response;
 mod table;
 mod transaction;
+mod view;
 
 pub use account::AccountData;
 pub use address::Address;
@@ -28,14 +30,17 @@ pub use move_types::{
     MoveScriptBytecode, MoveStructTag, MoveStructValue, MoveType, MoveValue, ScriptFunctionId,
     U128, U64,
 };
+pub use proof::{StateValueProof, WithProof};
 pub use response::{
-    Response, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+    Response, X_APTOS_CHAIN_ID, X_APTOS_CURSOR, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP,
+    X_APTOS_LEDGER_VERSION,
 };
-pub use table::TableItemRequest;
+pub use table::{TableItem, TableItemRequest};
 pub use transaction::{
     BlockMetadataTransaction, DirectWriteSet, Event, GenesisTransaction, PendingTransaction,
     ScriptFunctionPayload, ScriptPayload, ScriptWriteSet, Transaction, TransactionData,
     TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    TransactionSigningMessage, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, WriteSet, WriteSetChange, WriteSetPayload,
+    TransactionSigningMessage, TransactionSubmissionStatus, UserCreateSigningMessageRequest,
+    UserTransaction, UserTransactionRequest, WriteSet, WriteSetChange, WriteSetPayload,
 };
+pub use view::{ViewFunction, ViewRequest};
@@ -534,6 +534,32 @@ impl<'de> Deserialize<'de> for MoveType {
 }
 
 impl MoveType {
+    /// Replaces the generic type parameters in `self` with `type_args`, as in a function call
+    pub fn instantiate(&self, type_args: &[MoveType]) -> anyhow::Result<MoveType> {
+        Ok(match self {
+            MoveType::Vector { items } => MoveType::Vector {
+                items: Box::new(items.instantiate(type_args)?),
+            },
+            MoveType::Struct(s) => MoveType::Struct(MoveStructTag {
+                generic_type_params: s
+                    .generic_type_params
+                    .iter()
+                    .map(|t| t.instantiate(type_args))
+                    .collect::<anyhow::Result<_>>()?,
+                ..s.clone()
+            }),
+            MoveType::GenericTypeParam { index } => type_args
+                .get(*index as usize)
+                .cloned()
+                .ok_or_else(|| format_err!("missing type argument for T{}", index))?,
+            MoveType::Reference { mutable, to } => MoveType::Reference {
+                mutable: *mutable,
+                to: Box::new(to.instantiate(type_args)?),
+            },
+            _ => self.clone(),
+        })
+    }
+
     pub fn is_signer(&self) -> bool {
         match self {
             MoveType::Signer => true,
@@ -0,0 +1,71 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::HexEncodedBytes;
+use anyhow::{ensure, Result};
+use aptos_types::{
+    epoch_change::Verifier, ledger_info::LedgerInfoWithSignatures,
+    state_store::state_key::StateKey, state_store::state_value::StateValueWithProof,
+};
+use serde::{Deserialize, Serialize};
+
+/// A response body along with the proof of the state value it was read from, returned when
+/// `with_proof=true` is requested.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WithProof<T> {
+    pub data: T,
+    pub proof: StateValueProof,
+}
+
+/// The proof of a state value, all fields being hex encoded BCS bytes so that clients can verify
+/// the response independently of the node serving it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StateValueProof {
+    /// The `StateKey` the value was read from
+    pub state_key: HexEncodedBytes,
+    /// The `StateValueWithProof` at the version read: the raw value, its `SparseMerkleProof` and
+    /// the `TransactionInfoWithProof` of that version relative to `ledger_info_with_signatures`
+    pub state_value_with_proof: HexEncodedBytes,
+    /// The `LedgerInfoWithSignatures` the proof is relative to
+    pub ledger_info_with_signatures: HexEncodedBytes,
+}
+
+impl StateValueProof {
+    pub fn new(
+        state_key: &StateKey,
+        state_value_with_proof: &StateValueWithProof,
+        ledger_info_with_signatures: &LedgerInfoWithSignatures,
+    ) -> Result<Self> {
+        Ok(Self {
+            state_key: bcs::to_bytes(state_key)?.into(),
+            state_value_with_proof: bcs::to_bytes(state_value_with_proof)?.into(),
+            ledger_info_with_signatures: bcs::to_bytes(ledger_info_with_signatures)?.into(),
+        })
+    }
+
+    /// Verifies the ledger info signatures with `verifier`, then the state value against the
+    /// ledger info. Returns the proven raw value of `state_key`.
+    pub fn verify(&self, verifier: &dyn Verifier, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        let proven_state_key: StateKey = bcs::from_bytes(self.state_key.inner())?;
+        ensure!(
+            &proven_state_key == state_key,
+            "Proof is for state key {:?}, expected {:?}",
+            proven_state_key,
+            state_key
+        );
+        let ledger_info_with_signatures: LedgerInfoWithSignatures =
+            bcs::from_bytes(self.ledger_info_with_signatures.inner())?;
+        verifier.verify(&ledger_info_with_signatures)?;
+
+        let state_value_with_proof: StateValueWithProof =
+            bcs::from_bytes(self.state_value_with_proof.inner())?;
+        state_value_with_proof.verify(
+            ledger_info_with_signatures.ledger_info(),
+            state_value_with_proof.version,
+            state_key,
+        )?;
+        Ok(state_value_with_proof
+            .state_value
+            .and_then(|state_value| state_value.maybe_bytes))
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{Error, LedgerInfo};
+use crate::{mime_types, Error, LedgerInfo};
 
 use anyhow::Result;
 use serde::Serialize;
@@ -11,10 +11,13 @@ pub const X_APTOS_CHAIN_ID: &str = "X-Aptos-Chain-Id";
 pub const X_APTOS_EPOCH: &str = "X-Aptos-Epoch";
 pub const X_APTOS_LEDGER_VERSION: &str = "X-Aptos-Ledger-Version";
 pub const X_APTOS_LEDGER_TIMESTAMP: &str = "X-Aptos-Ledger-TimestampUsec";
+pub const X_APTOS_CURSOR: &str = "X-Aptos-Cursor";
 
 pub struct Response {
     pub ledger_info: LedgerInfo,
     pub body: Vec<u8>,
+    pub content_type: &'static str,
+    pub cursor: Option<String>,
 }
 
 impl Response {
@@ -22,8 +25,37 @@ impl Response {
         Ok(Self {
             ledger_info,
             body: serde_json::to_vec(body)?,
+            content_type: mime_types::JSON,
+            cursor: None,
         })
     }
+
+    /// Creates a response with the BCS serialized `body`, for clients that verify or decode the
+    /// on-chain data themselves
+    pub fn new_bcs<T: Serialize>(ledger_info: LedgerInfo, body: &T) -> Result<Self, Error> {
+        Ok(Self {
+            ledger_info,
+            body: bcs::to_bytes(body).map_err(anyhow::Error::from)?,
+            content_type: mime_types::BCS,
+            cursor: None,
+        })
+    }
+
+    /// Creates a BCS response from bytes which are already BCS encoded, such as raw state values
+    pub fn new_raw_bcs(ledger_info: LedgerInfo, body: Vec<u8>) -> Self {
+        Self {
+            ledger_info,
+            body,
+            content_type: mime_types::BCS,
+            cursor: None,
+        }
+    }
+
+    /// Sets the cursor to pass as the `start` of the request of the next page, when there's one
+    pub fn with_cursor(mut self, cursor: Option<String>) -> Self {
+        self.cursor = cursor;
+        self
+    }
 }
 
 impl warp::Reply for Response {
@@ -31,7 +63,7 @@ impl warp::Reply for Response {
         let mut res = warp::reply::Response::new(self.body.into());
         let headers = res.headers_mut();
 
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(self.content_type));
         headers.insert(X_APTOS_CHAIN_ID, (self.ledger_info.chain_id as u16).into());
         headers.insert(
             X_APTOS_LEDGER_VERSION,
@@ -42,6 +74,12 @@ impl warp::Reply for Response {
             self.ledger_info.ledger_timestamp.into(),
         );
         headers.insert(X_APTOS_EPOCH, self.ledger_info.epoch.into());
+        if let Some(cursor) = self.cursor {
+            headers.insert(
+                X_APTOS_CURSOR,
+                HeaderValue::from_str(&cursor).expect("cursor is a valid header value"),
+            );
+        }
 
         res
     }
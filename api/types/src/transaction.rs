@@ -16,10 +16,12 @@ use aptos_types::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
     contract_event::ContractEvent,
+    mempool_status::{MempoolStatus, MempoolStatusCode},
     transaction::{
         authenticator::{AccountAuthenticator, TransactionAuthenticator},
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
+    vm_status::DiscardedVMStatus,
 };
 
 use serde::{Deserialize, Serialize};
@@ -30,7 +32,7 @@ use std::{
     str::FromStr,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TransactionData {
     OnChain(TransactionOnChainData),
     Pending(Box<SignedTransaction>),
@@ -48,7 +50,7 @@ impl From<SignedTransaction> for TransactionData {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TransactionOnChainData {
     pub version: u64,
     pub transaction: aptos_types::transaction::Transaction,
@@ -299,6 +301,37 @@ pub struct PendingTransaction {
     pub request: UserTransactionRequest,
 }
 
+/// The outcome of submitting one transaction of a batch
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransactionSubmissionStatus {
+    pub hash: HashValue,
+    /// The mempool status code, `Accepted` once the transaction is in mempool
+    pub mempool_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// The reason the VM discarded the transaction, if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vm_status: Option<String>,
+}
+
+impl TransactionSubmissionStatus {
+    pub fn new(
+        hash: aptos_crypto::HashValue,
+        (mempool_status, vm_status): (MempoolStatus, Option<DiscardedVMStatus>),
+    ) -> Self {
+        Self {
+            hash: hash.into(),
+            mempool_status: mempool_status.code.to_string(),
+            message: Some(mempool_status.message).filter(|message| !message.is_empty()),
+            vm_status: vm_status.map(|status| format!("{:?}", status)),
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.mempool_status == MempoolStatusCode::Accepted.to_string()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UserTransaction {
     #[serde(flatten)]
@@ -0,0 +1,29 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MoveType, ScriptFunctionId};
+use move_deps::move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Request to execute a public or script function without a transaction, and return its result
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ViewRequest {
+    pub function: ScriptFunctionId,
+    pub type_arguments: Vec<MoveType>,
+    pub arguments: Vec<Value>,
+}
+
+/// A `ViewRequest` resolved against the ABI of its function, ready to be executed
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewFunction {
+    pub module: ModuleId,
+    pub function: Identifier,
+    pub type_arguments: Vec<TypeTag>,
+    /// BCS serialized arguments
+    pub arguments: Vec<Vec<u8>>,
+    pub return_types: Vec<TypeTag>,
+}
@@ -11,7 +11,7 @@ use crate::{
         charge_global_write_gas_usage, get_transaction_output, AptosVMImpl, AptosVMInternals,
     },
     counters::*,
-    data_cache::StateViewCache,
+    data_cache::{AsMoveResolver, StateViewCache},
     errors::expect_only_successful_execution,
     logging::AdapterLogSchema,
     move_vm_ext::{MoveResolverExt, SessionExt, SessionId},
@@ -46,7 +46,8 @@ use move_deps::{
     move_core_types::{
         account_address::AccountAddress,
         gas_schedule::{GasAlgebra, GasUnits},
-        language_storage::ModuleId,
+        identifier::IdentStr,
+        language_storage::{ModuleId, TypeTag},
         transaction_argument::convert_txn_args,
         value::{serialize_values, MoveValue},
     },
@@ -803,6 +804,41 @@ impl AptosVM {
         BLOCK_TRANSACTION_COUNT.observe(count as f64);
         Ok(res)
     }
+
+    /// Executes `function_name` of `module_id` with the BCS serialized `args` over `state_view`,
+    /// and returns the BCS serialized return values. Changes made by the function are discarded.
+    /// The execution is metered with the on-chain gas schedule and aborts once `max_gas_amount`
+    /// is used. Visibility isn't checked, callers restrict which functions can be called.
+    pub fn execute_view_function(
+        state_view: &impl StateView,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        type_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+        max_gas_amount: u64,
+    ) -> Result<Vec<Vec<u8>>, VMStatus> {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let gas_schedule = vm.0.get_gas_schedule(&log_context)?;
+        let mut gas_status = GasStatus::new(gas_schedule, GasUnits::new(max_gas_amount));
+
+        let resolver = state_view.as_move_resolver();
+        let mut session = vm.0.new_session(&resolver, SessionId::void());
+        let return_values = session
+            .execute_function_bypass_visibility(
+                module_id,
+                function_name,
+                type_args,
+                args,
+                &mut gas_status,
+            )
+            .map_err(|e| e.into_vm_status())?;
+        Ok(return_values
+            .return_values
+            .into_iter()
+            .map(|(bytes, _layout)| bytes)
+            .collect())
+    }
 }
 
 // Executor external API
@@ -11,6 +11,7 @@ module AptosFramework::Genesis {
     use AptosFramework::ChainId;
     use AptosFramework::Reconfiguration;
     use AptosFramework::Stake;
+    use AptosFramework::Table;
     use AptosFramework::TestCoin::{Self, TestCoin};
     use AptosFramework::Timestamp;
     use AptosFramework::TransactionFee;
@@ -80,6 +81,8 @@ module AptosFramework::Genesis {
         Account::rotate_authentication_key_internal(core_resource_account, copy core_resource_account_auth_key);
         // initialize the core framework account
         let core_framework_account = Account::create_core_framework_account();
+        // Record the types of the tables created from now on
+        Table::initialize(&core_framework_account);
 
         // Consensus config setup
         ConsensusConfig::initialize(core_resource_account);
@@ -7,12 +7,16 @@
 
 module AptosFramework::Table {
     use Std::Errors;
+    use Std::Signer;
+    use AptosFramework::TypeInfo;
 
     // native code raises this with Errors::invalid_arguments()
     const EALREADY_EXISTS: u64 = 100;
     // native code raises this with Errors::invalid_arguments()
     const ENOT_FOUND: u64 = 101;
     const ENOT_EMPTY: u64 = 102;
+    /// The table infos can only be published under the framework account
+    const ENOT_APTOS_FRAMEWORK: u64 = 103;
 
     /// Type of tables
     struct Table<phantom K: copy + drop, phantom V> has store {
@@ -20,10 +24,30 @@ module AptosFramework::Table {
         length: u64,
     }
 
+    /// The types of the keys and values of a table, as type names such as `u64` or
+    /// `0x1::ASCII::String`, so that the items of the table can be decoded knowing only its handle
+    struct TableInfo has copy, drop, store {
+        key_type: vector<u8>,
+        value_type: vector<u8>,
+    }
+
+    /// The infos of the tables, by handle, published under the framework account at genesis
+    struct TableInfos has key {
+        infos: Table<u128, TableInfo>,
+    }
+
+    /// Publishes the table infos, which every table created from then on is recorded in
+    public fun initialize(framework: &signer) acquires TableInfos {
+        assert!(Signer::address_of(framework) == @AptosFramework, Errors::requires_address(ENOT_APTOS_FRAMEWORK));
+        move_to(framework, TableInfos { infos: new() })
+    }
+
     /// Create a new Table.
-    public fun new<K: copy + drop, V: store>(): Table<K, V> {
+    public fun new<K: copy + drop, V: store>(): Table<K, V> acquires TableInfos {
+        let handle = new_table_handle();
+        record_table_info<K, V>(handle);
         Table{
-            handle: new_table_handle(),
+            handle,
             length: 0,
         }
     }
@@ -93,9 +117,34 @@ module AptosFramework::Table {
         drop_unchecked_box<K, V, Box<V>>(table)
     }
 
+    #[test(framework = @AptosFramework)]
+    fun test_table_info(framework: signer) acquires TableInfos {
+        initialize(&framework);
+        let table = new<u64, vector<u8>>();
+        let infos = &borrow_global<TableInfos>(@AptosFramework).infos;
+        let info = &borrow_box<u128, TableInfo, Box<TableInfo>>(infos, table.handle).val;
+        assert!(info.key_type == b"u64", 0);
+        assert!(info.value_type == b"vector<u8>", 1);
+        drop_unchecked(table)
+    }
+
     // ======================================================================================================
     // Internal API
 
+    /// Records the types of the table `handle` once the table infos are published, i.e. after
+    /// genesis. The length of the infos table isn't maintained, so that creating a table only
+    /// writes the item holding its info and not the `TableInfos` resource.
+    fun record_table_info<K, V>(handle: u128) acquires TableInfos {
+        if (exists<TableInfos>(@AptosFramework)) {
+            let infos = &mut borrow_global_mut<TableInfos>(@AptosFramework).infos;
+            let info = TableInfo {
+                key_type: TypeInfo::type_name<K>(),
+                value_type: TypeInfo::type_name<V>(),
+            };
+            add_box<u128, TableInfo, Box<TableInfo>>(infos, handle, Box{ val: info });
+        }
+    }
+
     /// Wrapper for values. Required for making values appear as resources in the implementation.
     struct Box<V> has key, drop, store {
         val: V
@@ -19,6 +19,9 @@ module AptosFramework::TypeInfo {
 
     public native fun type_of<T>(): TypeInfo;
 
+    /// The name of any type, e.g. `u64`, `vector<u8>` or `0x1::ASCII::String`
+    public native fun type_name<T>(): vector<u8>;
+
     #[test]
     fun test() {
         let type_info = type_of<TypeInfo>();
@@ -26,4 +29,10 @@ module AptosFramework::TypeInfo {
         assert!(module_name(&type_info) == b"TypeInfo", 1);
         assert!(struct_name(&type_info) == b"TypeInfo", 2);
     }
+
+    #[test]
+    fun test_type_name() {
+        assert!(type_name<u64>() == b"u64", 0);
+        assert!(type_name<vector<address>>() == b"vector<address>", 1);
+    }
 }
@@ -7,7 +7,8 @@
  * the next epoch.
  * 4. Validate and gain rewards.
  * 5. At any point, if the validator operator wants to switch validator node operator, they can call
- * Stake::rotate_consensus_key.
+ * Stake::rotate_consensus_key. The operator can also register a BLS12-381 consensus key with
+ * Stake::rotate_consensus_bls_key, which lets the validator set aggregate its signatures.
  * 6. When lockup has expired, validator (or the owner of owner capability) can choose to either (1) increase the lockup
  * to keep validating and receiving rewards, or (2) call Stake::unlock to unlock their stake and Stake::withdraw to
  * withdraw in the next epoch.
@@ -101,6 +102,11 @@ module AptosFramework::Stake {
         network_address: vector<u8>,
         // to make it compatible with previous definition, remove later
         fullnode_address: vector<u8>,
+        // BLS12-381 consensus key, empty if none was registered
+        consensus_bls_pubkey: vector<u8>,
+        // Proof-of-possession of the BLS12-381 consensus key. It's verified by the nodes, which
+        // ignore the key if the proof is invalid.
+        consensus_bls_proof_of_possession: vector<u8>,
     }
 
     /// Consensus information per validator, stored in ValidatorSet.
@@ -176,6 +182,8 @@ module AptosFramework::Stake {
             consensus_pubkey,
             network_address,
             fullnode_address,
+            consensus_bls_pubkey: Vector::empty(),
+            consensus_bls_proof_of_possession: Vector::empty(),
         });
         move_to(account, OwnerCapability {
            pool_address: account_address,
@@ -259,6 +267,22 @@ module AptosFramework::Stake {
         validator_info.consensus_pubkey = consensus_pubkey;
     }
 
+    /// Rotate the BLS12-381 consensus key of the validator, it'll take effect in next epoch.
+    public(script) fun rotate_consensus_bls_key(
+        account: &signer,
+        pool_address: address,
+        consensus_bls_pubkey: vector<u8>,
+        proof_of_possession: vector<u8>,
+    ) acquires StakePool, ValidatorConfig {
+        let stake_pool = borrow_global<StakePool>(pool_address);
+        assert!(Signer::address_of(account) == stake_pool.operator_address, Errors::invalid_argument(ENOT_OPERATOR));
+
+        assert!(exists<ValidatorConfig>(pool_address), Errors::not_published(EVALIDATOR_CONFIG));
+        let validator_info = borrow_global_mut<ValidatorConfig>(pool_address);
+        validator_info.consensus_bls_pubkey = consensus_bls_pubkey;
+        validator_info.consensus_bls_proof_of_possession = proof_of_possession;
+    }
+
     /// Similar to increase_lockup_with_cap but will use ownership capability from the signing account.
     public(script) fun increase_lockup(
         account: &signer,
@@ -668,8 +692,9 @@ module AptosFramework::Stake {
         assert!(is_current_validator(validator_1_address), 0);
         assert!(is_current_validator(validator_2_address), 1);
 
-        // Validator 1 rotates consensus key. Validator 2 leaves. Validator 3 joins.
+        // Validator 1 rotates consensus keys. Validator 2 leaves. Validator 3 joins.
         rotate_consensus_key(&validator_1, validator_1_address, x"1234");
+        rotate_consensus_bls_key(&validator_1, validator_1_address, x"5678", x"9abc");
         leave_validator_set(&validator_2, validator_2_address);
         join_validator_set(&validator_3, validator_3_address);
         // Validator 2 is not effectively removed until next epoch.
@@ -684,6 +709,8 @@ module AptosFramework::Stake {
         assert!(!is_current_validator(validator_2_address), 4);
         assert!(is_current_validator(validator_3_address), 5);
         assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.consensus_pubkey == x"1234", 0);
+        assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.consensus_bls_pubkey == x"5678", 0);
+        assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.consensus_bls_proof_of_possession == x"9abc", 0);
 
         // validators without enough stake will be removed
         Timestamp::update_global_time_for_test(100001000000);
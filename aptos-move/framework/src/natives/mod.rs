@@ -13,6 +13,7 @@ use move_deps::{
 
 pub mod cost {
     pub const APTOS_LIB_TYPE_OF: u64 = 10;
+    pub const APTOS_LIB_TYPE_NAME: u64 = 10;
     pub const APTOS_SIP_HASH: u64 = 10;
 }
 
@@ -35,6 +36,7 @@ pub fn all_natives(framework_addr: AccountAddress) -> NativeFunctionTable {
             signature::native_ed25519_signature_verification,
         ),
         ("TypeInfo", "type_of", type_info::type_of),
+        ("TypeInfo", "type_name", type_info::type_name),
         ("Hash", "sip_hash", hash::native_sip_hash),
     ];
     NATIVES
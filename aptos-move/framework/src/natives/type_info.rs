@@ -42,6 +42,24 @@ pub fn type_of(
     }
 }
 
+/// Returns the name of the type, as displayed by its `TypeTag`
+pub fn type_name(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.is_empty());
+
+    let cost = GasCost::new(super::cost::APTOS_LIB_TYPE_NAME, 1).total();
+
+    let type_tag = context.type_to_type_tag(&ty_args[0])?;
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(type_tag.to_string().into_bytes())],
+    ))
+}
+
 fn type_of_internal(struct_tag: &StructTag) -> Result<SmallVec<[Value; 1]>, std::fmt::Error> {
     let mut name = struct_tag.name.to_string();
     if let Some(first_ty) = struct_tag.type_params.first() {
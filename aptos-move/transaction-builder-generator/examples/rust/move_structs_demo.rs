@@ -0,0 +1,36 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_sdk::{
+    move_types::{account_address::AccountAddress, parser::parse_type_tag},
+    rest_client::Client,
+};
+use move_structs::coin::{CoinInfo, CoinStore};
+use url::Url;
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+
+    // The BCS-encoded `0x1::Coin::CoinInfo<0x1::TestCoin::TestCoin>` resource of a genesis
+    let path = args.next().expect("Path of the resource");
+    let coin_info: CoinInfo = bcs::from_bytes(&std::fs::read(path).unwrap()).unwrap();
+    println!(
+        "{} {} {} {:?}",
+        coin_info.name, coin_info.symbol, coin_info.decimals, coin_info.supply
+    );
+
+    // The phantom type argument is only part of the struct tag
+    let test_coin = parse_type_tag("0x1::TestCoin::TestCoin").unwrap();
+    println!("{}", CoinInfo::struct_tag_with(vec![test_coin.clone()]));
+
+    // Fetches the `0x1::Coin::CoinStore<0x1::TestCoin::TestCoin>` resource of an account
+    let url = Url::parse(&args.next().expect("URL of the REST API")).unwrap();
+    let address =
+        AccountAddress::from_hex_literal(&args.next().expect("Address of the account")).unwrap();
+    let coin_store = CoinStore::fetch(&Client::new(url), address, vec![test_coin])
+        .await
+        .unwrap()
+        .into_inner();
+    println!("{}", coin_store.coin.value);
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! # Code generator for Rust types of Move structs
+//!
+//! '''bash
+//! cargo run -p transaction-builder-generator --bin generate-move-structs -- --help
+//! '''
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+use transaction_builder_generator as buildgen;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "Move struct generator",
+    about = "Generate Rust types for the key and store structs of compiled Move modules"
+)]
+struct Options {
+    /// Path to the directories containing compiled Move modules (`.mv` files), such as the
+    /// `build/<package>/bytecode_modules` directory of a Move package.
+    module_directories: Vec<PathBuf>,
+
+    /// File where to write the generated code (otherwise print code on stdout).
+    #[structopt(long)]
+    output_file: Option<PathBuf>,
+
+    /// Path of the `move_core_types` crate in the generated code.
+    #[structopt(long, default_value = "aptos_sdk::move_types")]
+    move_types_path: String,
+
+    /// Path of the `aptos_rest_client` crate in the generated code.
+    #[structopt(long, default_value = "aptos_sdk::rest_client")]
+    rest_client_path: String,
+}
+
+fn main() {
+    let options = Options::from_args();
+    let modules = buildgen::read_modules(&options.module_directories)
+        .expect("Failed to read modules in directory");
+
+    match options.output_file {
+        Some(path) => {
+            let mut file = std::fs::File::create(path).expect("Failed to create output file");
+            buildgen::move_structs::output(
+                &mut file,
+                &modules,
+                &options.move_types_path,
+                &options.rest_client_path,
+            )
+            .unwrap()
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            buildgen::move_structs::output(
+                &mut out,
+                &modules,
+                &options.move_types_path,
+                &options.rest_client_path,
+            )
+            .unwrap()
+        }
+    }
+}
@@ -2,8 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_types::transaction::ScriptABI;
+use move_deps::move_binary_format::{access::ModuleAccess, CompiledModule};
 use serde_generate::CustomCode;
-use std::{ffi::OsStr, fs, io::Read, path::Path};
+use std::{
+    ffi::OsStr,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
 
 /// Support for code-generation in C++17.
 pub mod cpp;
@@ -13,6 +19,8 @@ pub mod csharp;
 pub mod golang;
 /// Support for code-generation in Java 8.
 pub mod java;
+/// Support for generating Rust types of Move structs.
+pub mod move_structs;
 /// Support for code-generation in Python 3.
 pub mod python3;
 /// Support for code-generation in Rust.
@@ -81,6 +89,38 @@ pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<ScriptABI
     Ok(abis)
 }
 
+fn get_module_paths(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut module_paths = Vec::new();
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                module_paths.append(&mut get_module_paths(&path)?);
+            } else if let Some("mv") = path.extension().and_then(OsStr::to_str) {
+                module_paths.push(path);
+            }
+        }
+    }
+    Ok(module_paths)
+}
+
+/// Read all compiled Move modules (`.mv` files) in the specified directories, e.g. the
+/// `build/<package>/bytecode_modules` directory of a Move package.
+pub fn read_modules(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<CompiledModule>> {
+    let mut modules = Vec::new();
+    for dir in dir_paths.iter() {
+        for path in get_module_paths(dir.as_ref())? {
+            let bytes = fs::read(&path)?;
+            modules.push(CompiledModule::deserialize(&bytes).map_err(|err| {
+                anyhow::anyhow!("Failed to deserialize {}: {:?}", path.display(), err)
+            })?);
+        }
+    }
+    // Sort modules by alphabetical order.
+    modules.sort_by_key(|module| module.self_id());
+    Ok(modules)
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
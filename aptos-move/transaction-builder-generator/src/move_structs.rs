@@ -0,0 +1,377 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use heck::SnakeCase;
+use move_deps::{
+    move_binary_format::{
+        access::ModuleAccess,
+        file_format::{
+            CompiledModule, SignatureToken, StructDefinition, StructFieldInformation,
+            StructHandleIndex,
+        },
+    },
+    move_core_types::{account_address::AccountAddress, language_storage::CORE_CODE_ADDRESS},
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Error, ErrorKind, Result, Write},
+};
+
+/// Output Rust definitions of the structs with `key` or `store` of the given modules.
+/// Each Move module becomes a Rust module of the same name in snake case. Struct fields are
+/// typed after their Move types, so that the structs decode the BCS encoding of Move values.
+///
+/// `move_types` and `rest_client` are the paths of the `move_core_types` and
+/// `aptos_rest_client` crates in the generated code, e.g. `aptos_sdk::move_types` and
+/// `aptos_sdk::rest_client`.
+pub fn output(
+    out: &mut dyn Write,
+    modules: &[CompiledModule],
+    move_types: &str,
+    rest_client: &str,
+) -> Result<()> {
+    let mut module_names = BTreeMap::new();
+    for module in modules {
+        let name = module.self_id().name().to_string();
+        if let Some(other) = module_names.insert(name.clone(), *module.address()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Modules {}::{} and {}::{} would have the same Rust module name",
+                    other.short_str_lossless(),
+                    name,
+                    module.address().short_str_lossless(),
+                    name
+                ),
+            ));
+        }
+    }
+
+    let mut emitter = StructEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        module_names,
+    };
+    emitter.output_preamble(move_types, rest_client)?;
+    for module in modules {
+        emitter.output_module(module)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the struct code generator.
+struct StructEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Address of each module to generate, by name.
+    module_names: BTreeMap<String, AccountAddress>,
+}
+
+impl<T> StructEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self, move_types: &str, rest_client: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// Rust definitions of Move structs, for decoding BCS-encoded resources and events.
+//
+// This code was generated by compiling Move modules with the tool `transaction-builder-generator`.
+
+#[allow(unused_imports)]
+use {}::{{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{{StructTag, TypeTag}},
+    move_resource::{{MoveResource, MoveStructType}},
+}};
+#[allow(unused_imports)]
+use {}::{{Client, Response}};
+use serde::{{Deserialize, Serialize}};"#,
+            move_types, rest_client
+        )
+    }
+
+    fn output_module(&mut self, module: &CompiledModule) -> Result<()> {
+        let structs = module
+            .struct_defs()
+            .iter()
+            .filter(|def| {
+                let abilities = module.struct_handle_at(def.struct_handle).abilities;
+                matches!(def.field_information, StructFieldInformation::Declared(_))
+                    && (abilities.has_key() || abilities.has_store())
+            })
+            .collect::<Vec<_>>();
+        if structs.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            self.out,
+            "\npub mod {} {{",
+            quote_name(&module.self_id().name().as_str().to_snake_case())
+        )?;
+        self.out.indent();
+        writeln!(self.out, "#[allow(unused_imports)]\nuse super::*;")?;
+        for def in structs {
+            self.output_struct(module, def)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(&mut self, module: &CompiledModule, def: &StructDefinition) -> Result<()> {
+        let handle = module.struct_handle_at(def.struct_handle);
+        let name = module.identifier_at(handle.name).as_str();
+        let type_params = handle
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(index, _)| format!("T{}", index))
+            .collect::<Vec<_>>();
+        let generics = quote_generics(type_params.iter().cloned());
+        let fields = match &def.field_information {
+            StructFieldInformation::Declared(fields) => fields,
+            StructFieldInformation::Native => unreachable!("native structs are filtered out"),
+        };
+
+        writeln!(
+            self.out,
+            "\n/// `{}::{}::{}`",
+            module.address().short_str_lossless(),
+            module.self_id().name(),
+            name
+        )?;
+        writeln!(
+            self.out,
+            "#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]\npub struct {}{} {{",
+            name, generics
+        )?;
+        self.out.indent();
+        let mut used_type_params = BTreeSet::new();
+        for field in fields {
+            writeln!(
+                self.out,
+                "pub {}: {},",
+                quote_name(module.identifier_at(field.name).as_str()),
+                self.quote_type(module, &field.signature.0)?
+            )?;
+            collect_type_params(module, &field.signature.0, &mut used_type_params);
+        }
+        let unused_type_params = handle
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(index, param)| {
+                !param.is_phantom && !used_type_params.contains(&(*index as u16))
+            })
+            .map(|(index, _)| format!("T{}", index))
+            .collect::<Vec<_>>();
+        if !unused_type_params.is_empty() {
+            // Move allows type parameters that no field uses, Rust doesn't
+            writeln!(
+                self.out,
+                "#[serde(skip)]\npub _type_params: std::marker::PhantomData<({},)>,",
+                unused_type_params.join(", ")
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        let address = quote_address(module.address());
+        let module_name = module.self_id().name().to_string();
+        if handle.type_parameters.is_empty() {
+            writeln!(
+                self.out,
+                r#"
+impl MoveStructType for {name} {{
+    const ADDRESS: AccountAddress = {address};
+    const MODULE_NAME: &'static IdentStr = ident_str!("{module}");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("{name}");
+}}"#,
+                name = name,
+                address = address,
+                module = module_name,
+            )?;
+            if handle.abilities.has_key() {
+                writeln!(self.out, "\nimpl MoveResource for {} {{}}", name)?;
+            }
+        } else {
+            // The type arguments of a generic struct are only known at runtime, even the phantom
+            // ones which the Rust type leaves out
+            writeln!(
+                self.out,
+                r#"
+impl{generics} {name}{generics} {{
+    pub fn struct_tag_with(type_params: Vec<TypeTag>) -> StructTag {{
+        StructTag {{
+            address: {address},
+            module: ident_str!("{module}").to_owned(),
+            name: ident_str!("{name}").to_owned(),
+            type_params,
+        }}
+    }}"#,
+                name = name,
+                generics = generics,
+                address = address,
+                module = module_name,
+            )?;
+            if handle.abilities.has_key() {
+                writeln!(
+                    self.out,
+                    r#"
+    /// Fetches the `{name}` resource of `address` with the type arguments `type_params`
+    pub async fn fetch(
+        client: &Client,
+        address: AccountAddress,
+        type_params: Vec<TypeTag>,
+    ) -> anyhow::Result<Response<Self>>
+    where
+        Self: serde::de::DeserializeOwned,
+    {{
+        client
+            .get_account_resource_bcs(address, &Self::struct_tag_with(type_params).to_string())
+            .await
+    }}"#,
+                    name = name,
+                )?;
+            }
+            writeln!(self.out, "}}")?;
+        }
+        Ok(())
+    }
+
+    fn quote_type(&self, module: &CompiledModule, token: &SignatureToken) -> Result<String> {
+        use SignatureToken::*;
+        Ok(match token {
+            Bool => "bool".into(),
+            U8 => "u8".into(),
+            U64 => "u64".into(),
+            U128 => "u128".into(),
+            Address => "AccountAddress".into(),
+            Vector(token) => format!("Vec<{}>", self.quote_type(module, token)?),
+            Struct(index) => self.quote_struct(module, *index, &[])?,
+            StructInstantiation(index, type_args) => {
+                self.quote_struct(module, *index, type_args)?
+            }
+            TypeParameter(index) => format!("T{}", index),
+            Signer | Reference(_) | MutableReference(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Stored structs cannot have fields of type {:?}", token),
+                ))
+            }
+        })
+    }
+
+    fn quote_struct(
+        &self,
+        module: &CompiledModule,
+        index: StructHandleIndex,
+        type_args: &[SignatureToken],
+    ) -> Result<String> {
+        let handle = module.struct_handle_at(index);
+        let module_handle = module.module_handle_at(handle.module);
+        let address = *module.address_identifier_at(module_handle.address);
+        let module_name = module.identifier_at(module_handle.name).as_str();
+        let name = module.identifier_at(handle.name).as_str();
+        // Phantom type parameters don't change the layout, so they're left out
+        let type_args = handle
+            .type_parameters
+            .iter()
+            .zip(type_args)
+            .filter(|(param, _)| !param.is_phantom)
+            .map(|(_, type_arg)| self.quote_type(module, type_arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Both have the BCS encoding of their Rust counterpart
+        if address == CORE_CODE_ADDRESS {
+            match (module_name, name) {
+                ("ASCII", "String") => return Ok("String".into()),
+                ("Option", "Option") => return Ok(format!("Option<{}>", type_args[0])),
+                _ => (),
+            }
+        }
+        if self.module_names.get(module_name) != Some(&address) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Struct {}::{}::{} is used by {}::{} but its module is not generated",
+                    address.short_str_lossless(),
+                    module_name,
+                    name,
+                    module.address().short_str_lossless(),
+                    module.self_id().name()
+                ),
+            ));
+        }
+        Ok(format!(
+            "super::{}::{}{}",
+            quote_name(&module_name.to_snake_case()),
+            name,
+            quote_generics(type_args.into_iter())
+        ))
+    }
+}
+
+/// Collects the type parameters used by a field of type `token`, leaving out the ones only
+/// passed to phantom type parameters, which the Rust types don't have
+fn collect_type_params(
+    module: &CompiledModule,
+    token: &SignatureToken,
+    params: &mut BTreeSet<u16>,
+) {
+    use SignatureToken::*;
+    match token {
+        TypeParameter(index) => {
+            params.insert(*index);
+        }
+        Vector(token) => collect_type_params(module, token, params),
+        StructInstantiation(index, type_args) => {
+            let handle = module.struct_handle_at(*index);
+            for (param, type_arg) in handle.type_parameters.iter().zip(type_args) {
+                if !param.is_phantom {
+                    collect_type_params(module, type_arg, params);
+                }
+            }
+        }
+        Bool | U8 | U64 | U128 | Address | Signer | Struct(_) | Reference(_)
+        | MutableReference(_) => (),
+    }
+}
+
+fn quote_generics(params: impl Iterator<Item = String>) -> String {
+    let params = params.collect::<Vec<_>>();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+fn quote_address(address: &AccountAddress) -> String {
+    format!(
+        "AccountAddress::new([{}])",
+        address
+            .to_vec()
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Escapes the Move identifiers that are Rust keywords.
+fn quote_name(name: &str) -> String {
+    match name {
+        "as" | "box" | "break" | "const" | "continue" | "else" | "enum" | "extern" | "false"
+        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move"
+        | "mut" | "pub" | "ref" | "return" | "static" | "struct" | "trait" | "true" | "type"
+        | "unsafe" | "use" | "where" | "while" | "async" | "await" | "dyn" | "abstract"
+        | "become" | "final" | "macro" | "override" | "priv" | "typeof" | "unsized" | "virtual"
+        | "yield" | "try" => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
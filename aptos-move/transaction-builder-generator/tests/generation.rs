@@ -1,15 +1,32 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_types::transaction::ScriptABI;
-use cached_framework_packages::abis;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::aptos_root_address,
+    state_store::state_key::StateKey,
+    transaction::{ChangeSet, ScriptABI},
+    write_set::WriteOp,
+};
+use cached_framework_packages::{abis, modules};
+use move_deps::move_core_types::{
+    language_storage::{ResourceKey, TypeTag, CORE_CODE_ADDRESS},
+    parser::parse_type_tag,
+};
 use serde_generate as serdegen;
 use serde_generate::SourceInstaller as _;
 use serde_reflection::Registry;
-use std::{io::Write, process::Command};
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    process::Command,
+    thread,
+};
 use tempfile::tempdir;
 use transaction_builder_generator as buildgen;
 use transaction_builder_generator::SourceInstaller as _;
+use vm_genesis::GenesisOptions;
 
 fn get_aptos_registry() -> Registry {
     let path = "../../testsuite/generate-format/tests/staged/aptos.yaml";
@@ -29,6 +46,192 @@ const EXPECTED_OUTPUT: &str = "224 1 161 28 235 11 1 0 0 0 7 1 0 2 2 2 4 3 6 16
 
 const EXPECTED_SCRIPT_FUN_OUTPUT: &str = "3 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 8 84 101 115 116 67 111 105 110 8 116 114 97 110 115 102 101 114 0 2 32 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 8 135 214 18 0 0 0 0 0 \n";
 
+#[test]
+fn test_rust_move_structs() {
+    let mut out = Vec::new();
+    buildgen::move_structs::output(&mut out, modules(), "move_core_types", "aptos_rest_client")
+        .unwrap();
+    let code = String::from_utf8(out).unwrap();
+
+    for expected in [
+        "pub mod coin {",
+        "pub struct CoinStore {",
+        "pub coin: super::coin::Coin,",
+        "pub deposit_events: super::event::EventHandle,",
+        "pub supply: Option<u64>,",
+        "pub name: String,",
+        "impl MoveStructType for DepositEvent {",
+        "impl MoveResource for CoinEvents {}",
+        // Structs with only phantom type parameters are generic too
+        "impl CoinStore {",
+        "pub fn struct_tag_with(type_params: Vec<TypeTag>) -> StructTag {",
+        // `Table::Box<V>` is a generic resource
+        "pub struct Box<T0> {",
+        "pub async fn fetch(",
+        // `BigVector<T>` only passes `T` to the phantom type parameters of `Table`
+        "pub struct BigVector<T0> {",
+        "pub _type_params: std::marker::PhantomData<(T0,)>,",
+    ] {
+        assert!(code.contains(expected), "missing `{}`", expected);
+    }
+    // Structs that are neither resources nor stored are left out
+    assert!(!code.contains("pub struct Result {"));
+    // A struct tag without the type arguments would name no resource
+    assert!(!code.contains("impl MoveStructType for CoinStore {"));
+    assert!(!code.contains("impl MoveResource for CoinInfo {}"));
+}
+
+/// Returns the BCS encoding of the resource `resource_type` of `address` written by `change_set`
+fn get_resource(change_set: &ChangeSet, address: AccountAddress, resource_type: &str) -> Vec<u8> {
+    let struct_tag = match parse_type_tag(resource_type).unwrap() {
+        TypeTag::Struct(struct_tag) => struct_tag,
+        _ => unreachable!(),
+    };
+    let resource_key = StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
+        address, struct_tag,
+    )));
+    change_set
+        .write_set()
+        .iter()
+        .find_map(|(key, op)| match op {
+            WriteOp::Value(bytes) if *key == resource_key => Some(bytes.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("Missing {} of {}", resource_type, address))
+}
+
+/// Serves `body` as the BCS response of the REST API to a single request, and returns the
+/// request line it answered
+fn serve_bcs_once(listener: TcpListener, body: Vec<u8>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let len = stream.read(&mut buf).unwrap();
+            assert_ne!(len, 0, "The request ended before its headers");
+            request.extend_from_slice(&buf[..len]);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/x-bcs\r\n\
+             Content-Length: {}\r\n\
+             X-Aptos-Chain-Id: 4\r\n\
+             X-Aptos-Epoch: 1\r\n\
+             X-Aptos-Ledger-Version: 0\r\n\
+             X-Aptos-Ledger-TimestampUsec: 0\r\n\
+             Connection: close\r\n\r\n",
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(&body).unwrap();
+        let request = String::from_utf8(request).unwrap();
+        request.lines().next().unwrap().to_owned()
+    })
+}
+
+/// Compiles the types generated for the framework, decodes a resource of a genesis with them, and
+/// fetches a resource with a phantom type argument through the generated API
+#[test]
+fn test_that_rust_move_structs_compile_and_decode() {
+    let change_set = vm_genesis::generate_genesis_change_set_for_testing(GenesisOptions::Compiled);
+    let coin_info_bytes = get_resource(
+        &change_set,
+        CORE_CODE_ADDRESS,
+        "0x1::Coin::CoinInfo<0x1::TestCoin::TestCoin>",
+    );
+    let coin_store_bytes = get_resource(
+        &change_set,
+        aptos_root_address(),
+        "0x1::Coin::CoinStore<0x1::TestCoin::TestCoin>",
+    );
+    // `Coin` is the first field of `CoinStore`, and its only field is the balance
+    let mut balance = [0u8; 8];
+    balance.copy_from_slice(&coin_store_bytes[..8]);
+    let balance = u64::from_le_bytes(balance);
+
+    let dir = tempdir().unwrap();
+    let crate_dir_path = dir.path().join("move_structs");
+    std::fs::create_dir_all(crate_dir_path.join("src")).unwrap();
+    let sdk_path = std::env::current_dir().unwrap().join("../../sdk");
+    let mut cargo = std::fs::File::create(crate_dir_path.join("Cargo.toml")).unwrap();
+    write!(
+        cargo,
+        r#"[package]
+name = "move_structs"
+version = "0.1.0"
+edition = "2018"
+
+[dependencies]
+anyhow = "1.0.57"
+aptos-sdk = {{ path = "{}" }}
+bcs = "0.1.3"
+serde = {{ version = "1.0.137", features = ["derive"] }}
+tokio = {{ version = "1.18.2", features = ["full"] }}
+url = "2.2.2"
+
+[[bin]]
+name = "move_structs_demo"
+path = "src/move_structs_demo.rs"
+test = false
+"#,
+        sdk_path.display()
+    )
+    .unwrap();
+    let mut source = std::fs::File::create(crate_dir_path.join("src/lib.rs")).unwrap();
+    buildgen::move_structs::output(
+        &mut source,
+        modules(),
+        "aptos_sdk::move_types",
+        "aptos_sdk::rest_client",
+    )
+    .unwrap();
+    std::fs::copy(
+        "examples/rust/move_structs_demo.rs",
+        crate_dir_path.join("src/move_structs_demo.rs"),
+    )
+    .unwrap();
+    let coin_info_path = dir.path().join("coin_info.bcs");
+    std::fs::write(&coin_info_path, coin_info_bytes).unwrap();
+
+    // Use a stable `target` dir to avoid downloading and recompiling crates everytime.
+    let target_dir = std::env::current_dir().unwrap().join("../../target");
+    let status = Command::new("cargo")
+        .current_dir(&crate_dir_path)
+        .arg("build")
+        .arg("--target-dir")
+        .arg(target_dir.clone())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = serve_bcs_once(listener, coin_store_bytes);
+    let output = Command::new(target_dir.join("debug/move_structs_demo"))
+        .arg(coin_info_path)
+        .arg(url)
+        .arg(aptos_root_address().to_hex_literal())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        std::str::from_utf8(&output.stdout).unwrap(),
+        format!(
+            "Test Coin TC 6 None\n0x1::Coin::CoinInfo<0x1::TestCoin::TestCoin>\n{}\n",
+            balance
+        ),
+    );
+    assert_eq!(
+        server.join().unwrap(),
+        format!(
+            "GET /accounts/{}/resource/0x1::Coin::CoinStore%3C0x1::TestCoin::TestCoin%3E HTTP/1.1",
+            aptos_root_address()
+        ),
+    );
+}
+
 #[test]
 fn test_typescript_replace_keywords() {
     let yamlpath = "./tests/keyworded_registry.yaml";
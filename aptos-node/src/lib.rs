@@ -4,15 +4,19 @@
 use aptos_api::runtime::bootstrap as bootstrap_api;
 use aptos_config::{
     config::{
-        AptosDataClientConfig, BaseConfig, DataStreamingServiceConfig, NetworkConfig, NodeConfig,
-        PersistableConfig, StorageServiceConfig,
+        AptosDataClientConfig, BaseConfig, DataStreamingServiceConfig, LogSinkType, NetworkConfig,
+        NodeConfig, PeerMonitoringServiceConfig, PersistableConfig, StorageServiceConfig,
     },
     network_id::NetworkId,
     utils::get_genesis_txn,
 };
 use aptos_data_client::aptosnet::AptosNetDataClient;
 use aptos_infallible::RwLock;
-use aptos_logger::{prelude::*, Logger};
+use aptos_logger::{
+    prelude::*,
+    sink::{OtlpHttpSink, RotatingFileSink, Sink},
+    Filter, Logger,
+};
 use aptos_metrics::{metric_server, system_information};
 use aptos_state_view::account_with_state_view::AsAccountWithStateView;
 use aptos_telemetry::{
@@ -37,13 +41,20 @@ use data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
 };
-use debug_interface::node_debug_service::NodeDebugService;
+use debug_interface::node_debug_service::{EquivocationEvidenceInfo, NodeDebugService};
 use event_notifications::EventSubscriptionService;
 use executor::{chunk_executor::ChunkExecutor, db_bootstrapper::maybe_bootstrap};
 use futures::{channel::mpsc::channel, stream::StreamExt};
 use mempool_notifications::MempoolNotificationSender;
 use network::application::storage::PeerMetadataStorage;
 use network_builder::builder::NetworkBuilder;
+use peer_monitoring_service_client::{
+    PeerMonitor, PeerMonitoringServiceClient, PeerMonitoringServiceMultiSender,
+    PeerMonitoringServiceNetworkSender,
+};
+use peer_monitoring_service_server::{
+    network::PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer,
+};
 use state_sync_multiplexer::{
     state_sync_v1_network_config, StateSyncMultiplexer, StateSyncRuntimes,
 };
@@ -61,7 +72,7 @@ use std::{
     thread,
     time::Instant,
 };
-use storage_interface::{state_view::LatestDbStateCheckpointView, DbReaderWriter};
+use storage_interface::{state_view::LatestDbStateCheckpointView, DbReader, DbReaderWriter};
 use storage_service::start_storage_service_with_db;
 use storage_service_client::{StorageServiceClient, StorageServiceMultiSender};
 use storage_service_server::{
@@ -81,6 +92,7 @@ pub struct AptosHandle {
     _debug: NodeDebugService,
     _mempool: Runtime,
     _network_runtimes: Vec<Runtime>,
+    _peer_monitoring_service_runtime: Runtime,
     _state_sync_runtimes: StateSyncRuntimes,
     _telemetry_runtime: Runtime,
 }
@@ -100,6 +112,29 @@ pub fn start(config: &NodeConfig, log_file: Option<PathBuf>) {
     if let Some(log_file) = log_file {
         logger.printer(Box::new(FileWriter::new(log_file)));
     }
+    for sink_config in &config.logger.sinks {
+        let sink: Box<dyn Sink> = match &sink_config.sink {
+            LogSinkType::RotatingFile {
+                path,
+                max_file_size_bytes,
+                max_rotated_files,
+            } => Box::new(
+                RotatingFileSink::new(path.clone(), *max_file_size_bytes, *max_rotated_files)
+                    .expect("Unable to open rotating log file"),
+            ),
+            LogSinkType::OtlpHttp {
+                endpoint,
+                batch_size,
+            } => Box::new(
+                OtlpHttpSink::new(endpoint, *batch_size).expect("Invalid OTLP log endpoint"),
+            ),
+        };
+        logger.add_sink(
+            sink_config.name.clone(),
+            sink,
+            Filter::builder().parse(&sink_config.filter).build(),
+        );
+    }
     let logger = Some(logger.build());
 
     // Let's now log some important information, since the logger is set up
@@ -365,6 +400,43 @@ fn setup_aptos_data_client(
     (aptos_data_client, aptos_data_client_runtime)
 }
 
+fn setup_peer_monitoring_service(
+    config: PeerMonitoringServiceConfig,
+    server_network_handles: Vec<PeerMonitoringServiceNetworkEvents>,
+    client_network_handles: HashMap<NetworkId, PeerMonitoringServiceNetworkSender>,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+    db_reader: Arc<dyn DbReader>,
+) -> Runtime {
+    // Create a new peer monitoring service runtime
+    let peer_monitoring_service_runtime = Builder::new_multi_thread()
+        .thread_name("peer-monitoring-service")
+        .enable_all()
+        .build()
+        .expect("Failed to start the peer monitoring service runtime.");
+
+    // Spawn all peer monitoring service servers on the same runtime
+    for events in server_network_handles {
+        let service = PeerMonitoringServiceServer::new(
+            config.clone(),
+            peer_monitoring_service_runtime.handle().clone(),
+            events,
+            peer_metadata_storage.clone(),
+            db_reader.clone(),
+        );
+        peer_monitoring_service_runtime.spawn(service.start());
+    }
+
+    // Spawn the peer monitor that measures all connected peers
+    let monitoring_client = PeerMonitoringServiceClient::new(
+        PeerMonitoringServiceMultiSender::new(client_network_handles),
+        peer_metadata_storage,
+    );
+    let peer_monitor = PeerMonitor::new(config, monitoring_client, TimeService::real());
+    peer_monitoring_service_runtime.spawn(peer_monitor.start());
+
+    peer_monitoring_service_runtime
+}
+
 fn setup_state_sync_storage_service(
     config: StorageServiceConfig,
     network_handles: Vec<StorageServiceNetworkEvents>,
@@ -575,6 +647,8 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
     let mut consensus_network_handles = None;
     let mut storage_service_server_network_handles = vec![];
     let mut storage_service_client_network_handles = HashMap::new();
+    let mut peer_monitoring_service_server_network_handles = vec![];
+    let mut peer_monitoring_service_client_network_handles = HashMap::new();
 
     // Create an event subscription service so that components can be notified of events and reconfigs
     let mut event_subscription_service = EventSubscriptionService::new(
@@ -661,6 +735,18 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
             network_builder.add_client(&storage_service_client::network_endpoint_config());
         storage_service_client_network_handles.insert(network_id, storage_service_sender);
 
+        // Register the peer monitoring service (both server and client) with Network
+        let peer_monitoring_service_events = network_builder.add_service(
+            &peer_monitoring_service_server::network::network_endpoint_config(
+                node_config.peer_monitoring_service.clone(),
+            ),
+        );
+        peer_monitoring_service_server_network_handles.push(peer_monitoring_service_events);
+        let peer_monitoring_service_sender =
+            network_builder.add_client(&peer_monitoring_service_client::network_endpoint_config());
+        peer_monitoring_service_client_network_handles
+            .insert(network_id, peer_monitoring_service_sender);
+
         // Create the endpoints to connect the Network to mempool.
         let (mempool_sender, mempool_events) = network_builder.add_p2p_service(
             &aptos_mempool::network::network_endpoint_config(MEMPOOL_NETWORK_CHANNEL_BUFFER_SIZE),
@@ -688,6 +774,15 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
         network_runtimes.push(runtime);
     }
 
+    // Start the peer monitoring service so that components can select the best peers
+    let peer_monitoring_service_runtime = setup_peer_monitoring_service(
+        node_config.peer_monitoring_service.clone(),
+        peer_monitoring_service_server_network_handles,
+        peer_monitoring_service_client_network_handles,
+        peer_metadata_storage.clone(),
+        db_rw.reader.clone(),
+    );
+
     // TODO set up on-chain discovery network based on UpstreamConfig.fallback_network
     // and pass network handles to mempool/state sync
 
@@ -747,7 +842,7 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
 
         // Initialize and start consensus.
         instant = Instant::now();
-        consensus_runtime = Some(start_consensus(
+        let (runtime, equivocation_evidence_reader) = start_consensus(
             node_config,
             consensus_network_sender,
             consensus_network_events,
@@ -757,7 +852,23 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
             consensus_reconfig_subscription
                 .expect("Consensus requires a reconfiguration subscription!"),
             peer_metadata_storage,
-        ));
+        );
+        consensus_runtime = Some(runtime);
+        debug_if.set_equivocation_evidence_provider(Box::new(move || {
+            Ok(equivocation_evidence_reader
+                .read()?
+                .iter()
+                .map(|evidence| {
+                    Ok(EquivocationEvidenceInfo {
+                        author: evidence.author().map(|author| author.to_string()),
+                        epoch: evidence.epoch(),
+                        round: evidence.round(),
+                        kind: evidence.kind().to_string(),
+                        bcs: hex::encode(bcs::to_bytes(evidence)?),
+                    })
+                })
+                .collect::<Result<_, bcs::Error>>()?)
+        }));
         debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     }
 
@@ -787,6 +898,7 @@ pub fn setup_environment(node_config: &NodeConfig, logger: Option<Arc<Logger>>)
         _debug: debug_if,
         _mempool: mempool,
         _network_runtimes: network_runtimes,
+        _peer_monitoring_service_runtime: peer_monitoring_service_runtime,
         _state_sync_runtimes: state_sync_runtimes,
         _telemetry_runtime: telemery_runtime,
     }
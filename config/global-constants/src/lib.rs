@@ -11,6 +11,7 @@
 /// Definitions of global cryptographic keys (e.g., as held in secure storage)
 pub const APTOS_ROOT_KEY: &str = "aptos_root";
 pub const CONSENSUS_KEY: &str = "consensus";
+pub const CONSENSUS_BLS_KEY: &str = "consensus_bls";
 pub const EXECUTION_KEY: &str = "execution";
 pub const FULLNODE_NETWORK_KEY: &str = "fullnode_network";
 pub const OPERATOR_ACCOUNT: &str = "operator_account";
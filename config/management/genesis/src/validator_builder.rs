@@ -390,14 +390,12 @@ impl ValidatorBuilder {
                 validator.storage(),
                 ChainId::test(),
                 0, // sequence_number
-                validator.config.full_node_networks[0]
-                    .listen_address
-                    .clone(),
+                validator.config.full_node_networks[0].listen_addresses(),
                 validator
                     .config
                     .validator_network
                     .as_ref()
-                    .map(|a| a.listen_address.clone())
+                    .map(|a| a.listen_addresses())
                     .unwrap(),
                 false, // This isn't a reconfiguration
                 false, // Don't disable address validation
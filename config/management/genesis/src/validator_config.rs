@@ -12,10 +12,12 @@ pub struct ValidatorConfig {
     owner_name: String,
     #[structopt(flatten)]
     validator_config: aptos_management::validator_config::ValidatorConfig,
-    #[structopt(long)]
-    validator_address: NetworkAddress,
-    #[structopt(long)]
-    fullnode_address: NetworkAddress,
+    /// Validator network addresses, one per listener (can be repeated)
+    #[structopt(long = "validator-address", required = true, number_of_values = 1)]
+    validator_addresses: Vec<NetworkAddress>,
+    /// Full node network addresses, one per listener (can be repeated)
+    #[structopt(long = "fullnode-address", required = true, number_of_values = 1)]
+    fullnode_addresses: Vec<NetworkAddress>,
     #[structopt(flatten)]
     shared_backend: SharedBackend,
     #[structopt(long, help = "Disables network address validation")]
@@ -37,8 +39,8 @@ impl ValidatorConfig {
 
         let txn = self.validator_config.build_transaction(
             0,
-            self.fullnode_address,
-            self.validator_address,
+            self.fullnode_addresses,
+            self.validator_addresses,
             false,
             self.disable_address_validation,
         )?;
@@ -24,17 +24,19 @@ pub struct SetValidatorConfig {
     #[structopt(flatten)]
     validator_config: aptos_management::validator_config::ValidatorConfig,
     #[structopt(
-        long,
+        long = "validator-address",
+        number_of_values = 1,
         required_unless = "fullnode-address",
-        help = "Validator Network Address"
+        help = "Validator Network Address, can be repeated to advertise multiple listeners"
     )]
-    validator_address: Option<NetworkAddress>,
+    validator_addresses: Vec<NetworkAddress>,
     #[structopt(
-        long,
+        long = "fullnode-address",
+        number_of_values = 1,
         required_unless = "validator-address",
-        help = "Full Node Network Address"
+        help = "Full Node Network Address, can be repeated to advertise multiple listeners"
     )]
-    fullnode_address: Option<NetworkAddress>,
+    fullnode_addresses: Vec<NetworkAddress>,
     #[structopt(flatten)]
     auto_validate: AutoValidate,
     #[structopt(long, help = "Disables network address validation")]
@@ -66,32 +68,27 @@ impl SetValidatorConfig {
             .iter()
             .any(|vi| vi.account_address() == &owner_account);
 
+        // Retrieve the current validator / fullnode addresses and update accordingly
         let validator_config = if in_set {
-            // Retrieve the current validator / fullnode addresses and update accordingly
-            let vc = client.validator_config(owner_account).await?;
-            DecodedValidatorConfig::from_validator_config_resource(&vc)
-                .map(Some)
-                .map_err(|e| {
-                    Error::UnexpectedError(format!("Error parsing validator config: {}", e))
-                })?
+            Some(client.validator_config(owner_account).await?)
         } else {
             None
         };
 
-        let validator_address = if let Some(validator_address) = &self.validator_address {
-            validator_address.clone()
+        let validator_addresses = if !self.validator_addresses.is_empty() {
+            self.validator_addresses.clone()
         } else if let Some(vc) = &validator_config {
-            strip_address(&vc.validator_network_address)
+            validator_addresses(vc)?.iter().map(strip_address).collect()
         } else {
             return Err(Error::UnexpectedError(
                 "Missing validator-network-address".to_string(),
             ));
         };
 
-        let fullnode_address = if let Some(fullnode_address) = &self.fullnode_address {
-            fullnode_address.clone()
+        let fullnode_addresses = if !self.fullnode_addresses.is_empty() {
+            self.fullnode_addresses.clone()
         } else if let Some(vc) = &validator_config {
-            strip_address(&vc.fullnode_network_address)
+            fullnode_addresses(vc)?.iter().map(strip_address).collect()
         } else {
             return Err(Error::UnexpectedError(
                 "Missing fullnode-network-address".to_string(),
@@ -100,8 +97,8 @@ impl SetValidatorConfig {
 
         let txn = self.validator_config.build_transaction(
             sequence_number,
-            fullnode_address,
-            validator_address,
+            fullnode_addresses,
+            validator_addresses,
             validator_config.is_some(),
             self.disable_address_validation,
         )?;
@@ -157,16 +154,14 @@ impl RotateKey {
         let mut storage_key = storage.ed25519_public_from_private(key_name)?;
         let keys_match = match key_name {
             CONSENSUS_KEY => storage_key == validator_config.consensus_public_key,
-            VALIDATOR_NETWORK_KEY => {
-                Some(to_x25519(storage_key.clone())?)
-                    == validator_config
-                        .validator_network_address
-                        .find_noise_proto()
-            }
-            FULLNODE_NETWORK_KEY => {
-                Some(to_x25519(storage_key.clone())?)
-                    == validator_config.fullnode_network_address.find_noise_proto()
-            }
+            VALIDATOR_NETWORK_KEY => addresses_use_key(
+                &validator_config.validator_network_addresses,
+                to_x25519(storage_key.clone())?,
+            ),
+            FULLNODE_NETWORK_KEY => addresses_use_key(
+                &validator_config.fullnode_network_addresses,
+                to_x25519(storage_key.clone())?,
+            ),
             _ => {
                 return Err(Error::UnexpectedError(
                     "Rotate key was called with an unknown key name!".into(),
@@ -181,8 +176,8 @@ impl RotateKey {
         let set_validator_config = SetValidatorConfig {
             json_server: self.json_server.clone(),
             validator_config: self.validator_config.clone(),
-            validator_address: None,
-            fullnode_address: None,
+            validator_addresses: Vec::new(),
+            fullnode_addresses: Vec::new(),
             auto_validate: self.auto_validate.clone(),
             disable_address_validation: true,
         };
@@ -285,8 +280,8 @@ impl ValidatorConfig {
 pub struct DecodedValidatorConfig {
     pub name: String,
     pub consensus_public_key: Ed25519PublicKey,
-    pub validator_network_address: NetworkAddress,
-    pub fullnode_network_address: NetworkAddress,
+    pub validator_network_addresses: Vec<NetworkAddress>,
+    pub fullnode_network_addresses: Vec<NetworkAddress>,
 }
 
 impl DecodedValidatorConfig {
@@ -310,8 +305,8 @@ impl DecodedValidatorConfig {
         Ok(DecodedValidatorConfig {
             name: "".to_string(),
             consensus_public_key: config.consensus_public_key.clone(),
-            fullnode_network_address: fullnode_network_addresses[0].clone(),
-            validator_network_address: validator_network_addresses[0].clone(),
+            fullnode_network_addresses,
+            validator_network_addresses,
         })
     }
 
@@ -322,6 +317,14 @@ impl DecodedValidatorConfig {
     }
 }
 
+/// Returns true iff there's at least one address and every address uses the given network key
+pub fn addresses_use_key(addresses: &[NetworkAddress], network_key: x25519::PublicKey) -> bool {
+    !addresses.is_empty()
+        && addresses
+            .iter()
+            .all(|address| address.find_noise_proto() == Some(network_key))
+}
+
 pub fn fullnode_addresses(
     config: &aptos_types::validator_config::ValidatorConfig,
 ) -> Result<Vec<NetworkAddress>, Error> {
@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{rest_client::RestClient, validator_config::DecodedValidatorConfig};
+use crate::{
+    rest_client::RestClient,
+    validator_config::{addresses_use_key, DecodedValidatorConfig},
+};
 use aptos_global_constants::{
     CONSENSUS_KEY, FULLNODE_NETWORK_KEY, OWNER_ACCOUNT, VALIDATOR_NETWORK_KEY,
 };
@@ -99,20 +102,18 @@ impl VerifyValidatorState {
         // Check if the validator network key held in secure storage
         // matches that registered on-chain.
         let storage_key = storage.ed25519_public_from_private(VALIDATOR_NETWORK_KEY)?;
-        result.validator_network_key_match = Some(
-            Some(to_x25519(storage_key)?)
-                == validator_config
-                    .validator_network_address
-                    .find_noise_proto(),
-        );
+        result.validator_network_key_match = Some(addresses_use_key(
+            &validator_config.validator_network_addresses,
+            to_x25519(storage_key)?,
+        ));
 
         // Check if the fullnode network key held in secure storage
         // matches that registered on-chain.
         let storage_key = storage.ed25519_public_from_private(FULLNODE_NETWORK_KEY)?;
-        result.fullnode_network_key_match = Some(
-            Some(to_x25519(storage_key)?)
-                == validator_config.fullnode_network_address.find_noise_proto(),
-        );
+        result.fullnode_network_key_match = Some(addresses_use_key(
+            &validator_config.fullnode_network_addresses,
+            to_x25519(storage_key)?,
+        ));
 
         Ok(result)
 
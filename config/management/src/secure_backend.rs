@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Error;
-use aptos_config::config::{self, GitHubConfig, OnDiskStorageConfig, Token, VaultConfig};
+use aptos_config::config::{
+    self, GitHubConfig, OnDiskStorageConfig, Pkcs11Config, Token, VaultConfig,
+};
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
@@ -15,6 +17,7 @@ pub const BACKEND: &str = "backend";
 pub const DISK: &str = "disk";
 pub const GITHUB: &str = "github";
 pub const MEMORY: &str = "memory";
+pub const PKCS11: &str = "pkcs11";
 pub const VAULT: &str = "vault";
 
 // Custom timeouts for vault backend operations when using the management tooling.
@@ -103,6 +106,26 @@ impl TryInto<config::SecureBackend> for SecureBackend {
                 })
             }
             MEMORY => config::SecureBackend::InMemoryStorage,
+            PKCS11 => {
+                let library_path = self
+                    .parameters
+                    .remove("library_path")
+                    .ok_or_else(|| Error::BackendParsingError("missing library path".into()))?;
+                let token_label = self
+                    .parameters
+                    .remove("token_label")
+                    .ok_or_else(|| Error::BackendParsingError("missing token label".into()))?;
+                let pin = self
+                    .parameters
+                    .remove("pin")
+                    .ok_or_else(|| Error::BackendParsingError("missing pin".into()))?;
+                config::SecureBackend::Pkcs11(Pkcs11Config {
+                    namespace: self.parameters.remove("namespace"),
+                    library_path: PathBuf::from(library_path),
+                    token_label,
+                    pin: Token::FromDisk(PathBuf::from(pin)),
+                })
+            }
             VAULT => {
                 let certificate = self.parameters.remove("ca_certificate").map(PathBuf::from);
                 let server = self
@@ -159,6 +182,8 @@ pair: "k0=v0;k1=v1;...".  The current supported formats are:
         an optional namespace: "namespace=NAMESPACE"
     InMemory: "backend=memory"
     OnDisk: "backend=disk;path=LOCAL_PATH"
+    PKCS#11: "backend=pkcs11;library_path=PATH_TO_MODULE;token_label=LABEL;pin=PATH_TO_PIN"
+        an optional namespace: "namespace=NAMESPACE"
                 "#)
             )]
             pub $field_name: Option<SecureBackend>,
@@ -267,6 +292,18 @@ mod tests {
         storage(vault).unwrap_err();
     }
 
+    #[test]
+    fn test_pkcs11() {
+        let pkcs11 = "backend=pkcs11;library_path=/usr/lib/softhsm/libsofthsm2.so;token_label=aptos;pin=/path/to/pin";
+        storage(pkcs11).unwrap();
+
+        let pkcs11 = "backend=pkcs11;library_path=/usr/lib/softhsm/libsofthsm2.so;token_label=aptos;pin=/path/to/pin;namespace=test";
+        storage(pkcs11).unwrap();
+
+        let pkcs11 = "backend=pkcs11;library_path=/usr/lib/softhsm/libsofthsm2.so";
+        storage(pkcs11).unwrap_err();
+    }
+
     fn storage(s: &str) -> Result<config::SecureBackend, Error> {
         let management_backend: SecureBackend = s.try_into()?;
         management_backend.try_into()
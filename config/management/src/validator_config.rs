@@ -22,7 +22,7 @@ use aptos_types::{
     transaction::{SignedTransaction, Transaction},
 };
 use core::str::FromStr;
-use std::net::{Ipv4Addr, ToSocketAddrs};
+use std::net::{IpAddr, ToSocketAddrs};
 use structopt::StructOpt;
 
 #[derive(Clone, Debug, StructOpt)]
@@ -46,8 +46,8 @@ impl ValidatorConfig {
     pub fn build_transaction(
         &self,
         sequence_number: u64,
-        fullnode_address: NetworkAddress,
-        validator_address: NetworkAddress,
+        fullnode_addresses: Vec<NetworkAddress>,
+        validator_addresses: Vec<NetworkAddress>,
         reconfigure: bool,
         disable_address_validation: bool,
     ) -> Result<Transaction, Error> {
@@ -59,8 +59,8 @@ impl ValidatorConfig {
             storage,
             chain_id,
             sequence_number,
-            fullnode_address,
-            validator_address,
+            fullnode_addresses,
+            validator_addresses,
             reconfigure,
             disable_address_validation,
         )
@@ -80,19 +80,32 @@ impl ValidatorConfig {
 /// * VALIDATOR_NETWORK_KEY
 /// * OPERATOR_ACCOUNT
 /// * OPERATOR_KEY
+///
+/// Every listener of a network should have its own advertised address, so that
+/// dialers can pick one that's reachable for them (e.g., internal vs. public, IPv4 vs. IPv6).
 pub fn build_validator_config_transaction<S: KVStorage + CryptoStorage>(
     validator_storage: S,
     chain_id: ChainId,
     sequence_number: u64,
-    fullnode_address: NetworkAddress,
-    validator_address: NetworkAddress,
+    fullnode_addresses: Vec<NetworkAddress>,
+    validator_addresses: Vec<NetworkAddress>,
     reconfigure: bool,
     disable_address_validation: bool,
 ) -> anyhow::Result<Transaction> {
+    if validator_addresses.is_empty() || fullnode_addresses.is_empty() {
+        return Err(Error::CommandArgumentError(
+            "At least one validator and one fullnode address is required".to_string(),
+        )
+        .into());
+    }
     if !disable_address_validation {
         // Verify addresses
-        validate_address("validator address", &validator_address)?;
-        validate_address("fullnode address", &fullnode_address)?;
+        for validator_address in &validator_addresses {
+            validate_address("validator address", validator_address)?;
+        }
+        for fullnode_address in &fullnode_addresses {
+            validate_address("fullnode address", fullnode_address)?;
+        }
     }
 
     let owner_account = validator_storage
@@ -116,15 +129,19 @@ pub fn build_validator_config_transaction<S: KVStorage + CryptoStorage>(
         .map_err(|e| Error::UnexpectedError(e.to_string()))
         .and_then(to_x25519)?;
 
-    // Build Validator address including protocols and encryption
+    // Build Validator addresses including protocols and encryption
     // Append noise-ik and handshake protocols to base network addresses
-    // and encrypt the validator address.
-    let validator_address =
-        validator_address.append_prod_protos(validator_network_key, HANDSHAKE_VERSION);
+    // and encrypt the validator addresses.
+    let validator_addresses: Vec<_> = validator_addresses
+        .into_iter()
+        .map(|address| address.append_prod_protos(validator_network_key, HANDSHAKE_VERSION))
+        .collect();
 
-    // Build Fullnode address including protocols
-    let fullnode_address =
-        fullnode_address.append_prod_protos(fullnode_network_key, HANDSHAKE_VERSION);
+    // Build Fullnode addresses including protocols
+    let fullnode_addresses: Vec<_> = fullnode_addresses
+        .into_iter()
+        .map(|address| address.append_prod_protos(fullnode_network_key, HANDSHAKE_VERSION))
+        .collect();
 
     // Generate the validator config script
     let transaction_callback = if reconfigure {
@@ -135,8 +152,8 @@ pub fn build_validator_config_transaction<S: KVStorage + CryptoStorage>(
     let validator_config_script = transaction_callback(
         owner_account,
         consensus_key.to_bytes().to_vec(),
-        bcs::to_bytes(&vec![validator_address]).unwrap(),
-        bcs::to_bytes(&vec![fullnode_address]).unwrap(),
+        bcs::to_bytes(&validator_addresses).unwrap(),
+        bcs::to_bytes(&fullnode_addresses).unwrap(),
     )
     .into_script_function();
 
@@ -167,21 +184,21 @@ pub fn validate_address(
     // Only allow DNS and IP addresses
     for protocol in network_address.as_slice().iter() {
         match protocol {
-            Protocol::Ip4(_) => has_addr = true,
-            Protocol::Dns4(dns_name) => {
+            Protocol::Ip4(_) | Protocol::Ip6(_) => has_addr = true,
+            Protocol::Dns4(dns_name) | Protocol::Dns6(dns_name) => {
                 let dns_name = format!("{}", dns_name);
-                if Ipv4Addr::from_str(&dns_name).is_ok() {
+                if IpAddr::from_str(&dns_name).is_ok() {
                     return Err(Error::CommandArgumentError(format!(
-                        "{}: Please use the /ip4/ protocol for IP addresses",
+                        "{}: Please use the /ip4/ or /ip6/ protocol for IP addresses",
                         address_name
                     )));
                 }
                 has_addr = true
             }
             Protocol::Tcp(_) => has_port = true,
-            Protocol::Dns(_) | Protocol::Ip6(_) | Protocol::Dns6(_) => {
+            Protocol::Dns(_) => {
                 return Err(Error::CommandArgumentError(format!(
-                    "{}: IPv6 is currently not supported.  Protocol: '{}'",
+                    "{}: Please use /dns4/ or /dns6/ to select an address family.  Protocol: '{}'",
                     address_name, protocol
                 )))
             }
@@ -234,9 +251,9 @@ mod tests {
 
         //The Network layer is the first in the stack -- address cration allows empty next layers
         validate_address("no_port", &no_port).expect_err("Failed to check for port");
-        //Currently NetworkAddress doesnt disallow ipv6
-        validate_address("ipv6", &ipv6).expect_err("Failed to check for ipv6");
-        validate_address("ipv4_and_ipv6", &ipv4_and_ipv6).expect_err("Failed to check for ipv6");
+        validate_address("ipv6", &ipv6).expect_err("Failed to check for port");
+        validate_address("ipv4_and_ipv6", &ipv4_and_ipv6)
+            .expect_err("Failed to check for an address family");
         //TODO: Add this check in NetworkAddress Creation
         validate_address("ip_in_dns", &ip_in_dns).expect_err("Failed to check for ip in DNS");
     }
@@ -245,8 +262,10 @@ mod tests {
     fn test_valid_inputs() {
         let ip = NetworkAddress::from_str("/ip4/127.0.0.1/tcp/1234").unwrap();
         let dns = NetworkAddress::from_str("/dns4/localhost/tcp/1234").unwrap();
+        let ipv6 = NetworkAddress::from_str("/ip6/::1/tcp/1234").unwrap();
 
         validate_address("ip", &ip).expect("IP failed to validate");
         validate_address("dns", &dns).expect("DNS failed to validate");
+        validate_address("ipv6", &ipv6).expect("IPv6 failed to validate");
     }
 }
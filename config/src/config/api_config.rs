@@ -1,9 +1,13 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::utils;
+use crate::{
+    config::{Error, SecureBackend},
+    utils,
+};
+use aptos_secure_storage::{KVStorage, Storage};
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{fs, net::SocketAddr, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -18,11 +22,26 @@ pub struct ApiConfig {
     // optional for compatible with old configuration
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_length_limit: Option<u64>,
+    // gas units a view function can use before it's aborted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_view_gas_amount: Option<u64>,
+    // transactions a batch submission can hold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_submit_transaction_batch_size: Option<usize>,
+    // API keys and per client request quotas, requests are not limited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ApiAuthConfig>,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
 pub const DEFAULT_PORT: u16 = 8080;
 pub const DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT: u64 = 4 * 1024 * 1024; // 4mb
+pub const DEFAULT_MAX_VIEW_GAS_AMOUNT: u64 = 1_000_000;
+pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 100;
+pub const DEFAULT_API_KEY_REQUESTS_PER_SECOND: usize = 100;
+pub const DEFAULT_API_KEY_MAX_CONCURRENT_REQUESTS: usize = 20;
+pub const DEFAULT_ANONYMOUS_REQUESTS_PER_SECOND: usize = 10;
+pub const DEFAULT_ANONYMOUS_MAX_CONCURRENT_REQUESTS: usize = 5;
 
 fn default_enabled() -> bool {
     true
@@ -38,6 +57,9 @@ impl Default for ApiConfig {
             tls_cert_path: None,
             tls_key_path: None,
             content_length_limit: None,
+            max_view_gas_amount: None,
+            max_submit_transaction_batch_size: None,
+            auth: None,
         }
     }
 }
@@ -53,4 +75,163 @@ impl ApiConfig {
             None => DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT,
         }
     }
+
+    pub fn max_view_gas_amount(&self) -> u64 {
+        self.max_view_gas_amount
+            .unwrap_or(DEFAULT_MAX_VIEW_GAS_AMOUNT)
+    }
+
+    pub fn max_submit_transaction_batch_size(&self) -> usize {
+        self.max_submit_transaction_batch_size
+            .unwrap_or(DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE)
+    }
+}
+
+/// Authentication of the API clients: requests carrying a known API key are limited by the quota
+/// of that key, the ones without by the anonymous quota shared by all of them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiAuthConfig {
+    pub api_keys: ApiKeysConfig,
+    // quota of the API keys which don't set their own
+    #[serde(default = "ApiQuota::api_key")]
+    pub default_quota: ApiQuota,
+    #[serde(default = "ApiQuota::anonymous")]
+    pub anonymous_quota: ApiQuota,
+    // rejects the requests without an API key instead of applying the anonymous quota
+    #[serde(default)]
+    pub require_api_key: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeysConfig {
+    FromConfig(Vec<ApiKey>),
+    /// A YAML file holding the list of API keys
+    FromFile(PathBuf),
+    FromStorage(ApiKeysFromStorage),
+}
+
+impl ApiKeysConfig {
+    pub fn load(&self) -> Result<Vec<ApiKey>, Error> {
+        match self {
+            ApiKeysConfig::FromConfig(api_keys) => Ok(api_keys.clone()),
+            ApiKeysConfig::FromFile(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| Error::IO(path.display().to_string(), e))?;
+                serde_yaml::from_str(&content)
+                    .map_err(|e| Error::Yaml(path.display().to_string(), e))
+            }
+            ApiKeysConfig::FromStorage(config) => {
+                let storage: Storage = (&config.backend).into();
+                storage
+                    .get::<Vec<ApiKey>>(&config.key_name)
+                    .map(|response| response.value)
+                    .map_err(|e| Error::SecureStorage(config.key_name.clone(), e))
+            }
+        }
+    }
+}
+
+/// The list of API keys stored under `key_name` in a secure storage
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeysFromStorage {
+    pub backend: SecureBackend,
+    pub key_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+    // identifies the client in the metrics and logs, so that the key itself is never exposed
+    pub name: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ApiQuota>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiQuota {
+    pub requests_per_second: usize,
+    pub max_concurrent_requests: usize,
+}
+
+impl ApiQuota {
+    pub fn api_key() -> Self {
+        Self {
+            requests_per_second: DEFAULT_API_KEY_REQUESTS_PER_SECOND,
+            max_concurrent_requests: DEFAULT_API_KEY_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+
+    pub fn anonymous() -> Self {
+        Self {
+            requests_per_second: DEFAULT_ANONYMOUS_REQUESTS_PER_SECOND,
+            max_concurrent_requests: DEFAULT_ANONYMOUS_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OnDiskStorageConfig;
+    use aptos_temppath::TempPath;
+
+    fn api_keys() -> Vec<ApiKey> {
+        vec![
+            ApiKey {
+                name: "wallet".to_owned(),
+                key: "e0b7b0e6d1b14f6c".to_owned(),
+                quota: Some(ApiQuota {
+                    requests_per_second: 5,
+                    max_concurrent_requests: 1,
+                }),
+            },
+            ApiKey {
+                name: "explorer".to_owned(),
+                key: "2f0d9a4c8b3e7a15".to_owned(),
+                quota: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_api_keys_from_file() {
+        let path = TempPath::new();
+        path.create_as_file().unwrap();
+        fs::write(path.path(), serde_yaml::to_string(&api_keys()).unwrap()).unwrap();
+        let config = ApiKeysConfig::FromFile(path.path().to_path_buf());
+        assert_eq!(config.load().unwrap(), api_keys());
+
+        fs::write(path.path(), "- name: wallet").unwrap();
+        assert!(matches!(config.load(), Err(Error::Yaml(_, _))));
+
+        let missing = ApiKeysConfig::FromFile(TempPath::new().path().to_path_buf());
+        assert!(matches!(missing.load(), Err(Error::IO(_, _))));
+    }
+
+    #[test]
+    fn test_api_keys_from_storage() {
+        let path = TempPath::new();
+        let mut on_disk = OnDiskStorageConfig::default();
+        on_disk.path = path.path().to_path_buf();
+        let backend = SecureBackend::OnDiskStorage(on_disk);
+        let mut storage: Storage = (&backend).into();
+        storage.set("api_keys", api_keys()).unwrap();
+
+        let config = ApiKeysConfig::FromStorage(ApiKeysFromStorage {
+            backend: backend.clone(),
+            key_name: "api_keys".to_owned(),
+        });
+        assert_eq!(config.load().unwrap(), api_keys());
+
+        let missing = ApiKeysConfig::FromStorage(ApiKeysFromStorage {
+            backend,
+            key_name: "missing".to_owned(),
+        });
+        assert!(matches!(missing.load(), Err(Error::SecureStorage(_, _))));
+    }
 }
@@ -26,6 +26,9 @@ pub struct ConsensusConfig {
     // the period = (poll_count - 1) * 30ms
     pub mempool_poll_count: u64,
     pub channel_size: usize,
+    // Makes this validator misbehave, to test that the other validators stay safe and live and
+    // detect the misbehavior. Only takes effect in builds with the `failpoints` feature.
+    pub byzantine: ByzantineConfig,
 }
 
 impl Default for ConsensusConfig {
@@ -45,6 +48,7 @@ impl Default for ConsensusConfig {
             sync_only: false,
             mempool_poll_count: 20,
             channel_size: 30, // hard-coded
+            byzantine: ByzantineConfig::default(),
         }
     }
 }
@@ -55,6 +59,33 @@ impl ConsensusConfig {
     }
 }
 
+/// The ways in which a Byzantine validator misbehaves. All of them are disabled by default.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ByzantineConfig {
+    // As the leader, send two different proposals for the same round
+    pub equivocate_proposals: bool,
+    // Follow every vote with a conflicting vote for the same round
+    pub double_vote: bool,
+    // Never send votes for proposals. Timeout votes are still sent.
+    pub withhold_votes: bool,
+    // In every round, broadcast the SyncInfo of the first round this validator took part in
+    pub stale_sync_info: bool,
+    // In every round, broadcast a SyncInfo with a quorum certificate that only this validator
+    // signed
+    pub forged_sync_info: bool,
+}
+
+impl ByzantineConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.equivocate_proposals
+            || self.double_vote
+            || self.withhold_votes
+            || self.stale_sync_info
+            || self.forged_sync_info
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum ConsensusProposerType {
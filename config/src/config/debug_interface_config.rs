@@ -10,6 +10,9 @@ pub struct DebugInterfaceConfig {
     pub admission_control_node_debug_port: u16,
     pub address: String,
     pub metrics_server_port: u16,
+    // Bearer token required by the admin API (e.g., runtime log filter changes). The admin API
+    // is disabled if no token is set.
+    pub admin_api_token: Option<String>,
 }
 
 impl Default for DebugInterfaceConfig {
@@ -18,6 +21,7 @@ impl Default for DebugInterfaceConfig {
             admission_control_node_debug_port: 6191,
             address: "0.0.0.0".to_string(),
             metrics_server_port: 9101,
+            admin_api_token: None,
         }
     }
 }
@@ -13,6 +13,8 @@ pub enum Error {
     BCS(&'static str, #[source] bcs::Error),
     #[error("Error (de)serializing {0}: {1}")]
     Yaml(String, #[source] serde_yaml::Error),
+    #[error("Error reading {0} from secure storage: {1}")]
+    SecureStorage(String, #[source] aptos_secure_storage::Error),
     #[error("Config is missing expected value: {0}")]
     Missing(&'static str),
 }
@@ -3,6 +3,7 @@
 
 use aptos_logger::{Level, CHANNEL_SIZE};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -15,6 +16,8 @@ pub struct LoggerConfig {
     pub is_async: bool,
     // The default logging level for slog.
     pub level: Level,
+    // Additional structured log sinks, each with their own filter
+    pub sinks: Vec<LogSinkConfig>,
 }
 
 impl Default for LoggerConfig {
@@ -24,6 +27,33 @@ impl Default for LoggerConfig {
             enable_backtrace: false,
             is_async: true,
             level: Level::Info,
+            sinks: Vec::new(),
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogSinkConfig {
+    // Unique name of the sink, used for runtime filter changes and metrics
+    pub name: String,
+    // Filter directives for the sink, e.g. "info,consensus=debug"
+    pub filter: String,
+    pub sink: LogSinkType,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum LogSinkType {
+    // JSON lines written to a file, rotated once it exceeds the maximum size
+    RotatingFile {
+        path: PathBuf,
+        max_file_size_bytes: u64,
+        max_rotated_files: usize,
+    },
+    // Batches of logs exported to an OpenTelemetry collector using OTLP/JSON over HTTP
+    OtlpHttp {
+        endpoint: String,
+        batch_size: usize,
+    },
+}
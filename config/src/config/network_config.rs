@@ -11,7 +11,7 @@ use aptos_crypto::{x25519, Uniform};
 use aptos_secure_storage::{CryptoStorage, KVStorage, Storage};
 use aptos_types::{
     account_address::from_identity_public_key, network_address::NetworkAddress,
-    transaction::authenticator::AuthenticationKey, PeerId,
+    transaction::authenticator::AuthenticationKey, waypoint::Waypoint, PeerId,
 };
 use rand::{
     rngs::{OsRng, StdRng},
@@ -26,6 +26,7 @@ use std::{
     string::ToString,
     time::Duration,
 };
+use url::Url;
 
 // TODO: We could possibly move these constants somewhere else, but since they are defaults for the
 //   configurations of the system, we'll leave it here for now.
@@ -42,6 +43,7 @@ pub const MAX_CONNECTION_DELAY_MS: u64 = 60_000; /* 1 minute */
 // Max default fullnode outbound connections is now 2 to decrease load on network
 pub const MAX_FULLNODE_OUTBOUND_CONNECTIONS: usize = 2;
 pub const MAX_INBOUND_CONNECTIONS: usize = 100;
+pub const PEER_BAN_DURATION_MS: u64 = 300_000; /* 5 minutes */
 pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024; /* 16 MiB */
 pub const CONNECTION_BACKOFF_BASE: u64 = 2;
 pub const IP_BYTE_BUCKET_RATE: usize = 102400 /* 100 KiB */;
@@ -66,9 +68,11 @@ pub struct NetworkConfig {
     pub discovery_method: DiscoveryMethod,
     pub discovery_methods: Vec<DiscoveryMethod>,
     pub identity: Identity,
-    // TODO: Add support for multiple listen/advertised addresses in config.
     // The address that this node is listening on for new connections.
     pub listen_address: NetworkAddress,
+    // Additional addresses to listen on, e.g., an internal VPC address or an IPv6 address.
+    // Each listener should be advertised separately so dialers can pick a reachable one.
+    pub additional_listen_addresses: Vec<NetworkAddress>,
     // Select this to enforce that both peers should authenticate each other, otherwise
     // authentication only occurs for outgoing connections.
     pub mutual_authentication: bool,
@@ -93,6 +97,8 @@ pub struct NetworkConfig {
     pub max_outbound_connections: usize,
     // Maximum number of outbound connections, limited by PeerManager
     pub max_inbound_connections: usize,
+    // Time to ban a peer for after its reputation drops too low, not used on the validator network
+    pub peer_ban_duration_ms: u64,
     // Inbound rate limiting configuration, if not specified, no rate limiting
     pub inbound_rate_limit_config: Option<RateLimitConfig>,
     // Outbound rate limiting configuration, if not specified, no rate limiting
@@ -112,6 +118,7 @@ impl NetworkConfig {
             discovery_methods: Vec::new(),
             identity: Identity::None,
             listen_address: "/ip4/0.0.0.0/tcp/6180".parse().unwrap(),
+            additional_listen_addresses: Vec::new(),
             mutual_authentication: false,
             network_id,
             seed_addrs: HashMap::new(),
@@ -128,6 +135,7 @@ impl NetworkConfig {
             ping_failures_tolerated: PING_FAILURES_TOLERATED,
             max_outbound_connections: MAX_FULLNODE_OUTBOUND_CONNECTIONS,
             max_inbound_connections: MAX_INBOUND_CONNECTIONS,
+            peer_ban_duration_ms: PEER_BAN_DURATION_MS,
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
         };
@@ -138,24 +146,9 @@ impl NetworkConfig {
 
 impl NetworkConfig {
     pub fn identity_key(&self) -> x25519::PrivateKey {
-        let key = match &self.identity {
-            Identity::FromConfig(config) => Some(config.key.private_key()),
-            Identity::FromStorage(config) => {
-                let storage: Storage = (&config.backend).into();
-                let key = storage
-                    .export_private_key(&config.key_name)
-                    .expect("Unable to read key");
-                let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
-                    .expect("Unable to convert key");
-                Some(key)
-            }
-            Identity::FromFile(config) => {
-                let identity_blob: IdentityBlob = IdentityBlob::from_file(&config.path).unwrap();
-                Some(identity_blob.network_key)
-            }
-            Identity::None => None,
-        };
-        key.expect("identity key should be present")
+        self.identity
+            .private_key()
+            .expect("identity key should be present")
     }
 
     pub fn identity_from_storage(&self) -> IdentityFromStorage {
@@ -206,6 +199,13 @@ impl NetworkConfig {
         Ok(())
     }
 
+    /// Returns all addresses this node listens on, starting with the primary `listen_address`
+    pub fn listen_addresses(&self) -> Vec<NetworkAddress> {
+        std::iter::once(self.listen_address.clone())
+            .chain(self.additional_listen_addresses.iter().cloned())
+            .collect()
+    }
+
     pub fn peer_id(&self) -> PeerId {
         match &self.identity {
             Identity::FromConfig(config) => Some(config.peer_id),
@@ -310,6 +310,10 @@ impl NetworkConfig {
 pub struct PeerMonitoringServiceConfig {
     pub max_concurrent_requests: u64, // Max num of concurrent server tasks
     pub max_network_channel_size: u64, // Max num of pending network messages
+    pub max_throughput_probe_bytes: u64, // Max num of bytes the server will send for a probe
+    pub peer_monitor_interval_ms: u64, // Interval between peer monitoring rounds (client-side)
+    pub peer_monitor_request_timeout_ms: u64, // Timeout for each monitoring request (client-side)
+    pub throughput_probe_bytes: u64, // Num of bytes to request for throughput probes (opt-in, 0 disables)
 }
 
 impl Default for PeerMonitoringServiceConfig {
@@ -317,6 +321,10 @@ impl Default for PeerMonitoringServiceConfig {
         Self {
             max_concurrent_requests: 1000,
             max_network_channel_size: 1000,
+            max_throughput_probe_bytes: 1024 * 1024, // 1 MiB
+            peer_monitor_interval_ms: 30_000,
+            peer_monitor_request_timeout_ms: 10_000,
+            throughput_probe_bytes: 0, // Probes cost bandwidth on both ends, so they're opt-in
         }
     }
 }
@@ -326,6 +334,9 @@ impl Default for PeerMonitoringServiceConfig {
 pub enum DiscoveryMethod {
     Onchain,
     File(PathBuf, Duration),
+    /// Polls the REST API at the url for the validator set, which is verified against the
+    /// ledger the waypoint leads to
+    Rest(Url, Waypoint, Duration),
     None,
 }
 
@@ -355,6 +366,27 @@ impl Identity {
     pub fn from_file(path: PathBuf) -> Self {
         Identity::FromFile(IdentityFromFile { path })
     }
+
+    /// Loads the private key of the identity, if there's one
+    pub fn private_key(&self) -> Option<x25519::PrivateKey> {
+        match self {
+            Identity::FromConfig(config) => Some(config.key.private_key()),
+            Identity::FromStorage(config) => {
+                let storage: Storage = (&config.backend).into();
+                let key = storage
+                    .export_private_key(&config.key_name)
+                    .expect("Unable to read key");
+                let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
+                    .expect("Unable to convert key");
+                Some(key)
+            }
+            Identity::FromFile(config) => {
+                let identity_blob: IdentityBlob = IdentityBlob::from_file(&config.path).unwrap();
+                Some(identity_blob.network_key)
+            }
+            Identity::None => None,
+        }
+    }
 }
 
 /// The identity is stored within the config.
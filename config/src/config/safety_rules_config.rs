@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::{IdentityBlob, LoggerConfig, SecureBackend, WaypointConfig},
+    config::{Identity, IdentityBlob, LoggerConfig, SecureBackend, WaypointConfig},
     keys::ConfigKey,
 };
-use aptos_crypto::{ed25519::Ed25519PrivateKey, Uniform};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, x25519, Uniform};
 use aptos_types::{network_address::NetworkAddress, waypoint::Waypoint, PeerId};
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
@@ -48,8 +48,10 @@ impl Default for SafetyRulesConfig {
 
 impl SafetyRulesConfig {
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
-        if let SecureBackend::OnDiskStorage(backend) = &mut self.backend {
-            backend.set_data_dir(data_dir);
+        match &mut self.backend {
+            SecureBackend::OnDiskStorage(backend) => backend.set_data_dir(data_dir),
+            SecureBackend::EncryptedOnDiskStorage(backend) => backend.set_data_dir(data_dir),
+            _ => (),
         }
     }
 }
@@ -102,6 +104,9 @@ pub enum SafetyRulesService {
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    /// The connection between consensus and safety rules is authenticated and encrypted via a
+    /// Noise IK handshake with these keys
+    pub noise: RemoteServiceNoiseConfig,
 }
 
 impl RemoteService {
@@ -114,6 +119,24 @@ impl RemoteService {
     }
 }
 
+/// The Noise keys of one side of the connection between consensus and safety rules. Consensus
+/// holds its own key and pins the key of the safety rules process, and vice versa.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteServiceNoiseConfig {
+    /// Where the x25519 key of this side is loaded from, as for network identities
+    pub identity: Identity,
+    pub peer_public_key: x25519::PublicKey,
+}
+
+impl RemoteServiceNoiseConfig {
+    pub fn private_key(&self) -> x25519::PrivateKey {
+        self.identity
+            .private_key()
+            .expect("safety rules noise identity key should be present")
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SafetyRulesTestConfig {
     pub author: PeerId,
@@ -3,7 +3,8 @@
 
 use crate::config::Error;
 use aptos_secure_storage::{
-    GitHubStorage, InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage,
+    normalize_passphrase, EncryptedOnDiskStorage, GitHubStorage, InMemoryStorage, Namespaced,
+    OnDiskStorage, Pkcs11Storage, Storage, VaultStorage,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -19,6 +20,8 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig),
+    Pkcs11(Pkcs11Config),
 }
 
 impl SecureBackend {
@@ -26,9 +29,12 @@ impl SecureBackend {
         match self {
             SecureBackend::GitHub(GitHubConfig { namespace, .. })
             | SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
-                namespace.as_deref()
-            }
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => namespace.as_deref(),
             SecureBackend::InMemoryStorage => None,
         }
     }
@@ -37,7 +43,12 @@ impl SecureBackend {
         match self {
             SecureBackend::GitHub(GitHubConfig { namespace, .. })
             | SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => {
                 *namespace = None;
             }
             SecureBackend::InMemoryStorage => {}
@@ -110,6 +121,79 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+/// Like OnDiskStorageConfig, but the file is encrypted with a key derived from a passphrase or
+/// read from a keyfile.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedOnDiskStorageConfig {
+    // Required path for on disk storage
+    pub path: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within the storage. For
+    /// example, a key, S, without a namespace would be available in S, with a namespace, N, it
+    /// would be in N/S.
+    pub namespace: Option<String>,
+    pub encryption_key: OnDiskEncryptionKey,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnDiskEncryptionKey {
+    /// The key is derived from this passphrase via Argon2id
+    Passphrase(Token),
+    /// The key is read from this file, holding 32 hex encoded bytes. This is an absolute path and
+    /// not relative to data_dir
+    Keyfile(PathBuf),
+}
+
+impl EncryptedOnDiskStorageConfig {
+    pub fn new(path: PathBuf, encryption_key: OnDiskEncryptionKey) -> Self {
+        Self {
+            path,
+            namespace: None,
+            encryption_key,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// Path to the PKCS#11 module of the HSM, e.g., /usr/lib/softhsm/libsofthsm2.so. This is an
+    /// absolute path and not relative to data_dir.
+    pub library_path: PathBuf,
+    /// The label of the token holding the keys.
+    pub token_label: String,
+    /// The user PIN of the token.
+    pub pin: Token,
+    /// A namespace is an optional portion of the name of a key or data object stored within the
+    /// token. For example, a key, S, without a namespace would be labeled S, with a namespace, N,
+    /// it would be labeled N/S.
+    pub namespace: Option<String>,
+}
+
+impl Pkcs11Config {
+    /// Reads the user PIN, without the surrounding whitespace, e.g., the trailing newline most
+    /// editors and `echo` add to the pin file.
+    pub fn pin(&self) -> Result<String, Error> {
+        Ok(self.pin.read_token()?.trim().to_string())
+    }
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -202,6 +286,43 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             }
+            SecureBackend::EncryptedOnDiskStorage(config) => {
+                let storage = match &config.encryption_key {
+                    OnDiskEncryptionKey::Passphrase(passphrase) => {
+                        EncryptedOnDiskStorage::new_with_passphrase(
+                            config.path(),
+                            normalize_passphrase(
+                                &passphrase.read_token().expect("Unable to read passphrase"),
+                            ),
+                        )
+                    }
+                    OnDiskEncryptionKey::Keyfile(keyfile) => {
+                        EncryptedOnDiskStorage::new_with_keyfile(config.path(), keyfile)
+                    }
+                }
+                .expect("Unable to open encrypted storage");
+                let storage = Storage::from(storage);
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            }
+            SecureBackend::Pkcs11(config) => {
+                let storage = Storage::from(
+                    Pkcs11Storage::new(
+                        &config.library_path,
+                        &config.token_label,
+                        config.pin().expect("Unable to read pin"),
+                    )
+                    .expect("Unable to initialize PKCS#11 storage"),
+                );
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            }
             SecureBackend::Vault(config) => {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
@@ -227,6 +348,7 @@ impl From<&SecureBackend> for Storage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aptos_secure_storage::KVStorage;
     use std::io::Write;
 
     #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -292,6 +414,34 @@ vault:
         serde_yaml::to_string(&from_config).unwrap();
     }
 
+    #[test]
+    fn test_encrypted_on_disk_parsing() {
+        let text_from_config = r#"
+type: "encrypted_on_disk_storage"
+path: "secure_storage.sealed"
+encryption_key:
+    passphrase:
+        from_disk: "/opt/aptos/passphrase"
+        "#;
+
+        let backend: SecureBackend = serde_yaml::from_str(text_from_config).unwrap();
+        let mut config = match backend {
+            SecureBackend::EncryptedOnDiskStorage(config) => config,
+            _ => panic!("Unexpected backend: {:?}", backend),
+        };
+        assert_eq!(
+            config.encryption_key,
+            OnDiskEncryptionKey::Passphrase(Token::FromDisk(PathBuf::from(
+                "/opt/aptos/passphrase"
+            )))
+        );
+        config.set_data_dir(PathBuf::from("/opt/aptos/data"));
+        assert_eq!(
+            config.path(),
+            PathBuf::from("/opt/aptos/data/secure_storage.sealed")
+        );
+    }
+
     #[test]
     fn test_token_disk_parsing() {
         let from_disk = Config {
@@ -333,4 +483,54 @@ vault:
         let config = Token::FromConfig("config_token".to_string());
         assert_eq!("config_token", config.read_token().unwrap());
     }
+
+    #[test]
+    fn test_encrypted_on_disk_passphrase_round_trip() {
+        // The passphrase file as written by `echo`, with a trailing line break
+        let passphrase_path = aptos_temppath::TempPath::new();
+        passphrase_path.create_as_file().unwrap();
+        let mut file = File::create(passphrase_path.path()).unwrap();
+        file.write_all(b"correct horse battery staple\r\n").unwrap();
+
+        // Seal the storage the way the CLI does from the same file
+        let storage_path = aptos_temppath::TempPath::new();
+        let passphrase = read_file(passphrase_path.path()).unwrap();
+        let mut storage = EncryptedOnDiskStorage::new_with_passphrase(
+            storage_path.path().to_path_buf(),
+            normalize_passphrase(&passphrase),
+        )
+        .unwrap();
+        storage.set("key", "value".to_string()).unwrap();
+
+        // The node opens it through its config, whether the passphrase is on disk or inline
+        for passphrase in [
+            Token::FromDisk(passphrase_path.path().to_path_buf()),
+            Token::FromConfig("correct horse battery staple".to_string()),
+        ] {
+            let backend = SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                path: storage_path.path().to_path_buf(),
+                namespace: None,
+                encryption_key: OnDiskEncryptionKey::Passphrase(passphrase),
+                data_dir: PathBuf::new(),
+            });
+            let storage = Storage::from(&backend);
+            assert_eq!(storage.get::<String>("key").unwrap().value, "value");
+        }
+    }
+
+    #[test]
+    fn test_pkcs11_pin_reading() {
+        let temppath = aptos_temppath::TempPath::new();
+        temppath.create_as_file().unwrap();
+        let mut file = File::create(temppath.path()).unwrap();
+        file.write_all(b"1234\n").unwrap();
+
+        let config = Pkcs11Config {
+            library_path: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+            token_label: "aptos".to_string(),
+            pin: Token::FromDisk(temppath.path().to_path_buf()),
+            namespace: None,
+        };
+        assert_eq!("1234", config.pin().unwrap());
+    }
 }
@@ -177,7 +177,8 @@ impl Default for DataStreamingServiceConfig {
 pub struct AptosDataClientConfig {
     pub max_num_in_flight_priority_polls: u64, // Max num of in-flight polls for priority peers
     pub max_num_in_flight_regular_polls: u64,  // Max num of in-flight polls for regular peers
-    pub response_timeout_ms: u64, // Timeout (in milliseconds) when waiting for a response
+    pub max_sync_lag_versions: u64, // Max num of versions a peer may lag behind the most synced peer to be preferred
+    pub response_timeout_ms: u64,   // Timeout (in milliseconds) when waiting for a response
     pub summary_poll_interval_ms: u64, // Interval (in milliseconds) between data summary polls
 }
 
@@ -186,6 +187,7 @@ impl Default for AptosDataClientConfig {
         Self {
             max_num_in_flight_priority_polls: 10,
             max_num_in_flight_regular_polls: 10,
+            max_sync_lag_versions: 10_000,
             response_timeout_ms: 5000,
             summary_poll_interval_ms: 200,
         }
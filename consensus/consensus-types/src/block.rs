@@ -22,7 +22,7 @@ use aptos_types::{
 use mirai_annotations::debug_checked_verify_eq;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
-    collections::BTreeMap,
+    collections::BTreeSet,
     fmt::{self, Display, Formatter},
     iter::once,
 };
@@ -317,20 +317,28 @@ impl Block {
             self.epoch(),
             self.round(),
             // A bitmap of voters
-            Self::voters_to_bitmap(validators, self.quorum_cert().ledger_info().signatures()),
+            Self::voters_to_bitmap(
+                validators,
+                &self
+                    .quorum_cert()
+                    .ledger_info()
+                    .get_voters(validators)
+                    .into_iter()
+                    .collect(),
+            ),
             // For nil block, we use 0x0 which is convention for nil address in move.
             self.author().unwrap_or(AccountAddress::ZERO),
             self.timestamp_usecs(),
         )
     }
 
-    fn voters_to_bitmap<T>(
+    fn voters_to_bitmap(
         validators: &[AccountAddress],
-        voters: &BTreeMap<AccountAddress, T>,
+        voters: &BTreeSet<AccountAddress>,
     ) -> Vec<bool> {
         validators
             .iter()
-            .map(|address| voters.contains_key(address))
+            .map(|address| voters.contains(address))
             .collect()
     }
 }
@@ -339,7 +347,7 @@ impl Block {
 mod test {
     use crate::block::Block;
     use aptos_types::account_address::AccountAddress;
-    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
 
     #[test]
     fn test_voters_to_bitmap() {
@@ -349,10 +357,10 @@ mod test {
             .collect();
         let expected_voter_bitmap = vec![true, true, false, true];
 
-        let voters: BTreeMap<_, _> = validators
+        let voters: BTreeSet<_> = validators
             .iter()
             .zip(expected_voter_bitmap.iter())
-            .filter_map(|(&validator, &voted)| if voted { Some((validator, 0)) } else { None })
+            .filter_map(|(&validator, &voted)| if voted { Some(validator) } else { None })
             .collect();
 
         assert_eq!(
@@ -124,8 +124,10 @@ fn test_same_qc_different_authors() {
     );
 
     let signature = signer.sign(genesis_qc.ledger_info().ledger_info());
-    let mut ledger_info_altered = genesis_qc.ledger_info().clone();
-    ledger_info_altered.add_signature(signer.author(), signature);
+    let ledger_info_altered = LedgerInfoWithSignatures::new(
+        genesis_qc.ledger_info().ledger_info().clone(),
+        BTreeMap::from([(signer.author(), signature)]),
+    );
     let genesis_qc_altered = QuorumCert::new(genesis_qc.vote_data().clone(), ledger_info_altered);
 
     let block_round_1_altered = Block::new_proposal(
@@ -174,9 +176,8 @@ fn test_block_metadata_bitmaps() {
         block_metadata_1.previous_block_votes().len()
     );
 
-    let mut ledger_info_1 = LedgerInfoWithSignatures::new(ledger_info, BTreeMap::new());
     let votes_1 = vec![true, false, true, true];
-    votes_1
+    let signatures_1: BTreeMap<_, _> = votes_1
         .iter()
         .zip(
             validators.iter().zip(
@@ -185,11 +186,15 @@ fn test_block_metadata_bitmaps() {
                     .map(|signer| signer.sign(&TestAptosCrypto("msg".to_string()))),
             ),
         )
-        .for_each(|(&voted, (&address, signature))| {
+        .filter_map(|(&voted, (&address, signature))| {
             if voted {
-                ledger_info_1.add_signature(address, signature)
+                Some((address, signature))
+            } else {
+                None
             }
-        });
+        })
+        .collect();
+    let ledger_info_1 = LedgerInfoWithSignatures::new(ledger_info, signatures_1);
     let qc_1 = QuorumCert::new(
         VoteData::new(BlockInfo::empty(), BlockInfo::empty()),
         ledger_info_1,
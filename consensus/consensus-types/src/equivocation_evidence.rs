@@ -0,0 +1,193 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    block::Block,
+    common::{Author, Round},
+    vote::Vote,
+};
+use anyhow::ensure;
+use aptos_crypto::hash::CryptoHash;
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Proof that a validator signed two conflicting consensus messages in the same round.
+/// The evidence is self-contained: anyone holding the validator set of its epoch can check it
+/// with `verify`, e.g. a governance proposal acting on the misbehavior.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
+pub enum EquivocationEvidence {
+    /// Two different proposals for the same round
+    Proposals(Block, Block),
+    /// Two votes for different ledger infos in the same round
+    Votes(Vote, Vote),
+}
+
+impl EquivocationEvidence {
+    /// The validator which equivocated, None if the evidence is made of NIL blocks, which have no
+    /// author and thus can't be verified
+    pub fn author(&self) -> Option<Author> {
+        match self {
+            EquivocationEvidence::Proposals(first, _) => first.author(),
+            EquivocationEvidence::Votes(first, _) => Some(first.author()),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        match self {
+            EquivocationEvidence::Proposals(first, _) => first.epoch(),
+            EquivocationEvidence::Votes(first, _) => first.epoch(),
+        }
+    }
+
+    pub fn round(&self) -> Round {
+        match self {
+            EquivocationEvidence::Proposals(first, _) => first.round(),
+            EquivocationEvidence::Votes(first, _) => first.vote_data().proposed().round(),
+        }
+    }
+
+    /// A short name of the kind of equivocation, used as a metrics label
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EquivocationEvidence::Proposals(_, _) => "equivocating_proposal",
+            EquivocationEvidence::Votes(_, _) => "equivocating_vote",
+        }
+    }
+
+    /// Verifies that both messages are signed by the same validator of `validator`, for the same
+    /// epoch and round, and that they conflict.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        match self {
+            EquivocationEvidence::Proposals(first, second) => {
+                ensure!(first.author().is_some(), "Proposals have no author");
+                ensure!(
+                    first.author() == second.author(),
+                    "Proposals aren't from the same author"
+                );
+                ensure!(
+                    first.epoch() == second.epoch() && first.round() == second.round(),
+                    "Proposals aren't for the same epoch and round"
+                );
+                ensure!(first.id() != second.id(), "Proposals are identical");
+                first.validate_signature(validator)?;
+                second.validate_signature(validator)?;
+            }
+            EquivocationEvidence::Votes(first, second) => {
+                ensure!(
+                    first.author() == second.author(),
+                    "Votes aren't from the same author"
+                );
+                ensure!(
+                    first.epoch() == second.epoch()
+                        && first.vote_data().proposed().round()
+                            == second.vote_data().proposed().round(),
+                    "Votes aren't for the same epoch and round"
+                );
+                ensure!(
+                    first.ledger_info().hash() != second.ledger_info().hash(),
+                    "Votes are for the same ledger info"
+                );
+                first.verify(validator)?;
+                second.verify(validator)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for EquivocationEvidence {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} by {} in epoch {} round {}]",
+            self.kind(),
+            self.author()
+                .map_or_else(|| "unknown".to_string(), |author| author.to_string()),
+            self.epoch(),
+            self.round()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{block::block_test_utils::certificate_for_genesis, vote_data::VoteData};
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        block_info::BlockInfo, ledger_info::LedgerInfo,
+        validator_verifier::random_validator_verifier,
+    };
+
+    #[test]
+    fn test_proposals_evidence() {
+        let (signers, validator) = random_validator_verifier(2, None, false);
+        let genesis_qc = certificate_for_genesis();
+        let proposal = Block::new_proposal(vec![], 1, 1, genesis_qc.clone(), &signers[0]);
+        let conflicting_proposal = Block::new_proposal(vec![], 1, 2, genesis_qc, &signers[0]);
+
+        let evidence =
+            EquivocationEvidence::Proposals(proposal.clone(), conflicting_proposal.clone());
+        assert_eq!(evidence.author(), Some(signers[0].author()));
+        assert_eq!(evidence.round(), 1);
+        evidence.verify(&validator).unwrap();
+
+        assert!(
+            EquivocationEvidence::Proposals(proposal.clone(), proposal.clone())
+                .verify(&validator)
+                .is_err()
+        );
+
+        let other_author_proposal =
+            Block::new_proposal(vec![], 1, 2, certificate_for_genesis(), &signers[1]);
+        assert!(
+            EquivocationEvidence::Proposals(proposal, other_author_proposal)
+                .verify(&validator)
+                .is_err()
+        );
+
+        let nil_evidence = EquivocationEvidence::Proposals(
+            Block::new_nil(1, certificate_for_genesis()),
+            Block::new_nil(1, certificate_for_genesis()),
+        );
+        assert_eq!(nil_evidence.author(), None);
+        assert!(nil_evidence.to_string().contains("unknown"));
+        assert!(nil_evidence.verify(&validator).is_err());
+
+        let decoded: EquivocationEvidence =
+            bcs::from_bytes(&bcs::to_bytes(&evidence).unwrap()).unwrap();
+        assert_eq!(decoded, evidence);
+    }
+
+    #[test]
+    fn test_votes_evidence() {
+        let (signers, validator) = random_validator_verifier(2, None, false);
+        let vote = |proposed: BlockInfo, signer_index: usize| {
+            let vote_data = VoteData::new(proposed, BlockInfo::empty());
+            let ledger_info = LedgerInfo::new(BlockInfo::empty(), vote_data.hash());
+            Vote::new(
+                vote_data,
+                signers[signer_index].author(),
+                ledger_info,
+                &signers[signer_index],
+            )
+        };
+        let block_info = |id: HashValue| BlockInfo::new(1, 1, id, HashValue::zero(), 0, 0, None);
+
+        let first = vote(block_info(HashValue::random()), 0);
+        let second = vote(block_info(HashValue::random()), 0);
+        let evidence = EquivocationEvidence::Votes(first.clone(), second);
+        assert_eq!(evidence.author(), Some(signers[0].author()));
+        evidence.verify(&validator).unwrap();
+
+        assert!(EquivocationEvidence::Votes(first.clone(), first.clone())
+            .verify(&validator)
+            .is_err());
+        let other_author_vote = vote(block_info(HashValue::random()), 1);
+        assert!(EquivocationEvidence::Votes(first, other_author_vote)
+            .verify(&validator)
+            .is_err());
+    }
+}
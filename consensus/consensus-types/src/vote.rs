@@ -5,7 +5,7 @@ use crate::{
     common::Author, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeout, vote_data::VoteData,
 };
 use anyhow::{ensure, Context};
-use aptos_crypto::{ed25519::Ed25519Signature, hash::CryptoHash};
+use aptos_crypto::{bls12381, ed25519::Ed25519Signature, hash::CryptoHash};
 use aptos_types::{
     ledger_info::LedgerInfo, validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
@@ -28,6 +28,9 @@ pub struct Vote {
     ledger_info: LedgerInfo,
     /// Signature of the LedgerInfo
     signature: Ed25519Signature,
+    /// BLS12-381 signature share of the LedgerInfo, aggregated into the QC if the voter has a
+    /// BLS12-381 consensus key.
+    bls_signature: Option<bls12381::Signature>,
     /// The 2-chain timeout and corresponding signature.
     two_chain_timeout: Option<(TwoChainTimeout, Ed25519Signature)>,
 }
@@ -63,7 +66,13 @@ impl Vote {
     ) -> Self {
         ledger_info_placeholder.set_consensus_data_hash(vote_data.hash());
         let signature = validator_signer.sign(&ledger_info_placeholder);
-        Self::new_with_signature(vote_data, author, ledger_info_placeholder, signature)
+        let bls_signature = validator_signer.sign_bls(&ledger_info_placeholder);
+        let mut vote =
+            Self::new_with_signature(vote_data, author, ledger_info_placeholder, signature);
+        if let Some(bls_signature) = bls_signature {
+            vote.add_bls_signature(bls_signature);
+        }
+        vote
     }
 
     /// Generates a new Vote using a signature over the specified ledger_info
@@ -78,10 +87,16 @@ impl Vote {
             author,
             ledger_info,
             signature,
+            bls_signature: None,
             two_chain_timeout: None,
         }
     }
 
+    /// Add the BLS12-381 signature share of the LedgerInfo in the vote.
+    pub fn add_bls_signature(&mut self, signature: bls12381::Signature) {
+        self.bls_signature = Some(signature);
+    }
+
     /// Add the 2-chain timeout and signature in the vote.
     pub fn add_2chain_timeout(&mut self, timeout: TwoChainTimeout, signature: Ed25519Signature) {
         self.two_chain_timeout = Some((timeout, signature));
@@ -106,6 +121,11 @@ impl Vote {
         &self.signature
     }
 
+    /// Return the BLS12-381 signature share of the vote, if any
+    pub fn bls_signature(&self) -> Option<&bls12381::Signature> {
+        self.bls_signature.as_ref()
+    }
+
     /// Returns the 2-chain timeout.
     pub fn generate_2chain_timeout(&self, qc: QuorumCert) -> TwoChainTimeout {
         TwoChainTimeout::new(
@@ -141,6 +161,11 @@ impl Vote {
         validator
             .verify(self.author(), &self.ledger_info, &self.signature)
             .context("Failed to verify Vote")?;
+        if let Some(bls_signature) = &self.bls_signature {
+            validator
+                .verify_bls(self.author(), &self.ledger_info, bls_signature)
+                .context("Failed to verify Vote BLS signature")?;
+        }
         if let Some((timeout, signature)) = &self.two_chain_timeout {
             ensure!(
                 (timeout.epoch(), timeout.round())
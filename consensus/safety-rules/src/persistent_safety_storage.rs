@@ -7,10 +7,13 @@ use crate::{
     Error,
 };
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
 };
-use aptos_global_constants::{CONSENSUS_KEY, EXECUTION_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT};
+use aptos_global_constants::{
+    CONSENSUS_BLS_KEY, CONSENSUS_KEY, EXECUTION_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT,
+};
 use aptos_logger::prelude::*;
 use aptos_secure_storage::{CryptoStorage, KVStorage, Storage};
 use aptos_types::waypoint::Waypoint;
@@ -117,6 +120,27 @@ impl PersistentSafetyStorage {
             .export_private_key_for_version(CONSENSUS_KEY, version)?)
     }
 
+    /// Returns the BLS12-381 consensus key, if one was provisioned. Secure storage backends do
+    /// not support signing with BLS12-381 keys, so the key is stored as a regular value.
+    pub fn consensus_bls_key(&self) -> Result<Option<bls12381::PrivateKey>, Error> {
+        let _timer = counters::start_timer("get", CONSENSUS_BLS_KEY);
+        match self.internal_store.get(CONSENSUS_BLS_KEY) {
+            Ok(response) => Ok(Some(response.value)),
+            Err(aptos_secure_storage::Error::KeyNotSet(_)) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn set_consensus_bls_key(
+        &mut self,
+        consensus_bls_key: &bls12381::PrivateKey,
+    ) -> Result<(), Error> {
+        let _timer = counters::start_timer("set", CONSENSUS_BLS_KEY);
+        self.internal_store
+            .set(CONSENSUS_BLS_KEY, consensus_bls_key)?;
+        Ok(())
+    }
+
     pub fn execution_public_key(&self) -> Result<Ed25519PublicKey, Error> {
         let _timer = counters::start_timer("get", EXECUTION_KEY);
         Ok(self
@@ -6,7 +6,7 @@ use crate::{
     remote_service::{self, RemoteService},
     safety_rules_manager,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{RemoteServiceNoiseConfig, SafetyRulesConfig, SafetyRulesService};
 
 use std::net::SocketAddr;
 
@@ -25,6 +25,7 @@ impl Process {
             _ => panic!("Unexpected SafetyRules service: {:?}", config.service),
         };
         let server_addr = service.server_address();
+        let noise_config = service.noise.clone();
 
         Self {
             data: Some(ProcessData {
@@ -33,6 +34,7 @@ impl Process {
                 verify_vote_proposal_signature,
                 export_consensus_key,
                 network_timeout: config.network_timeout_ms,
+                noise_config,
             }),
         }
     }
@@ -45,6 +47,7 @@ impl Process {
             data.verify_vote_proposal_signature,
             data.export_consensus_key,
             data.network_timeout,
+            Some(data.noise_config),
         );
     }
 }
@@ -56,18 +59,25 @@ struct ProcessData {
     export_consensus_key: bool,
     // Timeout in Seconds for network operations
     network_timeout: u64,
+    noise_config: RemoteServiceNoiseConfig,
 }
 
 pub struct ProcessService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
+    noise_config: RemoteServiceNoiseConfig,
 }
 
 impl ProcessService {
-    pub fn new(server_addr: SocketAddr, network_timeout: u64) -> Self {
+    pub fn new(
+        server_addr: SocketAddr,
+        network_timeout: u64,
+        noise_config: RemoteServiceNoiseConfig,
+    ) -> Self {
         Self {
             server_addr,
             network_timeout_ms: network_timeout,
+            noise_config,
         }
     }
 }
@@ -80,4 +90,8 @@ impl RemoteService for ProcessService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
+
+    fn noise_config(&self) -> Option<&RemoteServiceNoiseConfig> {
+        Some(&self.noise_config)
+    }
 }
@@ -6,17 +6,27 @@ use crate::{
     serializer::{SafetyRulesInput, SerializerClient, SerializerService, TSerializerClient},
     Error, SafetyRules, TSafetyRules,
 };
+use aptos_config::config::RemoteServiceNoiseConfig;
 use aptos_logger::warn;
 use aptos_secure_net::{NetworkClient, NetworkServer};
 use std::net::SocketAddr;
 
 pub trait RemoteService {
     fn client(&self) -> SerializerClient {
-        let network_client = NetworkClient::new(
-            "safety-rules",
-            self.server_address(),
-            self.network_timeout_ms(),
-        );
+        let network_client = match self.noise_config() {
+            Some(noise) => NetworkClient::new_with_noise(
+                "safety-rules",
+                self.server_address(),
+                self.network_timeout_ms(),
+                noise.private_key(),
+                noise.peer_public_key,
+            ),
+            None => NetworkClient::new(
+                "safety-rules",
+                self.server_address(),
+                self.network_timeout_ms(),
+            ),
+        };
         let service = Box::new(RemoteClient::new(network_client));
         SerializerClient::new_client(service)
     }
@@ -25,6 +35,12 @@ pub trait RemoteService {
 
     /// Network Timeout in milliseconds.
     fn network_timeout_ms(&self) -> u64;
+
+    /// The keys used to authenticate and encrypt the connection. Only services within the
+    /// consensus process, like the thread service, connect without them.
+    fn noise_config(&self) -> Option<&RemoteServiceNoiseConfig> {
+        None
+    }
 }
 
 pub fn execute(
@@ -33,6 +49,7 @@ pub fn execute(
     verify_vote_proposal_signature: bool,
     export_consensus_key: bool,
     network_timeout_ms: u64,
+    noise_config: Option<RemoteServiceNoiseConfig>,
 ) {
     let mut safety_rules = SafetyRules::new(
         storage,
@@ -44,7 +61,16 @@ pub fn execute(
     }
 
     let mut serializer_service = SerializerService::new(safety_rules);
-    let mut network_server = NetworkServer::new("safety-rules", listen_addr, network_timeout_ms);
+    let mut network_server = match noise_config {
+        Some(noise) => NetworkServer::new_with_noise(
+            "safety-rules",
+            listen_addr,
+            network_timeout_ms,
+            noise.private_key(),
+            noise.peer_public_key,
+        ),
+        None => NetworkServer::new("safety-rules", listen_addr, network_timeout_ms),
+    };
 
     loop {
         if let Err(e) = process_one_message(&mut network_server, &mut serializer_service) {
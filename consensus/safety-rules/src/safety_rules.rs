@@ -11,9 +11,10 @@ use crate::{
     t_safety_rules::TSafetyRules,
 };
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
-    traits::Signature,
+    traits::{Signature, SigningKey},
 };
 use aptos_logger::prelude::*;
 use aptos_types::{
@@ -45,6 +46,8 @@ pub struct SafetyRules {
     pub(crate) execution_public_key: Option<Ed25519PublicKey>,
     pub(crate) export_consensus_key: bool,
     pub(crate) validator_signer: Option<ConfigurableValidatorSigner>,
+    // BLS12-381 consensus key matching the key of this validator in the current epoch, if any.
+    pub(crate) consensus_bls_key: Option<bls12381::PrivateKey>,
     pub(crate) epoch_state: Option<EpochState>,
 }
 
@@ -70,6 +73,7 @@ impl SafetyRules {
             execution_public_key,
             export_consensus_key,
             validator_signer: None,
+            consensus_bls_key: None,
             epoch_state: None,
         }
     }
@@ -115,6 +119,17 @@ impl SafetyRules {
         signer.sign(message, &self.persistent_storage)
     }
 
+    /// Produces a BLS12-381 signature share, if this validator has a BLS12-381 consensus key in
+    /// the current epoch.
+    pub(crate) fn sign_bls<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+    ) -> Option<bls12381::Signature> {
+        self.consensus_bls_key
+            .as_ref()
+            .map(|consensus_bls_key| consensus_bls_key.sign(message))
+    }
+
     pub(crate) fn signer(&self) -> Result<&ConfigurableValidatorSigner, Error> {
         self.validator_signer
             .as_ref()
@@ -330,13 +345,50 @@ impl SafetyRules {
                 }
             }
         };
-        initialize_result.map_err(|error| {
-            info!(
-                SafetyLogSchema::new(LogEntry::KeyReconciliation, LogEvent::Error).error(&error),
-            );
-            self.validator_signer = None;
-            error
-        })
+        self.consensus_bls_key = None;
+        initialize_result
+            .map(|()| self.reconcile_consensus_bls_key(author, &epoch_state))
+            .map_err(|error| {
+                info!(
+                    SafetyLogSchema::new(LogEntry::KeyReconciliation, LogEvent::Error)
+                        .error(&error),
+                );
+                self.validator_signer = None;
+                error
+            })
+    }
+
+    /// Loads the BLS12-381 consensus key if the validator set of the epoch expects one. A missing
+    /// or mismatching key is not fatal: votes then only carry the Ed25519 signature and quorum
+    /// certificates fall back to individual signatures.
+    fn reconcile_consensus_bls_key(&mut self, author: Author, epoch_state: &EpochState) {
+        let expected_key = match epoch_state.verifier.get_bls_public_key(&author) {
+            Some(expected_key) => expected_key,
+            None => return,
+        };
+        match self.persistent_storage.consensus_bls_key() {
+            Ok(Some(consensus_bls_key))
+                if bls12381::PublicKey::from(&consensus_bls_key) == expected_key =>
+            {
+                debug!(
+                    SafetyLogSchema::new(LogEntry::KeyReconciliation, LogEvent::Success),
+                    "BLS key in set",
+                );
+                self.consensus_bls_key = Some(consensus_bls_key);
+            }
+            Ok(_) => {
+                warn!(
+                    SafetyLogSchema::new(LogEntry::KeyReconciliation, LogEvent::Error),
+                    "BLS consensus key not found in storage",
+                );
+            }
+            Err(error) => {
+                warn!(
+                    SafetyLogSchema::new(LogEntry::KeyReconciliation, LogEvent::Error)
+                        .error(&error),
+                );
+            }
+        }
     }
 
     fn guarded_sign_proposal(&mut self, block_data: &BlockData) -> Result<Ed25519Signature, Error> {
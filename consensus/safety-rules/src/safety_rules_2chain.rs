@@ -81,7 +81,11 @@ impl SafetyRules {
         let author = self.signer()?.author();
         let ledger_info = self.construct_ledger_info_2chain(proposed_block, vote_data.hash())?;
         let signature = self.sign(&ledger_info)?;
-        let vote = Vote::new_with_signature(vote_data, author, ledger_info, signature);
+        let bls_signature = self.sign_bls(&ledger_info);
+        let mut vote = Vote::new_with_signature(vote_data, author, ledger_info, signature);
+        if let Some(bls_signature) = bls_signature {
+            vote.add_bls_signature(bls_signature);
+        }
 
         safety_data.last_vote = Some(vote.clone());
         self.persistent_storage.set_safety_data(safety_data)?;
@@ -3,8 +3,10 @@
 
 use crate::{test_utils, test_utils::make_timeout_cert, Error, SafetyRules, TSafetyRules};
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519Signature},
     hash::{HashValue, ACCUMULATOR_PLACEHOLDER_HASH},
+    Uniform,
 };
 use aptos_global_constants::CONSENSUS_KEY;
 use aptos_secure_storage::CryptoStorage;
@@ -14,7 +16,7 @@ use aptos_types::{
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     validator_signer::ValidatorSigner,
-    validator_verifier::ValidatorVerifier,
+    validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
 };
 use consensus_types::{
     block::block_test_utils::random_payload,
@@ -65,6 +67,7 @@ pub fn run_test_suite(safety_rules: &Callback) {
     test_sign_proposal_with_early_preferred_round(safety_rules);
     test_uninitialized_signer(safety_rules);
     test_reconcile_key(safety_rules);
+    test_bls_consensus_key(safety_rules);
     test_validator_not_in_set(safety_rules);
     test_key_not_in_store(safety_rules);
     test_2chain_rules(safety_rules);
@@ -481,6 +484,75 @@ fn test_reconcile_key(_safety_rules: &Callback) {
     );
 }
 
+fn test_bls_consensus_key(_safety_rules: &Callback) {
+    // Test to verify that votes carry a BLS12-381 signature share once the validator set of the
+    // epoch includes the BLS12-381 key held in storage.
+    let signer = ValidatorSigner::from_int(0);
+    let mut storage = test_utils::test_storage(&signer);
+    let bls_private_key = bls12381::PrivateKey::generate_for_testing();
+    let bls_public_key = bls12381::PublicKey::from(&bls_private_key);
+    let proof_of_possession =
+        bls12381::ProofOfPossession::create_with_pubkey(&bls_private_key, &bls_public_key);
+    storage.set_consensus_bls_key(&bls_private_key).unwrap();
+    let mut safety_rules = Box::new(SafetyRules::new(storage, false, false));
+
+    let (mut proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+    safety_rules.initialize(&proof).unwrap();
+
+    // The genesis validator set does not include a BLS12-381 key
+    let a1 = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer, None);
+    let vote = safety_rules
+        .construct_and_sign_vote_two_chain(&a1, None)
+        .unwrap();
+    assert!(vote.bls_signature().is_none());
+
+    // Move to an epoch where the validator set includes the BLS12-381 key
+    let mut next_epoch_state = EpochState::empty();
+    next_epoch_state.epoch = 2;
+    next_epoch_state.verifier = ValidatorVerifier::new(BTreeMap::from([(
+        signer.author(),
+        ValidatorConsensusInfo::new_with_bls_public_key(
+            signer.public_key(),
+            bls_public_key,
+            &proof_of_possession,
+            1,
+        )
+        .unwrap(),
+    )]));
+    let verifier = next_epoch_state.verifier.clone();
+    let a2 = test_utils::make_proposal_with_parent_and_overrides(
+        vec![],
+        round + 2,
+        &a1,
+        Some(&a1),
+        &signer,
+        Some(1),
+        Some(next_epoch_state),
+        None,
+    );
+    proof
+        .ledger_info_with_sigs
+        .push(a2.block().quorum_cert().ledger_info().clone());
+    safety_rules.initialize(&proof).unwrap();
+
+    let a3 = test_utils::make_proposal_with_parent_and_overrides(
+        vec![],
+        round + 3,
+        &a2,
+        Some(&a2),
+        &signer,
+        Some(2),
+        None,
+        None,
+    );
+    let vote = safety_rules
+        .construct_and_sign_vote_two_chain(&a3, None)
+        .unwrap();
+    assert!(vote.bls_signature().is_some());
+    vote.verify(&verifier).unwrap();
+}
+
 // Tests for fetching a missing validator key from persistent storage.
 fn test_key_not_in_store(safety_rules: &Callback) {
     let (mut safety_rules, signer, key) = safety_rules();
@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_config::{
-    config::{NodeConfig, PersistableConfig, RemoteService, SafetyRulesService},
+    config::{
+        Identity, NodeConfig, OnDiskStorageConfig, PersistableConfig, RemoteService,
+        RemoteServiceNoiseConfig, SafetyRulesConfig, SafetyRulesService, SecureBackend,
+    },
     utils,
 };
-use aptos_types::validator_signer::ValidatorSigner;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, x25519, Uniform};
+use aptos_secure_storage::{CryptoStorage, Storage};
+use aptos_temppath::TempPath;
+use aptos_types::{account_address::AccountAddress, validator_signer::ValidatorSigner};
+use rand::{rngs::StdRng, SeedableRng};
 use safety_rules::{test_utils, SafetyRulesManager};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
@@ -13,6 +20,50 @@ const BINARY: &str = env!("CARGO_BIN_EXE_safety-rules");
 
 #[test]
 fn test_consensus_state() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let consensus_key = x25519::PrivateKey::generate(&mut rng);
+    let safety_rules_key = x25519::PrivateKey::generate(&mut rng);
+
+    let (consensus_config, safety_rules_config) = new_configs(
+        Identity::from_config(consensus_key, AccountAddress::random()),
+        Identity::from_config(safety_rules_key, AccountAddress::random()),
+    );
+    run_consensus_state(&safety_rules_config, &consensus_config);
+}
+
+#[test]
+fn test_consensus_state_with_identities_from_storage() {
+    let storage_path = TempPath::new();
+    storage_path.create_as_file().unwrap();
+    let mut storage_config = OnDiskStorageConfig::default();
+    storage_config.path = storage_path.path().to_path_buf();
+    let backend = SecureBackend::OnDiskStorage(storage_config);
+
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let mut storage: Storage = (&backend).into();
+    for key_name in ["consensus_noise", "safety_rules_noise"] {
+        storage
+            .import_private_key(key_name, Ed25519PrivateKey::generate(&mut rng))
+            .unwrap();
+    }
+
+    let (consensus_config, safety_rules_config) = new_configs(
+        Identity::from_storage(
+            "consensus_noise".into(),
+            "owner_account".into(),
+            backend.clone(),
+        ),
+        Identity::from_storage("safety_rules_noise".into(), "owner_account".into(), backend),
+    );
+    run_consensus_state(&safety_rules_config, &consensus_config);
+}
+
+/// Returns the configs of consensus and of the safety rules process, each holding its own
+/// identity and pinning the public key of the other side.
+fn new_configs(
+    consensus_identity: Identity,
+    safety_rules_identity: Identity,
+) -> (SafetyRulesConfig, SafetyRulesConfig) {
     let mut config = NodeConfig::random().consensus.safety_rules;
     let test_config = config.test.as_mut().unwrap();
     let private_key = test_config.consensus_key.as_ref().unwrap().private_key();
@@ -22,11 +73,36 @@ fn test_consensus_state() {
 
     let server_port = utils::get_available_port();
     let server_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port).into();
-    config.service = SafetyRulesService::Process(RemoteService { server_address });
+    let consensus_public_key = consensus_identity.private_key().unwrap().public_key();
+    let safety_rules_public_key = safety_rules_identity.private_key().unwrap().public_key();
+
+    let mut consensus_config = config.clone();
+    consensus_config.service = SafetyRulesService::Process(RemoteService {
+        server_address: server_address.clone(),
+        noise: RemoteServiceNoiseConfig {
+            identity: consensus_identity,
+            peer_public_key: safety_rules_public_key,
+        },
+    });
+    config.service = SafetyRulesService::Process(RemoteService {
+        server_address,
+        noise: RemoteServiceNoiseConfig {
+            identity: safety_rules_identity,
+            peer_public_key: consensus_public_key,
+        },
+    });
+    (consensus_config, config)
+}
 
-    let config_path = aptos_temppath::TempPath::new();
+/// Runs the safety rules binary with `safety_rules_config` and queries it from consensus,
+/// configured via `consensus_config`.
+fn run_consensus_state(
+    safety_rules_config: &SafetyRulesConfig,
+    consensus_config: &SafetyRulesConfig,
+) {
+    let config_path = TempPath::new();
     config_path.create_as_file().unwrap();
-    config.save_config(config_path.path()).unwrap();
+    safety_rules_config.save_config(config_path.path()).unwrap();
 
     let mut command = std::process::Command::new(BINARY);
     command
@@ -36,7 +112,7 @@ fn test_consensus_state() {
         .stderr(std::process::Stdio::inherit());
     let mut child = command.spawn().unwrap();
 
-    let safety_rules_manager = SafetyRulesManager::new(&config);
+    let safety_rules_manager = SafetyRulesManager::new(consensus_config);
     let mut safety_rules = safety_rules_manager.client();
     let consensus_state = safety_rules.consensus_state();
 
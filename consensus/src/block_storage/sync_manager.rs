@@ -12,6 +12,7 @@ use crate::{
 use anyhow::bail;
 
 use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::{
     account_address::AccountAddress, epoch_change::EpochChangeProof,
@@ -25,7 +26,7 @@ use consensus_types::{
     sync_info::SyncInfo,
 };
 
-use rand::{prelude::*, Rng};
+use rand::{rngs::StdRng, Rng};
 use std::{clone::Clone, cmp::min, sync::Arc, time::Duration};
 
 #[derive(Debug, PartialEq)]
@@ -283,13 +284,16 @@ impl BlockStore {
 pub struct BlockRetriever {
     network: NetworkSender,
     preferred_peer: Author,
+    // Picks the peers to try after the preferred one
+    rng: Arc<Mutex<StdRng>>,
 }
 
 impl BlockRetriever {
-    pub fn new(network: NetworkSender, preferred_peer: Author) -> Self {
+    pub fn new(network: NetworkSender, preferred_peer: Author, rng: Arc<Mutex<StdRng>>) -> Self {
         Self {
             network,
             preferred_peer,
+            rng,
         }
     }
 
@@ -398,11 +402,10 @@ impl BlockRetriever {
         num_blocks: u64,
         target_block_id: HashValue,
     ) -> anyhow::Result<Vec<Block>> {
-        let mut peers = qc
+        let voters = qc
             .ledger_info()
-            .signatures()
-            .keys()
-            .collect::<Vec<&AccountAddress>>();
+            .get_voters(&self.network.validators().get_ordered_account_addresses());
+        let mut peers = voters.iter().collect::<Vec<&AccountAddress>>();
         self.retrieve_block_for_id(
             qc.certified_block().id(),
             target_block_id,
@@ -427,7 +430,7 @@ impl BlockRetriever {
             return self.preferred_peer;
         }
 
-        let peer_idx = thread_rng().gen_range(0, peers.len());
+        let peer_idx = self.rng.lock().gen_range(0, peers.len());
         *peers.remove(peer_idx)
     }
 }
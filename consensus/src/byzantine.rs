@@ -0,0 +1,286 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Byzantine behaviors a validator can be configured to exhibit through the `byzantine` section of
+//! the `ConsensusConfig`, in order to test that the honest validators stay safe and live, and that
+//! they detect the misbehavior.
+//!
+//! Conflicting votes and forged certificates can't be produced through SafetyRules, which refuses
+//! to sign them. The Byzantine validator therefore exports its consensus key from the SafetyRules
+//! storage and signs them itself.
+
+use anyhow::{anyhow, Result};
+use aptos_config::config::{ByzantineConfig, SafetyRulesConfig};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_logger::prelude::*;
+use aptos_secure_storage::Storage;
+use aptos_types::{
+    block_info::BlockInfo,
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_signer::ValidatorSigner,
+};
+use consensus_types::{
+    block_data::BlockData,
+    common::{Author, Round},
+    quorum_cert::QuorumCert,
+    sync_info::SyncInfo,
+    vote::Vote,
+    vote_data::VoteData,
+};
+use safety_rules::PersistentSafetyStorage;
+use std::collections::BTreeMap;
+
+pub struct Byzantine {
+    config: ByzantineConfig,
+    // Signs the messages SafetyRules refuses to sign. Behaviors which need it are skipped if the
+    // consensus key couldn't be exported.
+    signer: Option<ValidatorSigner>,
+    // The SyncInfo of the first round, which is replayed if `stale_sync_info` is set
+    stale_sync_info: Option<SyncInfo>,
+}
+
+impl Byzantine {
+    pub fn new(
+        config: ByzantineConfig,
+        safety_rules_config: &SafetyRulesConfig,
+        author: Author,
+        epoch_state: &EpochState,
+    ) -> Self {
+        let signer = if config.double_vote || config.forged_sync_info {
+            match export_signer(safety_rules_config, author, epoch_state) {
+                Ok(signer) => Some(signer),
+                Err(error) => {
+                    error!(
+                        error = ?error,
+                        "Unable to export the consensus key, conflicting votes and forged \
+                        certificates won't be sent"
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        warn!(config = ?config, "This validator is Byzantine");
+
+        Self {
+            config,
+            signer,
+            stale_sync_info: None,
+        }
+    }
+
+    pub fn equivocate_proposals(&self) -> bool {
+        self.config.equivocate_proposals
+    }
+
+    pub fn withhold_votes(&self) -> bool {
+        self.config.withhold_votes
+    }
+
+    /// Returns the block data of a second, different proposal for the same round
+    pub fn conflicting_proposal(block_data: &BlockData) -> Option<BlockData> {
+        Some(BlockData::new_proposal(
+            block_data.payload()?.clone(),
+            block_data.author()?,
+            block_data.round(),
+            block_data.timestamp_usecs() + 1,
+            block_data.quorum_cert().clone(),
+        ))
+    }
+
+    /// Returns a vote for a different block of the same round, if double voting is enabled
+    pub fn conflicting_vote(&self, vote: &Vote) -> Option<Vote> {
+        if !self.config.double_vote || vote.is_timeout() {
+            return None;
+        }
+        let signer = self.signer.as_ref()?;
+        let proposed = vote.vote_data().proposed();
+        let vote_data = VoteData::new(
+            BlockInfo::new(
+                proposed.epoch(),
+                proposed.round(),
+                HashValue::random(),
+                proposed.executed_state_id(),
+                proposed.version(),
+                proposed.timestamp_usecs(),
+                proposed.next_epoch_state().cloned(),
+            ),
+            vote.vote_data().parent().clone(),
+        );
+        Some(Vote::new(
+            vote_data,
+            signer.author(),
+            vote.ledger_info().clone(),
+            signer,
+        ))
+    }
+
+    /// Returns the SyncInfo messages to broadcast when entering `round`, given the actual
+    /// `sync_info` of this validator
+    pub fn sync_infos_to_broadcast(&mut self, sync_info: &SyncInfo, round: Round) -> Vec<SyncInfo> {
+        let mut sync_infos = vec![];
+        if self.config.stale_sync_info {
+            sync_infos.push(
+                self.stale_sync_info
+                    .get_or_insert_with(|| sync_info.clone())
+                    .clone(),
+            );
+        }
+        if self.config.forged_sync_info {
+            if let Some(forged_sync_info) = self.forged_sync_info(sync_info, round) {
+                sync_infos.push(forged_sync_info);
+            }
+        }
+        sync_infos
+    }
+
+    /// Returns a SyncInfo claiming that the block of `round` is certified, with a quorum
+    /// certificate only signed by this validator
+    fn forged_sync_info(&self, sync_info: &SyncInfo, round: Round) -> Option<SyncInfo> {
+        let signer = self.signer.as_ref()?;
+        let highest_quorum_cert = sync_info.highest_quorum_cert();
+        let certified = highest_quorum_cert.certified_block();
+        let vote_data = VoteData::new(
+            BlockInfo::new(
+                certified.epoch(),
+                round,
+                HashValue::random(),
+                certified.executed_state_id(),
+                certified.version(),
+                certified.timestamp_usecs(),
+                None,
+            ),
+            certified.clone(),
+        );
+        let ledger_info =
+            LedgerInfo::new(highest_quorum_cert.commit_info().clone(), vote_data.hash());
+        let mut signatures = BTreeMap::new();
+        signatures.insert(signer.author(), signer.sign(&ledger_info));
+        let forged_quorum_cert = QuorumCert::new(
+            vote_data,
+            LedgerInfoWithSignatures::new(ledger_info, signatures),
+        );
+
+        Some(SyncInfo::new_decoupled(
+            forged_quorum_cert,
+            sync_info.highest_ordered_cert().clone(),
+            Some(sync_info.highest_ledger_info().clone()),
+            None,
+        ))
+    }
+}
+
+fn export_signer(
+    safety_rules_config: &SafetyRulesConfig,
+    author: Author,
+    epoch_state: &EpochState,
+) -> Result<ValidatorSigner> {
+    let storage = PersistentSafetyStorage::new(Storage::from(&safety_rules_config.backend), false);
+    let public_key = epoch_state
+        .verifier
+        .get_public_key(&author)
+        .ok_or_else(|| {
+            anyhow!(
+                "{} isn't a validator in epoch {}",
+                author,
+                epoch_state.epoch
+            )
+        })?;
+    let mut signer = ValidatorSigner::new(author, storage.consensus_key_for_version(public_key)?);
+    if let Some(bls_private_key) = storage.consensus_bls_key()? {
+        signer = signer.with_bls_private_key(bls_private_key);
+    }
+    Ok(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pending_votes::{PendingVotes, VoteReceptionResult};
+    use aptos_types::validator_verifier::random_validator_verifier;
+    use consensus_types::{
+        block::block_test_utils::certificate_for_genesis,
+        equivocation_evidence::EquivocationEvidence,
+    };
+
+    fn byzantine(config: ByzantineConfig, signer: ValidatorSigner) -> Byzantine {
+        Byzantine {
+            config,
+            signer: Some(signer),
+            stale_sync_info: None,
+        }
+    }
+
+    #[test]
+    fn test_conflicting_vote_is_detected() {
+        let (signers, verifier) = random_validator_verifier(4, None, false);
+        let proposed = BlockInfo::random(1);
+        let vote = Vote::new(
+            VoteData::new(proposed.clone(), BlockInfo::random(0)),
+            signers[0].author(),
+            LedgerInfo::new(BlockInfo::empty(), HashValue::zero()),
+            &signers[0],
+        );
+
+        let honest = byzantine(ByzantineConfig::default(), signers[0].clone());
+        assert!(honest.conflicting_vote(&vote).is_none());
+
+        let byzantine = byzantine(
+            ByzantineConfig {
+                double_vote: true,
+                ..ByzantineConfig::default()
+            },
+            signers[0].clone(),
+        );
+        let conflicting_vote = byzantine.conflicting_vote(&vote).unwrap();
+        conflicting_vote.verify(&verifier).unwrap();
+        assert_eq!(
+            conflicting_vote.vote_data().proposed().round(),
+            proposed.round()
+        );
+        assert_ne!(conflicting_vote.vote_data().proposed().id(), proposed.id());
+
+        let mut pending_votes = PendingVotes::new();
+        assert_eq!(
+            pending_votes.insert_vote(&vote, &verifier),
+            VoteReceptionResult::VoteAdded(1)
+        );
+        let evidence = EquivocationEvidence::Votes(vote, conflicting_vote.clone());
+        evidence.verify(&verifier).unwrap();
+        assert_eq!(
+            pending_votes.insert_vote(&conflicting_vote, &verifier),
+            VoteReceptionResult::EquivocateVote(Box::new(evidence))
+        );
+    }
+
+    #[test]
+    fn test_bad_sync_infos() {
+        let (signers, verifier) = random_validator_verifier(4, None, false);
+        let genesis_qc = certificate_for_genesis();
+        let sync_info = SyncInfo::new(genesis_qc.clone(), genesis_qc, None);
+
+        let mut byzantine = byzantine(
+            ByzantineConfig {
+                stale_sync_info: true,
+                forged_sync_info: true,
+                ..ByzantineConfig::default()
+            },
+            signers[0].clone(),
+        );
+        let sync_infos = byzantine.sync_infos_to_broadcast(&sync_info, 1);
+        assert_eq!(sync_infos.len(), 2);
+        assert_eq!(sync_infos[0], sync_info);
+        let forged_sync_info = &sync_infos[1];
+        assert_eq!(forged_sync_info.highest_certified_round(), 1);
+        assert!(forged_sync_info.has_newer_certificates(&sync_info));
+        // A single signature doesn't make a quorum
+        assert!(forged_sync_info.verify(&verifier).is_err());
+
+        // The stale SyncInfo stays the same in later rounds
+        let later_sync_infos = byzantine.sync_infos_to_broadcast(forged_sync_info, 2);
+        assert_eq!(later_sync_infos[0], sync_info);
+        assert_eq!(later_sync_infos[1].highest_certified_round(), 2);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensusdb::ConsensusDB,
     counters,
     epoch_manager::EpochManager,
     network::NetworkTask,
@@ -11,20 +12,36 @@ use crate::{
     txn_manager::MempoolProxy,
     util::time_service::ClockTimeService,
 };
+use anyhow::Result;
 use aptos_config::config::NodeConfig;
 use aptos_logger::prelude::*;
 use aptos_mempool::ConsensusRequest;
 use aptos_vm::AptosVM;
 use consensus_notifications::ConsensusNotificationSender;
+use consensus_types::equivocation_evidence::EquivocationEvidence;
 use event_notifications::ReconfigNotificationListener;
 use executor::block_executor::BlockExecutor;
 use futures::channel::mpsc;
 use network::application::storage::PeerMetadataStorage;
+use rand::{rngs::StdRng, SeedableRng};
 use std::sync::Arc;
 use storage_interface::DbReaderWriter;
 use tokio::runtime::{self, Runtime};
 
-/// Helper function to start consensus based on configuration and return the runtime
+/// Reads the evidence of equivocating validators persisted by consensus
+#[derive(Clone)]
+pub struct EquivocationEvidenceReader {
+    db: Arc<ConsensusDB>,
+}
+
+impl EquivocationEvidenceReader {
+    pub fn read(&self) -> Result<Vec<EquivocationEvidence>> {
+        Ok(self.db.get_equivocation_evidence()?)
+    }
+}
+
+/// Helper function to start consensus based on configuration and return the runtime, along with a
+/// reader of the equivocation evidence it detects
 pub fn start_consensus(
     node_config: &NodeConfig,
     mut network_sender: ConsensusNetworkSender,
@@ -34,13 +51,16 @@ pub fn start_consensus(
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
-) -> Runtime {
+) -> (Runtime, EquivocationEvidenceReader) {
     let runtime = runtime::Builder::new_multi_thread()
         .thread_name("consensus")
         .enable_all()
         .build()
         .expect("Failed to create Tokio runtime!");
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
+    let equivocation_evidence_reader = EquivocationEvidenceReader {
+        db: storage.consensus_db(),
+    };
     let txn_manager = Arc::new(MempoolProxy::new(
         consensus_to_mempool_sender,
         node_config.consensus.mempool_poll_count,
@@ -71,6 +91,7 @@ pub fn start_consensus(
         state_computer,
         storage,
         reconfig_events,
+        StdRng::from_entropy(),
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
@@ -79,5 +100,5 @@ pub fn start_consensus(
     runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
 
     debug!("Consensus started.");
-    runtime
+    (runtime, equivocation_evidence_reader)
 }
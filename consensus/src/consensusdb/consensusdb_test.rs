@@ -3,6 +3,7 @@
 
 use super::*;
 use aptos_temppath::TempPath;
+use aptos_types::validator_signer::ValidatorSigner;
 use consensus_types::block::block_test_utils::certificate_for_genesis;
 
 #[test]
@@ -69,3 +70,34 @@ fn test_delete_block_and_qc() {
     assert_eq!(db.get_blocks().unwrap().len(), 0);
     assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
 }
+
+#[test]
+fn test_equivocation_evidence() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+    assert!(db.get_equivocation_evidence().unwrap().is_empty());
+
+    let signer = ValidatorSigner::random(None);
+    let evidence = EquivocationEvidence::Proposals(
+        Block::new_proposal(vec![], 1, 1, certificate_for_genesis(), &signer),
+        Block::new_proposal(vec![], 1, 2, certificate_for_genesis(), &signer),
+    );
+    let evidence_epoch = evidence.epoch();
+    db.save_equivocation_evidence(&evidence).unwrap();
+    // Saving the same evidence twice doesn't duplicate it
+    db.save_equivocation_evidence(&evidence).unwrap();
+    assert_eq!(db.get_equivocation_evidence().unwrap(), vec![evidence]);
+
+    // Evidence isn't affected by pruning blocks
+    db.delete_blocks_and_quorum_certificates(vec![HashValue::zero()])
+        .unwrap();
+    assert_eq!(db.get_equivocation_evidence().unwrap().len(), 1);
+
+    // Evidence is pruned once its epoch is over
+    db.delete_equivocation_evidence_before_epoch(evidence_epoch)
+        .unwrap();
+    assert_eq!(db.get_equivocation_evidence().unwrap().len(), 1);
+    db.delete_equivocation_evidence_before_epoch(evidence_epoch + 1)
+        .unwrap();
+    assert!(db.get_equivocation_evidence().unwrap().is_empty());
+}
@@ -8,16 +8,19 @@ mod schema;
 use crate::{
     consensusdb::schema::{
         block::BlockSchema,
+        equivocation_evidence::EquivocationEvidenceSchema,
         quorum_certificate::QCSchema,
         single_entry::{SingleEntryKey, SingleEntrySchema},
     },
     error::DbError,
 };
 use anyhow::Result;
-use aptos_crypto::HashValue;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
-use consensus_types::{block::Block, quorum_cert::QuorumCert};
-use schema::{BLOCK_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
+use consensus_types::{
+    block::Block, equivocation_evidence::EquivocationEvidence, quorum_cert::QuorumCert,
+};
+use schema::{BLOCK_CF_NAME, EQUIVOCATION_EVIDENCE_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
 use schemadb::{Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
 use std::{collections::HashMap, iter::Iterator, path::Path, time::Instant};
 
@@ -32,6 +35,7 @@ impl ConsensusDB {
             BLOCK_CF_NAME,
             QC_CF_NAME,
             SINGLE_ENTRY_CF_NAME,
+            EQUIVOCATION_EVIDENCE_CF_NAME,
         ];
 
         let path = db_root_path.as_ref().join("consensusdb");
@@ -125,6 +129,44 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    /// Persists the evidence of an equivocation. It is kept until the consensus data of its epoch
+    /// is pruned, see `delete_equivocation_evidence_before_epoch`.
+    pub fn save_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence,
+    ) -> Result<(), DbError> {
+        let mut batch = SchemaBatch::new();
+        batch.put::<EquivocationEvidenceSchema>(&evidence.hash(), evidence)?;
+        self.commit(batch)
+    }
+
+    /// Get all the equivocation evidence detected by this node.
+    pub fn get_equivocation_evidence(&self) -> Result<Vec<EquivocationEvidence>, DbError> {
+        let mut iter = self
+            .db
+            .iter::<EquivocationEvidenceSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        Ok(iter
+            .map(|result| result.map(|(_hash, evidence)| evidence))
+            .collect::<Result<Vec<_>>>()?)
+    }
+
+    /// Deletes the evidence of the equivocations which happened before `epoch`.
+    pub fn delete_equivocation_evidence_before_epoch(&self, epoch: u64) -> Result<(), DbError> {
+        let mut iter = self
+            .db
+            .iter::<EquivocationEvidenceSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        let mut batch = SchemaBatch::new();
+        for result in iter {
+            let (hash, evidence) = result?;
+            if evidence.epoch() < epoch {
+                batch.delete::<EquivocationEvidenceSchema>(&hash)?;
+            }
+        }
+        self.commit(batch)
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
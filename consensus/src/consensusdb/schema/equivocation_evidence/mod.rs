@@ -0,0 +1,47 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the evidence of equivocating validators.
+//!
+//! Serialized evidence bytes identified by the hash of the evidence.
+//! ```text
+//! |<-----key----->|<---value--->|
+//! | evidence_hash |  evidence   |
+//! ```
+
+use super::EQUIVOCATION_EVIDENCE_CF_NAME;
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use consensus_types::equivocation_evidence::EquivocationEvidence;
+use schemadb::schema::{KeyCodec, Schema, ValueCodec};
+
+pub struct EquivocationEvidenceSchema;
+
+impl Schema for EquivocationEvidenceSchema {
+    const COLUMN_FAMILY_NAME: schemadb::ColumnFamilyName = EQUIVOCATION_EVIDENCE_CF_NAME;
+    type Key = HashValue;
+    type Value = EquivocationEvidence;
+}
+
+impl KeyCodec<EquivocationEvidenceSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<EquivocationEvidenceSchema> for EquivocationEvidence {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,20 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::validator_signer::ValidatorSigner;
+use consensus_types::block::{block_test_utils::certificate_for_genesis, Block};
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_encode_decode() {
+    let signer = ValidatorSigner::random(None);
+    let evidence = EquivocationEvidence::Proposals(
+        Block::new_proposal(vec![], 1, 1, certificate_for_genesis(), &signer),
+        Block::new_proposal(vec![], 1, 2, certificate_for_genesis(), &signer),
+    );
+    assert_encode_decode::<EquivocationEvidenceSchema>(&evidence.hash(), &evidence);
+}
+
+test_no_panic_decoding!(EquivocationEvidenceSchema);
@@ -137,6 +137,17 @@ pub static TIMEOUT_ROUNDS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the misbehavior of other validators detected since last restart, by kind of
+/// misbehavior (0 in happy path).
+pub static MISBEHAVIOR_DETECTED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_misbehavior_detected_count",
+        "Count of the misbehavior of other validators detected since last restart, by kind of misbehavior.",
+        &["kind"]
+    )
+    .unwrap()
+});
+
 /// Count the number of timeouts a node experienced since last restart (close to 0 in happy path).
 /// This count is different from `TIMEOUT_ROUNDS_COUNT`, because not every time a node has
 /// a timeout there is an ultimate decision to move to the next round (it might take multiple
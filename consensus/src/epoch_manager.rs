@@ -1,6 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "failpoints")]
+use crate::byzantine::Byzantine;
 use crate::{
     block_storage::BlockStore,
     counters,
@@ -53,6 +55,7 @@ use futures::{
     SinkExt, StreamExt,
 };
 use network::protocols::network::{ApplicationNetworkSender, Event};
+use rand::rngs::StdRng;
 use safety_rules::SafetyRulesManager;
 use std::{
     cmp::Ordering,
@@ -90,6 +93,8 @@ pub struct EpochManager {
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
     reconfig_events: ReconfigNotificationListener,
+    // picks the peers to retrieve missing blocks from
+    block_retrieval_rng: Arc<Mutex<StdRng>>,
     // channels to buffer manager
     buffer_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     buffer_manager_reset_tx: Option<UnboundedSender<ResetRequest>>,
@@ -111,6 +116,7 @@ impl EpochManager {
         commit_state_computer: Arc<dyn StateComputer>,
         storage: Arc<dyn PersistentLivenessStorage>,
         reconfig_events: ReconfigNotificationListener,
+        block_retrieval_rng: StdRng,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -128,6 +134,7 @@ impl EpochManager {
             storage,
             safety_rules_manager,
             reconfig_events,
+            block_retrieval_rng: Arc::new(Mutex::new(block_retrieval_rng)),
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
             round_manager_tx: None,
@@ -461,8 +468,30 @@ impl EpochManager {
             self.storage.clone(),
             self.config.sync_only,
             onchain_config,
+            self.block_retrieval_rng.clone(),
         );
 
+        #[cfg(feature = "failpoints")]
+        {
+            if self.config.byzantine.is_enabled() {
+                round_manager.set_byzantine(Byzantine::new(
+                    self.config.byzantine.clone(),
+                    &self.config.safety_rules,
+                    self.author,
+                    round_manager.epoch_state(),
+                ));
+            }
+        }
+        #[cfg(not(feature = "failpoints"))]
+        {
+            if self.config.byzantine.is_enabled() {
+                warn!(
+                    "byzantine is set in the consensus config, but the binary doesn't compile \
+                    with failpoints"
+                );
+            }
+        }
+
         round_manager.init(last_vote).await;
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
             QueueStyle::LIFO,
@@ -11,7 +11,7 @@ use aptos_logger::prelude::*;
 use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
-    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures, LedgerInfoWithV0},
     validator_verifier::ValidatorVerifier,
 };
 use consensus_types::{
@@ -35,27 +35,32 @@ fn aggregate_ledger_info(
     commit_ledger_info: &LedgerInfo,
     unverified_signatures: BTreeMap<AccountAddress, Ed25519Signature>,
     validator: &ValidatorVerifier,
-) -> LedgerInfoWithSignatures {
+) -> LedgerInfoWithV0 {
     let valid_sigs = unverified_signatures
         .into_iter()
         .filter(|(author, sig)| validator.verify(*author, commit_ledger_info, sig).is_ok())
         .collect();
 
-    LedgerInfoWithSignatures::new(commit_ledger_info.clone(), valid_sigs)
+    LedgerInfoWithV0::new(commit_ledger_info.clone(), valid_sigs)
 }
 
 // we differentiate buffer items at different stages
 // for better code readability
 pub struct OrderedItem {
     pub unverified_signatures: BTreeMap<AccountAddress, Ed25519Signature>,
+    // an aggregated commit decision received before execution, it can't be split into the
+    // signatures of individual commit votes
+    pub aggregated_commit_proof: Option<LedgerInfoWithSignatures>,
     pub callback: StateComputerCommitCallBackType,
     pub ordered_blocks: Vec<ExecutedBlock>,
     pub ordered_proof: LedgerInfoWithSignatures,
 }
 
+// commit proofs of executed and signed items are aggregated locally from the Ed25519 signatures
+// of commit votes, until they have enough voting power or a commit decision is received
 pub struct ExecutedItem {
     pub executed_blocks: Vec<ExecutedBlock>,
-    pub commit_proof: LedgerInfoWithSignatures,
+    pub commit_proof: LedgerInfoWithV0,
     pub callback: StateComputerCommitCallBackType,
     pub commit_info: BlockInfo,
     pub ordered_proof: LedgerInfoWithSignatures,
@@ -63,7 +68,7 @@ pub struct ExecutedItem {
 
 pub struct SignedItem {
     pub executed_blocks: Vec<ExecutedBlock>,
-    pub commit_proof: LedgerInfoWithSignatures,
+    pub commit_proof: LedgerInfoWithV0,
     pub callback: StateComputerCommitCallBackType,
     pub commit_vote: CommitVote,
 }
@@ -95,6 +100,7 @@ impl BufferItem {
     ) -> Self {
         Self::Ordered(Box::new(OrderedItem {
             unverified_signatures: BTreeMap::new(),
+            aggregated_commit_proof: None,
             callback,
             ordered_blocks,
             ordered_proof,
@@ -112,6 +118,7 @@ impl BufferItem {
                 let OrderedItem {
                     ordered_blocks,
                     unverified_signatures,
+                    aggregated_commit_proof,
                     callback,
                     ordered_proof,
                 } = *ordered_item;
@@ -139,6 +146,19 @@ impl BufferItem {
                     );
                     commit_info.change_timestamp(ts);
                 }
+                if let Some(commit_proof) = aggregated_commit_proof
+                    .filter(|commit_proof| *commit_proof.commit_info() == commit_info)
+                {
+                    debug!(
+                        "{} advance to aggregated from ordered with commit decision",
+                        commit_proof.commit_info()
+                    );
+                    return Self::Aggregated(Box::new(AggregatedItem {
+                        executed_blocks,
+                        commit_proof,
+                        callback,
+                    }));
+                }
                 let commit_proof = aggregate_ledger_info(
                     &generate_commit_proof(&commit_info, &ordered_proof),
                     unverified_signatures,
@@ -151,7 +171,7 @@ impl BufferItem {
                     );
                     Self::Aggregated(Box::new(AggregatedItem {
                         executed_blocks,
-                        commit_proof,
+                        commit_proof: LedgerInfoWithSignatures::V0(commit_proof),
                         callback,
                     }))
                 } else {
@@ -259,10 +279,19 @@ impl BufferItem {
                     "{} received commit decision in ordered stage",
                     commit_proof.commit_info()
                 );
-                Self::Ordered(Box::new(OrderedItem {
-                    unverified_signatures: commit_proof.signatures().clone(),
-                    ..ordered
-                }))
+                match commit_proof {
+                    LedgerInfoWithSignatures::V0(ledger) => Self::Ordered(Box::new(OrderedItem {
+                        unverified_signatures: ledger.signatures().clone(),
+                        ..ordered
+                    })),
+                    // keep the aggregated proof, it's used once the commit info is known
+                    commit_proof @ LedgerInfoWithSignatures::V1(_) => {
+                        Self::Ordered(Box::new(OrderedItem {
+                            aggregated_commit_proof: Some(commit_proof),
+                            ..ordered
+                        }))
+                    }
+                }
             }
             Self::Aggregated(_) => {
                 unreachable!("Found aggregated buffer item but any aggregated buffer item should get dequeued right away.");
@@ -280,7 +309,7 @@ impl BufferItem {
                 {
                     Self::Aggregated(Box::new(AggregatedItem {
                         executed_blocks: signed_item.executed_blocks,
-                        commit_proof: signed_item.commit_proof,
+                        commit_proof: LedgerInfoWithSignatures::V0(signed_item.commit_proof),
                         callback: signed_item.callback,
                     }))
                 } else {
@@ -295,7 +324,7 @@ impl BufferItem {
                 {
                     Self::Aggregated(Box::new(AggregatedItem {
                         executed_blocks: executed_item.executed_blocks,
-                        commit_proof: executed_item.commit_proof,
+                        commit_proof: LedgerInfoWithSignatures::V0(executed_item.commit_proof),
                         callback: executed_item.callback,
                     }))
                 } else {
@@ -108,13 +108,11 @@ pub fn prepare_executed_blocks_with_ledger_info(
         consensus_hash,
     );
 
-    let mut li_sig = LedgerInfoWithSignatures::new(
+    let li_sig = LedgerInfoWithSignatures::new(
         li.clone(),
-        BTreeMap::<AccountAddress, Ed25519Signature>::new(),
+        BTreeMap::<AccountAddress, Ed25519Signature>::from([(signer.author(), signer.sign(&li))]),
     );
 
-    li_sig.add_signature(signer.author(), signer.sign(&li));
-
     let executed_blocks: Vec<ExecutedBlock> = proposals
         .iter()
         .map(|proposal| ExecutedBlock::new(proposal.block().clone(), compute_result.clone()))
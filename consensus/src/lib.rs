@@ -13,6 +13,9 @@
 #![recursion_limit = "512"]
 
 mod block_storage;
+#[cfg(any(test, feature = "failpoints"))]
+#[cfg_attr(not(feature = "failpoints"), allow(dead_code))]
+mod byzantine;
 mod consensusdb;
 mod counters;
 mod epoch_manager;
@@ -8,7 +8,7 @@ use crate::{
 };
 use aptos_logger::{prelude::*, Schema};
 use aptos_types::validator_verifier::ValidatorVerifier;
-use consensus_types::{common::Round, sync_info::SyncInfo, vote::Vote};
+use consensus_types::{block::Block, common::Round, sync_info::SyncInfo, vote::Vote};
 use futures::future::AbortHandle;
 use serde::Serialize;
 use std::{fmt, sync::Arc, time::Duration};
@@ -150,6 +150,8 @@ pub struct RoundState {
     pending_votes: PendingVotes,
     // Vote sent locally for the current round.
     vote_sent: Option<Vote>,
+    // The first proposal received for the current round, to detect equivocating proposers.
+    proposal_received: Option<Block>,
     // The handle to cancel previous timeout task when moving to next round.
     abort_handle: Option<AbortHandle>,
 }
@@ -196,6 +198,7 @@ impl RoundState {
             timeout_sender,
             pending_votes: PendingVotes::new(),
             vote_sent: None,
+            proposal_received: None,
             abort_handle: None,
         }
     }
@@ -234,6 +237,7 @@ impl RoundState {
             self.current_round = new_round;
             self.pending_votes = PendingVotes::new();
             self.vote_sent = None;
+            self.proposal_received = None;
             let timeout = self.setup_timeout();
             // The new round reason is QCReady in case both QC.round + 1 == new_round, otherwise
             // it's Timeout and TC.round + 1 == new_round.
@@ -278,6 +282,22 @@ impl RoundState {
         self.vote_sent.clone()
     }
 
+    /// Records a proposal for the current round. Returns the proposal received before if it is a
+    /// different one, i.e. if the proposer equivocated.
+    pub fn record_proposal(&mut self, proposal: &Block) -> Option<Block> {
+        if proposal.round() != self.current_round {
+            return None;
+        }
+        match &self.proposal_received {
+            Some(previous) if previous.id() != proposal.id() => Some(previous.clone()),
+            Some(_) => None,
+            None => {
+                self.proposal_received = Some(proposal.clone());
+                None
+            }
+        }
+    }
+
     /// Setup the timeout task and return the duration of the current timeout
     fn setup_timeout(&mut self) -> Duration {
         let timeout_sender = self.timeout_sender.clone();
@@ -12,8 +12,10 @@ use aptos_crypto::HashValue;
 use aptos_types::{
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_signer::ValidatorSigner,
 };
 use consensus_types::{
+    block::Block,
     common::Round,
     quorum_cert::QuorumCert,
     sync_info::SyncInfo,
@@ -81,6 +83,33 @@ fn test_round_event_generation() {
     );
 }
 
+#[test]
+fn test_proposal_equivocation_detection() {
+    let (mut pm, _) = make_round_state();
+    let signer = ValidatorSigner::random(None);
+    let sync_info = generate_sync_info(Some(0), None, None);
+    let qc = sync_info.highest_quorum_cert().clone();
+    pm.process_certificates(sync_info);
+
+    let proposal = Block::new_proposal(vec![], 1, 1, qc.clone(), &signer);
+    let conflicting_proposal = Block::new_proposal(vec![], 1, 2, qc.clone(), &signer);
+    assert!(pm.record_proposal(&proposal).is_none());
+    // Receiving the same proposal again is fine
+    assert!(pm.record_proposal(&proposal).is_none());
+    assert_eq!(pm.record_proposal(&conflicting_proposal), Some(proposal));
+
+    // Proposals for other rounds are ignored, and a new round starts from scratch
+    let next_proposal = Block::new_proposal(vec![], 2, 3, qc.clone(), &signer);
+    assert!(pm.record_proposal(&next_proposal).is_none());
+    pm.process_certificates(generate_sync_info(Some(1), None, None));
+    assert!(pm.record_proposal(&next_proposal).is_none());
+    let conflicting_next_proposal = Block::new_proposal(vec![], 2, 4, qc, &signer);
+    assert_eq!(
+        pm.record_proposal(&conflicting_next_proposal),
+        Some(next_proposal)
+    );
+}
+
 fn make_round_state() -> (RoundState, channel::Receiver<Round>) {
     let time_interval = Box::new(ExponentialTimeInterval::fixed(Duration::from_millis(2)));
     let simulated_time = SimulatedTimeService::auto_advance_until(Duration::from_millis(4));
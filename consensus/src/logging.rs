@@ -19,6 +19,7 @@ pub enum LogEvent {
     CommitViaBlock,
     CommitViaSync,
     HelpPeerSync,
+    Misbehave,
     NewEpoch,
     NewRound,
     Propose,
@@ -10,11 +10,13 @@
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
 use aptos_types::{
-    ledger_info::LedgerInfoWithSignatures,
+    aggregate_signature::PartialSignatures,
+    ledger_info::{LedgerInfoWithSignatures, LedgerInfoWithV0},
     validator_verifier::{ValidatorVerifier, VerifyError},
 };
 use consensus_types::{
-    common::Author, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    common::Author, equivocation_evidence::EquivocationEvidence, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -32,7 +34,7 @@ pub enum VoteReceptionResult {
     /// The very same vote message has been processed in past.
     DuplicateVote,
     /// The very same author has already voted for another proposal in this round (equivocation).
-    EquivocateVote,
+    EquivocateVote(Box<EquivocationEvidence>),
     /// This block has just been certified after adding the vote.
     NewQuorumCertificate(Arc<QuorumCert>),
     /// The vote completes a new TwoChainTimeoutCertificate
@@ -45,10 +47,12 @@ pub enum VoteReceptionResult {
 
 /// A PendingVotes structure keep track of votes
 pub struct PendingVotes {
-    /// Maps LedgerInfo digest to associated signatures (contained in a partial LedgerInfoWithSignatures).
+    /// Maps LedgerInfo digest to associated signatures (contained in a partial LedgerInfoWithSignatures)
+    /// and BLS12-381 signature shares (to be aggregated once a quorum is reached).
     /// This might keep multiple LedgerInfos for the current round: either due to different proposals (byzantine behavior)
     /// or due to different NIL proposals (clients can have a different view of what block to extend).
-    li_digest_to_votes: HashMap<HashValue /* LedgerInfo digest */, LedgerInfoWithSignatures>,
+    li_digest_to_votes:
+        HashMap<HashValue /* LedgerInfo digest */, (LedgerInfoWithV0, PartialSignatures)>,
     /// Tracks all the signatures of the 2-chain timeout for the given round.
     maybe_partial_2chain_tc: Option<TwoChainTimeoutCertificate>,
     /// Map of Author to vote. This is useful to discard multiple votes.
@@ -90,14 +94,10 @@ impl PendingVotes {
                 }
             } else {
                 // we have seen a different vote for the same round
-                error!(
-                    SecurityEvent::ConsensusEquivocatingVote,
-                    remote_peer = vote.author(),
-                    vote = vote,
-                    previous_vote = previously_seen_vote
-                );
-
-                return VoteReceptionResult::EquivocateVote;
+                return VoteReceptionResult::EquivocateVote(Box::new(EquivocationEvidence::Votes(
+                    previously_seen_vote.clone(),
+                    vote.clone(),
+                )));
             }
         }
 
@@ -112,13 +112,20 @@ impl PendingVotes {
         //
 
         // obtain the ledger info with signatures associated to the vote's ledger info
-        let li_with_sig = self.li_digest_to_votes.entry(li_digest).or_insert_with(|| {
-            // if the ledger info with signatures doesn't exist yet, create it
-            LedgerInfoWithSignatures::new(vote.ledger_info().clone(), BTreeMap::new())
-        });
+        let (li_with_sig, bls_signatures) =
+            self.li_digest_to_votes.entry(li_digest).or_insert_with(|| {
+                // if the ledger info with signatures doesn't exist yet, create it
+                (
+                    LedgerInfoWithV0::new(vote.ledger_info().clone(), BTreeMap::new()),
+                    PartialSignatures::empty(),
+                )
+            });
 
         // add this vote to the ledger info with signatures
         li_with_sig.add_signature(vote.author(), vote.signature().clone());
+        if let Some(bls_signature) = vote.bls_signature() {
+            bls_signatures.add_signature(vote.author(), bls_signature.clone());
+        }
 
         // check if we have enough signatures to create a QC
         let voting_power =
@@ -127,7 +134,7 @@ impl PendingVotes {
                 Ok(_) => {
                     return VoteReceptionResult::NewQuorumCertificate(Arc::new(QuorumCert::new(
                         vote.vote_data().clone(),
-                        li_with_sig.clone(),
+                        Self::quorum_ledger_info(li_with_sig, bls_signatures, validator_verifier),
                     )));
                 }
 
@@ -176,6 +183,37 @@ impl PendingVotes {
 
         VoteReceptionResult::VoteAdded(voting_power)
     }
+
+    /// Aggregates the BLS12-381 signature shares into a single signature if all validators of the
+    /// epoch have a BLS12-381 key and the shares reach a quorum on their own. Otherwise the QC
+    /// carries the individual Ed25519 signatures.
+    fn quorum_ledger_info(
+        li_with_sig: &LedgerInfoWithV0,
+        bls_signatures: &PartialSignatures,
+        validator_verifier: &ValidatorVerifier,
+    ) -> LedgerInfoWithSignatures {
+        if validator_verifier.supports_aggregate_signatures()
+            && validator_verifier
+                .check_voting_power(bls_signatures.signatures().keys())
+                .is_ok()
+        {
+            match validator_verifier.aggregate_signatures(bls_signatures) {
+                Ok(aggregated_signature) => {
+                    return LedgerInfoWithSignatures::new_aggregated(
+                        li_with_sig.ledger_info().clone(),
+                        aggregated_signature,
+                    );
+                }
+                Err(error) => {
+                    error!(
+                        "Failed to aggregate BLS signatures for {}: {}",
+                        li_with_sig, error
+                    );
+                }
+            }
+        }
+        LedgerInfoWithSignatures::V0(li_with_sig.clone())
+    }
 }
 
 //
@@ -188,7 +226,7 @@ impl fmt::Display for PendingVotes {
         let votes = self
             .li_digest_to_votes
             .iter()
-            .map(|(li_digest, li)| (li_digest, li.signatures().keys().collect::<Vec<_>>()))
+            .map(|(li_digest, (li, _))| (li_digest, li.signatures().keys().collect::<Vec<_>>()))
             .collect::<BTreeMap<_, _>>();
 
         // collect timeout votes
@@ -221,11 +259,13 @@ mod tests {
     use super::{PendingVotes, VoteReceptionResult};
     use aptos_crypto::HashValue;
     use aptos_types::{
-        block_info::BlockInfo, ledger_info::LedgerInfo,
-        validator_verifier::random_validator_verifier,
+        block_info::BlockInfo,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+        validator_verifier::{random_validator_verifier, random_validator_verifier_with_bls_keys},
     };
     use consensus_types::{
-        block::block_test_utils::certificate_for_genesis, vote::Vote, vote_data::VoteData,
+        block::block_test_utils::certificate_for_genesis,
+        equivocation_evidence::EquivocationEvidence, vote::Vote, vote_data::VoteData,
     };
 
     /// Creates a random ledger info for epoch 1 and round 1.
@@ -279,7 +319,10 @@ mod tests {
         );
         assert_eq!(
             pending_votes.insert_vote(&vote_data_2_author_0, &validator),
-            VoteReceptionResult::EquivocateVote
+            VoteReceptionResult::EquivocateVote(Box::new(EquivocationEvidence::Votes(
+                vote_data_1_author_0.clone(),
+                vote_data_2_author_0.clone()
+            )))
         );
 
         // a different author voting for a different result -> VoteAdded
@@ -298,9 +341,45 @@ mod tests {
         let vote_data_2_author_2 = Vote::new(vote_data_2, signers[2].author(), li2, &signers[2]);
         match pending_votes.insert_vote(&vote_data_2_author_2, &validator) {
             VoteReceptionResult::NewQuorumCertificate(qc) => {
-                assert!(validator
-                    .check_voting_power(qc.ledger_info().signatures().keys())
-                    .is_ok());
+                assert!(matches!(qc.ledger_info(), LedgerInfoWithSignatures::V0(_)));
+                assert!(qc.ledger_info().check_voting_power(&validator).is_ok());
+            }
+            _ => {
+                panic!("No QC formed.");
+            }
+        };
+    }
+
+    #[test]
+    /// Verify that BLS12-381 signature shares are aggregated into a single signature
+    fn test_qc_bls_aggregation() {
+        ::aptos_logger::Logger::init_for_testing();
+
+        // set up 4 validators with BLS12-381 keys
+        let (signers, validator) = random_validator_verifier_with_bls_keys(4, Some(2));
+        let mut pending_votes = PendingVotes::new();
+
+        let li = random_ledger_info();
+        let vote_data = random_vote_data();
+        let vote_author_0 = Vote::new(
+            vote_data.clone(),
+            signers[0].author(),
+            li.clone(),
+            &signers[0],
+        );
+        assert!(vote_author_0.bls_signature().is_some());
+        assert!(vote_author_0.verify(&validator).is_ok());
+        assert_eq!(
+            pending_votes.insert_vote(&vote_author_0, &validator),
+            VoteReceptionResult::VoteAdded(1)
+        );
+
+        let vote_author_1 = Vote::new(vote_data, signers[1].author(), li, &signers[1]);
+        match pending_votes.insert_vote(&vote_author_1, &validator) {
+            VoteReceptionResult::NewQuorumCertificate(qc) => {
+                assert!(matches!(qc.ledger_info(), LedgerInfoWithSignatures::V1(_)));
+                assert_eq!(qc.ledger_info().get_num_voters(), 2);
+                assert!(qc.ledger_info().verify_signatures(&validator).is_ok());
             }
             _ => {
                 panic!("No QC formed.");
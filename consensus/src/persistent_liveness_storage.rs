@@ -10,7 +10,8 @@ use aptos_types::{
     epoch_change::EpochChangeProof, ledger_info::LedgerInfoWithSignatures, transaction::Version,
 };
 use consensus_types::{
-    block::Block, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    block::Block, equivocation_evidence::EquivocationEvidence, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
 };
 use executor::components::in_memory_state_calculator::IntoLedgerView;
 use std::{cmp::max, collections::HashSet, sync::Arc};
@@ -44,6 +45,10 @@ pub trait PersistentLivenessStorage: Send + Sync {
         highest_timeout_cert: &TwoChainTimeoutCertificate,
     ) -> Result<()>;
 
+    /// Persist the evidence of a validator equivocating, so that it survives restarts and can
+    /// later be acted upon
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<()>;
+
     /// Retrieve a epoch change proof for SafetyRules so it can instantiate its
     /// ValidatorVerifier.
     fn retrieve_epoch_change_proof(&self, version: u64) -> Result<EpochChangeProof>;
@@ -296,6 +301,11 @@ impl StorageWriteProxy {
         let db = Arc::new(ConsensusDB::new(config.storage.dir()));
         StorageWriteProxy { db, aptos_db }
     }
+
+    /// Returns a handle of the consensusdb.
+    pub fn consensus_db(&self) -> Arc<ConsensusDB> {
+        self.db.clone()
+    }
 }
 
 impl PersistentLivenessStorage for StorageWriteProxy {
@@ -388,6 +398,10 @@ impl PersistentLivenessStorage for StorageWriteProxy {
                 (self as &dyn PersistentLivenessStorage)
                     .prune_tree(initial_data.take_blocks_to_prune())
                     .expect("unable to prune dangling blocks during restart");
+                // Like the last vote and timeout certificate, evidence from past epochs is dropped
+                self.db
+                    .delete_equivocation_evidence_before_epoch(initial_data.root_block().epoch())
+                    .expect("unable to prune equivocation evidence during restart");
                 if initial_data.last_vote.is_none() {
                     self.db
                         .delete_last_vote_msg()
@@ -422,6 +436,10 @@ impl PersistentLivenessStorage for StorageWriteProxy {
             .save_highest_2chain_timeout_certificate(bcs::to_bytes(highest_timeout_cert)?)?)
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<()> {
+        Ok(self.db.save_equivocation_evidence(evidence)?)
+    }
+
     fn retrieve_epoch_change_proof(&self, version: u64) -> Result<EpochChangeProof> {
         let (_, proofs) = self
             .aptos_db
@@ -1,6 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "failpoints")]
+use crate::byzantine::Byzantine;
 use crate::{
     block_storage::{
         tracing::{observe_block, BlockStage},
@@ -33,6 +35,7 @@ use consensus_types::{
     block::Block,
     block_retrieval::{BlockRetrievalResponse, BlockRetrievalStatus},
     common::{Author, Round},
+    equivocation_evidence::EquivocationEvidence,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
     proposal_msg::ProposalMsg,
     quorum_cert::QuorumCert,
@@ -43,6 +46,7 @@ use consensus_types::{
 };
 use fail::fail_point;
 use futures::{channel::oneshot, FutureExt, StreamExt};
+use rand::rngs::StdRng;
 #[cfg(test)]
 use safety_rules::ConsensusState;
 use safety_rules::TSafetyRules;
@@ -145,6 +149,9 @@ pub struct RoundManager {
     storage: Arc<dyn PersistentLivenessStorage>,
     sync_only: bool,
     onchain_config: OnChainConsensusConfig,
+    block_retrieval_rng: Arc<Mutex<StdRng>>,
+    #[cfg(feature = "failpoints")]
+    byzantine: Option<Byzantine>,
 }
 
 impl RoundManager {
@@ -159,6 +166,7 @@ impl RoundManager {
         storage: Arc<dyn PersistentLivenessStorage>,
         sync_only: bool,
         onchain_config: OnChainConsensusConfig,
+        block_retrieval_rng: Arc<Mutex<StdRng>>,
     ) -> Self {
         // when decoupled execution is false,
         // the counter is still static.
@@ -179,6 +187,9 @@ impl RoundManager {
             storage,
             sync_only,
             onchain_config,
+            block_retrieval_rng,
+            #[cfg(feature = "failpoints")]
+            byzantine: None,
         }
     }
 
@@ -191,7 +202,11 @@ impl RoundManager {
     }
 
     fn create_block_retriever(&self, author: Author) -> BlockRetriever {
-        BlockRetriever::new(self.network.clone(), author)
+        BlockRetriever::new(
+            self.network.clone(),
+            author,
+            self.block_retrieval_rng.clone(),
+        )
     }
 
     /// Leader:
@@ -224,6 +239,11 @@ impl RoundManager {
             self.new_log(LogEvent::NewRound),
             reason = new_round_event.reason
         );
+        #[cfg(feature = "failpoints")]
+        {
+            self.attempt_to_broadcast_byzantine_sync_infos(new_round_event.round)
+                .await;
+        }
         if self
             .proposer_election
             .is_valid_proposer(self.proposal_generator.author(), new_round_event.round)
@@ -234,6 +254,10 @@ impl RoundManager {
             {
                 self.attempt_to_inject_reconfiguration_error(&proposal_msg)
                     .await?;
+                if self.attempt_to_equivocate_proposal(&proposal_msg).await? {
+                    counters::PROPOSALS_COUNT.inc();
+                    return Ok(());
+                }
             }
             network
                 .broadcast(ConsensusMsg::ProposalMsg(proposal_msg))
@@ -347,6 +371,9 @@ impl RoundManager {
                         remote_peer = author,
                         error = ?e,
                     );
+                    counters::MISBEHAVIOR_DETECTED_COUNT
+                        .with_label_values(&["invalid_sync_info"])
+                        .inc();
                     VerifyError::from(e)
                 })?;
             let result = self
@@ -528,6 +555,15 @@ impl RoundManager {
             proposal,
         );
 
+        // The equivocating proposal is still processed as usual, SafetyRules prevents voting twice
+        // in the same round.
+        if let Some(previous_proposal) = self.round_state.record_proposal(&proposal) {
+            self.report_equivocation(EquivocationEvidence::Proposals(
+                previous_proposal,
+                proposal.clone(),
+            ));
+        }
+
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
         ensure!(
@@ -555,6 +591,15 @@ impl RoundManager {
         );
 
         self.round_state.record_vote(vote.clone());
+        #[cfg(feature = "failpoints")]
+        {
+            if self
+                .attempt_to_inject_byzantine_vote(&vote, recipient)
+                .await
+            {
+                return Ok(());
+            }
+        }
         let vote_msg = VoteMsg::new(vote, self.block_store.sync_info());
         self.network.send_vote(vote_msg, vec![recipient]).await;
         Ok(())
@@ -690,10 +735,37 @@ impl RoundManager {
             VoteReceptionResult::New2ChainTimeoutCertificate(tc) => {
                 self.new_2chain_tc_aggregated(tc).await
             }
+            VoteReceptionResult::EquivocateVote(evidence) => {
+                self.report_equivocation(*evidence);
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
 
+    /// Logs and persists the evidence of a validator equivocating, so that it can be inspected
+    /// through the debug interface and later submitted to governance.
+    fn report_equivocation(&self, evidence: EquivocationEvidence) {
+        let security_event = match &evidence {
+            EquivocationEvidence::Proposals(_, _) => SecurityEvent::ConsensusEquivocatingProposal,
+            EquivocationEvidence::Votes(_, _) => SecurityEvent::ConsensusEquivocatingVote,
+        };
+        error!(
+            security_event,
+            remote_peer = evidence.author(),
+            evidence = evidence,
+        );
+        counters::MISBEHAVIOR_DETECTED_COUNT
+            .with_label_values(&[evidence.kind()])
+            .inc();
+        if let Err(e) = self.storage.save_equivocation_evidence(&evidence) {
+            error!(
+                error = ?e, evidence = %evidence,
+                "[RoundManager] Fail to persist equivocation evidence"
+            );
+        }
+    }
+
     async fn new_qc_aggregated(
         &mut self,
         qc: Arc<QuorumCert>,
@@ -785,6 +857,14 @@ impl RoundManager {
         self.safety_rules.lock().consensus_state().unwrap()
     }
 
+    /// Makes this validator misbehave as configured.
+    ///
+    /// It's only enabled with fault injection (failpoints feature).
+    #[cfg(feature = "failpoints")]
+    pub fn set_byzantine(&mut self, byzantine: Byzantine) {
+        self.byzantine = Some(byzantine);
+    }
+
     #[cfg(test)]
     pub fn set_safety_rules(&mut self, safety_rules: Arc<Mutex<MetricsSafetyRules>>) {
         self.safety_rules = safety_rules
@@ -900,4 +980,122 @@ impl RoundManager {
             Ok(())
         }
     }
+
+    /// Sends a second, different proposal for the round if the validator is configured to
+    /// equivocate. Half of the validators receive the original proposal first and the other half
+    /// the conflicting one, so that both are voted for. Returns true if the proposals were sent.
+    ///
+    /// It's only enabled with fault injection (failpoints feature).
+    #[cfg(feature = "failpoints")]
+    async fn attempt_to_equivocate_proposal(
+        &self,
+        proposal_msg: &ProposalMsg,
+    ) -> anyhow::Result<bool> {
+        if !self
+            .byzantine
+            .as_ref()
+            .map_or(false, Byzantine::equivocate_proposals)
+        {
+            return Ok(false);
+        }
+        let block_data = match Byzantine::conflicting_proposal(proposal_msg.proposal().block_data())
+        {
+            Some(block_data) => block_data,
+            None => return Ok(false),
+        };
+        let signature = self.safety_rules.lock().sign_proposal(&block_data)?;
+        let conflicting_proposal =
+            Block::new_proposal_from_block_data_and_signature(block_data, signature);
+        warn!(
+            self.new_log(LogEvent::Misbehave),
+            "Equivocating with {} and {}",
+            proposal_msg.proposal(),
+            conflicting_proposal
+        );
+
+        let mut first_half: Vec<_> = self
+            .epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .collect();
+        let second_half = first_half.split_off(first_half.len() / 2);
+        let proposal = ConsensusMsg::ProposalMsg(Box::new(proposal_msg.clone()));
+        let conflicting_proposal = ConsensusMsg::ProposalMsg(Box::new(ProposalMsg::new(
+            conflicting_proposal,
+            proposal_msg.sync_info().clone(),
+        )));
+        self.network
+            .send(proposal.clone(), first_half.clone())
+            .await;
+        self.network
+            .send(conflicting_proposal.clone(), second_half.clone())
+            .await;
+        self.network.send(conflicting_proposal, first_half).await;
+        self.network.send(proposal, second_half).await;
+        Ok(true)
+    }
+
+    /// Withholds the vote, or precedes it with a conflicting vote for the same round, if the
+    /// validator is configured to. Returns true if the vote was handled.
+    ///
+    /// It's only enabled with fault injection (failpoints feature).
+    #[cfg(feature = "failpoints")]
+    async fn attempt_to_inject_byzantine_vote(&self, vote: &Vote, recipient: Author) -> bool {
+        let byzantine = match &self.byzantine {
+            Some(byzantine) => byzantine,
+            None => return false,
+        };
+        if byzantine.withhold_votes() {
+            warn!(self.new_log(LogEvent::Misbehave), "Withholding {}", vote);
+            return true;
+        }
+        match byzantine.conflicting_vote(vote) {
+            Some(conflicting_vote) => {
+                warn!(
+                    self.new_log(LogEvent::Misbehave).remote_peer(recipient),
+                    "Double voting with {}", conflicting_vote
+                );
+                // The recipient keeps the latest vote if it can't keep up, so the honest vote is
+                // sent last
+                for vote in vec![conflicting_vote, vote.clone()] {
+                    let vote_msg = VoteMsg::new(vote, self.block_store.sync_info());
+                    self.network.send_vote(vote_msg, vec![recipient]).await;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Broadcasts stale or forged SyncInfo messages when entering a new round, if the validator
+    /// is configured to.
+    ///
+    /// It's only enabled with fault injection (failpoints feature).
+    #[cfg(feature = "failpoints")]
+    async fn attempt_to_broadcast_byzantine_sync_infos(&mut self, round: Round) {
+        let sync_info = self.block_store.sync_info();
+        let sync_infos = match &mut self.byzantine {
+            Some(byzantine) => byzantine.sync_infos_to_broadcast(&sync_info, round),
+            None => return,
+        };
+        let author = self.proposal_generator.author();
+        let other_validators: Vec<_> = self
+            .epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .filter(|validator| *validator != author)
+            .collect();
+        for sync_info in sync_infos {
+            warn!(
+                self.new_log(LogEvent::Misbehave),
+                "Broadcasting {}", sync_info
+            );
+            self.network
+                .send(
+                    ConsensusMsg::SyncInfo(Box::new(sync_info)),
+                    other_validators.clone(),
+                )
+                .await;
+        }
+    }
 }
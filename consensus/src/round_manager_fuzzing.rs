@@ -34,6 +34,7 @@ use network::{
     protocols::network::NewNetworkSender,
 };
 use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, SeedableRng};
 use safety_rules::{test_utils, SafetyRules, TSafetyRules};
 use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
@@ -168,6 +169,7 @@ fn create_node_for_fuzzing() -> RoundManager {
         storage,
         false,
         OnChainConsensusConfig::default(),
+        Arc::new(Mutex::new(StdRng::seed_from_u64(0))),
     )
 }
 
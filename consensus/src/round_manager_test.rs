@@ -41,6 +41,7 @@ use consensus_types::{
     },
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalStatus},
     common::{Author, Payload},
+    equivocation_evidence::EquivocationEvidence,
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
@@ -61,6 +62,7 @@ use network::{
     transport::ConnectionMetadata,
     ProtocolId,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
 use std::{iter::FromIterator, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
@@ -222,6 +224,7 @@ impl NodeSetup {
             storage.clone(),
             false,
             OnChainConsensusConfig::default(),
+            Arc::new(Mutex::new(StdRng::seed_from_u64(0))),
         );
         block_on(round_manager.init(last_vote_sent));
         Self {
@@ -389,6 +392,51 @@ fn no_vote_on_old_proposal() {
     });
 }
 
+#[test]
+/// Two different proposals for the same round are recorded as equivocation evidence
+fn equivocation_evidence_on_conflicting_proposals() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1);
+    let node = &mut nodes[0];
+    let genesis_qc = certificate_for_genesis();
+    let first_block = Block::new_proposal(vec![], 1, 1, genesis_qc.clone(), &node.signer);
+    let second_block = Block::new_proposal(vec![], 1, 2, genesis_qc, &node.signer);
+    timed_block_on(&mut runtime, async {
+        // clear the message queue
+        node.next_proposal().await;
+
+        node.round_manager
+            .process_proposal(first_block.clone())
+            .await
+            .unwrap();
+        assert!(node
+            .storage
+            .shared_storage
+            .equivocation_evidence
+            .lock()
+            .is_empty());
+
+        node.round_manager
+            .process_proposal(second_block.clone())
+            .await
+            .unwrap_err();
+        let evidence = node
+            .storage
+            .shared_storage
+            .equivocation_evidence
+            .lock()
+            .clone();
+        assert_eq!(
+            evidence,
+            vec![EquivocationEvidence::Proposals(first_block, second_block)]
+        );
+        evidence[0]
+            .verify(&node.round_manager.epoch_state().verifier)
+            .unwrap();
+    });
+}
+
 #[test]
 /// We don't vote for proposals that 'skips' rounds
 /// After that when we then receive proposal for correct round, we vote for it
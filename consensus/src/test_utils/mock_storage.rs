@@ -16,7 +16,8 @@ use aptos_types::{
     on_chain_config::ValidatorSet,
 };
 use consensus_types::{
-    block::Block, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    block::Block, equivocation_evidence::EquivocationEvidence, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -33,6 +34,7 @@ pub struct MockSharedStorage {
 
     // Liveness state
     pub highest_2chain_timeout_certificate: Mutex<Option<TwoChainTimeoutCertificate>>,
+    pub equivocation_evidence: Mutex<Vec<EquivocationEvidence>>,
     pub validator_set: ValidatorSet,
 }
 
@@ -44,6 +46,7 @@ impl MockSharedStorage {
             lis: Mutex::new(HashMap::new()),
             last_vote: Mutex::new(None),
             highest_2chain_timeout_certificate: Mutex::new(None),
+            equivocation_evidence: Mutex::new(vec![]),
             validator_set,
         }
     }
@@ -215,6 +218,14 @@ impl PersistentLivenessStorage for MockStorage {
         Ok(())
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<()> {
+        self.shared_storage
+            .equivocation_evidence
+            .lock()
+            .push(evidence.clone());
+        Ok(())
+    }
+
     fn retrieve_epoch_change_proof(&self, version: u64) -> Result<EpochChangeProof> {
         let lis = self
             .shared_storage
@@ -289,6 +300,10 @@ impl PersistentLivenessStorage for EmptyStorage {
         Ok(())
     }
 
+    fn save_equivocation_evidence(&self, _: &EquivocationEvidence) -> Result<()> {
+        Ok(())
+    }
+
     fn retrieve_epoch_change_proof(&self, _version: u64) -> Result<EpochChangeProof> {
         Ok(EpochChangeProof::new(vec![], false))
     }
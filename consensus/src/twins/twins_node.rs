@@ -38,6 +38,7 @@ use network::{
     transport::ConnectionMetadata,
     ProtocolId,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use std::{collections::HashMap, iter::FromIterator, sync::Arc};
 use tokio::runtime::{Builder, Runtime};
 
@@ -134,6 +135,7 @@ impl SMRNode {
             state_computer,
             storage.clone(),
             reconfig_listener,
+            StdRng::from_entropy(),
         );
         let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
 
@@ -27,7 +27,7 @@ use crate::{
 use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
 use serde::Serialize;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 #[derive(Clone, Eq, SerializeKey, DeserializeKey)]
 /// A BLS12381 public key
@@ -212,6 +212,18 @@ impl TryFrom<&[u8]> for PublicKey {
     }
 }
 
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bls12381::PublicKey({})", self)
+    }
+}
+
 impl std::hash::Hash for PublicKey {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         let encoded_pubkey = self.to_bytes();
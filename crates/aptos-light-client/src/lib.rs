@@ -0,0 +1,158 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A light client for the Aptos REST API.
+//!
+//! The client doesn't trust the node it talks to. It's bootstrapped from a waypoint, and ratchets
+//! its `TrustedState` through the epoch changes served by the node, verifying the signatures of
+//! each validator set along the way. Every state value and transaction it returns is verified
+//! against the latest ledger info it has verified.
+
+use anyhow::{format_err, Result};
+use aptos_rest_client::Client;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::AccountResource,
+    ledger_info::LedgerInfo,
+    state_store::state_key::StateKey,
+    transaction::{TransactionWithProof, Version},
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use move_deps::move_core_types::move_resource::MoveResource;
+use url::Url;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod mock;
+#[cfg(test)]
+mod tests;
+
+pub struct LightClient {
+    client: Client,
+    trusted_state: TrustedState,
+    /// The latest ledger info verified by `sync`, against which all reads are verified
+    ledger_info: Option<LedgerInfo>,
+}
+
+impl LightClient {
+    pub fn new(base_url: Url, waypoint: Waypoint) -> Self {
+        Self::with_client(Client::new(base_url), waypoint)
+    }
+
+    pub fn with_client(client: Client, waypoint: Waypoint) -> Self {
+        Self {
+            client,
+            trusted_state: TrustedState::from_epoch_waypoint(waypoint),
+            ledger_info: None,
+        }
+    }
+
+    pub fn trusted_state(&self) -> &TrustedState {
+        &self.trusted_state
+    }
+
+    /// The latest verified ledger info, `None` until the first `sync`
+    pub fn ledger_info(&self) -> Option<&LedgerInfo> {
+        self.ledger_info.as_ref()
+    }
+
+    /// Ratchets the trusted state to the latest ledger info of the node, following every epoch
+    /// change since the current trusted version. Returns the new latest verified ledger info.
+    pub async fn sync(&mut self) -> Result<&LedgerInfo> {
+        loop {
+            let state_proof = self
+                .client
+                .get_state_proof(self.trusted_state.version())
+                .await?
+                .into_inner();
+            let latest_li = state_proof.latest_ledger_info();
+            let (new_state, ledger_info, more) = match self
+                .trusted_state
+                .verify_and_ratchet(&state_proof)?
+            {
+                TrustedStateChange::Epoch {
+                    new_state,
+                    latest_epoch_change_li,
+                } => {
+                    // The latest ledger info is only verified if it's in the epoch we moved
+                    // into, otherwise we stop at the epoch change and ask for more.
+                    let ledger_info = if latest_li.version() == new_state.version() {
+                        latest_li.clone()
+                    } else {
+                        latest_epoch_change_li.ledger_info().clone()
+                    };
+                    let more = state_proof.epoch_changes().more;
+                    (new_state, ledger_info, more)
+                }
+                TrustedStateChange::Version { new_state } => (new_state, latest_li.clone(), false),
+                TrustedStateChange::NoChange => {
+                    (self.trusted_state.clone(), latest_li.clone(), false)
+                }
+            };
+            self.trusted_state = new_state;
+            self.ledger_info = Some(ledger_info);
+            if !more {
+                break;
+            }
+        }
+        Ok(self
+            .ledger_info
+            .as_ref()
+            .expect("Ledger info is set by sync"))
+    }
+
+    /// Returns the raw state value of `state_key` at the latest verified version
+    pub async fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        let ledger_info = self.synced_ledger_info()?;
+        let version = ledger_info.version();
+        let state_value_with_proof = self
+            .client
+            .get_state_value_with_proof(state_key, version)
+            .await?
+            .into_inner();
+        state_value_with_proof.verify(ledger_info, version, state_key)?;
+        Ok(state_value_with_proof
+            .state_value
+            .and_then(|state_value| state_value.maybe_bytes))
+    }
+
+    /// Returns the resource `T` of `address` at the latest verified version
+    pub async fn get_resource<T: MoveResource>(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Option<T>> {
+        let state_key = StateKey::AccessPath(AccessPath::new(
+            address,
+            AccessPath::resource_access_vec(T::struct_tag()),
+        ));
+        self.get_state_value(&state_key)
+            .await?
+            .map(|bytes| bcs::from_bytes(&bytes))
+            .transpose()
+            .map_err(|e| format_err!("Failed to deserialize {}: {}", T::struct_tag(), e))
+    }
+
+    pub async fn get_account(&self, address: AccountAddress) -> Result<Option<AccountResource>> {
+        self.get_resource(address).await
+    }
+
+    /// Returns the transaction at `version` and its events, verified to be included in the ledger
+    /// at the latest verified version
+    pub async fn get_transaction(&self, version: Version) -> Result<TransactionWithProof> {
+        let ledger_info = self.synced_ledger_info()?;
+        let transaction_with_proof = self
+            .client
+            .get_transaction_with_proof(version, ledger_info.version())
+            .await?
+            .into_inner();
+        transaction_with_proof.verify(ledger_info, version)?;
+        Ok(transaction_with_proof)
+    }
+
+    fn synced_ledger_info(&self) -> Result<&LedgerInfo> {
+        self.ledger_info
+            .as_ref()
+            .ok_or_else(|| format_err!("Light client must be synced before reading"))
+    }
+}
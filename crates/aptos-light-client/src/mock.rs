@@ -0,0 +1,234 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A mock ledger, served over the REST endpoints the light client reads from, to test clients
+//! that verify what they read.
+//!
+//! The ledger has two transactions: genesis at version 0, which moves the ledger into epoch 1,
+//! and a transaction at version 1, after which the state tree holds a single value. The ledger
+//! info at version 1 is signed by the validators of epoch 1.
+
+use aptos_api_types::{
+    X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+};
+use aptos_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher},
+    HashValue,
+};
+use aptos_types::{
+    block_info::BlockInfo,
+    chain_id::ChainId,
+    epoch_change::EpochChangeProof,
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{
+        accumulator::InMemoryAccumulator, SparseMerkleLeafNode, SparseMerkleProof,
+        TransactionAccumulatorProof, TransactionInfoWithProof,
+    },
+    state_proof::StateProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueWithProof},
+    },
+    transaction::{ExecutionStatus, TransactionInfo, Version},
+    validator_signer::ValidatorSigner,
+    validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
+    waypoint::Waypoint,
+};
+use std::{collections::BTreeMap, sync::Arc};
+use url::Url;
+use warp::{http::Response, Filter};
+
+const LATEST_VERSION: Version = 1;
+const LATEST_TIMESTAMP_USECS: u64 = 1_000_000;
+
+pub struct MockLedger {
+    state_key: StateKey,
+    /// The bytes under `state_key` in the state tree
+    value: Vec<u8>,
+    /// The bytes served for `state_key`, which only differ from the ones in the state tree when
+    /// the ledger is forged
+    served_value: Vec<u8>,
+    genesis_li: LedgerInfoWithSignatures,
+    latest_li: LedgerInfoWithSignatures,
+    txn_infos: [TransactionInfo; 2],
+}
+
+impl MockLedger {
+    /// Creates a ledger with `num_validators` validators in epoch 1, whose state holds `value`
+    /// under `state_key`
+    pub fn new(num_validators: usize, state_key: StateKey, value: Vec<u8>) -> Self {
+        let signers: Vec<_> = (0..num_validators)
+            .map(|i| ValidatorSigner::random([i as u8; 32]))
+            .collect();
+        Self::with_signers(&signers, state_key, value)
+    }
+
+    fn with_signers(signers: &[ValidatorSigner], state_key: StateKey, value: Vec<u8>) -> Self {
+        let verifier = ValidatorVerifier::new(
+            signers
+                .iter()
+                .map(|signer| {
+                    (
+                        signer.author(),
+                        ValidatorConsensusInfo::new(signer.public_key(), 1),
+                    )
+                })
+                .collect(),
+        );
+        let state_root =
+            SparseMerkleLeafNode::new(state_key.hash(), StateValue::from(value.clone()).hash())
+                .hash();
+        let txn_infos = [
+            TransactionInfo::new(
+                HashValue::zero(),
+                HashValue::zero(),
+                HashValue::zero(),
+                Some(HashValue::zero()),
+                0,
+                ExecutionStatus::Success,
+            ),
+            TransactionInfo::new(
+                HashValue::zero(),
+                HashValue::zero(),
+                HashValue::zero(),
+                Some(state_root),
+                0,
+                ExecutionStatus::Success,
+            ),
+        ];
+
+        let genesis_li = LedgerInfo::new(
+            BlockInfo::new(
+                0,
+                0,
+                HashValue::zero(),
+                txn_infos[0].hash(),
+                0,
+                0,
+                Some(EpochState { epoch: 1, verifier }),
+            ),
+            HashValue::zero(),
+        );
+        let accumulator_root = InMemoryAccumulator::<TransactionAccumulatorHasher>::from_leaves(&[
+            txn_infos[0].hash(),
+            txn_infos[1].hash(),
+        ])
+        .root_hash();
+        let latest_li = LedgerInfo::new(
+            BlockInfo::new(
+                1,
+                1,
+                HashValue::zero(),
+                accumulator_root,
+                LATEST_VERSION,
+                LATEST_TIMESTAMP_USECS,
+                None,
+            ),
+            HashValue::zero(),
+        );
+        let signatures = signers
+            .iter()
+            .map(|signer| (signer.author(), signer.sign(&latest_li)))
+            .collect();
+
+        Self {
+            state_key,
+            served_value: value.clone(),
+            value,
+            genesis_li: LedgerInfoWithSignatures::new(genesis_li, BTreeMap::new()),
+            latest_li: LedgerInfoWithSignatures::new(latest_li, signatures),
+            txn_infos,
+        }
+    }
+
+    /// Serves `value` for the state key, along with the proof of the genuine value
+    pub fn with_forged_value(mut self, value: Vec<u8>) -> Self {
+        self.served_value = value;
+        self
+    }
+
+    /// Replaces the latest ledger info with one of a ledger holding `value`, signed by
+    /// `num_validators` validators that are not the validators of epoch 1
+    pub fn with_forged_signatures(mut self, num_validators: usize, value: Vec<u8>) -> Self {
+        let signers: Vec<_> = (0..num_validators)
+            .map(|i| ValidatorSigner::random([u8::MAX - i as u8; 32]))
+            .collect();
+        let forged = Self::with_signers(&signers, self.state_key.clone(), value);
+        self.value = forged.value;
+        self.served_value = forged.served_value;
+        self.latest_li = forged.latest_li;
+        self.txn_infos[1] = forged.txn_infos[1].clone();
+        self
+    }
+
+    /// The waypoint of the genesis ledger info, to bootstrap clients from
+    pub fn waypoint(&self) -> Waypoint {
+        Waypoint::new_epoch_boundary(self.genesis_li.ledger_info())
+            .expect("Genesis ledger info is an epoch boundary")
+    }
+
+    pub fn state_proof(&self, known_version: Version) -> StateProof {
+        // Only a client that trusts nothing but the genesis waypoint needs the epoch change
+        let epoch_changes = if known_version == 0 {
+            vec![self.genesis_li.clone()]
+        } else {
+            vec![]
+        };
+        StateProof::new(
+            self.latest_li.clone(),
+            EpochChangeProof::new(epoch_changes, false),
+        )
+    }
+
+    pub fn state_value_with_proof(&self) -> StateValueWithProof {
+        // The state tree has a single leaf, so the proof has no siblings
+        let value_hash = StateValue::from(self.value.clone()).hash();
+        StateValueWithProof::new(
+            LATEST_VERSION,
+            Some(StateValue::from(self.served_value.clone())),
+            SparseMerkleProof::new(
+                Some(SparseMerkleLeafNode::new(self.state_key.hash(), value_hash)),
+                vec![],
+            ),
+            TransactionInfoWithProof::new(
+                TransactionAccumulatorProof::new(vec![self.txn_infos[0].hash()]),
+                self.txn_infos[1].clone(),
+            ),
+        )
+    }
+
+    /// Starts serving the ledger on an ephemeral port, returns the url to reach it at
+    pub fn serve(self) -> Url {
+        let ledger = Arc::new(self);
+        let state_proof_ledger = ledger.clone();
+        let state_proof = warp::path!("state_proof")
+            .and(warp::get())
+            .and(warp::query::<BTreeMap<String, Version>>())
+            .map(move |query: BTreeMap<String, Version>| {
+                let known_version = query.get("known_version").copied().unwrap_or(0);
+                bcs_response(&state_proof_ledger.state_proof(known_version))
+            });
+        let state_value_ledger = ledger;
+        let state_value = warp::path!("state_values" / String / "proof")
+            .and(warp::get())
+            .map(move |_state_key: String| {
+                bcs_response(&state_value_ledger.state_value_with_proof())
+            });
+        let (address, future) =
+            warp::serve(state_proof.or(state_value)).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(future);
+        Url::parse(&format!("http://localhost:{}/", address.port()))
+            .expect("Url of the mock ledger is valid")
+    }
+}
+
+fn bcs_response<T: serde::Serialize>(value: &T) -> Response<Vec<u8>> {
+    Response::builder()
+        .header(X_APTOS_CHAIN_ID, ChainId::test().id().to_string())
+        .header(X_APTOS_EPOCH, "1")
+        .header(X_APTOS_LEDGER_VERSION, LATEST_VERSION.to_string())
+        .header(X_APTOS_LEDGER_TIMESTAMP, LATEST_TIMESTAMP_USECS.to_string())
+        .body(bcs::to_bytes(value).expect("Mock ledger responses serialize"))
+        .expect("Mock ledger responses are valid")
+}
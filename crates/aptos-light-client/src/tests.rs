@@ -0,0 +1,101 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{mock::MockLedger, LightClient};
+use aptos_types::{
+    access_path::AccessPath, account_address::AccountAddress, account_config::AccountResource,
+    state_store::state_key::StateKey, trusted_state::TrustedState,
+};
+use move_deps::move_core_types::move_resource::MoveStructType;
+
+fn account_state_key(address: AccountAddress) -> StateKey {
+    StateKey::AccessPath(AccessPath::new(
+        address,
+        AccessPath::resource_access_vec(AccountResource::struct_tag()),
+    ))
+}
+
+fn account_ledger(address: AccountAddress, sequence_number: u64) -> MockLedger {
+    let account = AccountResource::new(sequence_number, vec![0; 32], address);
+    MockLedger::new(
+        4,
+        account_state_key(address),
+        bcs::to_bytes(&account).unwrap(),
+    )
+}
+
+fn forged_account(address: AccountAddress) -> Vec<u8> {
+    bcs::to_bytes(&AccountResource::new(1000, vec![1; 32], address)).unwrap()
+}
+
+#[tokio::test]
+async fn test_sync_from_waypoint() {
+    let address = AccountAddress::random();
+    let ledger = account_ledger(address, 7);
+    let waypoint = ledger.waypoint();
+    let mut client = LightClient::new(ledger.serve(), waypoint);
+
+    // Reads are only verified against a synced ledger info
+    assert!(client.get_account(address).await.is_err());
+
+    // The genesis waypoint moves the client into epoch 1, whose validators signed version 1
+    let ledger_info = client.sync().await.unwrap().clone();
+    assert_eq!(ledger_info.epoch(), 1);
+    assert_eq!(ledger_info.version(), 1);
+    match client.trusted_state() {
+        TrustedState::EpochState {
+            waypoint: new_waypoint,
+            epoch_state,
+        } => {
+            assert_eq!(epoch_state.epoch, 1);
+            assert_eq!(epoch_state.verifier.len(), 4);
+            assert_eq!(new_waypoint.version(), 1);
+        }
+        TrustedState::EpochWaypoint(_) => panic!("Trusted state must have left the waypoint"),
+    }
+
+    let account = client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(account.sequence_number(), 7);
+
+    // Nothing changed since, so the trusted state stays the same
+    let trusted_state = client.trusted_state().clone();
+    assert_eq!(client.sync().await.unwrap(), &ledger_info);
+    assert_eq!(client.trusted_state(), &trusted_state);
+}
+
+#[tokio::test]
+async fn test_reject_wrong_waypoint() {
+    let address = AccountAddress::random();
+    let ledger = account_ledger(address, 7);
+    // The genesis of a ledger with other validators has another waypoint
+    let waypoint = MockLedger::new(3, account_state_key(address), vec![]).waypoint();
+    let mut client = LightClient::new(ledger.serve(), waypoint);
+
+    assert!(client.sync().await.is_err());
+    assert!(client.trusted_state().is_epoch_waypoint());
+    assert!(client.ledger_info().is_none());
+}
+
+#[tokio::test]
+async fn test_reject_forged_signatures() {
+    let address = AccountAddress::random();
+    let ledger = account_ledger(address, 7).with_forged_signatures(4, forged_account(address));
+    let waypoint = ledger.waypoint();
+    let mut client = LightClient::new(ledger.serve(), waypoint);
+
+    assert!(client.sync().await.is_err());
+    assert!(client.trusted_state().is_epoch_waypoint());
+    assert!(client.get_account(address).await.is_err());
+}
+
+#[tokio::test]
+async fn test_reject_forged_state_value() {
+    let address = AccountAddress::random();
+    let ledger = account_ledger(address, 7).with_forged_value(forged_account(address));
+    let waypoint = ledger.waypoint();
+    let mut client = LightClient::new(ledger.serve(), waypoint);
+
+    // The ledger info is genuine, but the value doesn't match its proof
+    client.sync().await.unwrap();
+    assert!(client.get_account(address).await.is_err());
+}
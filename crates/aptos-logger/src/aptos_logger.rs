@@ -10,6 +10,7 @@ use crate::{
         STRUCT_LOG_PARSE_ERROR_COUNT, STRUCT_LOG_QUEUE_ERROR_COUNT, STRUCT_LOG_SEND_ERROR_COUNT,
     },
     logger::Logger,
+    sink::{NamedSink, Sink},
     struct_log::TcpWriter,
     Event, Filter, Key, Level, LevelFilter, Metadata,
 };
@@ -19,14 +20,16 @@ use chrono::{SecondsFormat, Utc};
 use once_cell::sync::Lazy;
 use serde::Serialize;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     env, fmt,
     io::Write,
+    str::FromStr,
     sync::{
-        mpsc::{self, Receiver, SyncSender},
+        mpsc::{self, Receiver, RecvTimeoutError, SyncSender},
         Arc,
     },
     thread,
+    time::Duration,
 };
 
 const RUST_LOG: &str = "RUST_LOG";
@@ -34,9 +37,22 @@ const RUST_LOG_REMOTE: &str = "RUST_LOG_REMOTE";
 /// Default size of log write channel, if the channel is full, logs will be dropped
 pub const CHANNEL_SIZE: usize = 10000;
 const NUM_SEND_RETRIES: u8 = 1;
+/// How often buffered sinks are flushed by the sink service
+const SINK_FLUSH_INTERVAL_MS: u64 = 1000;
+
+static HOSTNAME: Lazy<Option<String>> = Lazy::new(|| {
+    hostname::get()
+        .ok()
+        .and_then(|name| name.into_string().ok())
+});
+
+/// The hostname of the machine, if it could be determined
+pub(crate) fn hostname() -> Option<&'static str> {
+    HOSTNAME.as_deref()
+}
 
 /// A single log entry emitted by a logging macro with associated metadata
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct LogEntry {
     #[serde(flatten)]
     metadata: Metadata,
@@ -56,7 +72,7 @@ pub struct LogEntry {
 }
 
 impl LogEntry {
-    fn new(event: &Event, thread_name: Option<&str>, enable_backtrace: bool) -> Self {
+    pub(crate) fn new(event: &Event, thread_name: Option<&str>, enable_backtrace: bool) -> Self {
         use crate::{Value, Visitor};
 
         struct JsonVisitor<'a>(&'a mut BTreeMap<Key, serde_json::Value>);
@@ -83,13 +99,7 @@ impl LogEntry {
         let thread_name = thread_name.map(ToOwned::to_owned);
         let message = event.message().map(fmt::format);
 
-        static HOSTNAME: Lazy<Option<String>> = Lazy::new(|| {
-            hostname::get()
-                .ok()
-                .and_then(|name| name.into_string().ok())
-        });
-
-        let hostname = HOSTNAME.as_deref();
+        let hostname = hostname();
 
         let backtrace = if enable_backtrace && matches!(metadata.level(), Level::Error) {
             let mut backtrace = Backtrace::new();
@@ -158,6 +168,7 @@ pub struct AptosDataBuilder {
     printer: Option<Box<dyn Writer>>,
     is_async: bool,
     custom_format: Option<fn(&LogEntry) -> Result<String, fmt::Error>>,
+    sinks: Vec<(String, Box<dyn Sink>, Filter)>,
 }
 
 impl AptosDataBuilder {
@@ -172,6 +183,7 @@ impl AptosDataBuilder {
             printer: Some(Box::new(StderrWriter)),
             is_async: false,
             custom_format: None,
+            sinks: Vec::new(),
         }
     }
 
@@ -212,6 +224,18 @@ impl AptosDataBuilder {
         self
     }
 
+    /// Adds a named sink, which is written to with all logs that pass its own `Filter`.
+    /// The names `local` and `remote` are reserved for the printer and the remote writer.
+    pub fn add_sink(&mut self, name: String, sink: Box<dyn Sink>, filter: Filter) -> &mut Self {
+        assert!(
+            !matches!(name.as_str(), "" | "local" | "remote"),
+            "Invalid log sink name: {:?}",
+            name
+        );
+        self.sinks.push((name, sink, filter));
+        self
+    }
+
     pub fn is_async(&mut self, is_async: bool) -> &mut Self {
         self.is_async = is_async;
         self
@@ -230,7 +254,7 @@ impl AptosDataBuilder {
     }
 
     pub fn build(&mut self) -> Arc<AptosData> {
-        let filter = {
+        let mut filter = {
             let local_filter = {
                 let mut filter_builder = Filter::builder();
 
@@ -260,17 +284,34 @@ impl AptosDataBuilder {
                 filter_builder.build()
             };
 
-            FilterPair {
+            Filters {
                 local_filter,
                 remote_filter,
+                sink_filters: HashMap::new(),
             }
         };
 
+        let mut sinks = Vec::new();
+        for (name, sink, sink_filter) in self.sinks.drain(..) {
+            filter.sink_filters.insert(name.clone(), sink_filter);
+            sinks.push(NamedSink::new(name, sink));
+        }
+
+        // Sinks may block (e.g., on a slow collector), so they're always written to from their
+        // own thread, fed by a bounded channel that drops logs when full
+        let (sink_sender, sink_receiver) = if sinks.is_empty() {
+            (None, None)
+        } else {
+            let (sender, receiver) = mpsc::sync_channel(self.channel_size);
+            (Some(sender), Some(receiver))
+        };
+
         let logger = if self.is_async {
             let (sender, receiver) = mpsc::sync_channel(self.channel_size);
             let logger = Arc::new(AptosData {
                 enable_backtrace: self.enable_backtrace,
                 sender: Some(sender),
+                sink_sender,
                 printer: None,
                 filter: RwLock::new(filter),
                 formatter: self.custom_format.take().unwrap_or(default_format),
@@ -288,36 +329,110 @@ impl AptosDataBuilder {
             Arc::new(AptosData {
                 enable_backtrace: self.enable_backtrace,
                 sender: None,
+                sink_sender,
                 printer: self.printer.take(),
                 filter: RwLock::new(filter),
                 formatter: self.custom_format.take().unwrap_or(default_format),
             })
         };
 
+        if let Some(receiver) = sink_receiver {
+            let service = SinkService {
+                receiver,
+                sinks,
+                facade: logger.clone(),
+            };
+            thread::spawn(move || service.run());
+        }
+
         crate::logger::set_global_logger(logger.clone());
         logger
     }
 }
 
+/// Identifies one of the `Filter`s controlling where logs are written
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FilterTarget {
+    /// The local printer
+    Local,
+    /// The remote TCP writer
+    Remote,
+    /// The sink registered under the given name
+    Sink(String),
+}
+
+impl FromStr for FilterTarget {
+    type Err = FilterTargetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(FilterTargetParseError),
+            "local" => Ok(FilterTarget::Local),
+            "remote" => Ok(FilterTarget::Remote),
+            name => Ok(FilterTarget::Sink(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for FilterTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterTarget::Local => write!(f, "local"),
+            FilterTarget::Remote => write!(f, "remote"),
+            FilterTarget::Sink(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// An error given when a `FilterTarget` can't be parsed
+#[derive(Debug, Eq, PartialEq)]
+pub struct FilterTargetParseError;
+
 /// A combination of `Filter`s to control where logs are written
-struct FilterPair {
+struct Filters {
     /// The local printer `Filter` to control what is logged in text output
     local_filter: Filter,
     /// The remote logging `Filter` to control what is sent to external logging
     remote_filter: Filter,
+    /// The `Filter` of each additional sink, keyed by the sink name
+    sink_filters: HashMap<String, Filter>,
 }
 
-impl FilterPair {
+impl Filters {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.local_filter.enabled(metadata) || self.remote_filter.enabled(metadata)
+        self.local_filter.enabled(metadata)
+            || self.remote_filter.enabled(metadata)
+            || self.any_sink_enabled(metadata)
+    }
+
+    fn any_sink_enabled(&self, metadata: &Metadata) -> bool {
+        self.sink_filters
+            .values()
+            .any(|filter| filter.enabled(metadata))
+    }
+
+    fn sink_enabled(&self, name: &str, metadata: &Metadata) -> bool {
+        self.sink_filters
+            .get(name)
+            .map_or(false, |filter| filter.enabled(metadata))
+    }
+
+    fn get_mut(&mut self, target: &FilterTarget) -> Option<&mut Filter> {
+        match target {
+            FilterTarget::Local => Some(&mut self.local_filter),
+            FilterTarget::Remote => Some(&mut self.remote_filter),
+            FilterTarget::Sink(name) => self.sink_filters.get_mut(name),
+        }
     }
 }
 
 pub struct AptosData {
     enable_backtrace: bool,
     sender: Option<SyncSender<LoggerServiceEvent>>,
+    /// Sends logs to the `SinkService`, if there are any sinks
+    sink_sender: Option<SyncSender<LoggerServiceEvent>>,
     printer: Option<Box<dyn Writer>>,
-    filter: RwLock<FilterPair>,
+    filter: RwLock<Filters>,
     pub(crate) formatter: fn(&LogEntry) -> Result<String, fmt::Error>,
 }
 
@@ -351,12 +466,51 @@ impl AptosData {
         self.filter.write().remote_filter = filter;
     }
 
+    /// Updates the `Filter` of the given target at runtime, returns false if there is no such
+    /// target (i.e., the sink doesn't exist).
+    pub fn update_filter<F: FnOnce(&mut Filter)>(&self, target: &FilterTarget, update: F) -> bool {
+        match self.filter.write().get_mut(target) {
+            Some(filter) => {
+                update(filter);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the directives of all `Filter`s, keyed by their target
+    pub fn filter_directives(&self) -> BTreeMap<String, String> {
+        let filters = self.filter.read();
+        let mut directives = BTreeMap::new();
+        directives.insert(
+            FilterTarget::Local.to_string(),
+            filters.local_filter.to_string(),
+        );
+        directives.insert(
+            FilterTarget::Remote.to_string(),
+            filters.remote_filter.to_string(),
+        );
+        for (name, filter) in &filters.sink_filters {
+            directives.insert(name.clone(), filter.to_string());
+        }
+        directives
+    }
+
     fn send_entry(&self, entry: LogEntry) {
         if let Some(printer) = &self.printer {
             let s = (self.formatter)(&entry).expect("Unable to format");
             printer.write(s);
         }
 
+        if let Some(sink_sender) = &self.sink_sender {
+            if self.filter.read().any_sink_enabled(&entry.metadata) {
+                if let Err(e) = sink_sender.try_send(LoggerServiceEvent::LogEntry(entry.clone())) {
+                    STRUCT_LOG_QUEUE_ERROR_COUNT.inc();
+                    eprintln!("Failed to send log to sinks: {}", e);
+                }
+            }
+        }
+
         if let Some(sender) = &self.sender {
             if let Err(e) = sender.try_send(LoggerServiceEvent::LogEntry(entry)) {
                 STRUCT_LOG_QUEUE_ERROR_COUNT.inc();
@@ -382,7 +536,7 @@ impl Logger for AptosData {
     }
 
     fn flush(&self) {
-        if let Some(sender) = &self.sender {
+        for sender in self.sender.iter().chain(&self.sink_sender) {
             let (oneshot_sender, oneshot_receiver) = mpsc::sync_channel(1);
             sender
                 .send(LoggerServiceEvent::Flush(oneshot_sender))
@@ -493,6 +647,57 @@ impl LoggerService {
     }
 }
 
+/// A service writing logs to the `Sink`s, so slow sinks never block the threads that log
+struct SinkService {
+    receiver: Receiver<LoggerServiceEvent>,
+    sinks: Vec<NamedSink>,
+    facade: Arc<AptosData>,
+}
+
+impl SinkService {
+    pub fn run(mut self) {
+        loop {
+            // Periodically flush the sinks, so batched logs are exported even when idle
+            let event = match self
+                .receiver
+                .recv_timeout(Duration::from_millis(SINK_FLUSH_INTERVAL_MS))
+            {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.flush_sinks();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            match event {
+                LoggerServiceEvent::LogEntry(entry) => {
+                    for sink in &mut self.sinks {
+                        if self
+                            .facade
+                            .filter
+                            .read()
+                            .sink_enabled(sink.name(), &entry.metadata)
+                        {
+                            sink.write(&entry);
+                        }
+                    }
+                }
+                LoggerServiceEvent::Flush(sender) => {
+                    self.flush_sinks();
+                    let _ = sender.send(());
+                }
+            }
+        }
+    }
+
+    fn flush_sinks(&mut self) {
+        for sink in &mut self.sinks {
+            sink.flush();
+        }
+    }
+}
+
 /// An trait encapsulating the operations required for writing logs.
 pub trait Writer: Send + Sync {
     /// Write the log.
@@ -570,7 +775,7 @@ fn default_format(entry: &LogEntry) -> Result<String, fmt::Error> {
 
 #[cfg(test)]
 mod tests {
-    use super::LogEntry;
+    use super::{FilterTarget, FilterTargetParseError, LogEntry};
     use crate::{
         debug, error, info, logger::Logger, trace, warn, Event, Key, KeyValue, Level, Metadata,
         Schema, Value, Visitor,
@@ -757,6 +962,15 @@ mod tests {
         error!("Literal" = %display_struct, other = "value", identifier = ?debug_struct, "Mixed test");
     }
 
+    #[test]
+    fn filter_target_parse() {
+        assert_eq!("local".parse(), Ok(FilterTarget::Local));
+        assert_eq!("remote".parse(), Ok(FilterTarget::Remote));
+        assert_eq!("otlp".parse(), Ok(FilterTarget::Sink("otlp".to_string())));
+        assert_eq!("".parse::<FilterTarget>(), Err(FilterTargetParseError));
+        assert_eq!(FilterTarget::Sink("otlp".to_string()).to_string(), "otlp");
+    }
+
     struct DebugStruct {}
 
     impl std::fmt::Debug for DebugStruct {
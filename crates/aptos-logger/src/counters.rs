@@ -3,7 +3,7 @@
 
 //! Logging metrics for determining quality of log submission
 use once_cell::sync::Lazy;
-use prometheus::{register_int_counter, IntCounter};
+use prometheus::{register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec};
 
 /// Count of the struct logs submitted by macro
 pub static STRUCT_LOG_COUNT: Lazy<IntCounter> = Lazy::new(|| {
@@ -79,3 +79,23 @@ pub static STRUCT_LOG_PARSE_ERROR_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Count of struct logs written to each sink
+pub static STRUCT_LOG_SINK_WRITE_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_struct_log_sink_write_count",
+        "Count of the struct logs written to each sink.",
+        &["sink"]
+    )
+    .unwrap()
+});
+
+/// Count of struct logs dropped by each sink, e.g. due to write or export errors
+pub static STRUCT_LOG_SINK_DROP_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_struct_log_sink_drop_count",
+        "Count of the struct logs dropped by each sink.",
+        &["sink"]
+    )
+    .unwrap()
+});
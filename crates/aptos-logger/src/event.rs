@@ -14,7 +14,7 @@ pub struct Event<'a> {
 }
 
 impl<'a> Event<'a> {
-    fn new(
+    pub(crate) fn new(
         metadata: &'a Metadata,
         message: Option<fmt::Arguments<'a>>,
         keys_and_values: &'a [&'a dyn Schema],
@@ -4,7 +4,7 @@
 //! Filtering definitions for controlling what modules and levels are logged
 
 use crate::{Level, Metadata};
-use std::{env, str::FromStr};
+use std::{env, fmt, str::FromStr};
 
 pub struct FilterParseError;
 
@@ -40,6 +40,20 @@ impl FromStr for LevelFilter {
     }
 }
 
+impl fmt::Display for LevelFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = match self {
+            LevelFilter::Off => "OFF",
+            LevelFilter::Error => "ERROR",
+            LevelFilter::Warn => "WARN",
+            LevelFilter::Info => "INFO",
+            LevelFilter::Debug => "DEBUG",
+            LevelFilter::Trace => "TRACE",
+        };
+        f.pad(level)
+    }
+}
+
 impl From<Level> for LevelFilter {
     fn from(level: Level) -> Self {
         match level {
@@ -107,13 +121,7 @@ impl Builder {
             // Add the default filter if none exist
             self.filter_level(LevelFilter::Error);
         } else {
-            // Sort the directives by length of their name, this allows a
-            // little more efficient lookup at runtime.
-            self.directives.sort_by(|a, b| {
-                let alen = a.name.as_ref().map(|a| a.len()).unwrap_or(0);
-                let blen = b.name.as_ref().map(|b| b.len()).unwrap_or(0);
-                alen.cmp(&blen)
-            });
+            sort_directives(&mut self.directives);
         }
 
         Filter {
@@ -133,6 +141,15 @@ impl Filter {
         Builder::new()
     }
 
+    /// Sets the most verbose level logged for a single module, replacing any existing directive
+    /// for exactly that module and leaving all other directives untouched.
+    pub fn set_module_level(&mut self, module: &str, level: LevelFilter) {
+        self.directives
+            .retain(|directive| directive.name.as_deref() != Some(module));
+        self.directives.push(Directive::new(Some(module), level));
+        sort_directives(&mut self.directives);
+    }
+
     pub fn enabled(&self, metadata: &Metadata) -> bool {
         // Search for the longest match, the vector is assumed to be pre-sorted.
         for directive in self.directives.iter().rev() {
@@ -145,6 +162,32 @@ impl Filter {
     }
 }
 
+/// Formats the filter as a directives string, which can be parsed by `Builder::parse`
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, directive) in self.directives.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            match &directive.name {
+                Some(name) => write!(f, "{}={}", name, directive.level)?,
+                None => write!(f, "{}", directive.level)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sort the directives by length of their name, this allows a
+/// little more efficient lookup at runtime.
+fn sort_directives(directives: &mut [Directive]) {
+    directives.sort_by(|a, b| {
+        let alen = a.name.as_ref().map(|a| a.len()).unwrap_or(0);
+        let blen = b.name.as_ref().map(|b| b.len()).unwrap_or(0);
+        alen.cmp(&blen)
+    });
+}
+
 /// A `Filter` directive for which logs to keep based on a module `name` based filter
 #[derive(Debug)]
 struct Directive {
@@ -277,6 +320,36 @@ mod tests {
         assert!(logger.enabled(&make_metadata(Level::Info, "crate2::mod2")));
     }
 
+    #[test]
+    fn set_module_level() {
+        let mut logger = Builder::new()
+            .filter(None, LevelFilter::Info)
+            .filter(Some("crate1::mod1"), LevelFilter::Warn)
+            .build();
+        assert!(!logger.enabled(&make_metadata(Level::Debug, "crate2::mod2")));
+
+        // Add a directive for a new module
+        logger.set_module_level("crate2", LevelFilter::Debug);
+        assert!(logger.enabled(&make_metadata(Level::Debug, "crate2::mod2")));
+        assert!(!logger.enabled(&make_metadata(Level::Debug, "crate3")));
+
+        // Replace the directive of an existing module
+        logger.set_module_level("crate1::mod1", LevelFilter::Off);
+        assert!(!logger.enabled(&make_metadata(Level::Error, "crate1::mod1")));
+        assert_eq!(logger.to_string(), "INFO,crate2=DEBUG,crate1::mod1=OFF");
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let filter = Builder::new().parse("warn,crate1::mod1=debug").build();
+        let directives = filter.to_string();
+        assert_eq!(directives, "WARN,crate1::mod1=DEBUG");
+        assert_eq!(
+            Builder::new().parse(&directives).build().to_string(),
+            directives
+        );
+    }
+
     #[test]
     fn parse_valid() {
         let mut builder = Builder::new();
@@ -151,12 +151,15 @@ mod logger;
 mod macros;
 mod metadata;
 pub mod sample;
+pub mod sink;
 pub mod tracing_adapter;
 
 mod security;
 mod struct_log;
 
-pub use crate::aptos_logger::{AptosData as Logger, AptosDataBuilder, Writer, CHANNEL_SIZE};
+pub use crate::aptos_logger::{
+    AptosData as Logger, AptosDataBuilder, FilterTarget, Writer, CHANNEL_SIZE,
+};
 pub use event::Event;
 pub use filter::{Filter, LevelFilter};
 pub use logger::flush;
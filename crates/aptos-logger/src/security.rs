@@ -40,6 +40,9 @@ pub enum SecurityEvent {
     /// Consensus received an equivocating vote
     ConsensusEquivocatingVote,
 
+    /// Consensus received an equivocating proposal
+    ConsensusEquivocatingProposal,
+
     /// Consensus received an invalid proposal
     InvalidConsensusProposal,
 
@@ -0,0 +1,498 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pluggable log sinks, each of which receives structured `LogEntry`s that pass its own `Filter`.
+//!
+//! In addition to the local printer and the remote TCP writer, any number of named sinks can be
+//! registered with the `AptosDataBuilder`, e.g. size capped rotating files or an OTLP/JSON HTTP
+//! exporter. Sinks are written to from a dedicated thread, so logs are dropped when it falls
+//! behind rather than blocking the threads that log. Writes that fail are dropped and counted per
+//! sink.
+
+use crate::{
+    aptos_logger::LogEntry,
+    counters::{STRUCT_LOG_SINK_DROP_COUNT, STRUCT_LOG_SINK_WRITE_COUNT},
+    Level,
+};
+use chrono::DateTime;
+use serde_json::json;
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const HTTP_CONNECTION_TIMEOUT_MS: u64 = 5000;
+const HTTP_IO_TIMEOUT_MS: u64 = 5000;
+
+/// An error given when a `Sink` fails to write, carrying the number of entries that were dropped
+#[derive(Debug)]
+pub struct SinkError {
+    num_dropped: u64,
+    error: io::Error,
+}
+
+impl SinkError {
+    pub fn new(num_dropped: u64, error: io::Error) -> Self {
+        Self { num_dropped, error }
+    }
+
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped
+    }
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dropped {} log entries: {}",
+            self.num_dropped, self.error
+        )
+    }
+}
+
+/// A trait encapsulating the operations required for writing structured logs to a destination.
+///
+/// Sinks are only handed the entries that pass their `Filter`, so they don't need to filter.
+pub trait Sink: Send {
+    /// Write (or buffer) a single log entry
+    fn write(&mut self, entry: &LogEntry) -> Result<(), SinkError>;
+
+    /// Flush any buffered log entries
+    fn flush(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// A `Sink` together with the name it was registered under, used for filters and metrics
+pub(crate) struct NamedSink {
+    name: String,
+    sink: Box<dyn Sink>,
+}
+
+impl NamedSink {
+    pub fn new(name: String, sink: Box<dyn Sink>) -> Self {
+        Self { name, sink }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn write(&mut self, entry: &LogEntry) {
+        match self.sink.write(entry) {
+            Ok(()) => STRUCT_LOG_SINK_WRITE_COUNT
+                .with_label_values(&[&self.name])
+                .inc(),
+            Err(error) => self.record_error(error),
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if let Err(error) = self.sink.flush() {
+            self.record_error(error);
+        }
+    }
+
+    fn record_error(&self, error: SinkError) {
+        STRUCT_LOG_SINK_DROP_COUNT
+            .with_label_values(&[&self.name])
+            .inc_by(error.num_dropped());
+        eprintln!(
+            "[Logging] Error while writing to sink {}: {}",
+            self.name, error
+        );
+    }
+}
+
+/// A `Sink` writing JSON lines to a file, which is rotated once it exceeds a maximum size.
+///
+/// Rotated files are suffixed with an index, e.g. `node.log.1` is the most recently rotated file.
+/// Only `max_rotated_files` are kept, older files are deleted.
+pub struct RotatingFileSink {
+    path: PathBuf,
+    max_file_size_bytes: u64,
+    max_rotated_files: usize,
+    file: File,
+    file_size_bytes: u64,
+}
+
+impl RotatingFileSink {
+    pub fn new(
+        path: PathBuf,
+        max_file_size_bytes: u64,
+        max_rotated_files: usize,
+    ) -> io::Result<Self> {
+        let file = open_log_file(&path)?;
+        let file_size_bytes = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_file_size_bytes,
+            max_rotated_files,
+            file,
+            file_size_bytes,
+        })
+    }
+
+    /// Returns the path of the rotated file with the given index
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        path.into()
+    }
+
+    /// Shifts all rotated files by one (deleting the oldest) and starts a new log file
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_rotated_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..self.max_rotated_files).rev() {
+                let rotated_path = self.rotated_path(index);
+                if rotated_path.exists() {
+                    fs::rename(rotated_path, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = open_log_file(&self.path)?;
+        self.file_size_bytes = 0;
+        Ok(())
+    }
+}
+
+impl Sink for RotatingFileSink {
+    fn write(&mut self, entry: &LogEntry) -> Result<(), SinkError> {
+        let line = serde_json::to_string(entry).map_err(|e| SinkError::new(1, e.into()))? + "\n";
+        let line_length = line.len() as u64;
+
+        // Never rotate an empty file, even if a single entry exceeds the maximum size
+        if self.file_size_bytes > 0 && self.file_size_bytes + line_length > self.max_file_size_bytes
+        {
+            self.rotate().map_err(|e| SinkError::new(1, e))?;
+        }
+
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| SinkError::new(1, e))?;
+        self.file_size_bytes += line_length;
+        Ok(())
+    }
+}
+
+fn open_log_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().append(true).create(true).open(path)
+}
+
+/// A `Sink` exporting batches of log entries to an OpenTelemetry collector using OTLP/JSON over
+/// HTTP, e.g. `http://localhost:4318/v1/logs`.
+///
+/// Entries are buffered until `batch_size` entries are pending or the sink is flushed. A failed
+/// export drops the whole batch. Only plain HTTP is supported, so the collector is expected to run
+/// alongside the node.
+pub struct OtlpHttpSink {
+    /// The `host:port` of the collector
+    address: String,
+    /// The HTTP path logs are posted to
+    path: String,
+    batch_size: usize,
+    pending_records: Vec<serde_json::Value>,
+}
+
+impl OtlpHttpSink {
+    pub fn new(endpoint: &str, batch_size: usize) -> io::Result<Self> {
+        let endpoint = endpoint.strip_prefix("http://").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Only http:// OTLP endpoints are supported: {}", endpoint),
+            )
+        })?;
+        let (address, path) = match endpoint.find('/') {
+            Some(index) => endpoint.split_at(index),
+            None => (endpoint, "/"),
+        };
+
+        Ok(Self {
+            address: address.to_string(),
+            path: path.to_string(),
+            batch_size: batch_size.max(1),
+            pending_records: Vec::new(),
+        })
+    }
+
+    /// Sends all pending records to the collector
+    fn export(&mut self) -> Result<(), SinkError> {
+        let records = std::mem::take(&mut self.pending_records);
+        let num_records = records.len() as u64;
+        let body = otlp_logs_request(records).to_string();
+        self.post(body.as_bytes())
+            .map_err(|e| SinkError::new(num_records, e))
+    }
+
+    /// Posts the body to the collector, reads the whole response and checks for a successful
+    /// status code
+    fn post(&self, body: &[u8]) -> io::Result<()> {
+        let mut stream = self.connect()?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            self.address,
+            body.len()
+        )?;
+        stream.write_all(body)?;
+        stream.flush()?;
+
+        // The status line looks like `HTTP/1.1 200 OK`. The rest of the response is drained until
+        // the collector closes the connection, so it's never reset with unread data.
+        let mut reader = BufReader::new(stream);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line)?;
+        io::copy(&mut reader, &mut io::sink())?;
+        match status_line.split_whitespace().nth(1) {
+            Some(status) if status.starts_with('2') => Ok(()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Unexpected OTLP response: {}", status_line.trim()),
+            )),
+        }
+    }
+
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_error = io::Error::new(
+            io::ErrorKind::Other,
+            format!("Unable to resolve and connect to {}", self.address),
+        );
+
+        for addr in self.address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(
+                &addr,
+                Duration::from_millis(HTTP_CONNECTION_TIMEOUT_MS),
+            ) {
+                Ok(stream) => {
+                    stream.set_write_timeout(Some(Duration::from_millis(HTTP_IO_TIMEOUT_MS)))?;
+                    stream.set_read_timeout(Some(Duration::from_millis(HTTP_IO_TIMEOUT_MS)))?;
+                    return Ok(stream);
+                }
+                Err(err) => last_error = err,
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+impl Sink for OtlpHttpSink {
+    fn write(&mut self, entry: &LogEntry) -> Result<(), SinkError> {
+        self.pending_records.push(otlp_log_record(entry));
+        if self.pending_records.len() >= self.batch_size {
+            self.export()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        if self.pending_records.is_empty() {
+            Ok(())
+        } else {
+            self.export()
+        }
+    }
+}
+
+/// Wraps log records into an OTLP `ExportLogsServiceRequest`
+fn otlp_logs_request(records: Vec<serde_json::Value>) -> serde_json::Value {
+    let mut resource_attributes = Vec::new();
+    if let Some(hostname) = crate::aptos_logger::hostname() {
+        resource_attributes.push(otlp_attribute(
+            "host.name",
+            json!({ "stringValue": hostname }),
+        ));
+    }
+
+    json!({
+        "resourceLogs": [{
+            "resource": { "attributes": resource_attributes },
+            "scopeLogs": [{
+                "scope": { "name": "aptos-logger" },
+                "logRecords": records,
+            }],
+        }],
+    })
+}
+
+/// Converts a log entry into an OTLP `LogRecord`
+fn otlp_log_record(entry: &LogEntry) -> serde_json::Value {
+    let metadata = entry.metadata();
+    let time_unix_nano = DateTime::parse_from_rfc3339(entry.timestamp())
+        .map(|timestamp| timestamp.timestamp_nanos())
+        .unwrap_or_default();
+
+    let mut attributes = vec![
+        otlp_attribute(
+            "code.namespace",
+            json!({ "stringValue": metadata.module_path() }),
+        ),
+        otlp_attribute("code.filepath", json!({ "stringValue": metadata.file() })),
+        otlp_attribute(
+            "code.lineno",
+            json!({ "intValue": metadata.line().to_string() }),
+        ),
+    ];
+    if let Some(thread_name) = entry.thread_name() {
+        attributes.push(otlp_attribute(
+            "thread.name",
+            json!({ "stringValue": thread_name }),
+        ));
+    }
+    if let Some(backtrace) = entry.backtrace() {
+        attributes.push(otlp_attribute(
+            "exception.stacktrace",
+            json!({ "stringValue": backtrace }),
+        ));
+    }
+    if let Ok(serde_json::Value::Object(data)) = serde_json::to_value(entry.data()) {
+        for (key, value) in data {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+            attributes.push(otlp_attribute(&key, json!({ "stringValue": value })));
+        }
+    }
+
+    json!({
+        "timeUnixNano": time_unix_nano.to_string(),
+        "severityNumber": otlp_severity_number(metadata.level()),
+        "severityText": metadata.level().to_string(),
+        "body": { "stringValue": entry.message().unwrap_or_default() },
+        "attributes": attributes,
+    })
+}
+
+fn otlp_attribute(key: &str, value: serde_json::Value) -> serde_json::Value {
+    json!({ "key": key, "value": value })
+}
+
+/// Maps a `Level` to the lowest OTLP severity number of the matching severity range
+fn otlp_severity_number(level: Level) -> u8 {
+    match level {
+        Level::Trace => 1,
+        Level::Debug => 5,
+        Level::Info => 9,
+        Level::Warn => 13,
+        Level::Error => 17,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Metadata};
+    use std::{io::Read, net::TcpListener, thread};
+
+    fn log_entry(level: Level, message: &str) -> LogEntry {
+        let metadata = Metadata::new(
+            level,
+            "aptos_logger",
+            "aptos_logger::sink::tests",
+            file!(),
+            line!(),
+            concat!(file!(), ":", line!()),
+        );
+        let event = Event::new(&metadata, Some(format_args!("{}", message)), &[]);
+        LogEntry::new(&event, None, false)
+    }
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aptos-logger-sink-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("node.log")
+    }
+
+    #[test]
+    fn rotating_file_sink_rotates_and_caps_files() {
+        let path = temp_log_path("rotate");
+        let line_length = serde_json::to_string(&log_entry(Level::Info, "0"))
+            .unwrap()
+            .len() as u64
+            + 1;
+
+        // Allow two entries per file and keep two rotated files
+        let mut sink = RotatingFileSink::new(path.clone(), 2 * line_length, 2).unwrap();
+        for index in 0..7 {
+            sink.write(&log_entry(Level::Info, &index.to_string()))
+                .unwrap();
+        }
+
+        let read_messages = |path: &Path| -> Vec<String> {
+            fs::read_to_string(path)
+                .unwrap()
+                .lines()
+                .map(|line| {
+                    let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                    value["message"].as_str().unwrap().to_string()
+                })
+                .collect()
+        };
+        assert_eq!(read_messages(&path), vec!["6"]);
+        assert_eq!(read_messages(&sink.rotated_path(1)), vec!["4", "5"]);
+        assert_eq!(read_messages(&sink.rotated_path(2)), vec!["2", "3"]);
+        assert!(!sink.rotated_path(3).exists());
+    }
+
+    #[test]
+    fn otlp_http_sink_exports_batches() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/v1/logs", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            // Read until the full JSON body has arrived
+            loop {
+                let num_bytes = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..num_bytes]);
+                if num_bytes == 0 || request.ends_with(b"}") {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 21\r\n\r\n{\"partialSuccess\":{}}")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let mut sink = OtlpHttpSink::new(&endpoint, 2).unwrap();
+        sink.write(&log_entry(Level::Warn, "first")).unwrap();
+        assert_eq!(sink.pending_records.len(), 1);
+        sink.write(&log_entry(Level::Error, "second")).unwrap();
+        assert!(sink.pending_records.is_empty());
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/logs HTTP/1.1\r\n"));
+        let body = request.split("\r\n\r\n").nth(1).unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let records = &body["resourceLogs"][0]["scopeLogs"][0]["logRecords"];
+        assert_eq!(records[0]["body"]["stringValue"], "first");
+        assert_eq!(records[0]["severityNumber"], 13);
+        assert_eq!(records[1]["body"]["stringValue"], "second");
+        assert_eq!(records[1]["severityText"], "ERROR");
+    }
+
+    #[test]
+    fn otlp_http_sink_rejects_https() {
+        assert!(OtlpHttpSink::new("https://localhost:4318/v1/logs", 1).is_err());
+        let sink = OtlpHttpSink::new("http://localhost:4318", 1).unwrap();
+        assert_eq!(sink.address, "localhost:4318");
+        assert_eq!(sink.path, "/");
+    }
+}
@@ -2,18 +2,31 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, Result};
-use aptos_api_types::mime_types::BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE;
-pub use aptos_api_types::{self, MoveModuleBytecode, PendingTransaction, Transaction};
+use aptos_api_types::mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE};
+pub use aptos_api_types::{
+    self, MoveModuleBytecode, PendingTransaction, Transaction, TransactionData,
+    TransactionOnChainData, TransactionSubmissionStatus,
+};
 use aptos_crypto::HashValue;
 use aptos_types::{
-    account_address::AccountAddress, account_config::aptos_root_address,
-    transaction::SignedTransaction,
+    account_address::AccountAddress,
+    account_config::{aptos_root_address, AccountResource},
+    contract_event::ContractEvent,
+    event::EventKey,
+    ledger_info::LedgerInfoWithSignatures,
+    state_proof::StateProof,
+    state_store::{state_key::StateKey, state_value::StateValueWithProof},
+    transaction::{SignedTransaction, TransactionWithProof},
+};
+use move_deps::move_core_types::{language_storage::StructTag, move_resource::MoveResource};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Client as ReqwestClient, RequestBuilder, StatusCode,
 };
-use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 use state::State;
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 use url::Url;
 
 pub mod error;
@@ -83,11 +96,18 @@ impl Client {
             epoch: r.epoch,
             version: r.ledger_version,
             timestamp_usecs: r.ledger_timestamp,
+            cursor: None,
         });
 
         Ok(response)
     }
 
+    /// Returns the latest ledger info along with the signatures of the validators
+    pub async fn get_ledger_information_bcs(&self) -> Result<Response<LedgerInfoWithSignatures>> {
+        let response = self.get_bcs(self.base_url.clone()).send().await?;
+        self.bcs(response).await
+    }
+
     pub async fn submit(&self, txn: &SignedTransaction) -> Result<Response<PendingTransaction>> {
         let txn_payload = bcs::to_bytes(txn)?;
         let url = self.base_url.join("transactions")?;
@@ -103,6 +123,26 @@ impl Client {
         self.json(response).await
     }
 
+    /// Submits `txns` in one request. Each transaction is accepted or rejected by mempool on its
+    /// own, the returned statuses are in the order of `txns`.
+    pub async fn submit_batch(
+        &self,
+        txns: &[SignedTransaction],
+    ) -> Result<Response<Vec<TransactionSubmissionStatus>>> {
+        let txn_payload = bcs::to_bytes(txns)?;
+        let url = self.base_url.join("transactions/batch")?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     pub async fn submit_and_wait(&self, txn: &SignedTransaction) -> Result<Response<Transaction>> {
         self.submit(txn).await?;
         self.wait_for_signed_transaction(txn).await
@@ -192,6 +232,27 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_transactions_bcs(
+        &self,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Response<Vec<TransactionOnChainData>>> {
+        let url = self.base_url.join("transactions")?;
+
+        let mut request = self.get_bcs(url);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+
+        self.bcs(response).await
+    }
+
     pub async fn get_transaction(&self, hash: HashValue) -> Result<Response<Transaction>> {
         self.json(
             self.get_transaction_by_version_or_hash(hash.to_hex_literal())
@@ -208,6 +269,24 @@ impl Client {
         .await
     }
 
+    /// Returns the transaction with `hash`, either committed or still pending in mempool
+    pub async fn get_transaction_bcs(&self, hash: HashValue) -> Result<Response<TransactionData>> {
+        let url = self
+            .base_url
+            .join(&format!("transactions/{}", hash.to_hex_literal()))?;
+        let response = self.get_bcs(url).send().await?;
+        self.bcs(response).await
+    }
+
+    pub async fn get_transaction_by_version_bcs(
+        &self,
+        version: u64,
+    ) -> Result<Response<TransactionData>> {
+        let url = self.base_url.join(&format!("transactions/{}", version))?;
+        let response = self.get_bcs(url).send().await?;
+        self.bcs(response).await
+    }
+
     async fn get_transaction_by_version_or_hash(
         &self,
         version_or_hash: String,
@@ -243,6 +322,30 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_account_transactions_bcs(
+        &self,
+        address: AccountAddress,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Response<Vec<TransactionOnChainData>>> {
+        let url = self
+            .base_url
+            .join(&format!("accounts/{}/transactions", address))?;
+
+        let mut request = self.get_bcs(url);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+
+        self.bcs(response).await
+    }
+
     pub async fn get_account_resources(
         &self,
         address: AccountAddress,
@@ -256,6 +359,20 @@ impl Client {
         self.json(response).await
     }
 
+    /// Returns the raw BCS encoded resources of `address`, by their type
+    pub async fn get_account_resources_bcs(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<BTreeMap<StructTag, Vec<u8>>>> {
+        let url = self
+            .base_url
+            .join(&format!("accounts/{}/resources", address))?;
+
+        let response = self.get_bcs(url).send().await?;
+
+        self.bcs(response).await
+    }
+
     pub async fn get_resource<T: DeserializeOwned>(
         &self,
         address: AccountAddress,
@@ -289,6 +406,29 @@ impl Client {
         self.json(response).await
     }
 
+    /// Returns the resource `T` of `address`, decoded from its BCS encoding
+    pub async fn get_account_resource_bcs<T: DeserializeOwned>(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+    ) -> Result<Response<T>> {
+        let url = self
+            .base_url
+            .join(&format!("accounts/{}/resource/{}", address, resource_type))?;
+
+        let response = self.get_bcs(url).send().await?;
+        self.bcs(response).await
+    }
+
+    /// Returns the resource `T` of `address`, e.g. a type generated by `generate-move-structs`
+    pub async fn get_move_resource<T: MoveResource>(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<T>> {
+        self.get_account_resource_bcs(address, &T::struct_tag().to_string())
+            .await
+    }
+
     pub async fn get_account_modules(
         &self,
         address: AccountAddress,
@@ -301,6 +441,19 @@ impl Client {
         self.json(response).await
     }
 
+    /// Returns the bytecode of the modules published under `address`
+    pub async fn get_account_modules_bcs(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<Vec<Vec<u8>>>> {
+        let url = self
+            .base_url
+            .join(&format!("accounts/{}/modules", address))?;
+
+        let response = self.get_bcs(url).send().await?;
+        self.bcs(response).await
+    }
+
     pub async fn get_table_item<K: Serialize>(
         &self,
         table_handle: u128,
@@ -321,12 +474,126 @@ impl Client {
         self.json(response).await
     }
 
+    /// Returns a page of the items of a table, sorted by key. The next page starts from the
+    /// `cursor` of the response state, which is `None` on the last page. A page may hold fewer
+    /// than `limit` items even when it isn't the last one.
+    pub async fn get_table_items(
+        &self,
+        table_handle: u128,
+        start: Option<&str>,
+        limit: Option<u16>,
+    ) -> Result<Response<Vec<Value>>> {
+        let url = self
+            .base_url
+            .join(&format!("tables/{}/items", table_handle))?;
+
+        let mut request = self.inner.get(url);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+        self.json(response).await
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> Result<Response<Account>> {
         let url = self.base_url.join(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
         self.json(response).await
     }
 
+    pub async fn get_account_bcs(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<AccountResource>> {
+        let url = self.base_url.join(&format!("accounts/{}", address))?;
+        let response = self.get_bcs(url).send().await?;
+        self.bcs(response).await
+    }
+
+    pub async fn get_events_bcs(
+        &self,
+        key: EventKey,
+        start: Option<u64>,
+        limit: Option<u16>,
+    ) -> Result<Response<Vec<ContractEvent>>> {
+        let url = self.base_url.join(&format!("events/{}", key))?;
+
+        let mut request = self.get_bcs(url);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+
+        self.bcs(response).await
+    }
+
+    /// Returns the latest ledger info with signatures, and the epoch change proof from the epoch
+    /// of `known_version` to the latest one.
+    pub async fn get_state_proof(&self, known_version: u64) -> Result<Response<StateProof>> {
+        let url = self.base_url.join("state_proof")?;
+        let response = self
+            .inner
+            .get(url)
+            .query(&[("known_version", known_version)])
+            .send()
+            .await?;
+        self.bcs(response).await
+    }
+
+    /// Returns the state value of `state_key` at `ledger_version`, with the proofs needed to
+    /// verify it against the ledger info at that version.
+    pub async fn get_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        ledger_version: u64,
+    ) -> Result<Response<StateValueWithProof>> {
+        let url = self.base_url.join(&format!(
+            "state_values/{}/proof",
+            hex::encode(bcs::to_bytes(state_key)?)
+        ))?;
+        let response = self
+            .inner
+            .get(url)
+            .query(&[("ledger_version", ledger_version)])
+            .send()
+            .await?;
+        self.bcs(response).await
+    }
+
+    /// Returns the transaction at `version` and its events, with the proof needed to verify it
+    /// against the ledger info at `ledger_version`.
+    pub async fn get_transaction_with_proof(
+        &self,
+        version: u64,
+        ledger_version: u64,
+    ) -> Result<Response<TransactionWithProof>> {
+        let url = self
+            .base_url
+            .join(&format!("transactions/{}/proof", version))?;
+        let response = self
+            .inner
+            .get(url)
+            .query(&[("ledger_version", ledger_version)])
+            .send()
+            .await?;
+        self.bcs(response).await
+    }
+
+    /// A GET request asking for the BCS encoding of the response
+    fn get_bcs(&self, url: Url) -> RequestBuilder {
+        self.inner.get(url).header(ACCEPT, BCS)
+    }
+
     async fn check_response(
         &self,
         response: reqwest::Response,
@@ -349,6 +616,15 @@ impl Client {
         Ok(Response::new(json, state))
     }
 
+    async fn bcs<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<Response<T>> {
+        let (response, state) = self.check_response(response).await?;
+        let bytes = response.bytes().await?;
+        Ok(Response::new(bcs::from_bytes(&bytes)?, state))
+    }
+
     pub async fn health_check(&self, seconds: u64) -> Result<()> {
         let url = self.base_url.join("-/healthy")?;
         let response = self
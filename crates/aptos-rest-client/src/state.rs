@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_api_types::{
-    X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+    X_APTOS_CHAIN_ID, X_APTOS_CURSOR, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP,
+    X_APTOS_LEDGER_VERSION,
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -11,6 +12,8 @@ pub struct State {
     pub epoch: u64,
     pub version: u64,
     pub timestamp_usecs: u64,
+    /// Where the next page starts, for the paginated endpoints which have one
+    pub cursor: Option<String>,
 }
 
 impl State {
@@ -31,6 +34,10 @@ impl State {
             .get(X_APTOS_EPOCH)
             .and_then(|h| h.to_str().ok())
             .and_then(|s| s.parse().ok());
+        let cursor = headers
+            .get(X_APTOS_CURSOR)
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.to_owned());
 
         let state = if let (Some(chain_id), Some(version), Some(timestamp_usecs), Some(epoch)) =
             (maybe_chain_id, maybe_version, maybe_timestamp, maybe_epoch)
@@ -40,6 +47,7 @@ impl State {
                 epoch,
                 version,
                 timestamp_usecs,
+                cursor,
             }
         } else {
             todo!()
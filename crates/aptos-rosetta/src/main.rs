@@ -27,6 +27,9 @@ async fn main() {
         tls_cert_path: args.tls_cert_path,
         tls_key_path: args.tls_key_path,
         content_length_limit: args.content_length_limit,
+        max_view_gas_amount: None,
+        max_submit_transaction_batch_size: None,
+        auth: None,
     };
 
     // Ensure runtime for Rosetta is up and running
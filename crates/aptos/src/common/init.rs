@@ -9,13 +9,14 @@ use crate::{
         },
         utils::{fund_account, prompt_yes_with_override, read_line},
     },
-    op::key::GenerateKey,
+    op::key::{derive_ed25519_from_mnemonic, GenerateKey},
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
+use aptos_sdk::derivation_path::{DerivationPath, DEFAULT_DERIVATION_PATH};
 use async_trait::async_trait;
 use clap::Parser;
 use reqwest::Url;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 pub const DEFAULT_REST_URL: &str = "https://fullnode.devnet.aptoslabs.com";
 pub const DEFAULT_FAUCET_URL: &str = "https://faucet.devnet.aptoslabs.com";
@@ -32,6 +33,12 @@ pub struct InitTool {
     /// URL for the Faucet endpoint
     #[clap(long)]
     pub faucet_url: Option<Url>,
+    /// Derive the private key from a BIP-39 mnemonic phrase, read from the prompt
+    #[clap(long)]
+    pub from_mnemonic: bool,
+    /// SLIP-0010 derivation path of the key from the mnemonic phrase [default: m/44'/637'/0'/0'/0']
+    #[clap(long, requires = "from_mnemonic")]
+    pub derivation_path: Option<String>,
     #[clap(flatten)]
     pub(crate) private_key_options: PrivateKeyInputOptions,
     #[clap(flatten)]
@@ -120,11 +127,30 @@ impl CliCommand<()> for InitTool {
         profile_config.faucet_url = Some(faucet_url.to_string());
 
         // Private key
-        let private_key = if let Some(private_key) = self
+        let cli_private_key = self
             .private_key_options
-            .extract_private_key_cli(self.encoding_options.encoding)?
-        {
+            .extract_private_key_cli(self.encoding_options.encoding)?;
+        let private_key = if self.from_mnemonic {
+            if cli_private_key.is_some() {
+                return Err(CliError::CommandArgumentError(
+                    "--from-mnemonic can't be used with a private key".to_string(),
+                ));
+            }
+            let derivation_path = self
+                .derivation_path
+                .as_deref()
+                .unwrap_or(DEFAULT_DERIVATION_PATH);
+            let derivation_path = DerivationPath::from_str(derivation_path)
+                .map_err(|err| CliError::UnableToParse("DerivationPath", err.to_string()))?;
+            eprintln!("Enter your mnemonic phrase");
+            let mnemonic = read_line("Mnemonic phrase")?;
+            let private_key = derive_ed25519_from_mnemonic(&derivation_path, &mnemonic)?;
+            // The phrase is never stored, only the path to derive the key again from it
+            profile_config.derivation_path = Some(derivation_path.to_string());
+            private_key
+        } else if let Some(private_key) = cli_private_key {
             eprintln!("Using command line argument for private key");
+            profile_config.derivation_path = None;
             private_key
         } else {
             eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
@@ -139,6 +165,7 @@ impl CliCommand<()> for InitTool {
                     GenerateKey::generate_ed25519_in_memory()
                 }
             } else {
+                profile_config.derivation_path = None;
                 Ed25519PrivateKey::from_encoded_string(input)
                     .map_err(|err| CliError::UnableToParse("Ed25519PrivateKey", err.to_string()))?
             }
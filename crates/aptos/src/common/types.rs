@@ -142,6 +142,8 @@ pub struct ProfileConfig {
     pub rest_url: Option<String>,
     /// URL for the Faucet endpoint (if applicable)
     pub faucet_url: Option<String>,
+    /// Derivation path of the private key, if it was derived from a mnemonic phrase
+    pub derivation_path: Option<String>,
 }
 
 impl Default for CliConfig {
@@ -651,6 +653,15 @@ pub struct MovePackageDir {
 }
 
 impl MovePackageDir {
+    /// Options for the package in `package_dir`, without named addresses
+    pub fn new(package_dir: PathBuf) -> Self {
+        Self {
+            package_dir,
+            output_dir: None,
+            named_addresses: Default::default(),
+        }
+    }
+
     /// Retrieve the NamedAddresses, resolving all the account addresses accordingly
     pub fn named_addresses(&self) -> BTreeMap<String, AccountAddress> {
         self.named_addresses
@@ -723,11 +734,11 @@ pub trait CliCommand<T: Serialize + Send>: Sized + Send {
 /// A shortened transaction output
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct TransactionSummary {
-    changes: Vec<ChangeSummary>,
-    gas_used: Option<u64>,
-    success: bool,
-    version: Option<u64>,
-    vm_status: String,
+    pub changes: Vec<ChangeSummary>,
+    pub gas_used: Option<u64>,
+    pub success: bool,
+    pub version: Option<u64>,
+    pub vm_status: String,
 }
 
 impl From<Transaction> for TransactionSummary {
@@ -10,6 +10,7 @@ pub mod genesis;
 pub mod move_tool;
 pub mod op;
 pub mod test;
+pub mod transaction;
 
 use crate::common::types::{CliCommand, CliResult};
 use clap::Parser;
@@ -30,6 +31,8 @@ pub enum Tool {
     Key(op::key::KeyTool),
     #[clap(subcommand)]
     Move(move_tool::MoveTool),
+    #[clap(subcommand)]
+    Transaction(transaction::TransactionTool),
 }
 
 impl Tool {
@@ -42,6 +45,7 @@ impl Tool {
             Tool::Init(tool) => tool.execute_serialized_success().await,
             Tool::Key(tool) => tool.execute().await,
             Tool::Move(tool) => tool.execute().await,
+            Tool::Transaction(tool) => tool.execute().await,
         }
     }
 }
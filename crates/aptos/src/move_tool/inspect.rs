@@ -0,0 +1,654 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to inspect the Move modules and resources of accounts on chain
+
+use crate::common::{
+    types::{
+        CliCommand, CliConfig, CliError, CliTypedResult, MovePackageDir, ProfileOptions,
+        PromptOptions, RestOptions,
+    },
+    utils::{check_if_file_exists, write_to_file},
+};
+use aptos_infallible::Mutex;
+use aptos_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, AptosValueAnnotator};
+use aptos_rest_client::{aptos_api_types::MoveModule, Client};
+use aptos_state_view::StateView;
+use aptos_types::{
+    access_path::Path as AccessPathType, account_address::AccountAddress,
+    state_store::state_key::StateKey,
+};
+use aptos_vm::data_cache::RemoteStorage;
+use async_trait::async_trait;
+use clap::Parser;
+use move_deps::{
+    move_binary_format::{
+        access::ModuleAccess, binary_views::BinaryIndexedView, compatibility::Compatibility,
+        normalized::Module, CompiledModule,
+    },
+    move_core_types::{
+        identifier::Identifier,
+        language_storage::{ModuleId, StructTag},
+    },
+    move_disassembler::disassembler::Disassembler,
+    move_ir_types::location::Spanned,
+    move_package::{compilation::compiled_package::CompiledPackage, BuildConfig},
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    future::Future,
+    path::PathBuf,
+};
+
+/// Lists the modules published under an account, with their exposed functions and structs
+#[derive(Parser)]
+pub struct ListModules {
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    /// Address of the account the modules are published under
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, parse(try_from_str = crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+}
+
+#[async_trait]
+impl CliCommand<Vec<MoveModule>> for ListModules {
+    fn command_name(&self) -> &'static str {
+        "ListModules"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<MoveModule>> {
+        let account = account_or_profile(self.account, &self.profile_options)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        client
+            .get_account_modules(account)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .into_iter()
+            .map(|module| {
+                module
+                    .try_parse_abi()
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                    .abi
+                    .ok_or_else(|| {
+                        CliError::UnexpectedError("Unable to parse a module on chain".to_string())
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Downloads the bytecode of a module published under an account
+///
+/// The bytecode is saved to `<output_dir>/<module_name>.mv`, and its disassembly to
+/// `<output_dir>/<module_name>.mvasm` with `--disassemble`
+#[derive(Parser)]
+pub struct DownloadModule {
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+    /// Address of the account the module is published under
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, parse(try_from_str = crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+    /// Name of the module to download
+    #[clap(long)]
+    pub(crate) module_name: Identifier,
+    /// Directory to save the module to
+    #[clap(long, parse(from_os_str), default_value_os_t = crate::common::utils::current_dir())]
+    pub(crate) output_dir: PathBuf,
+    /// Also save the disassembled bytecode of the module
+    #[clap(long)]
+    pub(crate) disassemble: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<PathBuf>> for DownloadModule {
+    fn command_name(&self) -> &'static str {
+        "DownloadModule"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<PathBuf>> {
+        let account = account_or_profile(self.account, &self.profile_options)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let module_id = ModuleId::new(account, self.module_name);
+        let (bytes, module) = get_account_modules(&client, account)
+            .await?
+            .into_iter()
+            .find(|(_, module)| module.self_id() == module_id)
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(format!("Module {} not found", module_id))
+            })?;
+
+        let module_path = self.output_dir.join(format!("{}.mv", module_id.name()));
+        check_if_file_exists(module_path.as_path(), self.prompt_options)?;
+        write_to_file(module_path.as_path(), "Module bytecode", &bytes)?;
+        let mut paths = vec![module_path];
+
+        if self.disassemble {
+            let disassembly = Disassembler::from_view(
+                BinaryIndexedView::Module(&module),
+                Spanned::unsafe_no_loc(()).loc,
+            )
+            .and_then(|disassembler| disassembler.disassemble())
+            .map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Unable to disassemble module {}: {}",
+                    module_id, err
+                ))
+            })?;
+            let disassembly_path = self.output_dir.join(format!("{}.mvasm", module_id.name()));
+            check_if_file_exists(disassembly_path.as_path(), self.prompt_options)?;
+            write_to_file(
+                disassembly_path.as_path(),
+                "Module disassembly",
+                disassembly.as_bytes(),
+            )?;
+            paths.push(disassembly_path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Shows the resources held by an account, with the names of their fields
+#[derive(Parser)]
+pub struct ViewResources {
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    /// Address of the account holding the resources
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, parse(try_from_str = crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+}
+
+#[async_trait]
+impl CliCommand<BTreeMap<String, Value>> for ViewResources {
+    fn command_name(&self) -> &'static str {
+        "ViewResources"
+    }
+
+    async fn execute(self) -> CliTypedResult<BTreeMap<String, Value>> {
+        let account = account_or_profile(self.account, &self.profile_options)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let resources = client
+            .get_account_resources_bcs(account)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner();
+
+        let mut modules = RemoteModules::default();
+        let mut annotated = BTreeMap::new();
+        for (tag, bytes) in resources {
+            let resource = annotate_resource(&mut modules, &tag, &bytes, |address| {
+                get_account_modules(&client, address)
+            })
+            .await?;
+            annotated.insert(tag.to_string(), struct_to_json(&resource));
+        }
+        Ok(annotated)
+    }
+}
+
+/// Annotates a resource with the names of its fields
+///
+/// The layout of a resource may need modules of other accounts, which are fetched with `fetch`
+/// as the annotator asks for them. Each account is fetched at most once, so this ends once the
+/// annotator stops asking for new accounts.
+async fn annotate_resource<F, Fut>(
+    modules: &mut RemoteModules,
+    tag: &StructTag,
+    bytes: &[u8],
+    mut fetch: F,
+) -> CliTypedResult<AnnotatedMoveStruct>
+where
+    F: FnMut(AccountAddress) -> Fut,
+    Fut: Future<Output = CliTypedResult<Vec<(Vec<u8>, CompiledModule)>>>,
+{
+    loop {
+        let result =
+            AptosValueAnnotator::new(&RemoteStorage::new(&*modules)).view_resource(tag, bytes);
+        let missing = modules.take_missing();
+        match result {
+            Ok(resource) => return Ok(resource),
+            Err(err) if missing.is_empty() => {
+                return Err(CliError::UnexpectedError(format!(
+                    "Unable to annotate resource {}: {}",
+                    tag, err
+                )))
+            }
+            Err(_) => {
+                for address in missing {
+                    let fetched = fetch(address).await?;
+                    modules.add(address, fetched);
+                }
+            }
+        }
+    }
+}
+
+/// Checks that the modules of a package can upgrade the modules already published on chain
+///
+/// Upgrades must keep the linking API of structs and functions, and the layout of structs
+#[derive(Parser)]
+pub struct CheckCompatibility {
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The compatibility of a module of a package with the one published on chain
+#[derive(Debug, Serialize)]
+pub struct ModuleCompatibility {
+    pub module: String,
+    /// Whether the module is already published
+    pub published: bool,
+    /// Whether the structs and functions used by other modules are unchanged
+    pub struct_and_function_linking: bool,
+    /// Whether the layout of the structs is unchanged
+    pub struct_layout: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<ModuleCompatibility>> for CheckCompatibility {
+    fn command_name(&self) -> &'static str {
+        "CheckCompatibility"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<ModuleCompatibility>> {
+        let build_config = BuildConfig {
+            additional_named_addresses: self.move_options.named_addresses(),
+            generate_abis: false,
+            install_dir: self.move_options.output_dir.clone(),
+            ..Default::default()
+        };
+        let package = super::compile_move(build_config, self.move_options.package_dir.as_path())?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        check_compatibility(&client, &package).await
+    }
+}
+
+/// Checks that the modules of a compiled package can upgrade the modules published on chain
+///
+/// Returns an error listing the incompatibilities if there are any
+pub async fn check_compatibility(
+    client: &Client,
+    package: &CompiledPackage,
+) -> CliTypedResult<Vec<ModuleCompatibility>> {
+    let modules: Vec<CompiledModule> = package
+        .root_modules_map()
+        .iter_modules()
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let mut published = HashMap::new();
+    let mut compatibilities = Vec::new();
+    for module in &modules {
+        let module_id = module.self_id();
+        if !published.contains_key(module_id.address()) {
+            let modules: HashMap<ModuleId, CompiledModule> =
+                get_account_modules(client, *module_id.address())
+                    .await?
+                    .into_iter()
+                    .map(|(_, module)| (module.self_id(), module))
+                    .collect();
+            published.insert(*module_id.address(), modules);
+        }
+
+        let compatibility = match published[module_id.address()].get(&module_id) {
+            Some(old_module) => {
+                let compatibility =
+                    Compatibility::check(&Module::new(old_module), &Module::new(module));
+                ModuleCompatibility {
+                    module: module_id.to_string(),
+                    published: true,
+                    struct_and_function_linking: compatibility.struct_and_function_linking,
+                    struct_layout: compatibility.struct_layout,
+                }
+            }
+            // New modules can always be published
+            None => ModuleCompatibility {
+                module: module_id.to_string(),
+                published: false,
+                struct_and_function_linking: true,
+                struct_layout: true,
+            },
+        };
+        compatibilities.push(compatibility);
+    }
+
+    let mut incompatibilities = Vec::new();
+    for module in &compatibilities {
+        if !module.struct_and_function_linking {
+            incompatibilities.push(format!(
+                "the linking API of the structs or functions of {} changed",
+                module.module
+            ));
+        }
+        if !module.struct_layout {
+            incompatibilities.push(format!(
+                "the layout of the structs of {} changed",
+                module.module
+            ));
+        }
+    }
+    if !incompatibilities.is_empty() {
+        return Err(CliError::CommandArgumentError(format!(
+            "Package is incompatible with the modules on chain: {}",
+            incompatibilities.join(", ")
+        )));
+    }
+    Ok(compatibilities)
+}
+
+/// Returns the given account, or else the account of the profile
+fn account_or_profile(
+    account: Option<AccountAddress>,
+    profile_options: &ProfileOptions,
+) -> CliTypedResult<AccountAddress> {
+    if let Some(account) = account {
+        Ok(account)
+    } else if let Some(Some(account)) =
+        CliConfig::load_profile(&profile_options.profile)?.map(|p| p.account)
+    {
+        Ok(account)
+    } else {
+        Err(CliError::CommandArgumentError(
+            "Please provide an account using --account or run aptos init".to_string(),
+        ))
+    }
+}
+
+/// Retrieves the bytecode of the modules published under `address`, with the modules it encodes
+async fn get_account_modules(
+    client: &Client,
+    address: AccountAddress,
+) -> CliTypedResult<Vec<(Vec<u8>, CompiledModule)>> {
+    client
+        .get_account_modules_bcs(address)
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_inner()
+        .into_iter()
+        .map(|bytes| {
+            let module = CompiledModule::deserialize(&bytes).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Unable to deserialize a module of {}: {}",
+                    address, err
+                ))
+            })?;
+            Ok((bytes, module))
+        })
+        .collect()
+}
+
+/// The modules of accounts fetched from the REST API, as a [`StateView`] for the resource viewer
+#[derive(Default)]
+struct RemoteModules {
+    modules: HashMap<ModuleId, Vec<u8>>,
+    fetched: HashSet<AccountAddress>,
+    /// Accounts of the modules asked for and not fetched yet
+    missing: Mutex<BTreeSet<AccountAddress>>,
+}
+
+impl RemoteModules {
+    /// Adds the modules fetched for `address`, which is then no longer reported as missing
+    fn add(&mut self, address: AccountAddress, modules: Vec<(Vec<u8>, CompiledModule)>) {
+        self.fetched.insert(address);
+        for (bytes, module) in modules {
+            self.modules.insert(module.self_id(), bytes);
+        }
+    }
+
+    fn take_missing(&self) -> BTreeSet<AccountAddress> {
+        std::mem::take(&mut *self.missing.lock())
+    }
+}
+
+impl StateView for RemoteModules {
+    fn get_state_value(&self, state_key: &StateKey) -> anyhow::Result<Option<Vec<u8>>> {
+        if let StateKey::AccessPath(access_path) = state_key {
+            if let AccessPathType::Code(module_id) = access_path.get_path() {
+                if !self.fetched.contains(module_id.address()) {
+                    self.missing.lock().insert(*module_id.address());
+                }
+                return Ok(self.modules.get(&module_id).cloned());
+            }
+        }
+        Ok(None)
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
+
+fn struct_to_json(annotated: &AnnotatedMoveStruct) -> Value {
+    Value::Object(
+        annotated
+            .value
+            .iter()
+            .map(|(name, value)| (name.to_string(), value_to_json(value)))
+            .collect(),
+    )
+}
+
+/// Integers beyond 32 bits are strings, as in the REST API
+fn value_to_json(annotated: &AnnotatedMoveValue) -> Value {
+    match annotated {
+        AnnotatedMoveValue::U8(value) => json!(value),
+        AnnotatedMoveValue::U64(value) => json!(value.to_string()),
+        AnnotatedMoveValue::U128(value) => json!(value.to_string()),
+        AnnotatedMoveValue::Bool(value) => json!(value),
+        AnnotatedMoveValue::Address(address) => json!(address.to_hex_literal()),
+        AnnotatedMoveValue::Vector(_, values) => {
+            Value::Array(values.iter().map(value_to_json).collect())
+        }
+        AnnotatedMoveValue::Bytes(bytes) => json!(format!("0x{}", hex::encode(bytes))),
+        AnnotatedMoveValue::Struct(annotated) => struct_to_json(annotated),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::access_path::AccessPath;
+    use move_deps::{
+        move_binary_format::file_format::AbilitySet,
+        move_core_types::language_storage::{TypeTag, CORE_CODE_ADDRESS},
+    };
+    use std::future::ready;
+
+    fn struct_tag(
+        address: AccountAddress,
+        module: &str,
+        name: &str,
+        type_params: Vec<TypeTag>,
+    ) -> StructTag {
+        StructTag {
+            address,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params,
+        }
+    }
+
+    fn annotated_struct(value: Vec<(&str, AnnotatedMoveValue)>) -> AnnotatedMoveStruct {
+        AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: struct_tag(CORE_CODE_ADDRESS, "M", "S", vec![]),
+            value: value
+                .into_iter()
+                .map(|(name, value)| (Identifier::new(name).unwrap(), value))
+                .collect(),
+        }
+    }
+
+    fn module_key(address: AccountAddress, name: &str) -> StateKey {
+        StateKey::AccessPath(AccessPath::code_access_path(ModuleId::new(
+            address,
+            Identifier::new(name).unwrap(),
+        )))
+    }
+
+    /// The framework modules published under `address`, as the REST API would return them
+    fn framework_modules(address: AccountAddress) -> Vec<(Vec<u8>, CompiledModule)> {
+        cached_framework_packages::modules_with_blobs()
+            .filter(|(_, module)| *module.self_id().address() == address)
+            .map(|(bytes, module)| (bytes.clone(), module.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_value_to_json() {
+        assert_eq!(value_to_json(&AnnotatedMoveValue::U8(7)), json!(7));
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::U64(u64::MAX)),
+            json!("18446744073709551615")
+        );
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::U128(u128::MAX)),
+            json!("340282366920938463463374607431768211455")
+        );
+        assert_eq!(value_to_json(&AnnotatedMoveValue::Bool(true)), json!(true));
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::Address(CORE_CODE_ADDRESS)),
+            json!("0x1")
+        );
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::Bytes(vec![0xca, 0xfe])),
+            json!("0xcafe")
+        );
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::Vector(
+                TypeTag::U64,
+                vec![AnnotatedMoveValue::U64(1), AnnotatedMoveValue::U64(2)]
+            )),
+            json!(["1", "2"])
+        );
+        assert_eq!(
+            value_to_json(&AnnotatedMoveValue::Vector(TypeTag::Bool, vec![])),
+            json!([])
+        );
+    }
+
+    #[test]
+    fn test_struct_to_json() {
+        let symbol = annotated_struct(vec![("bytes", AnnotatedMoveValue::Bytes(b"TC".to_vec()))]);
+        let info = annotated_struct(vec![
+            ("symbol", AnnotatedMoveValue::Struct(symbol)),
+            ("decimals", AnnotatedMoveValue::U64(6)),
+            ("frozen", AnnotatedMoveValue::Bool(false)),
+        ]);
+        assert_eq!(
+            struct_to_json(&info),
+            json!({"symbol": {"bytes": "0x5443"}, "decimals": "6", "frozen": false})
+        );
+        assert_eq!(struct_to_json(&annotated_struct(vec![])), json!({}));
+    }
+
+    #[test]
+    fn test_remote_modules_records_missing_accounts() {
+        let account = AccountAddress::from_hex_literal("0xcafe").unwrap();
+        let mut modules = RemoteModules::default();
+        assert_eq!(
+            modules.get_state_value(&module_key(account, "M")).unwrap(),
+            None
+        );
+        assert_eq!(
+            modules
+                .get_state_value(&module_key(CORE_CODE_ADDRESS, "Coin"))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            modules.take_missing(),
+            vec![CORE_CODE_ADDRESS, account]
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+        );
+        assert!(modules.take_missing().is_empty());
+
+        // A fetched account is no longer missing, even if it has no such module
+        modules.add(account, vec![]);
+        modules.add(CORE_CODE_ADDRESS, framework_modules(CORE_CODE_ADDRESS));
+        assert_eq!(
+            modules.get_state_value(&module_key(account, "M")).unwrap(),
+            None
+        );
+        assert!(modules
+            .get_state_value(&module_key(CORE_CODE_ADDRESS, "Coin"))
+            .unwrap()
+            .is_some());
+        assert!(modules.take_missing().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_annotate_resource_fetches_accounts_once() {
+        let test_coin = struct_tag(CORE_CODE_ADDRESS, "TestCoin", "TestCoin", vec![]);
+        let tag = struct_tag(
+            CORE_CODE_ADDRESS,
+            "Coin",
+            "CoinInfo",
+            vec![TypeTag::Struct(test_coin)],
+        );
+        let bytes = bcs::to_bytes(&(
+            b"Test Coin".to_vec(),
+            b"TC".to_vec(),
+            6u64,
+            Vec::<u64>::new(),
+        ))
+        .unwrap();
+
+        let mut modules = RemoteModules::default();
+        let mut fetched = vec![];
+        let resource = annotate_resource(&mut modules, &tag, &bytes, |address| {
+            fetched.push(address);
+            ready(Ok(framework_modules(address)))
+        })
+        .await
+        .unwrap();
+        assert_eq!(fetched, vec![CORE_CODE_ADDRESS]);
+        assert_eq!(
+            struct_to_json(&resource),
+            json!({
+                "name": {"bytes": "0x5465737420436f696e"},
+                "symbol": {"bytes": "0x5443"},
+                "decimals": "6",
+                "supply": {"vec": []}
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_annotate_resource_ends_without_the_module() {
+        let account = AccountAddress::from_hex_literal("0xcafe").unwrap();
+        let tag = struct_tag(account, "M", "R", vec![]);
+
+        let mut modules = RemoteModules::default();
+        let mut fetched = vec![];
+        let result = annotate_resource(&mut modules, &tag, &[], |address| {
+            fetched.push(address);
+            ready(Ok(framework_modules(address)))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(fetched, vec![account]);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aptos_debug_natives;
+pub mod inspect;
 
 use crate::{
     common::{
@@ -14,7 +15,7 @@ use crate::{
     },
     CliCommand, CliResult,
 };
-use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_rest_client::{aptos_api_types::MoveType, Client};
 use aptos_types::transaction::{ModuleBundle, ScriptFunction, TransactionPayload};
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -46,21 +47,29 @@ use std::{
 ///
 #[derive(Subcommand)]
 pub enum MoveTool {
+    CheckCompatibility(inspect::CheckCompatibility),
     Compile(CompilePackage),
+    Download(inspect::DownloadModule),
     Init(InitPackage),
+    List(inspect::ListModules),
     Publish(PublishPackage),
     Run(RunFunction),
     Test(TestPackage),
+    ViewResources(inspect::ViewResources),
 }
 
 impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            MoveTool::CheckCompatibility(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
+            MoveTool::Download(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
+            MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
+            MoveTool::ViewResources(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -234,13 +243,16 @@ fn compile_move(build_config: BuildConfig, package_dir: &Path) -> CliTypedResult
 #[derive(Parser)]
 pub struct PublishPackage {
     #[clap(flatten)]
-    encoding_options: EncodingOptions,
+    pub(crate) encoding_options: EncodingOptions,
     #[clap(flatten)]
-    move_options: MovePackageDir,
+    pub(crate) move_options: MovePackageDir,
     #[clap(flatten)]
-    write_options: WriteTransactionOptions,
+    pub(crate) write_options: WriteTransactionOptions,
     #[clap(flatten)]
-    profile_options: ProfileOptions,
+    pub(crate) profile_options: ProfileOptions,
+    /// Publish without checking that the modules can upgrade the ones already on chain
+    #[clap(long)]
+    pub(crate) skip_compatibility_check: bool,
 }
 
 #[async_trait]
@@ -258,6 +270,13 @@ impl CliCommand<TransactionSummary> for PublishPackage {
             ..Default::default()
         };
         let package = compile_move(build_config, self.move_options.package_dir.as_path())?;
+        let url = self
+            .write_options
+            .rest_options
+            .url(&self.profile_options.profile)?;
+        if !self.skip_compatibility_check {
+            inspect::check_compatibility(&Client::new(url.clone()), &package).await?;
+        }
         let compiled_units: Vec<Vec<u8>> = package
             .root_compiled_units
             .iter()
@@ -276,9 +295,7 @@ impl CliCommand<TransactionSummary> for PublishPackage {
         )?;
 
         submit_transaction(
-            self.write_options
-                .rest_options
-                .url(&self.profile_options.profile)?,
+            url,
             self.write_options
                 .chain_id(&self.profile_options.profile)
                 .await?,
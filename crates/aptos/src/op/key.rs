@@ -7,12 +7,19 @@ use crate::{
             CliError, CliTypedResult, EncodingOptions, EncodingType, ExtractPublicKey, KeyType,
             PrivateKeyInputOptions, ProfileOptions, SaveFile,
         },
-        utils::{append_file_extension, check_if_file_exists, write_to_file},
+        utils::{
+            append_file_extension, check_if_file_exists, read_from_file, write_to_file,
+            write_to_user_only_file,
+        },
     },
     CliCommand, CliResult,
 };
 use aptos_config::config::{Peer, PeerRole};
 use aptos_crypto::{ed25519, x25519, PrivateKey, Uniform, ValidCryptoMaterial};
+use aptos_sdk::derivation_path::{
+    generate_mnemonic, mnemonic_to_seed, DerivationPath, DEFAULT_DERIVATION_PATH,
+};
+use aptos_secure_storage::{normalize_passphrase, EncryptedOnDiskStorage};
 use aptos_types::account_address::{from_identity_public_key, AccountAddress};
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -20,15 +27,18 @@ use rand::SeedableRng;
 use std::{
     collections::{HashMap, HashSet},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 pub const PUBLIC_KEY_EXTENSION: &str = "pub";
+pub const MNEMONIC_EXTENSION: &str = "mnemonic";
 
 /// CLI tool for generating, inspecting, and interacting with keys.
 #[derive(Debug, Subcommand)]
 pub enum KeyTool {
     Generate(GenerateKey),
     ExtractPeer(ExtractPeer),
+    EncryptStorage(EncryptStorage),
 }
 
 impl KeyTool {
@@ -36,6 +46,7 @@ impl KeyTool {
         match self {
             KeyTool::Generate(tool) => tool.execute_serialized().await,
             KeyTool::ExtractPeer(tool) => tool.execute_serialized().await,
+            KeyTool::EncryptStorage(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -92,17 +103,93 @@ impl CliCommand<HashMap<AccountAddress, Peer>> for ExtractPeer {
     }
 }
 
+/// Migrates a plaintext on disk secure storage file to an encrypted one
+///
+/// The encryption key is either derived from the passphrase in `passphrase-file`, or read from
+/// `keyfile` which contains 32 hex encoded bytes.  All keys of the plaintext file are copied
+/// into the encrypted file, which is created if it does not exist.  The plaintext file is left
+/// untouched unless `--remove-plaintext` is given.
+#[derive(Debug, Parser)]
+pub struct EncryptStorage {
+    /// Plaintext on disk secure storage file to migrate
+    #[clap(long, parse(from_os_str))]
+    plaintext_file: PathBuf,
+    /// Encrypted on disk secure storage file to migrate into
+    #[clap(long, parse(from_os_str))]
+    encrypted_file: PathBuf,
+    /// File containing the passphrase to derive the encryption key from
+    #[clap(long, parse(from_os_str), group = "encryption_key")]
+    passphrase_file: Option<PathBuf>,
+    /// File containing the hex encoded encryption key
+    #[clap(long, parse(from_os_str), group = "encryption_key")]
+    keyfile: Option<PathBuf>,
+    /// Delete the plaintext file after a successful migration
+    #[clap(long)]
+    remove_plaintext: bool,
+}
+
+#[async_trait]
+impl CliCommand<PathBuf> for EncryptStorage {
+    fn command_name(&self) -> &'static str {
+        "EncryptStorage"
+    }
+
+    async fn execute(self) -> CliTypedResult<PathBuf> {
+        let mut storage = match (&self.passphrase_file, &self.keyfile) {
+            (Some(passphrase_file), None) => {
+                let passphrase = String::from_utf8(read_from_file(passphrase_file)?)?;
+                EncryptedOnDiskStorage::new_with_passphrase(
+                    self.encrypted_file.clone(),
+                    normalize_passphrase(&passphrase),
+                )
+            }
+            (None, Some(keyfile)) => {
+                EncryptedOnDiskStorage::new_with_keyfile(self.encrypted_file.clone(), keyfile)
+            }
+            _ => {
+                return Err(CliError::CommandArgumentError(
+                    "Exactly one of --passphrase-file or --keyfile must be provided".to_string(),
+                ))
+            }
+        }
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+        storage
+            .migrate_from_plaintext(&self.plaintext_file)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+        if self.remove_plaintext {
+            std::fs::remove_file(&self.plaintext_file)
+                .map_err(|err| CliError::IO(self.plaintext_file.display().to_string(), err))?;
+        }
+        Ok(self.encrypted_file)
+    }
+}
+
 /// Generates a `x25519` or `ed25519` key.
 ///
 /// This can be used for generating an identity.  Two files will be created
 /// `output_file` and `output_file.pub`.  `output_file` will contain the private
 /// key encoded with the `encoding` and `output_file.pub` will contain the public
 /// key encoded with the `encoding`.
+///
+/// With `--mnemonic`, an `ed25519` key is derived from a new BIP-39 mnemonic phrase, which is
+/// saved in a third file `output_file.mnemonic`.  The same key can be recovered from the phrase
+/// with `aptos init --from-mnemonic`.
 #[derive(Debug, Parser)]
 pub struct GenerateKey {
     /// Key type: `x25519` or `ed25519`
     #[clap(long, default_value_t = KeyType::Ed25519)]
     key_type: KeyType,
+    /// Derive the key from a new mnemonic phrase
+    #[clap(long)]
+    mnemonic: bool,
+    /// Number of words of the mnemonic phrase: 12, 15, 18, 21 or 24 [default: 12]
+    #[clap(long)]
+    mnemonic_word_count: Option<usize>,
+    /// SLIP-0010 derivation path of the key from the mnemonic phrase [default: m/44'/637'/0'/0'/0']
+    #[clap(long)]
+    derivation_path: Option<String>,
     #[clap(flatten)]
     save_params: SaveKey,
 }
@@ -115,6 +202,13 @@ impl CliCommand<HashMap<&'static str, PathBuf>> for GenerateKey {
 
     async fn execute(self) -> CliTypedResult<HashMap<&'static str, PathBuf>> {
         self.save_params.check_key_file()?;
+        if self.mnemonic {
+            return self.generate_from_mnemonic();
+        } else if self.mnemonic_word_count.is_some() || self.derivation_path.is_some() {
+            return Err(CliError::CommandArgumentError(
+                "--mnemonic-word-count and --derivation-path require --mnemonic".to_string(),
+            ));
+        }
 
         // Generate a ed25519 key
         let ed25519_key = Self::generate_ed25519_in_memory();
@@ -133,6 +227,35 @@ impl CliCommand<HashMap<&'static str, PathBuf>> for GenerateKey {
 }
 
 impl GenerateKey {
+    fn generate_from_mnemonic(&self) -> CliTypedResult<HashMap<&'static str, PathBuf>> {
+        if let KeyType::X25519 = self.key_type {
+            return Err(CliError::CommandArgumentError(
+                "Only ed25519 keys can be derived from a mnemonic phrase".to_string(),
+            ));
+        }
+        let mnemonic_file = append_file_extension(
+            self.save_params.file_options.output_file.as_path(),
+            MNEMONIC_EXTENSION,
+        )?;
+        check_if_file_exists(&mnemonic_file, self.save_params.file_options.prompt_options)?;
+
+        let derivation_path = DerivationPath::from_str(
+            self.derivation_path
+                .as_deref()
+                .unwrap_or(DEFAULT_DERIVATION_PATH),
+        )
+        .map_err(|err| CliError::UnableToParse("DerivationPath", err.to_string()))?;
+        let mut rng = rand::rngs::StdRng::from_entropy();
+        let mnemonic = generate_mnemonic(&mut rng, self.mnemonic_word_count.unwrap_or(12))
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+        let ed25519_key = derive_ed25519_from_mnemonic(&derivation_path, &mnemonic)?;
+
+        let mut map = self.save_params.save_key(&ed25519_key, "ed25519")?;
+        write_to_user_only_file(&mnemonic_file, "mnemonic", mnemonic.as_bytes())?;
+        map.insert("Mnemonic Path", mnemonic_file);
+        Ok(map)
+    }
+
     /// A test friendly typed key generation for x25519 keys.
     pub async fn generate_x25519(
         encoding: EncodingType,
@@ -191,6 +314,18 @@ impl GenerateKey {
     }
 }
 
+/// Derives the `ed25519` key at `derivation_path` of a BIP-39 mnemonic phrase
+pub fn derive_ed25519_from_mnemonic(
+    derivation_path: &DerivationPath,
+    mnemonic: &str,
+) -> CliTypedResult<ed25519::Ed25519PrivateKey> {
+    let seed = mnemonic_to_seed(mnemonic)
+        .map_err(|err| CliError::UnableToParse("Mnemonic", err.to_string()))?;
+    derivation_path
+        .derive_private_key(&seed)
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))
+}
+
 #[derive(Debug, Parser)]
 pub struct SaveKey {
     #[clap(flatten)]
@@ -11,18 +11,24 @@ use crate::{
     common::{
         init::InitTool,
         types::{
-            CliConfig, CliTypedResult, EncodingOptions, PrivateKeyInputOptions, ProfileOptions,
-            PromptOptions, RestOptions, WriteTransactionOptions,
+            CliConfig, CliTypedResult, EncodingOptions, MovePackageDir, PrivateKeyInputOptions,
+            ProfileOptions, PromptOptions, RestOptions, TransactionSummary,
+            WriteTransactionOptions,
         },
     },
+    move_tool::{
+        inspect::{CheckCompatibility, DownloadModule, ListModules, ModuleCompatibility},
+        PublishPackage,
+    },
     op::key::GenerateKey,
     CliCommand,
 };
 use aptos_crypto::ed25519::Ed25519PrivateKey;
-use aptos_sdk::move_types::account_address::AccountAddress;
+use aptos_rest_client::aptos_api_types::MoveModule;
+use aptos_sdk::move_types::{account_address::AccountAddress, identifier::Identifier};
 use reqwest::Url;
 use serde_json::Value;
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 use tokio::time::{sleep, Instant};
 
 /// A framework for testing the CLI
@@ -129,10 +135,73 @@ impl CliTestFramework {
         .await
     }
 
+    pub async fn publish_package(
+        &self,
+        index: usize,
+        package_dir: PathBuf,
+        skip_compatibility_check: bool,
+    ) -> CliTypedResult<TransactionSummary> {
+        PublishPackage {
+            encoding_options: Default::default(),
+            move_options: MovePackageDir::new(package_dir),
+            write_options: Default::default(),
+            profile_options: profile(index),
+            skip_compatibility_check,
+        }
+        .execute()
+        .await
+    }
+
+    pub async fn list_modules(&self, index: usize) -> CliTypedResult<Vec<MoveModule>> {
+        ListModules {
+            rest_options: Default::default(),
+            profile_options: profile(index),
+            account: Some(Self::account_id(index)),
+        }
+        .execute()
+        .await
+    }
+
+    /// Downloads a module of the account and its disassembly to `output_dir`
+    pub async fn download_module(
+        &self,
+        index: usize,
+        module_name: &str,
+        output_dir: PathBuf,
+    ) -> CliTypedResult<Vec<PathBuf>> {
+        DownloadModule {
+            rest_options: Default::default(),
+            profile_options: profile(index),
+            prompt_options: PromptOptions::yes(),
+            account: Some(Self::account_id(index)),
+            module_name: Identifier::new(module_name).expect("Expected a valid module name"),
+            output_dir,
+            disassemble: true,
+        }
+        .execute()
+        .await
+    }
+
+    pub async fn check_compatibility(
+        &self,
+        index: usize,
+        package_dir: PathBuf,
+    ) -> CliTypedResult<Vec<ModuleCompatibility>> {
+        CheckCompatibility {
+            move_options: MovePackageDir::new(package_dir),
+            rest_options: Default::default(),
+            profile_options: profile(index),
+        }
+        .execute()
+        .await
+    }
+
     pub async fn init(&self, index: usize, private_key: &Ed25519PrivateKey) -> CliTypedResult<()> {
         InitTool {
             rest_url: Some(self.endpoint.clone()),
             faucet_url: Some(self.faucet_endpoint.clone()),
+            from_mnemonic: false,
+            derivation_path: None,
             private_key_options: private_key_options(private_key),
             profile_options: profile(index),
             prompt_options: PromptOptions::yes(),
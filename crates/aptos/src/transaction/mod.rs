@@ -0,0 +1,22 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliCommand, CliResult};
+use clap::Subcommand;
+
+pub mod show;
+
+/// CLI tool for inspecting transactions
+///
+#[derive(Subcommand)]
+pub enum TransactionTool {
+    Show(show::ShowTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            TransactionTool::Show(tool) => tool.execute_serialized().await,
+        }
+    }
+}
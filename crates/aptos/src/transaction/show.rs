@@ -0,0 +1,351 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions};
+use aptos_crypto::HashValue;
+use aptos_rest_client::{
+    aptos_api_types::{Event, WriteSetChange},
+    Client, Transaction, TransactionData,
+};
+use aptos_transaction_builder::error_explain::get_explanation;
+use aptos_transaction_replay::AptosDebugger;
+use aptos_types::{
+    transaction::{ExecutionStatus, TransactionStatus},
+    vm_status::AbortLocation,
+};
+use async_trait::async_trait;
+use clap::Parser;
+use serde::Serialize;
+use std::{path::PathBuf, str::FromStr};
+
+/// Shows a transaction by hash or version
+///
+/// Abort codes of the Aptos framework are explained from its error descriptions.  With
+/// `--replay-db`, the transaction is also executed again locally against the database of a node.
+/// The VM charges gas as a single total, so there's no split of the gas used into execution, IO
+/// and storage to show
+#[derive(Parser)]
+pub struct ShowTransaction {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    /// Hash or version of the transaction
+    ///
+    /// Example: `0x5f6f...e9a1` or `1024`
+    #[clap(parse(try_from_str))]
+    transaction: HashOrVersion,
+    /// Path to the database of a node to replay the transaction with
+    ///
+    /// The database must hold the state right before the transaction
+    #[clap(long, parse(from_os_str))]
+    replay_db: Option<PathBuf>,
+}
+
+/// A transaction reference, either its hash or its version
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashOrVersion {
+    Hash(HashValue),
+    Version(u64),
+}
+
+impl FromStr for HashOrVersion {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(version) = u64::from_str(s) {
+            return Ok(HashOrVersion::Version(version));
+        }
+        HashValue::from_hex(s.strip_prefix("0x").unwrap_or(s))
+            .map(HashOrVersion::Hash)
+            .map_err(|_| {
+                CliError::CommandArgumentError(format!(
+                    "Invalid transaction '{}'.  Must be a transaction hash or version",
+                    s
+                ))
+            })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionDetails {
+    #[serde(rename = "type")]
+    pub transaction_type: &'static str,
+    pub hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    pub success: bool,
+    /// The execution status, with the explanation of the abort code if there's one
+    pub vm_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<GasSummary>,
+    pub changes: Vec<WriteSetChange>,
+    pub events: Vec<Event>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay: Option<ReplaySummary>,
+}
+
+/// The gas charged for a user transaction
+#[derive(Debug, Serialize)]
+pub struct GasSummary {
+    pub gas_used: u64,
+    pub max_gas_amount: u64,
+    pub gas_unit_price: u64,
+    /// `gas_used * gas_unit_price`, which may not fit in a u64
+    pub fee: u128,
+}
+
+/// The result of executing the transaction again locally
+#[derive(Debug, Serialize)]
+pub struct ReplaySummary {
+    /// The total gas used, the VM doesn't break it down into execution, IO and storage
+    pub gas_used: u64,
+    pub vm_status: String,
+    /// Whether the gas used and the status are the same as on chain
+    pub matches_chain: bool,
+}
+
+#[async_trait]
+impl CliCommand<TransactionDetails> for ShowTransaction {
+    fn command_name(&self) -> &'static str {
+        "ShowTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionDetails> {
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let map_err_func = |err: anyhow::Error| CliError::ApiError(err.to_string());
+        let txn = match self.transaction {
+            HashOrVersion::Hash(hash) => client.get_transaction(hash).await,
+            HashOrVersion::Version(version) => client.get_transaction_by_version(version).await,
+        }
+        .map_err(map_err_func)?
+        .into_inner();
+
+        let mut details = TransactionDetails {
+            transaction_type: txn.type_str(),
+            hash: String::new(),
+            version: txn.version(),
+            success: txn.success(),
+            vm_status: txn.vm_status(),
+            sender: None,
+            sequence_number: None,
+            payload: None,
+            gas: None,
+            changes: Vec::new(),
+            events: Vec::new(),
+            replay: None,
+        };
+        match &txn {
+            Transaction::PendingTransaction(txn) => {
+                details.hash = txn.hash.to_string();
+                details.sender = Some(txn.request.sender.to_string());
+                details.sequence_number = Some(txn.request.sequence_number.0);
+                details.payload = Some(payload_to_json(&txn.request.payload)?);
+            }
+            Transaction::UserTransaction(txn) => {
+                details.sender = Some(txn.request.sender.to_string());
+                details.sequence_number = Some(txn.request.sequence_number.0);
+                details.payload = Some(payload_to_json(&txn.request.payload)?);
+                details.gas = Some(GasSummary {
+                    gas_used: txn.info.gas_used.0,
+                    max_gas_amount: txn.request.max_gas_amount.0,
+                    gas_unit_price: txn.request.gas_unit_price.0,
+                    fee: u128::from(txn.info.gas_used.0) * u128::from(txn.request.gas_unit_price.0),
+                });
+                details.events = txn.events.clone();
+            }
+            Transaction::GenesisTransaction(txn) => details.events = txn.events.clone(),
+            Transaction::BlockMetadataTransaction(_)
+            | Transaction::StateCheckpointTransaction(_) => (),
+        }
+        if let Ok(info) = txn.transaction_info() {
+            details.hash = info.hash.to_string();
+            details.changes = info.changes.clone();
+        }
+
+        let version = match details.version {
+            Some(version) => version,
+            // Pending transactions have no outcome yet
+            None => return Ok(details),
+        };
+        let data = match client
+            .get_transaction_by_version_bcs(version)
+            .await
+            .map_err(map_err_func)?
+            .into_inner()
+        {
+            TransactionData::OnChain(data) => data,
+            TransactionData::Pending(_) => {
+                return Err(CliError::UnexpectedError(format!(
+                    "Transaction at version {} is pending",
+                    version
+                )))
+            }
+        };
+        // Explain the abort with the error descriptions of the framework of this CLI, rather
+        // than the ones the node was built with
+        let status = data.info.status();
+        if let Some(explanation) = explain_abort(status) {
+            details.vm_status = explanation;
+        }
+
+        if let Some(replay_db) = self.replay_db {
+            let debugger = AptosDebugger::db(replay_db.as_path()).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Unable to open database {}: {}",
+                    replay_db.display(),
+                    err
+                ))
+            })?;
+            let output = debugger
+                .execute_transactions_at_version(version, vec![data.transaction])
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!(
+                        "Unable to replay transaction {}: {}",
+                        version, err
+                    ))
+                })?
+                .pop()
+                .ok_or_else(|| {
+                    CliError::UnexpectedError(format!(
+                        "No output replaying transaction {}",
+                        version
+                    ))
+                })?;
+            let (replay_status, matches_status) = match output.status() {
+                TransactionStatus::Keep(replay_status) => (
+                    explain_abort(replay_status).unwrap_or_else(|| format!("{:?}", replay_status)),
+                    replay_status == status,
+                ),
+                other => (format!("{:?}", other), false),
+            };
+            details.replay = Some(ReplaySummary {
+                gas_used: output.gas_used(),
+                vm_status: replay_status,
+                matches_chain: matches_status && output.gas_used() == data.info.gas_used(),
+            });
+        }
+        Ok(details)
+    }
+}
+
+fn payload_to_json<T: Serialize>(payload: &T) -> CliTypedResult<serde_json::Value> {
+    serde_json::to_value(payload).map_err(|err| CliError::UnexpectedError(err.to_string()))
+}
+
+/// Explains a Move abort with the error descriptions of the Aptos framework.  Framework abort
+/// codes hold the error category in their lowest byte and the reason in the bytes above it
+fn explain_abort(status: &ExecutionStatus) -> Option<String> {
+    let (location, code) = match status {
+        ExecutionStatus::MoveAbort { location, code } => (location, *code),
+        _ => return None,
+    };
+    Some(match location {
+        AbortLocation::Module(module_id) => match get_explanation(module_id, code) {
+            Some(context) => format!(
+                "Move abort in {} with code {}: {} ({}) - {}: {}",
+                module_id,
+                code,
+                context.category.code_name,
+                context.reason.code_name,
+                context.category.code_description,
+                context.reason.code_description
+            ),
+            None => format!(
+                "Move abort in {} with code {} (category {}, reason {})",
+                module_id,
+                code,
+                code & 0xff,
+                code >> 8
+            ),
+        },
+        AbortLocation::Script => format!("Move abort in script with code {}", code),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::account_address::AccountAddress;
+    use move_deps::move_core_types::{
+        identifier::Identifier,
+        language_storage::{ModuleId, CORE_CODE_ADDRESS},
+    };
+
+    const HASH: &str = "5f6f2ed2b5bd5a7bb6fa9c3e4a2b4e1a8c1d8c3c0a2f7bd9d0e1c6f1a4b3e9a1";
+
+    #[test]
+    fn test_hash_or_version_from_str() {
+        assert_eq!(
+            HashOrVersion::from_str("1024").unwrap(),
+            HashOrVersion::Version(1024)
+        );
+        let hash = HashOrVersion::Hash(HashValue::from_hex(HASH).unwrap());
+        assert_eq!(
+            HashOrVersion::from_str(&format!("0x{}", HASH)).unwrap(),
+            hash
+        );
+        assert_eq!(HashOrVersion::from_str(HASH).unwrap(), hash);
+
+        // Too large for a version, so it's a hash
+        let digits = "1".repeat(64);
+        assert_eq!(
+            HashOrVersion::from_str(&digits).unwrap(),
+            HashOrVersion::Hash(HashValue::from_hex(&digits).unwrap())
+        );
+
+        for junk in ["", "-1", "0x", "0x1024", "hello", &HASH[1..]] {
+            assert!(HashOrVersion::from_str(junk).is_err(), "{}", junk);
+        }
+    }
+
+    #[test]
+    fn test_explain_abort() {
+        assert_eq!(explain_abort(&ExecutionStatus::Success), None);
+        assert_eq!(explain_abort(&ExecutionStatus::OutOfGas), None);
+
+        // `Errors::invalid_argument(EINSUFFICIENT_BALANCE)` in the Coin module
+        let coin = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Coin").unwrap());
+        let explanation = explain_abort(&ExecutionStatus::MoveAbort {
+            location: AbortLocation::Module(coin.clone()),
+            code: 0x0507,
+        })
+        .unwrap();
+        let expected = format!(
+            "Move abort in {} with code 1287: INVALID_ARGUMENT (EINSUFFICIENT_BALANCE) - ",
+            coin
+        );
+        assert!(explanation.starts_with(&expected), "{}", explanation);
+
+        let unknown = ModuleId::new(
+            AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            Identifier::new("M").unwrap(),
+        );
+        assert_eq!(
+            explain_abort(&ExecutionStatus::MoveAbort {
+                location: AbortLocation::Module(unknown.clone()),
+                code: 0x0507,
+            })
+            .unwrap(),
+            format!(
+                "Move abort in {} with code 1287 (category 7, reason 5)",
+                unknown
+            )
+        );
+
+        assert_eq!(
+            explain_abort(&ExecutionStatus::MoveAbort {
+                location: AbortLocation::Script,
+                code: 3,
+            })
+            .unwrap(),
+            "Move abort in script with code 3"
+        );
+    }
+}
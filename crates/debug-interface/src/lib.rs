@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use node_debug_service::EquivocationEvidenceInfo;
 use reqwest::{blocking, Url};
 use std::collections::HashMap;
 
@@ -78,6 +79,22 @@ impl NodeDebugClient {
             })
             .collect()
     }
+
+    /// Retrieves the evidence of equivocating validators detected by the node
+    pub fn get_equivocation_evidence(&self) -> Result<Vec<EquivocationEvidenceInfo>> {
+        let mut url = self.url.clone();
+        url.set_path("consensus/equivocation-evidence");
+        let response = self.client.get(url).send()?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Error querying equivocation evidence: {}",
+                response.status()
+            );
+        }
+
+        Ok(response.json()?)
+    }
 }
 
 /// Implement default utility client for AsyncNodeDebugInterface
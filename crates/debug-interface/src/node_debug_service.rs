@@ -4,16 +4,39 @@
 //! Debug interface to access information in a specific node.
 
 use aptos_config::config::NodeConfig;
-use aptos_logger::{info, Filter, Logger};
+use aptos_infallible::RwLock;
+use aptos_logger::{info, Filter, FilterTarget, LevelFilter, Logger};
 use aptos_metrics::{metric_server, system_information::get_git_rev};
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, sync::Arc};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
 use tokio::runtime::{Builder, Runtime};
-use warp::Filter as _;
+use warp::{
+    http::StatusCode,
+    reject::{self, Reject, Rejection},
+    reply::{self, Reply},
+    Filter as _,
+};
 
-#[derive(Debug)]
 pub struct NodeDebugService {
     runtime: Runtime,
+    equivocation_evidence_provider: Arc<RwLock<Option<EquivocationEvidenceProvider>>>,
+}
+
+/// Returns the evidence of equivocating validators detected by consensus
+pub type EquivocationEvidenceProvider =
+    Box<dyn Fn() -> anyhow::Result<Vec<EquivocationEvidenceInfo>> + Send + Sync>;
+
+/// The evidence of a validator equivocating, as served by `/consensus/equivocation-evidence`
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EquivocationEvidenceInfo {
+    /// None if the evidence is made of NIL blocks, which have no author
+    pub author: Option<String>,
+    pub epoch: u64,
+    pub round: u64,
+    pub kind: String,
+    /// The hex encoded BCS serialization of the evidence, which can be verified by anyone knowing
+    /// the validator set of the epoch
+    pub bcs: String,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -24,6 +47,32 @@ struct NodeInfo {
     git_revision: String,
 }
 
+/// A request to change the level of a single module for one filter target
+#[derive(Debug, Deserialize)]
+struct ModuleLevelRequest {
+    /// The filter to update: `local`, `remote` or the name of a sink
+    #[serde(default = "default_filter_target")]
+    target: String,
+    module: String,
+    level: String,
+}
+
+fn default_filter_target() -> String {
+    FilterTarget::Local.to_string()
+}
+
+/// A rejection given when an admin API request isn't authenticated
+#[derive(Debug)]
+struct Unauthorized;
+
+impl Reject for Unauthorized {}
+
+/// A rejection given when an admin API request is made, but no token is configured
+#[derive(Debug)]
+struct AdminApiDisabled;
+
+impl Reject for AdminApiDisabled {}
+
 impl NodeDebugService {
     pub fn new(address: SocketAddr, logger: Option<Arc<Logger>>, node_config: &NodeConfig) -> Self {
         let runtime = Builder::new_multi_thread()
@@ -36,6 +85,10 @@ impl NodeDebugService {
         let metrics =
             warp::path("metrics").map(|| warp::reply::json(&metric_server::get_all_metrics()));
 
+        // Apart from the local and remote filters, the log routes are part of the admin API and
+        // require authentication
+        let admin_auth = admin_auth(node_config.debug_interface.admin_api_token.clone());
+
         // Post /log/filter
         let local_filter = {
             let logger = logger.clone();
@@ -45,51 +98,232 @@ impl NodeDebugService {
                 .and(warp::body::content_length_limit(1024 * 16))
                 .and(warp::body::bytes())
                 .map(move |bytes: bytes::Bytes| {
-                    if let (Some(logger), Ok(filter)) = (&logger, ::std::str::from_utf8(&bytes)) {
-                        info!(filter = filter, "Updating local logging filter");
-                        logger.set_filter(Filter::builder().parse(filter).build());
-                    }
-
-                    warp::reply::reply()
+                    set_filter_directives(&logger, FilterTarget::Local, &bytes)
                 })
         };
 
         // Post /log/remote-filter
-        let remote_filter = warp::path("remote-filter")
-            // 16kb should be long enough for a filter
-            .and(warp::body::content_length_limit(1024 * 16))
-            .and(warp::body::bytes())
-            .map(move |bytes: bytes::Bytes| {
-                if let (Some(logger), Ok(filter)) = (&logger, ::std::str::from_utf8(&bytes)) {
-                    info!(filter = filter, "Updating remote logging filter");
-                    logger.set_remote_filter(Filter::builder().parse(filter).build());
-                }
+        let remote_filter = {
+            let logger = logger.clone();
+
+            warp::path("remote-filter")
+                // 16kb should be long enough for a filter
+                .and(warp::body::content_length_limit(1024 * 16))
+                .and(warp::body::bytes())
+                .map(move |bytes: bytes::Bytes| {
+                    set_filter_directives(&logger, FilterTarget::Remote, &bytes)
+                })
+        };
 
-                warp::reply::reply()
-            });
+        // Post /log/sink-filter/<sink name>
+        let sink_filter = {
+            let logger = logger.clone();
 
-        // Post /log
-        let log = warp::post()
-            .and(warp::path("log"))
-            .and(local_filter.or(remote_filter));
+            warp::path!("sink-filter" / String)
+                // 16kb should be long enough for a filter
+                .and(warp::body::content_length_limit(1024 * 16))
+                .and(warp::body::bytes())
+                .map(move |name: String, bytes: bytes::Bytes| {
+                    set_filter_directives(&logger, FilterTarget::Sink(name), &bytes)
+                })
+        };
+
+        // Post /log/module-level (changes the level of a single module for one target)
+        let module_level = {
+            let logger = logger.clone();
+
+            warp::path("module-level")
+                .and(warp::body::content_length_limit(1024 * 16))
+                .and(warp::body::json())
+                .map(move |request: ModuleLevelRequest| set_module_level(&logger, request))
+        };
+
+        // Get /log/filters (the directives of all filters, keyed by target)
+        let filters = warp::path("filters").map(move || {
+            let directives = logger
+                .as_ref()
+                .map(|logger| logger.filter_directives())
+                .unwrap_or_default();
+            warp::reply::json(&directives)
+        });
+
+        // Post /log or Get /log
+        let log = warp::path("log").and(
+            warp::post()
+                .and(local_filter.or(remote_filter))
+                .or(admin_auth.and(
+                    warp::post()
+                        .and(sink_filter.or(module_level))
+                        .or(warp::get().and(filters)),
+                )),
+        );
 
         // Get /node-info (git revision the node was built at and the node config being used)
+        let mut node_config = node_config.clone();
+        node_config.debug_interface.admin_api_token = None;
         let node_info = NodeInfo {
             git_revision: get_git_rev(),
-            node_config: node_config.clone(),
+            node_config,
         };
         let node_info_route = warp::path("node-info").map(move || warp::reply::json(&node_info));
 
-        let routes = log.or(warp::get().and(metrics.or(node_info_route)));
+        // Get /consensus/equivocation-evidence (evidence of equivocating validators)
+        let equivocation_evidence_provider: Arc<RwLock<Option<EquivocationEvidenceProvider>>> =
+            Arc::new(RwLock::new(None));
+        let equivocation_evidence = {
+            let provider = equivocation_evidence_provider.clone();
+
+            warp::path!("consensus" / "equivocation-evidence")
+                .map(move || get_equivocation_evidence(&provider.read()))
+        };
+
+        let routes = log
+            .or(warp::get().and(metrics.or(node_info_route).or(equivocation_evidence)))
+            .recover(handle_rejection);
 
         runtime
             .handle()
             .spawn(async move { warp::serve(routes).bind(address).await });
 
-        Self { runtime }
+        Self {
+            runtime,
+            equivocation_evidence_provider,
+        }
     }
 
     pub fn runtime(&self) -> &Runtime {
         &self.runtime
     }
+
+    /// Sets where the equivocation evidence is read from, once consensus is started
+    pub fn set_equivocation_evidence_provider(&self, provider: EquivocationEvidenceProvider) {
+        *self.equivocation_evidence_provider.write() = Some(provider);
+    }
+}
+
+impl std::fmt::Debug for NodeDebugService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeDebugService")
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+/// Requires a valid `Authorization: Bearer <token>` header for the admin API
+fn admin_auth(
+    admin_api_token: Option<String>,
+) -> impl warp::Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and_then(move |authorization: Option<String>| {
+            let admin_api_token = admin_api_token.clone();
+            async move {
+                let admin_api_token =
+                    admin_api_token.ok_or_else(|| reject::custom(AdminApiDisabled))?;
+                let token = authorization
+                    .as_deref()
+                    .and_then(|authorization| authorization.strip_prefix("Bearer "));
+                match token {
+                    Some(token)
+                        if constant_time_eq(token.as_bytes(), admin_api_token.as_bytes()) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(reject::custom(Unauthorized)),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Compares the two byte strings in time independent of their contents
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Infallible> {
+    let status = if rejection.find::<Unauthorized>().is_some() {
+        StatusCode::UNAUTHORIZED
+    } else if rejection.find::<AdminApiDisabled>().is_some() {
+        StatusCode::FORBIDDEN
+    } else if rejection.is_not_found() {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::BAD_REQUEST
+    };
+    Ok(reply::with_status(reply::reply(), status))
+}
+
+/// Serves the equivocation evidence, or an empty list if consensus isn't running on this node
+fn get_equivocation_evidence(
+    provider: &Option<EquivocationEvidenceProvider>,
+) -> reply::WithStatus<reply::Json> {
+    match provider.as_ref().map(|provider| provider()).transpose() {
+        Ok(evidence) => {
+            reply::with_status(reply::json(&evidence.unwrap_or_default()), StatusCode::OK)
+        }
+        Err(error) => reply::with_status(
+            reply::json(&error.to_string()),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ),
+    }
+}
+
+/// Replaces the filter of the given target with the directives in the request body
+fn set_filter_directives(
+    logger: &Option<Arc<Logger>>,
+    target: FilterTarget,
+    bytes: &[u8],
+) -> reply::WithStatus<impl Reply> {
+    let status = match (logger, std::str::from_utf8(bytes)) {
+        (Some(logger), Ok(directives)) => {
+            let filter = Filter::builder().parse(directives).build();
+            if logger.update_filter(&target, |existing| *existing = filter) {
+                info!(
+                    filter_target = target.to_string(),
+                    filter = directives,
+                    "Updating logging filter"
+                );
+                StatusCode::OK
+            } else {
+                StatusCode::NOT_FOUND
+            }
+        }
+        (Some(_), Err(_)) => StatusCode::BAD_REQUEST,
+        (None, _) => StatusCode::OK,
+    };
+    reply::with_status(reply::reply(), status)
+}
+
+/// Changes the level of a single module for the requested target
+fn set_module_level(
+    logger: &Option<Arc<Logger>>,
+    request: ModuleLevelRequest,
+) -> reply::WithStatus<impl Reply> {
+    let (target, level) = match (
+        request.target.parse::<FilterTarget>(),
+        request.level.parse::<LevelFilter>(),
+    ) {
+        (Ok(target), Ok(level)) => (target, level),
+        _ => return reply::with_status(reply::reply(), StatusCode::BAD_REQUEST),
+    };
+
+    let status = match logger {
+        Some(logger) => {
+            if logger.update_filter(&target, |filter| {
+                filter.set_module_level(&request.module, level)
+            }) {
+                info!(
+                    filter_target = target.to_string(),
+                    module = request.module,
+                    level = level.to_string(),
+                    "Updating module logging level"
+                );
+                StatusCode::OK
+            } else {
+                StatusCode::NOT_FOUND
+            }
+        }
+        None => StatusCode::OK,
+    };
+    reply::with_status(reply::reply(), status)
 }
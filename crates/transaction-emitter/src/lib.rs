@@ -3,7 +3,7 @@
 
 use ::aptos_logger::*;
 use anyhow::{format_err, Context, Result};
-use aptos_rest_client::{Client as RestClient, PendingTransaction, Response};
+use aptos_rest_client::Client as RestClient;
 use aptos_sdk::{
     move_types::account_address::AccountAddress,
     transaction_builder::TransactionFactory,
@@ -50,6 +50,8 @@ use rand::rngs::StdRng;
 
 /// Max transactions per account in mempool
 const MAX_TXN_BATCH_SIZE: usize = 100;
+/// Max transactions per submission request, the default limit of the API
+const MAX_SUBMIT_BATCH_SIZE: usize = 100;
 const MAX_TXNS: u64 = 1_000_000;
 const SEND_AMOUNT: u64 = 1;
 const TXN_EXPIRATION_SECONDS: u64 = 180;
@@ -210,13 +212,31 @@ impl SubmissionWorker {
             let start_time = Instant::now();
             let wait_until = start_time + wait_duration;
             let mut txn_offset_time = 0u64;
-            for request in requests {
+            for batch in requests.chunks(MAX_SUBMIT_BATCH_SIZE) {
                 let cur_time = Instant::now();
-                txn_offset_time += (cur_time - start_time).as_millis() as u64;
-                self.stats.submitted.fetch_add(1, Ordering::Relaxed);
-                let resp = self.client.submit(&request).await;
-                if let Err(e) = resp {
-                    warn!("[{:?}] Failed to submit request: {:?}", self.client, e);
+                txn_offset_time += (cur_time - start_time).as_millis() as u64 * batch.len() as u64;
+                self.stats
+                    .submitted
+                    .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                match self.client.submit_batch(batch).await {
+                    Ok(statuses) => {
+                        let num_rejected = statuses
+                            .inner()
+                            .iter()
+                            .filter(|status| !status.is_accepted())
+                            .count();
+                        if num_rejected > 0 {
+                            warn!(
+                                "[{:?}] {} of {} transactions rejected",
+                                self.client,
+                                num_rejected,
+                                batch.len()
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        warn!("[{:?}] Failed to submit requests: {:?}", self.client, e);
+                    }
                 }
             }
             if self.params.wait_committed {
@@ -696,12 +716,21 @@ pub async fn execute_and_wait_transactions(
         account.address()
     );
 
-    let pending_txns: Vec<Response<PendingTransaction>> =
-        try_join_all(txns.iter().map(|t| client.submit(t))).await?;
+    for batch in txns.chunks(MAX_SUBMIT_BATCH_SIZE) {
+        let statuses = client.submit_batch(batch).await?.into_inner();
+        if let Some(status) = statuses.iter().find(|status| !status.is_accepted()) {
+            return Err(format_err!(
+                "[{:?}] Transaction {} was rejected: {:?}",
+                client,
+                status.hash,
+                status
+            ));
+        }
+    }
 
-    for pt in pending_txns {
+    for txn in &txns {
         client
-            .wait_for_transaction(&pt.into_inner())
+            .wait_for_signed_transaction(txn)
             .await
             .context("wait for transactions failed")?;
     }
@@ -126,6 +126,27 @@ async fn handle_client_request<V>(
                 ))
                 .await;
         }
+        MempoolClientRequest::SubmitTransactionBatch(txns, callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_transaction_batch_submission(
+                    smp.clone(),
+                    txns,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        }
         MempoolClientRequest::GetTransactionByHash(hash, callback) => {
             // This timer measures how long it took for the bounded executor to *schedule* the
             // task.
@@ -224,18 +224,24 @@ impl MempoolNetworkInterface {
             let peer_states = self.sync_states.read_all();
             peer_states
                 .iter()
-                .map(|(peer, state)| (*peer, state.metadata.role))
+                .map(|(peer, state)| {
+                    let ping_latency = self
+                        .peer_metadata_storage
+                        .read_monitoring_metadata(*peer)
+                        .and_then(|metadata| metadata.average_ping_latency);
+                    (*peer, state.metadata.role, ping_latency)
+                })
                 .collect()
         };
 
-        // Order peers by network and by type
+        // Order peers by network, by type and by latency
         // Origin doesn't matter at this point, only inserted ones into peer_states are upstream
         // Validators will always have the full set
         let mut prioritized_peers = self.prioritized_peers.lock();
         let peers: Vec<_> = peers
             .iter()
             .sorted_by(|peer_a, peer_b| compare_prioritized_peers(peer_a, peer_b))
-            .map(|(peer, _)| *peer)
+            .map(|(peer, _, _)| *peer)
             .collect();
         let _ = std::mem::replace(&mut *prioritized_peers, peers);
     }
@@ -558,8 +564,8 @@ impl NetworkInterface<MempoolSyncMsg, MempoolMultiNetworkSender> for MempoolNetw
 
 /// Provides ordering for peers to send transactions to
 fn compare_prioritized_peers(
-    peer_a: &(PeerNetworkId, PeerRole),
-    peer_b: &(PeerNetworkId, PeerRole),
+    peer_a: &(PeerNetworkId, PeerRole, Option<Duration>),
+    peer_b: &(PeerNetworkId, PeerRole, Option<Duration>),
 ) -> Ordering {
     let peer_network_id_a = peer_a.0;
     let peer_network_id_b = peer_b.0;
@@ -574,11 +580,19 @@ fn compare_prioritized_peers(
             let role_a = peer_a.1;
             let role_b = peer_b.1;
             match role_a.cmp(&role_b) {
-                // Then tiebreak by PeerId for stability
                 Ordering::Equal => {
-                    let peer_id_a = peer_network_id_a.peer_id();
-                    let peer_id_b = peer_network_id_b.peer_id();
-                    peer_id_a.cmp(&peer_id_b)
+                    // Then prefer lower ping latencies (unmeasured peers go last)
+                    let latency_a = peer_a.2.unwrap_or(Duration::MAX);
+                    let latency_b = peer_b.2.unwrap_or(Duration::MAX);
+                    match latency_a.cmp(&latency_b) {
+                        // Then tiebreak by PeerId for stability
+                        Ordering::Equal => {
+                            let peer_id_a = peer_network_id_a.peer_id();
+                            let peer_id_b = peer_network_id_b.peer_id();
+                            peer_id_a.cmp(&peer_id_b)
+                        }
+                        ordering => ordering,
+                    }
                 }
                 ordering => ordering,
             }
@@ -600,18 +614,32 @@ mod test {
         let val_1 = (
             PeerNetworkId::new(NetworkId::Vfn, peer_id_1),
             PeerRole::Validator,
+            None,
         );
         let val_2 = (
             PeerNetworkId::new(NetworkId::Vfn, peer_id_2),
             PeerRole::Validator,
+            None,
         );
         let vfn_1 = (
             PeerNetworkId::new(NetworkId::Public, peer_id_1),
             PeerRole::ValidatorFullNode,
+            None,
         );
         let preferred_1 = (
             PeerNetworkId::new(NetworkId::Public, peer_id_1),
             PeerRole::PreferredUpstream,
+            None,
+        );
+        let fast_val_2 = (
+            PeerNetworkId::new(NetworkId::Vfn, peer_id_2),
+            PeerRole::Validator,
+            Some(Duration::from_millis(10)),
+        );
+        let slow_val_1 = (
+            PeerNetworkId::new(NetworkId::Vfn, peer_id_1),
+            PeerRole::Validator,
+            Some(Duration::from_millis(100)),
         );
 
         // NetworkId ordering
@@ -628,6 +656,20 @@ mod test {
             compare_prioritized_peers(&preferred_1, &vfn_1)
         );
 
+        // Latency ordering (unmeasured peers go last)
+        assert_eq!(
+            Ordering::Less,
+            compare_prioritized_peers(&fast_val_2, &slow_val_1)
+        );
+        assert_eq!(
+            Ordering::Greater,
+            compare_prioritized_peers(&slow_val_1, &fast_val_2)
+        );
+        assert_eq!(
+            Ordering::Less,
+            compare_prioritized_peers(&slow_val_1, &val_1)
+        );
+
         // Tiebreaker on peer_id
         assert_eq!(Ordering::Greater, compare_prioritized_peers(&val_2, &val_1));
         assert_eq!(Ordering::Less, compare_prioritized_peers(&val_1, &val_2));
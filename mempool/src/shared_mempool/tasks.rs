@@ -30,7 +30,7 @@ use network::application::interface::NetworkInterface;
 use rayon::prelude::*;
 use std::{
     cmp,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -119,6 +119,52 @@ pub(crate) async fn process_client_transaction_submission<V>(
     }
 }
 
+/// Processes a batch of transactions directly submitted by client, in one pass through mempool.
+pub(crate) async fn process_client_transaction_batch_submission<V>(
+    smp: SharedMempool<V>,
+    transactions: Vec<SignedTransaction>,
+    callback: oneshot::Sender<Result<Vec<SubmissionStatus>>>,
+    timer: HistogramTimer,
+) where
+    V: TransactionValidation,
+{
+    timer.stop_and_record();
+    let _timer = counters::process_txn_submit_latency_timer_client();
+    let hashes: Vec<_> = transactions
+        .iter()
+        .map(|txn| txn.clone().committed_hash())
+        .collect();
+    let statuses = process_incoming_transactions(&smp, transactions, TimelineState::NotReady);
+    log_txn_process_results(&statuses, None);
+
+    // Statuses come back grouped by the stage which processed them, put them back in the order
+    // of submission. A transaction failing validation altogether has no status.
+    let mut statuses_by_hash: HashMap<HashValue, Vec<SubmissionStatus>> = HashMap::new();
+    for (txn, status) in statuses.into_iter().rev() {
+        statuses_by_hash
+            .entry(txn.committed_hash())
+            .or_default()
+            .push(status);
+    }
+    let statuses = hashes
+        .iter()
+        .map(|hash| {
+            statuses_by_hash
+                .get_mut(hash)
+                .and_then(|statuses| statuses.pop())
+                .unwrap_or_else(|| (MempoolStatus::new(MempoolStatusCode::UnknownStatus), None))
+        })
+        .collect();
+
+    if callback.send(Ok(statuses)).is_err() {
+        error!(LogSchema::event_log(
+            LogEntry::JsonRpc,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes get transaction by hash request by client.
 pub(crate) async fn process_client_get_transaction<V>(
     smp: SharedMempool<V>,
@@ -215,6 +215,11 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
+    /// Submits the transactions in one request, the statuses are returned in the same order
+    SubmitTransactionBatch(
+        Vec<SignedTransaction>,
+        oneshot::Sender<Result<Vec<SubmissionStatus>>>,
+    ),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
 }
 
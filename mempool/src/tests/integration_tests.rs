@@ -122,6 +122,15 @@ async fn single_outbound_node_test() {
     }
 }
 
+/// Tests that a batch submitted by client is added to mempool at once
+#[tokio::test]
+async fn test_batch_submission_via_client() {
+    let mut node = MempoolTestFrameworkBuilder::single_validator();
+    node.assert_txns_not_in_mempool(ALL_TXNS);
+    node.add_txns_batch_via_client(ALL_TXNS).await;
+    node.assert_only_txns_in_mempool(ALL_TXNS);
+}
+
 /// Tests if the node is a VFN, and it's getting forwarded messages from a PFN.  It should forward
 /// messages to the upstream VAL.  Upstream and downstream nodes are mocked.
 #[tokio::test]
@@ -149,6 +149,24 @@ impl MempoolNode {
         }
     }
 
+    /// Queues transactions for sending on a node in a single batch request, uses client
+    pub async fn add_txns_batch_via_client(&mut self, txns: &[TestTransaction]) {
+        let (sender, receiver) = oneshot::channel();
+
+        self.mempool_client_sender
+            .send(MempoolClientRequest::SubmitTransactionBatch(
+                sign_transactions(txns),
+                sender,
+            ))
+            .await
+            .unwrap();
+        let statuses = receiver.await.unwrap().unwrap();
+        assert_eq!(statuses.len(), txns.len());
+        for status in statuses {
+            assert_eq!(status.0.code, MempoolStatusCode::Accepted)
+        }
+    }
+
     /// Asynchronously waits for up to 1 second for txns to appear in mempool
     pub async fn wait_on_txns_in_mempool(&self, txns: &[TestTransaction]) {
         for _ in 0..10 {
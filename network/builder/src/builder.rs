@@ -14,7 +14,7 @@ use aptos_config::{
         DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RateLimitConfig, RoleType,
         CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS,
         MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS,
-        MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
+        MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE, PEER_BAN_DURATION_MS,
     },
     network_id::NetworkContext,
 };
@@ -42,6 +42,7 @@ use std::{
     clone::Clone,
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 use tokio::runtime::Handle;
 
@@ -79,7 +80,7 @@ impl NetworkBuilder {
         peer_metadata_storage: Arc<PeerMetadataStorage>,
         network_context: NetworkContext,
         time_service: TimeService,
-        listen_address: NetworkAddress,
+        listen_addresses: Vec<NetworkAddress>,
         authentication_mode: AuthenticationMode,
         max_frame_size: usize,
         enable_proxy_protocol: bool,
@@ -95,7 +96,7 @@ impl NetworkBuilder {
             chain_id,
             network_context,
             time_service.clone(),
-            listen_address,
+            listen_addresses,
             peer_metadata_storage.clone(),
             trusted_peers,
             authentication_mode,
@@ -139,7 +140,7 @@ impl NetworkBuilder {
             peer_metadata_storage,
             network_context,
             time_service,
-            listen_address,
+            vec![listen_address],
             authentication_mode,
             MAX_FRAME_SIZE,
             false, /* Disable proxy protocol */
@@ -159,6 +160,7 @@ impl NetworkBuilder {
             CONNECTIVITY_CHECK_INTERVAL_MS,
             NETWORK_CHANNEL_SIZE,
             mutual_authentication,
+            PEER_BAN_DURATION_MS,
         );
 
         builder
@@ -193,7 +195,7 @@ impl NetworkBuilder {
             peer_metadata_storage,
             network_context,
             time_service,
-            config.listen_address.clone(),
+            config.listen_addresses(),
             authentication_mode,
             config.max_frame_size,
             config.enable_proxy_protocol,
@@ -222,6 +224,7 @@ impl NetworkBuilder {
             config.connectivity_check_interval_ms,
             config.network_channel_size,
             config.mutual_authentication,
+            config.peer_ban_duration_ms,
         );
 
         network_builder.discovery_listeners = Some(Vec::new());
@@ -317,8 +320,8 @@ impl NetworkBuilder {
             .map(|conn_mgr_builder| conn_mgr_builder.conn_mgr_reqs_tx())
     }
 
-    pub fn listen_address(&self) -> NetworkAddress {
-        self.peer_manager_builder.listen_address()
+    pub fn listen_addresses(&self) -> Vec<NetworkAddress> {
+        self.peer_manager_builder.listen_addresses()
     }
 
     /// Add a [`network::connectivity_manager::ConnectivityManager`] to the network.
@@ -340,6 +343,7 @@ impl NetworkBuilder {
         connectivity_check_interval_ms: u64,
         channel_size: usize,
         mutual_authentication: bool,
+        peer_ban_duration_ms: u64,
     ) -> &mut Self {
         let pm_conn_mgr_notifs_rx = self.peer_manager_builder.add_connection_event_listener();
         let outbound_connection_limit = if !self.network_context.network_id().is_validator_network()
@@ -348,6 +352,12 @@ impl NetworkBuilder {
         } else {
             None
         };
+        // Validators must stay connected to each other, so they are never banned
+        let peer_ban_duration = if !self.network_context.network_id().is_validator_network() {
+            Some(Duration::from_millis(peer_ban_duration_ms))
+        } else {
+            None
+        };
 
         self.connectivity_manager_builder = Some(ConnectivityManagerBuilder::create(
             self.network_context(),
@@ -362,6 +372,8 @@ impl NetworkBuilder {
             pm_conn_mgr_notifs_rx,
             outbound_connection_limit,
             mutual_authentication,
+            self.peer_metadata_storage.clone(),
+            peer_ban_duration,
         ));
         self
     }
@@ -394,6 +406,16 @@ impl NetworkBuilder {
                 *interval_duration,
                 self.time_service.clone(),
             ),
+            DiscoveryMethod::Rest(url, waypoint, interval_duration) => {
+                DiscoveryChangeListener::rest(
+                    self.network_context,
+                    conn_mgr_reqs_tx,
+                    url.clone(),
+                    *waypoint,
+                    *interval_duration,
+                    self.time_service.clone(),
+                )
+            }
             DiscoveryMethod::None => return,
         };
 
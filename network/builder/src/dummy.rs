@@ -152,11 +152,10 @@ pub fn setup_network() -> DummyNetwork {
         .add_p2p_service::<DummyNetworkSender, DummyNetworkEvents>(&network_endpoint_config());
     network_builder.build(runtime.handle().clone()).start();
 
-    // Add the listener address with port
-    let listener_addr = network_builder.listen_address();
+    // Add the listener addresses with ports
     seeds.insert(
         listener_peer_id,
-        Peer::from_addrs(PeerRole::Validator, vec![listener_addr]),
+        Peer::from_addrs(PeerRole::Validator, network_builder.listen_addresses()),
     );
 
     let authentication_mode = AuthenticationMode::Mutual(dialer_identity_private_key);
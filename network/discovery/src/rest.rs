@@ -0,0 +1,195 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::DISCOVERY_COUNTS, validator_set::extract_validator_set_updates, DiscoveryError,
+};
+use anyhow::{format_err, Result};
+use aptos_config::{config::PeerSet, network_id::NetworkContext};
+use aptos_light_client::LightClient;
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use aptos_types::{
+    on_chain_config::{access_path_for_config, OnChainConfig, ValidatorSet},
+    state_store::state_key::StateKey,
+    waypoint::Waypoint,
+};
+use futures::{future::BoxFuture, Future, FutureExt, Stream};
+use network::counters::inc_by_with_context;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use url::Url;
+
+/// Periodically polls a node's REST API for the current `ValidatorSet`.  The node isn't trusted:
+/// every `ValidatorSet` is verified against the ledger info the light client ratcheted to from
+/// the configured waypoint.
+pub struct RestStream {
+    network_context: NetworkContext,
+    /// The light client, unless it's held by the pending request
+    light_client: Option<LightClient>,
+    interval: Pin<Box<Interval>>,
+    pending_request: Option<BoxFuture<'static, (LightClient, Result<ValidatorSet>)>>,
+}
+
+impl RestStream {
+    pub(crate) fn new(
+        network_context: NetworkContext,
+        rest_url: Url,
+        waypoint: Waypoint,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        RestStream {
+            network_context,
+            light_client: Some(LightClient::new(rest_url, waypoint)),
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_request: None,
+        }
+    }
+}
+
+impl Stream for RestStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Only start a new request once the previous one has finished
+        if self.pending_request.is_none() {
+            futures::ready!(self.interval.as_mut().poll_next(cx));
+            let light_client = self
+                .light_client
+                .take()
+                .expect("Light client must exist without a pending request");
+            self.pending_request = Some(fetch_validator_set(light_client).boxed());
+        }
+
+        let (light_client, result) = futures::ready!(self
+            .pending_request
+            .as_mut()
+            .expect("Request must exist")
+            .as_mut()
+            .poll(cx));
+        self.pending_request = None;
+        self.light_client = Some(light_client);
+
+        Poll::Ready(Some(match result {
+            Ok(validator_set) => Ok(extract_validator_set_updates(
+                self.network_context,
+                validator_set,
+            )),
+            Err(error) => {
+                inc_by_with_context(&DISCOVERY_COUNTS, &self.network_context, "rest_failure", 1);
+                Err(DiscoveryError::Rest(error))
+            }
+        }))
+    }
+}
+
+/// Retrieves the latest `ValidatorSet`, verified by the light client
+async fn fetch_validator_set(mut light_client: LightClient) -> (LightClient, Result<ValidatorSet>) {
+    let result = verified_validator_set(&mut light_client).await;
+    (light_client, result)
+}
+
+async fn verified_validator_set(light_client: &mut LightClient) -> Result<ValidatorSet> {
+    light_client.sync().await?;
+    let state_key = StateKey::AccessPath(access_path_for_config(ValidatorSet::CONFIG_ID));
+    let bytes = light_client
+        .get_state_value(&state_key)
+        .await?
+        .ok_or_else(|| format_err!("No ValidatorSet on chain"))?;
+    ValidatorSet::deserialize_into_config(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::{Peer, PeerRole};
+    use aptos_crypto::{x25519, Uniform};
+    use aptos_light_client::mock::MockLedger;
+    use aptos_types::{
+        network_address::NetworkAddress, validator_config::ValidatorConfig,
+        validator_info::ValidatorInfo, validator_signer::ValidatorSigner,
+    };
+    use futures::StreamExt;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn mock_validator_set(num_validators: u8) -> (ValidatorSet, PeerSet) {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let mut validators = Vec::new();
+        let mut peers = PeerSet::new();
+        for i in 0..num_validators {
+            let signer = ValidatorSigner::random([i; 32]);
+            let network_key = x25519::PrivateKey::generate(&mut rng).public_key();
+            let addresses = vec![NetworkAddress::mock().append_prod_protos(network_key, 0)];
+            let encoded_addresses = bcs::to_bytes(&addresses).unwrap();
+            validators.push(ValidatorInfo::new(
+                signer.author(),
+                1,
+                ValidatorConfig::new(
+                    signer.public_key(),
+                    encoded_addresses.clone(),
+                    encoded_addresses,
+                ),
+            ));
+            peers.insert(
+                signer.author(),
+                Peer::from_addrs(PeerRole::Validator, addresses),
+            );
+        }
+        validators.sort_by_key(|validator| *validator.account_address());
+        (ValidatorSet::new(validators), peers)
+    }
+
+    fn rest_stream(ledger: MockLedger) -> RestStream {
+        let waypoint = ledger.waypoint();
+        RestStream::new(
+            NetworkContext::mock(),
+            ledger.serve(),
+            waypoint,
+            Duration::from_millis(1),
+            TimeService::real(),
+        )
+    }
+
+    fn ledger(validator_set: &ValidatorSet) -> MockLedger {
+        MockLedger::new(
+            4,
+            StateKey::AccessPath(access_path_for_config(ValidatorSet::CONFIG_ID)),
+            bcs::to_bytes(validator_set).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_verified_validator_set() {
+        let (validator_set, peers) = mock_validator_set(3);
+        let mut stream = rest_stream(ledger(&validator_set));
+
+        // The stream keeps yielding the verified set
+        for _ in 0..2 {
+            assert_eq!(stream.next().await.unwrap().unwrap(), peers);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reject_forged_validator_set() {
+        let (validator_set, _) = mock_validator_set(3);
+        let (forged_set, _) = mock_validator_set(4);
+        let forged_set = bcs::to_bytes(&forged_set).unwrap();
+
+        // A forged set served with the proof of the genuine one
+        let mut stream = rest_stream(ledger(&validator_set).with_forged_value(forged_set.clone()));
+        assert!(matches!(
+            stream.next().await.unwrap(),
+            Err(DiscoveryError::Rest(_))
+        ));
+
+        // A forged set in a ledger that isn't signed by the validators of the waypoint's epoch
+        let mut stream = rest_stream(ledger(&validator_set).with_forged_signatures(4, forged_set));
+        assert!(matches!(
+            stream.next().await.unwrap(),
+            Err(DiscoveryError::Rest(_))
+        ));
+    }
+}
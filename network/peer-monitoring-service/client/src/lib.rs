@@ -20,11 +20,15 @@ use network::{
 };
 use peer_monitoring_service_types::{
     PeerMonitoringServiceError, PeerMonitoringServiceMessage, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse,
+    PeerMonitoringServiceResponse, UnexpectedResponseError,
 };
 use std::{sync::Arc, time::Duration};
 use thiserror::Error;
 
+mod peer_monitor;
+
+pub use peer_monitor::PeerMonitor;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Aptos network rpc error: {0}")]
@@ -32,6 +36,9 @@ pub enum Error {
 
     #[error("Error from remote monitoring service: {0}")]
     PeerMonitoringServiceError(#[from] PeerMonitoringServiceError),
+
+    #[error("Unexpected response received: {0}")]
+    UnexpectedResponse(#[from] UnexpectedResponseError),
 }
 
 /// The interface for sending peer monitoring service requests and querying
@@ -0,0 +1,220 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, PeerMonitoringServiceClient};
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::PeerNetworkId};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::{future::join_all, StreamExt};
+use network::{
+    application::{interface::NetworkInterface, storage::PeerMetadataStorage},
+    ProtocolId,
+};
+use peer_monitoring_service_types::{
+    NodeInformationResponse, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    ThroughputProbeResponse, UnexpectedResponseError,
+};
+use std::{convert::TryFrom, time::Duration};
+
+/// The actor that periodically measures the latency, throughput and sync
+/// progress of all connected peers, and stores the results in the
+/// `PeerMetadataStorage` so other components can pick the best peers.
+pub struct PeerMonitor {
+    config: PeerMonitoringServiceConfig,
+    monitoring_client: PeerMonitoringServiceClient,
+    time_service: TimeService,
+}
+
+impl PeerMonitor {
+    pub fn new(
+        config: PeerMonitoringServiceConfig,
+        monitoring_client: PeerMonitoringServiceClient,
+        time_service: TimeService,
+    ) -> Self {
+        Self {
+            config,
+            monitoring_client,
+            time_service,
+        }
+    }
+
+    /// Starts the peer monitor loop
+    pub async fn start(self) {
+        info!("Starting the peer monitor!");
+        let ticker = self
+            .time_service
+            .interval(Duration::from_millis(self.config.peer_monitor_interval_ms));
+        futures::pin_mut!(ticker);
+
+        loop {
+            // Wait for the next round before monitoring
+            ticker.next().await;
+
+            // Monitor all connected peers concurrently
+            let peers = self.get_connected_peers();
+            join_all(peers.into_iter().map(|peer| self.monitor_peer(peer))).await;
+        }
+    }
+
+    /// Returns all connected peers that support the peer monitoring service
+    fn get_connected_peers(&self) -> Vec<PeerNetworkId> {
+        let peer_metadata = self.peer_metadata();
+        peer_metadata
+            .networks()
+            .flat_map(|network_id| {
+                peer_metadata
+                    .read_filtered(network_id, |(_, peer_info)| {
+                        peer_info.is_connected()
+                            && peer_info.supports_protocol(ProtocolId::PeerMonitoringServiceRpc)
+                    })
+                    .into_keys()
+            })
+            .collect()
+    }
+
+    /// Measures the given peer and updates its monitoring metadata. Each
+    /// measurement is independent, so a failure only leaves the
+    /// corresponding metadata untouched.
+    async fn monitor_peer(&self, peer: PeerNetworkId) {
+        let mut metadata = self
+            .peer_metadata()
+            .read_monitoring_metadata(peer)
+            .unwrap_or_default();
+
+        // Measure the round trip time
+        match self
+            .send_timed_request(peer, PeerMonitoringServiceRequest::Ping)
+            .await
+        {
+            Ok((_, latency)) => {
+                metadata.average_ping_latency = Some(update_average_latency(
+                    metadata.average_ping_latency,
+                    latency,
+                ));
+            }
+            Err(error) => log_monitoring_error(peer, "ping", error),
+        }
+
+        // Fetch the peer's sync progress and connectivity
+        match self
+            .send_request_and_decode::<NodeInformationResponse>(
+                peer,
+                PeerMonitoringServiceRequest::GetNodeInformation,
+            )
+            .await
+        {
+            Ok(node_information) => {
+                metadata.highest_synced_epoch = Some(node_information.highest_synced_epoch);
+                metadata.highest_synced_version = Some(node_information.highest_synced_version);
+                metadata.num_connected_peers = Some(node_information.num_connected_peers);
+            }
+            Err(error) => log_monitoring_error(peer, "node_information", error),
+        }
+
+        // Measure the throughput (if enabled)
+        let num_bytes = self.config.throughput_probe_bytes;
+        if num_bytes > 0 {
+            let request = PeerMonitoringServiceRequest::GetThroughputProbe(num_bytes);
+            match self.send_timed_request(peer, request).await {
+                Ok((response, duration)) => match ThroughputProbeResponse::try_from(response) {
+                    Ok(probe) => {
+                        metadata.throughput_bytes_per_sec =
+                            Some(calculate_throughput(probe.data.len() as u64, duration));
+                    }
+                    Err(error) => log_monitoring_error(peer, "throughput_probe", error.into()),
+                },
+                Err(error) => log_monitoring_error(peer, "throughput_probe", error),
+            }
+        }
+
+        self.peer_metadata()
+            .update_monitoring_metadata(peer, metadata);
+    }
+
+    /// Sends a request to the peer and returns the response together
+    /// with the time it took to receive it.
+    async fn send_timed_request(
+        &self,
+        peer: PeerNetworkId,
+        request: PeerMonitoringServiceRequest,
+    ) -> Result<(PeerMonitoringServiceResponse, Duration), Error> {
+        let start_time = self.time_service.now();
+        let response = self
+            .monitoring_client
+            .send_request(peer, request, self.request_timeout())
+            .await?;
+        Ok((response, self.time_service.now().duration_since(start_time)))
+    }
+
+    /// Sends a request to the peer and decodes the expected response type
+    async fn send_request_and_decode<T>(
+        &self,
+        peer: PeerNetworkId,
+        request: PeerMonitoringServiceRequest,
+    ) -> Result<T, Error>
+    where
+        T: TryFrom<PeerMonitoringServiceResponse, Error = UnexpectedResponseError>,
+    {
+        let response = self
+            .monitoring_client
+            .send_request(peer, request, self.request_timeout())
+            .await?;
+        T::try_from(response).map_err(Error::from)
+    }
+
+    fn peer_metadata(&self) -> &PeerMetadataStorage {
+        self.monitoring_client.peer_metadata_storage()
+    }
+
+    fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.config.peer_monitor_request_timeout_ms)
+    }
+}
+
+/// Updates the moving average latency with a new measurement. Newer
+/// measurements are weighted equally to the entire history, so the
+/// average adapts quickly to changing network conditions.
+fn update_average_latency(average_latency: Option<Duration>, latency: Duration) -> Duration {
+    match average_latency {
+        Some(average_latency) => (average_latency + latency) / 2,
+        None => latency,
+    }
+}
+
+/// Calculates the throughput of a transfer in bytes per second
+fn calculate_throughput(num_bytes: u64, duration: Duration) -> u64 {
+    let duration_micros = duration.as_micros().max(1);
+    ((num_bytes as u128 * 1_000_000) / duration_micros) as u64
+}
+
+/// Logs a failed monitoring request for the given peer
+fn log_monitoring_error(peer: PeerNetworkId, request_label: &str, error: Error) {
+    debug!(
+        "Failed to monitor peer {:?} using the {} request! Error: {:?}",
+        peer, request_label, error
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_average_latency() {
+        let latency = Duration::from_millis(100);
+        assert_eq!(update_average_latency(None, latency), latency);
+
+        let average_latency = update_average_latency(Some(latency), Duration::from_millis(300));
+        assert_eq!(average_latency, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_calculate_throughput() {
+        assert_eq!(calculate_throughput(1024, Duration::from_secs(1)), 1024);
+        assert_eq!(calculate_throughput(1024, Duration::from_millis(500)), 2048);
+        assert_eq!(
+            calculate_throughput(1024, Duration::from_secs(0)),
+            1024 * 1_000_000
+        );
+    }
+}
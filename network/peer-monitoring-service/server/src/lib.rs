@@ -8,17 +8,22 @@ use crate::{
     metrics::{increment_counter, start_timer},
     network::PeerMonitoringServiceNetworkEvents,
 };
-use ::network::{application::storage::PeerMetadataStorage, ProtocolId};
-use aptos_config::config::PeerMonitoringServiceConfig;
+use ::network::{
+    application::{storage::PeerMetadataStorage, types::PeerInfo},
+    ProtocolId,
+};
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::PeerNetworkId};
 use aptos_logger::prelude::*;
 use bounded_executor::BoundedExecutor;
 use futures::stream::StreamExt;
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse, Result, ServerProtocolVersionResponse,
+    ConnectedPeersResponse, NodeInformationResponse, PeerMonitoringServiceError,
+    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, PingResponse, Result,
+    ServerProtocolVersionResponse, ThroughputProbeResponse,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
+use storage_interface::DbReader;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
@@ -36,6 +41,8 @@ pub const PEER_MONITORING_SERVER_VERSION: u64 = 1;
 pub enum Error {
     #[error("Invalid request received: {0}")]
     InvalidRequest(String),
+    #[error("Storage error encountered: {0}")]
+    StorageErrorEncountered(String),
     #[error("Unexpected error encountered: {0}")]
     UnexpectedErrorEncountered(String),
 }
@@ -45,6 +52,7 @@ impl Error {
     fn get_label(&self) -> &'static str {
         match self {
             Error::InvalidRequest(_) => "invalid_request",
+            Error::StorageErrorEncountered(_) => "storage_error",
             Error::UnexpectedErrorEncountered(_) => "unexpected_error",
         }
     }
@@ -53,8 +61,10 @@ impl Error {
 /// The server-side actor for the peer monitoring service
 pub struct PeerMonitoringServiceServer {
     bounded_executor: BoundedExecutor,
+    config: PeerMonitoringServiceConfig,
     network_requests: PeerMonitoringServiceNetworkEvents,
     peer_metadata: Arc<PeerMetadataStorage>,
+    storage: Arc<dyn DbReader>,
 }
 
 impl PeerMonitoringServiceServer {
@@ -63,14 +73,17 @@ impl PeerMonitoringServiceServer {
         executor: Handle,
         network_requests: PeerMonitoringServiceNetworkEvents,
         peer_metadata: Arc<PeerMetadataStorage>,
+        storage: Arc<dyn DbReader>,
     ) -> Self {
         let bounded_executor =
             BoundedExecutor::new(config.max_concurrent_requests as usize, executor);
 
         Self {
             bounded_executor,
+            config,
             network_requests,
             peer_metadata,
+            storage,
         }
     }
 
@@ -89,10 +102,13 @@ impl PeerMonitoringServiceServer {
 
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
+            let config = self.config.clone();
             let peer_metadata = self.peer_metadata.clone();
+            let storage = self.storage.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
-                    let response = Handler::new(peer_metadata).call(protocol, request);
+                    let response =
+                        Handler::new(config, peer_metadata, storage).call(protocol, request);
                     log_monitoring_service_response(&response);
                     response_sender.send(response);
                 })
@@ -106,12 +122,22 @@ impl PeerMonitoringServiceServer {
 /// request. We usually clone/create a new handler for every request.
 #[derive(Clone)]
 pub struct Handler {
+    config: PeerMonitoringServiceConfig,
     peer_metadata: Arc<PeerMetadataStorage>,
+    storage: Arc<dyn DbReader>,
 }
 
 impl Handler {
-    pub fn new(peer_metadata: Arc<PeerMetadataStorage>) -> Self {
-        Self { peer_metadata }
+    pub fn new(
+        config: PeerMonitoringServiceConfig,
+        peer_metadata: Arc<PeerMetadataStorage>,
+        storage: Arc<dyn DbReader>,
+    ) -> Self {
+        Self {
+            config,
+            peer_metadata,
+            storage,
+        }
     }
 
     pub fn call(
@@ -140,9 +166,13 @@ impl Handler {
                 self.get_depth_from_validators()
             }
             PeerMonitoringServiceRequest::GetKnownPeers => self.get_known_peers(),
+            PeerMonitoringServiceRequest::GetNodeInformation => self.get_node_information(),
             PeerMonitoringServiceRequest::GetServerProtocolVersion => {
                 self.get_server_protocol_version()
             }
+            PeerMonitoringServiceRequest::GetThroughputProbe(num_bytes) => {
+                self.get_throughput_probe(*num_bytes)
+            }
             PeerMonitoringServiceRequest::GetValidatorsAndVFNs => self.get_validators_and_vfns(),
             PeerMonitoringServiceRequest::Ping => self.handle_ping(),
         };
@@ -181,7 +211,16 @@ impl Handler {
     }
 
     fn get_connected_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        // Fetch all connected peers
+        // Return the connected peers
+        Ok(PeerMonitoringServiceResponse::ConnectedPeers(
+            ConnectedPeersResponse {
+                connected_peers: self.fetch_connected_peers(),
+            },
+        ))
+    }
+
+    /// Fetches all connected peers across all networks
+    fn fetch_connected_peers(&self) -> HashMap<PeerNetworkId, PeerInfo> {
         let mut connected_peers = HashMap::new();
         for network in self.peer_metadata.networks() {
             for (peer_network_id, peer_info) in self.peer_metadata.read_all(network) {
@@ -190,11 +229,7 @@ impl Handler {
                 }
             }
         }
-
-        // Return the connected peers
-        Ok(PeerMonitoringServiceResponse::ConnectedPeers(
-            ConnectedPeersResponse { connected_peers },
-        ))
+        connected_peers
     }
 
     fn get_depth_from_validators(&self) -> Result<PeerMonitoringServiceResponse, Error> {
@@ -205,6 +240,25 @@ impl Handler {
         unimplemented!();
     }
 
+    fn get_node_information(&self) -> Result<PeerMonitoringServiceResponse, Error> {
+        // Fetch the latest ledger info from storage
+        let latest_ledger_info = self
+            .storage
+            .get_latest_ledger_info()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let ledger_info = latest_ledger_info.ledger_info();
+
+        // Return the sync progress and the number of connected peers
+        Ok(PeerMonitoringServiceResponse::NodeInformation(
+            NodeInformationResponse {
+                highest_synced_epoch: ledger_info.epoch(),
+                highest_synced_version: ledger_info.version(),
+                ledger_timestamp_usecs: ledger_info.timestamp_usecs(),
+                num_connected_peers: self.fetch_connected_peers().len() as u64,
+            },
+        ))
+    }
+
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         Ok(PeerMonitoringServiceResponse::ServerProtocolVersion(
             ServerProtocolVersionResponse {
@@ -213,12 +267,30 @@ impl Handler {
         ))
     }
 
+    fn get_throughput_probe(&self, num_bytes: u64) -> Result<PeerMonitoringServiceResponse, Error> {
+        // Ensure the requested probe isn't too large
+        let max_throughput_probe_bytes = self.config.max_throughput_probe_bytes;
+        if num_bytes > max_throughput_probe_bytes {
+            return Err(Error::InvalidRequest(format!(
+                "Requested throughput probe is too large! Requested: {:?}, max: {:?}",
+                num_bytes, max_throughput_probe_bytes
+            )));
+        }
+
+        // Return the probe data (the contents are irrelevant)
+        Ok(PeerMonitoringServiceResponse::ThroughputProbe(
+            ThroughputProbeResponse {
+                data: vec![0; num_bytes as usize],
+            },
+        ))
+    }
+
     fn get_validators_and_vfns(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         unimplemented!();
     }
 
     fn handle_ping(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Ok(PeerMonitoringServiceResponse::Ping(PingResponse { todo: false }))
     }
 }
 
@@ -6,12 +6,19 @@
 use crate::{
     PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer, PEER_MONITORING_SERVER_VERSION,
 };
+use anyhow::Result;
 use aptos_config::{
     config::{PeerMonitoringServiceConfig, PeerRole},
     network_id::{NetworkId, PeerNetworkId},
 };
+use aptos_crypto::HashValue;
 use aptos_logger::Level;
-use aptos_types::{network_address::NetworkAddress, PeerId};
+use aptos_types::{
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    network_address::NetworkAddress,
+    PeerId,
+};
 use channel::aptos_channel;
 use futures::channel::oneshot;
 use netcore::transport::ConnectionOrigin;
@@ -29,14 +36,21 @@ use network::{
     transport::{ConnectionId, ConnectionMetadata},
 };
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
-    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    ConnectedPeersResponse, NodeInformationResponse, PeerMonitoringServiceError,
+    PeerMonitoringServiceMessage, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    PingResponse, ServerProtocolVersionResponse, ThroughputProbeResponse,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap},
     str::FromStr,
     sync::Arc,
 };
+use storage_interface::DbReader;
+
+// Useful test constants
+const HIGHEST_SYNCED_EPOCH: u64 = 5;
+const HIGHEST_SYNCED_VERSION: u64 = 1000;
+const LEDGER_TIMESTAMP_USECS: u64 = 123_456;
 
 #[tokio::test]
 async fn test_get_server_protocol_version() {
@@ -123,6 +137,150 @@ async fn test_get_connected_peers() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_ping() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Process a ping request
+    let request = PeerMonitoringServiceRequest::Ping;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    assert_eq!(
+        response,
+        PeerMonitoringServiceResponse::Ping(PingResponse { todo: false })
+    );
+}
+
+#[test]
+fn test_wire_format() {
+    // The requests and responses of older peers must keep their encoding
+    assert_eq!(
+        bcs::to_bytes(&PeerMonitoringServiceRequest::GetServerProtocolVersion).unwrap(),
+        vec![3]
+    );
+    assert_eq!(
+        bcs::to_bytes(&PeerMonitoringServiceRequest::Ping).unwrap(),
+        vec![5]
+    );
+    assert_eq!(
+        bcs::to_bytes(&PeerMonitoringServiceResponse::Ping(PingResponse {
+            todo: false
+        }))
+        .unwrap(),
+        vec![3, 0]
+    );
+    assert_eq!(
+        bcs::to_bytes(&PeerMonitoringServiceResponse::ServerProtocolVersion(
+            ServerProtocolVersionResponse { version: 1 }
+        ))
+        .unwrap(),
+        vec![4, 1, 0, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[tokio::test]
+async fn test_get_node_information() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, peer_metadata_storage) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the node information
+    let request = PeerMonitoringServiceRequest::GetNodeInformation;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    let expected_response =
+        PeerMonitoringServiceResponse::NodeInformation(create_node_information_response(0));
+    assert_eq!(response, expected_response);
+
+    // Connect a new peer
+    let peer_id = PeerId::random();
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
+    let connection_metadata = ConnectionMetadata::new(
+        peer_id,
+        ConnectionId::default(),
+        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
+        ConnectionOrigin::Inbound,
+        MessagingProtocolVersion::V1,
+        ProtocolIdSet::empty(),
+        PeerRole::Unknown,
+    );
+    peer_metadata_storage.insert(peer_network_id, PeerInfo::new(connection_metadata));
+
+    // Verify the connected peer is now counted
+    let request = PeerMonitoringServiceRequest::GetNodeInformation;
+    let response = mock_client.send_request(request).await.unwrap();
+    let expected_response =
+        PeerMonitoringServiceResponse::NodeInformation(create_node_information_response(1));
+    assert_eq!(response, expected_response);
+}
+
+#[tokio::test]
+async fn test_get_throughput_probe() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Process a request for a valid throughput probe
+    let num_bytes = 1024;
+    let request = PeerMonitoringServiceRequest::GetThroughputProbe(num_bytes);
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    let expected_response =
+        PeerMonitoringServiceResponse::ThroughputProbe(ThroughputProbeResponse {
+            data: vec![0; num_bytes as usize],
+        });
+    assert_eq!(response, expected_response);
+
+    // Process a request for a probe that is too large
+    let max_throughput_probe_bytes =
+        PeerMonitoringServiceConfig::default().max_throughput_probe_bytes;
+    let request = PeerMonitoringServiceRequest::GetThroughputProbe(max_throughput_probe_bytes + 1);
+    let response = mock_client.send_request(request).await.unwrap_err();
+
+    // Verify the request was rejected
+    assert!(matches!(
+        response,
+        PeerMonitoringServiceError::InvalidRequest(_)
+    ));
+}
+
+/// Creates the node information response expected from the mock database
+fn create_node_information_response(num_connected_peers: u64) -> NodeInformationResponse {
+    NodeInformationResponse {
+        highest_synced_epoch: HIGHEST_SYNCED_EPOCH,
+        highest_synced_version: HIGHEST_SYNCED_VERSION,
+        ledger_timestamp_usecs: LEDGER_TIMESTAMP_USECS,
+        num_connected_peers,
+    }
+}
+
+/// A simple mock database that only returns a fixed latest ledger info
+struct MockDatabaseReader;
+
+impl DbReader for MockDatabaseReader {
+    fn get_latest_ledger_info_option(&self) -> Result<Option<LedgerInfoWithSignatures>> {
+        let block_info = BlockInfo::new(
+            HIGHEST_SYNCED_EPOCH,
+            0,
+            HashValue::zero(),
+            HashValue::zero(),
+            HIGHEST_SYNCED_VERSION,
+            LEDGER_TIMESTAMP_USECS,
+            None,
+        );
+        let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
+        Ok(Some(LedgerInfoWithSignatures::new(
+            ledger_info,
+            BTreeMap::new(),
+        )))
+    }
+}
+
 /// A wrapper around the inbound network interface/channel for easily sending
 /// mock client requests to a [`PeerMonitoringServiceServer`].
 struct MockClient {
@@ -156,6 +314,7 @@ impl MockClient {
             executor,
             network_request_stream,
             peer_metadata_storage.clone(),
+            Arc::new(MockDatabaseReader),
         );
 
         // Create the mock client
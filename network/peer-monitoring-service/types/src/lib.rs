@@ -6,7 +6,7 @@
 use aptos_config::network_id::PeerNetworkId;
 use network::application::types::PeerInfo;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::HashMap, convert::TryFrom, fmt};
 use thiserror::Error;
 
 pub type Result<T, E = PeerMonitoringServiceError> = ::std::result::Result<T, E>;
@@ -31,6 +31,8 @@ pub enum PeerMonitoringServiceMessage {
 }
 
 /// A peer monitoring service request
+///
+/// Note: new variants must be appended, as the variant index is part of the wire format.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum PeerMonitoringServiceRequest {
     GetConnectedPeers,        // Returns all connected peers
@@ -39,6 +41,8 @@ pub enum PeerMonitoringServiceRequest {
     GetServerProtocolVersion, // Fetches the protocol version run by the server
     GetValidatorsAndVFNs,     // Returns the current validators and VFNs
     Ping, // A simple message used by the client to ensure liveness and measure latency
+    GetNodeInformation, // Returns the sync progress and connectivity of the server
+    GetThroughputProbe(u64), // Returns the requested number of bytes to measure throughput
 }
 
 impl PeerMonitoringServiceRequest {
@@ -48,7 +52,9 @@ impl PeerMonitoringServiceRequest {
             Self::GetConnectedPeers => "get_connected_peers",
             Self::GetDepthFromValidators => "get_depth_from_validators",
             Self::GetKnownPeers => "get_known_peers",
+            Self::GetNodeInformation => "get_node_information",
             Self::GetServerProtocolVersion => "get_server_protocol_version",
+            Self::GetThroughputProbe(_) => "get_throughput_probe",
             Self::GetValidatorsAndVFNs => "get_validators_and_vfns",
             Self::Ping => "ping",
         }
@@ -56,6 +62,8 @@ impl PeerMonitoringServiceRequest {
 }
 
 /// A peer monitoring service response
+///
+/// Note: new variants must be appended, as the variant index is part of the wire format.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum PeerMonitoringServiceResponse {
@@ -65,6 +73,8 @@ pub enum PeerMonitoringServiceResponse {
     Ping(PingResponse), // A simple message to respond to liveness checks (i.e., pings)
     ServerProtocolVersion(ServerProtocolVersionResponse), // Returns the current server protocol version
     ValidatorsAndVFNs(ValidatorsAndVFNsResponse), // Holds the current validator set and VFNs
+    NodeInformation(NodeInformationResponse), // Holds the sync progress and connectivity of the server
+    ThroughputProbe(ThroughputProbeResponse), // Holds the probe data used to measure throughput
 }
 
 impl PeerMonitoringServiceResponse {
@@ -74,8 +84,10 @@ impl PeerMonitoringServiceResponse {
             Self::ConnectedPeers(_) => "connected_peers",
             Self::DepthFromValidators(_) => "depth_from_validators",
             Self::KnownPeers(_) => "known_peers",
+            Self::NodeInformation(_) => "node_information",
             Self::Ping(_) => "ping",
             Self::ServerProtocolVersion(_) => "server_protocol_version",
+            Self::ThroughputProbe(_) => "throughput_probe",
             Self::ValidatorsAndVFNs(_) => "validators_and_vfns",
         }
     }
@@ -99,10 +111,19 @@ pub struct KnownPeersResponse {
     pub todo: bool,
 }
 
+/// A response for the node information request
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NodeInformationResponse {
+    pub highest_synced_epoch: u64,   // The epoch of the latest ledger info
+    pub highest_synced_version: u64, // The version of the latest ledger info
+    pub ledger_timestamp_usecs: u64, // The timestamp of the latest ledger info
+    pub num_connected_peers: u64,    // The number of peers connected across all networks
+}
+
 /// A response for the ping request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PingResponse {
-    pub todo: bool,
+    pub todo: bool, // Carries no information, but is part of the wire format
 }
 
 /// A response for the server protocol version request
@@ -111,6 +132,21 @@ pub struct ServerProtocolVersionResponse {
     pub version: u64,
 }
 
+/// A response for the throughput probe request
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ThroughputProbeResponse {
+    pub data: Vec<u8>,
+}
+
+impl fmt::Debug for ThroughputProbeResponse {
+    // Avoid dumping the (potentially large) probe data in logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThroughputProbeResponse")
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
+
 /// A response for the current validators and VFNs
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ValidatorsAndVFNsResponse {
@@ -160,6 +196,19 @@ impl TryFrom<PeerMonitoringServiceResponse> for KnownPeersResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for NodeInformationResponse {
+    type Error = UnexpectedResponseError;
+    fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::NodeInformation(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected node_information_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for PingResponse {
     type Error = UnexpectedResponseError;
     fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
@@ -186,6 +235,19 @@ impl TryFrom<PeerMonitoringServiceResponse> for ServerProtocolVersionResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for ThroughputProbeResponse {
+    type Error = UnexpectedResponseError;
+    fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::ThroughputProbe(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected throughput_probe_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for ValidatorsAndVFNsResponse {
     type Error = UnexpectedResponseError;
     fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
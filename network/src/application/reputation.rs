@@ -0,0 +1,66 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Scores for peer reputations based on interactions reported by applications.
+pub const MAX_SCORE: f64 = 100.0;
+pub const MIN_SCORE: f64 = 0.0;
+pub const STARTING_SCORE: f64 = 50.0;
+/// Add this score on a successful interaction.
+const SUCCESSFUL_INTERACTION_DELTA: f64 = 1.0;
+/// Not necessarily a malicious interaction, but not super useful.
+const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
+/// Likely to be a malicious interaction.
+const MALICIOUS_MULTIPLIER: f64 = 0.8;
+/// Disconnect and ban a peer when their score dips below this threshold.
+pub const BAN_PEER_THRESHOLD: f64 = 25.0;
+
+/// An interaction with a peer, as observed and reported by an application
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PeerInteraction {
+    /// The peer responded in a timely and useful manner.
+    Success,
+    /// An interaction that's not actively malicious but also doesn't help
+    /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
+    NotUseful,
+    /// An interaction that appears to be actively hindering progress or
+    /// attempting to deceive us, e.g., invalid proofs.
+    Malicious,
+}
+
+/// The network-wide reputation of a single peer
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PeerReputation {
+    score: f64,
+}
+
+impl Default for PeerReputation {
+    fn default() -> Self {
+        Self {
+            score: STARTING_SCORE,
+        }
+    }
+}
+
+impl PeerReputation {
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Returns true iff the peer should be disconnected and banned
+    pub fn is_below_ban_threshold(&self) -> bool {
+        self.score <= BAN_PEER_THRESHOLD
+    }
+
+    /// Updates the score of the peer according to the given interaction
+    pub fn update(&mut self, interaction: PeerInteraction) {
+        self.score = match interaction {
+            PeerInteraction::Success => {
+                f64::min(self.score + SUCCESSFUL_INTERACTION_DELTA, MAX_SCORE)
+            }
+            PeerInteraction::NotUseful => f64::max(self.score * NOT_USEFUL_MULTIPLIER, MIN_SCORE),
+            PeerInteraction::Malicious => f64::max(self.score * MALICIOUS_MULTIPLIER, MIN_SCORE),
+        };
+    }
+}
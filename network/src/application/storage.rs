@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application::types::{PeerError, PeerInfo},
+    application::{
+        reputation::{PeerInteraction, PeerReputation},
+        types::{PeerError, PeerInfo, PeerMonitoringMetadata},
+    },
     transport::ConnectionMetadata,
 };
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
@@ -20,6 +23,10 @@ use std::{
 #[derive(Debug)]
 pub struct PeerMetadataStorage {
     storage: HashMap<NetworkId, LockingHashMap<PeerId, PeerInfo>>,
+    /// Reputations are kept separately from `PeerInfo` so they outlive disconnects
+    reputations: HashMap<NetworkId, LockingHashMap<PeerId, PeerReputation>>,
+    /// Monitoring metadata also outlives disconnects, so it can be used when redialing peers
+    monitoring_metadata: HashMap<NetworkId, LockingHashMap<PeerId, PeerMonitoringMetadata>>,
 }
 
 impl PeerMetadataStorage {
@@ -32,11 +39,19 @@ impl PeerMetadataStorage {
     pub fn new(network_ids: &[NetworkId]) -> Arc<PeerMetadataStorage> {
         let mut peer_metadata_storage = PeerMetadataStorage {
             storage: HashMap::new(),
+            reputations: HashMap::new(),
+            monitoring_metadata: HashMap::new(),
         };
         network_ids.iter().for_each(|network_id| {
             peer_metadata_storage
                 .storage
                 .insert(*network_id, LockingHashMap::new());
+            peer_metadata_storage
+                .reputations
+                .insert(*network_id, LockingHashMap::new());
+            peer_metadata_storage
+                .monitoring_metadata
+                .insert(*network_id, LockingHashMap::new());
         });
         Arc::new(peer_metadata_storage)
     }
@@ -52,6 +67,26 @@ impl PeerMetadataStorage {
             .unwrap_or_else(|| panic!("Unexpected network requested: {}", network_id))
     }
 
+    /// Handle common logic of getting the reputations of a network
+    fn get_reputations(
+        &self,
+        network_id: NetworkId,
+    ) -> &LockingHashMap<AccountAddress, PeerReputation> {
+        self.reputations
+            .get(&network_id)
+            .unwrap_or_else(|| panic!("Unexpected network requested: {}", network_id))
+    }
+
+    /// Handle common logic of getting the monitoring metadata of a network
+    fn get_monitoring_metadata(
+        &self,
+        network_id: NetworkId,
+    ) -> &LockingHashMap<AccountAddress, PeerMonitoringMetadata> {
+        self.monitoring_metadata
+            .get(&network_id)
+            .unwrap_or_else(|| panic!("Unexpected network requested: {}", network_id))
+    }
+
     pub fn read(&self, peer_network_id: PeerNetworkId) -> Option<PeerInfo> {
         let network = self.get_network(peer_network_id.network_id());
         network.read(&peer_network_id.peer_id())
@@ -139,6 +174,51 @@ impl PeerMetadataStorage {
             }
         }
     }
+
+    /// Updates the reputation of a peer according to an interaction observed by an application
+    pub fn report_peer_interaction(
+        &self,
+        peer_network_id: PeerNetworkId,
+        interaction: PeerInteraction,
+    ) {
+        self.get_reputations(peer_network_id.network_id())
+            .write_lock()
+            .entry(peer_network_id.peer_id())
+            .or_default()
+            .update(interaction);
+    }
+
+    /// Returns the current reputation of a peer, peers without reports have the starting reputation
+    pub fn read_reputation(&self, peer_network_id: PeerNetworkId) -> PeerReputation {
+        self.get_reputations(peer_network_id.network_id())
+            .read(&peer_network_id.peer_id())
+            .unwrap_or_default()
+    }
+
+    /// Forgets all reports for a peer, e.g., after it has served a ban
+    pub fn reset_reputation(&self, peer_network_id: &PeerNetworkId) {
+        self.get_reputations(peer_network_id.network_id())
+            .remove(&peer_network_id.peer_id())
+    }
+
+    /// Replaces the monitoring metadata of a peer with the latest measurements
+    pub fn update_monitoring_metadata(
+        &self,
+        peer_network_id: PeerNetworkId,
+        monitoring_metadata: PeerMonitoringMetadata,
+    ) {
+        self.get_monitoring_metadata(peer_network_id.network_id())
+            .insert(peer_network_id.peer_id(), monitoring_metadata)
+    }
+
+    /// Returns the latest monitoring metadata of a peer, if the peer was ever monitored
+    pub fn read_monitoring_metadata(
+        &self,
+        peer_network_id: PeerNetworkId,
+    ) -> Option<PeerMonitoringMetadata> {
+        self.get_monitoring_metadata(peer_network_id.network_id())
+            .read(&peer_network_id.peer_id())
+    }
 }
 
 fn to_peer_network_ids(
@@ -4,15 +4,16 @@
 use crate::{
     application::{
         interface::NetworkInterface,
+        reputation::{PeerInteraction, STARTING_SCORE},
         storage::{LockingHashMap, PeerMetadataStorage},
-        types::{PeerError, PeerState},
+        types::{PeerError, PeerMonitoringMetadata, PeerState},
     },
     protocols::health_checker::HealthCheckerMsg,
     transport::ConnectionMetadata,
 };
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_types::PeerId;
-use std::{collections::hash_map::Entry, sync::Arc};
+use std::{collections::hash_map::Entry, sync::Arc, time::Duration};
 
 #[derive(Clone)]
 struct DummySender {}
@@ -80,6 +81,85 @@ fn test_interface() {
     assert_eq!(0, interface.connected_peers(network_id).len());
 }
 
+#[test]
+fn test_peer_reputation() {
+    let network_id = NetworkId::Validator;
+    let peer_metadata_storage = PeerMetadataStorage::test();
+    let peer = PeerNetworkId::new(network_id, PeerId::random());
+
+    // Peers without any reports start with the default reputation
+    let score = peer_metadata_storage.read_reputation(peer).score();
+    assert_eq!(STARTING_SCORE, score);
+
+    // Reputations are kept regardless of whether the peer is connected
+    peer_metadata_storage.report_peer_interaction(peer, PeerInteraction::Success);
+    assert!(peer_metadata_storage.read_reputation(peer).score() > score);
+
+    // Bad interactions eventually lead to a ban
+    for _ in 0..10 {
+        peer_metadata_storage.report_peer_interaction(peer, PeerInteraction::NotUseful);
+    }
+    assert!(peer_metadata_storage.read_reputation(peer).score() < score);
+    for _ in 0..10 {
+        peer_metadata_storage.report_peer_interaction(peer, PeerInteraction::Malicious);
+    }
+    assert!(peer_metadata_storage
+        .read_reputation(peer)
+        .is_below_ban_threshold());
+
+    // Resetting the reputation gives the peer a fresh start
+    peer_metadata_storage.reset_reputation(&peer);
+    assert_eq!(
+        STARTING_SCORE,
+        peer_metadata_storage.read_reputation(peer).score()
+    );
+}
+
+#[test]
+fn test_peer_monitoring_metadata() {
+    let network_id = NetworkId::Validator;
+    let peer_metadata_storage = PeerMetadataStorage::test();
+    let peer = PeerNetworkId::new(network_id, PeerId::random());
+
+    // Peers that were never monitored have no metadata
+    assert_eq!(None, peer_metadata_storage.read_monitoring_metadata(peer));
+
+    // Monitoring metadata is kept regardless of whether the peer is connected
+    let monitoring_metadata = PeerMonitoringMetadata {
+        average_ping_latency: Some(Duration::from_millis(50)),
+        highest_synced_version: Some(100),
+        ..Default::default()
+    };
+    peer_metadata_storage.update_monitoring_metadata(peer, monitoring_metadata);
+    assert_eq!(
+        Some(monitoring_metadata),
+        peer_metadata_storage.read_monitoring_metadata(peer)
+    );
+}
+
+#[test]
+fn test_peer_monitoring_sync_lag() {
+    let most_synced = (5, 1000);
+
+    // Peers whose sync progress wasn't measured have no lag
+    let mut monitoring_metadata = PeerMonitoringMetadata::default();
+    assert_eq!(None, monitoring_metadata.sync_lag(most_synced));
+
+    // The lag is measured in versions
+    monitoring_metadata.highest_synced_epoch = Some(5);
+    monitoring_metadata.highest_synced_version = Some(900);
+    assert_eq!(Some(100), monitoring_metadata.sync_lag(most_synced));
+
+    // A peer in an older epoch lags behind, even at the same version
+    monitoring_metadata.highest_synced_epoch = Some(4);
+    monitoring_metadata.highest_synced_version = Some(1000);
+    assert_eq!(Some(1), monitoring_metadata.sync_lag(most_synced));
+
+    // The most synced peer doesn't lag
+    monitoring_metadata.highest_synced_epoch = Some(5);
+    assert_eq!(Some(0), monitoring_metadata.sync_lag(most_synced));
+}
+
 fn update_state(
     peer_metadata_storage: Arc<PeerMetadataStorage>,
     peer_network_id: PeerNetworkId,
@@ -3,6 +3,7 @@
 
 use crate::{protocols::wire::handshake::v1::ProtocolId, transport::ConnectionMetadata};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Errors related to the peer layer in the `NetworkInterface`
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -36,6 +37,39 @@ impl PeerInfo {
     }
 }
 
+/// Latency, throughput and sync progress of a peer, as periodically
+/// measured by the peer monitoring service. Fields are `None` until
+/// the corresponding measurement has succeeded at least once.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerMonitoringMetadata {
+    pub average_ping_latency: Option<Duration>, // A moving average of the ping round trip times
+    pub throughput_bytes_per_sec: Option<u64>,  // The throughput observed for the latest probe
+    pub highest_synced_epoch: Option<u64>,      // The highest epoch reported by the peer
+    pub highest_synced_version: Option<u64>,    // The highest version reported by the peer
+    pub num_connected_peers: Option<u64>,       // The number of peers the peer is connected to
+}
+
+impl PeerMonitoringMetadata {
+    /// Returns the (epoch, version) the peer has synced to, if it was ever measured
+    pub fn sync_progress(&self) -> Option<(u64, u64)> {
+        self.highest_synced_epoch.zip(self.highest_synced_version)
+    }
+
+    /// Returns the number of versions the peer lags behind `most_synced`, the (epoch, version)
+    /// of the most synced peer, or `None` if the peer's sync progress was never measured.
+    /// A peer in an older epoch lags by at least one version.
+    pub fn sync_lag(&self, most_synced: (u64, u64)) -> Option<u64> {
+        self.sync_progress().map(|(epoch, version)| {
+            let lag = most_synced.1.saturating_sub(version);
+            if epoch < most_synced.0 {
+                lag.max(1)
+            } else {
+                lag
+            }
+        })
+    }
+}
+
 /// The current state of a `Peer` at any one time
 /// TODO: Allow nodes that are unhealthy to stay connected
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
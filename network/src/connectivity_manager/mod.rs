@@ -25,8 +25,15 @@
 //! absolutely important that we maintain connectivity with all peers and heal
 //! any partitions asap, as we aren't currently gossiping consensus messages or
 //! using a relay protocol.
+//!
+//! Applications report good and bad interactions with peers to the
+//! [`PeerMetadataStorage`]. Outside of the validator network, peers whose
+//! reputation drops below the ban threshold are disconnected and banned for a
+//! while, and peers with a better reputation are preferred when choosing which
+//! peers to dial.
 
 use crate::{
+    application::{storage::PeerMetadataStorage, types::PeerMonitoringMetadata},
     counters,
     logging::NetworkSchema,
     peer_manager::{self, conn_notifs_channel, ConnectionRequestSender, PeerManagerError},
@@ -34,7 +41,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -63,7 +70,7 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     fmt, mem,
     sync::Arc,
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio_retry::strategy::jitter;
 
@@ -122,6 +129,12 @@ pub struct ConnectivityManager<TBackoff> {
     rng: SmallRng,
     /// Whether we are using mutual authentication or not
     mutual_authentication: bool,
+    /// Shared peer metadata, used to look up the reputation of peers
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+    /// How long to ban peers with a bad reputation for. `None` disables banning.
+    peer_ban_duration: Option<Duration>,
+    /// Peers which are currently banned, and the time at which their ban expires.
+    banned_peers: HashMap<PeerId, Instant>,
 }
 
 /// Different sources for peer addresses, ordered by priority (Onchain=highest,
@@ -131,6 +144,7 @@ pub struct ConnectivityManager<TBackoff> {
 pub enum DiscoverySource {
     OnChainValidatorSet,
     File,
+    Rest,
     Config,
 }
 
@@ -148,6 +162,7 @@ impl fmt::Display for DiscoverySource {
             match self {
                 DiscoverySource::OnChainValidatorSet => "OnChainValidatorSet",
                 DiscoverySource::File => "File",
+                DiscoverySource::Rest => "Rest",
                 DiscoverySource::Config => "Config",
             }
         )
@@ -304,6 +319,7 @@ where
     TBackoff: Iterator<Item = Duration> + Clone,
 {
     /// Creates a new instance of the [`ConnectivityManager`] actor.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         network_context: NetworkContext,
         time_service: TimeService,
@@ -317,6 +333,8 @@ where
         max_delay: Duration,
         outbound_connection_limit: Option<usize>,
         mutual_authentication: bool,
+        peer_metadata_storage: Arc<PeerMetadataStorage>,
+        peer_ban_duration: Option<Duration>,
     ) -> Self {
         assert!(
             eligible.read().is_empty(),
@@ -347,6 +365,9 @@ where
             outbound_connection_limit,
             rng: SmallRng::from_entropy(),
             mutual_authentication,
+            peer_metadata_storage,
+            peer_ban_duration,
+            banned_peers: HashMap::new(),
         };
 
         // set the initial config addresses and pubkeys
@@ -490,6 +511,116 @@ where
         }
     }
 
+    /// Lift the bans of all peers whose ban has expired.
+    ///
+    /// Their reputation is reset, so that they are given a fresh start.
+    fn unban_expired_peers(&mut self) {
+        let now = self.time_service.now();
+        let expired_bans: Vec<_> = self
+            .banned_peers
+            .iter()
+            .filter(|(_, ban_expiry)| **ban_expiry <= now)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+
+        for peer_id in expired_bans.into_iter() {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Ban expired for peer {}",
+                self.network_context,
+                peer_id.short_str()
+            );
+            self.banned_peers.remove(&peer_id);
+            self.peer_metadata_storage
+                .reset_reputation(&self.peer_network_id(peer_id));
+        }
+    }
+
+    /// Ban all peers whose reputation has dropped below the ban threshold, and
+    /// disconnect from any banned peers that we are still connected to.
+    ///
+    /// For instance, a peer might keep serving invalid state sync proofs. Once
+    /// enough of these have been reported, calling this function will close our
+    /// connection to it and stop us from dialing it until the ban expires.
+    async fn evict_bad_peers(&mut self) {
+        let peer_ban_duration = match self.peer_ban_duration {
+            Some(peer_ban_duration) => peer_ban_duration,
+            None => return,
+        };
+
+        let bad_peers: HashSet<_> = self
+            .connected
+            .keys()
+            .chain(self.discovered_peers.0.keys())
+            .filter(|peer_id| !self.banned_peers.contains_key(peer_id))
+            .filter(|peer_id| {
+                self.peer_metadata_storage
+                    .read_reputation(self.peer_network_id(**peer_id))
+                    .is_below_ban_threshold()
+            })
+            .copied()
+            .collect();
+
+        let ban_expiry = self.time_service.now() + peer_ban_duration;
+        for peer_id in bad_peers.into_iter() {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Banning peer {} due to a low reputation: {}",
+                self.network_context,
+                peer_id.short_str(),
+                self.reputation_score(&peer_id)
+            );
+            counters::peers_banned(&self.network_context).inc();
+            self.banned_peers.insert(peer_id, ban_expiry);
+            self.dial_queue.remove(&peer_id);
+        }
+
+        // Banned peers may still be connected, e.g., if they dialed us.
+        let banned_connections: Vec<_> = self
+            .connected
+            .keys()
+            .filter(|peer_id| self.banned_peers.contains_key(peer_id))
+            .copied()
+            .collect();
+        for p in banned_connections.into_iter() {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&p),
+                "{} Closing connection to banned peer {}",
+                self.network_context,
+                p.short_str()
+            );
+
+            if let Err(e) = self.connection_reqs_tx.disconnect_peer(p).await {
+                info!(
+                    NetworkSchema::new(&self.network_context)
+                        .remote_peer(&p),
+                    error = %e,
+                    "{} Failed to close connection to banned peer {} : {}",
+                    self.network_context,
+                    p.short_str(),
+                    e
+                );
+            }
+        }
+    }
+
+    fn peer_network_id(&self, peer_id: PeerId) -> PeerNetworkId {
+        PeerNetworkId::new(self.network_context.network_id(), peer_id)
+    }
+
+    fn reputation_score(&self, peer_id: &PeerId) -> f64 {
+        self.peer_metadata_storage
+            .read_reputation(self.peer_network_id(*peer_id))
+            .score()
+    }
+
+    /// Returns the last monitoring measurements of the peer, if it was ever measured
+    fn monitoring_metadata(&self, peer_id: &PeerId) -> PeerMonitoringMetadata {
+        self.peer_metadata_storage
+            .read_monitoring_metadata(self.peer_network_id(*peer_id))
+            .unwrap_or_default()
+    }
+
     fn dial_eligible_peers<'a>(
         &'a mut self,
         pending_dials: &'a mut FuturesUnordered<BoxFuture<'static, PeerId>>,
@@ -504,7 +635,7 @@ where
         let network_id = self.network_context.network_id();
         let role = self.network_context.role();
         let roles_to_dial = network_id.upstream_roles(&role);
-        let mut eligible: Vec<_> = self
+        let eligible: Vec<_> = self
             .discovered_peers
             .0
             .iter()
@@ -512,17 +643,49 @@ where
                 peer.is_eligible_to_be_dialed() // The node is eligible to dial
                     && !self.connected.contains_key(peer_id) // The node is not already connected.
                     && !self.dial_queue.contains_key(peer_id) // There is no pending dial to this node.
+                    && !self.banned_peers.contains_key(peer_id) // The node is not banned.
                     && roles_to_dial.contains(&peer.role) // We can dial this role
             })
+            .map(|(peer_id, peer)| {
+                let score = self.reputation_score(peer_id);
+                let metadata = self.monitoring_metadata(peer_id);
+                (peer_id, peer, (score, metadata))
+            })
+            .collect();
+
+        // The sync lag of each peer is relative to the most synced eligible peer. Unmeasured
+        // peers (e.g., peers we've never connected to) lag the most and have the highest latency.
+        let most_synced = eligible
+            .iter()
+            .filter_map(|(_, _, (_, metadata))| metadata.sync_progress())
+            .max();
+        let mut eligible: Vec<_> = eligible
+            .into_iter()
+            .map(|(peer_id, peer, (score, metadata))| {
+                let sync_lag = most_synced
+                    .and_then(|most_synced| metadata.sync_lag(most_synced))
+                    .unwrap_or(u64::MAX);
+                let latency = metadata.average_ping_latency.unwrap_or(Duration::MAX);
+                (peer_id, peer, (score, sync_lag, latency))
+            })
             .collect();
 
         // Prioritize by PeerRole
         // Shuffle so we don't get stuck on certain peers
         eligible.shuffle(&mut self.rng);
 
-        // Sort by peer priority
-        eligible
-            .sort_by(|(_, peer), (_, other)| peer.partial_cmp(other).unwrap_or(Ordering::Equal));
+        // Sort by peer priority, then prefer peers with a better reputation, then
+        // peers that lag less behind and finally peers that were measured to be closer.
+        eligible.sort_by(
+            |(_, peer, (score, sync_lag, latency)),
+             (_, other, (other_score, other_sync_lag, other_latency))| {
+                peer.partial_cmp(other)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| other_score.partial_cmp(score).unwrap_or(Ordering::Equal))
+                    .then_with(|| sync_lag.cmp(other_sync_lag))
+                    .then_with(|| latency.cmp(other_latency))
+            },
+        );
 
         // Limit the number of dialed connections from a Full Node
         // This does not limit the number of incoming connections
@@ -548,7 +711,7 @@ where
         eligible
             .iter()
             .take(to_connect)
-            .map(|(peer_id, peer)| (**peer_id, (*peer).clone()))
+            .map(|(peer_id, peer, _)| (**peer_id, (*peer).clone()))
             .collect()
     }
 
@@ -646,6 +809,9 @@ where
         self.cancel_stale_dials().await;
         // Disconnect from connected peers that are no longer eligible.
         self.close_stale_connections().await;
+        // Lift expired bans, and ban and disconnect from peers with a bad reputation.
+        self.unban_expired_peers();
+        self.evict_bad_peers().await;
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials);
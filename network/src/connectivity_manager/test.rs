@@ -3,6 +3,7 @@
 
 use super::*;
 use crate::{
+    application::reputation::PeerInteraction,
     peer::DisconnectReason,
     peer_manager::{conn_notifs_channel, ConnectionRequest},
     transport::ConnectionMetadata,
@@ -23,6 +24,7 @@ const MAX_TEST_CONNECTIONS: usize = 3;
 const CONNECTIVITY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 const CONNECTION_DELAY: Duration = Duration::from_millis(100);
 const MAX_CONNECTION_DELAY: Duration = Duration::from_secs(60);
+const PEER_BAN_DURATION: Duration = Duration::from_secs(10);
 const DEFAULT_BASE_ADDR: &str = "/ip4/127.0.0.1/tcp/9090";
 
 // TODO(philiphayes): just use `CONNECTION_DELAY + MAX_CONNNECTION_DELAY_JITTER`
@@ -70,6 +72,7 @@ fn update_peer_with_address(mut peer: Peer, addr_str: &'static str) -> (Peer, Ne
 
 struct TestHarness {
     trusted_peers: Arc<RwLock<PeerSet>>,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
     mock_time: MockTimeService,
     connection_reqs_rx: aptos_channel::Receiver<PeerId, ConnectionRequest>,
     connection_notifs_tx: conn_notifs_channel::Sender,
@@ -85,6 +88,7 @@ impl TestHarness {
         let (connection_notifs_tx, connection_notifs_rx) = conn_notifs_channel::new();
         let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) = channel::new_test(0);
         let trusted_peers = Arc::new(RwLock::new(HashMap::new()));
+        let peer_metadata_storage = PeerMetadataStorage::new(&[network_context.network_id()]);
 
         let conn_mgr = ConnectivityManager::new(
             network_context,
@@ -99,9 +103,12 @@ impl TestHarness {
             MAX_CONNECTION_DELAY,
             Some(MAX_TEST_CONNECTIONS),
             true, /* mutual_authentication */
+            peer_metadata_storage.clone(),
+            Some(PEER_BAN_DURATION),
         );
         let mock = Self {
             trusted_peers,
+            peer_metadata_storage,
             mock_time: time_service.into_mock(),
             connection_reqs_rx,
             connection_notifs_tx,
@@ -462,6 +469,57 @@ fn disconnect() {
     block_on(future::join(conn_mgr.start(), test));
 }
 
+#[test]
+fn ban_peer_with_bad_reputation() {
+    let (other_peer_id, other_peer, _, other_addr) = test_peer(AccountAddress::ZERO);
+    let (mut mock, conn_mgr) = TestHarness::new(HashMap::new());
+
+    let test = async move {
+        // Sending pubkey & address of other peer
+        let peers = hashmap! {other_peer_id => other_peer};
+        mock.send_update_discovered_peers(DiscoverySource::OnChainValidatorSet, peers)
+            .await;
+
+        // Waiting to receive dial request
+        mock.trigger_connectivity_check().await;
+        mock.trigger_pending_dials().await;
+        mock.expect_one_dial_success(other_peer_id, other_addr.clone())
+            .await;
+
+        // Report enough malicious interactions to drop below the ban threshold
+        let peer_network_id =
+            PeerNetworkId::new(NetworkContext::mock().network_id(), other_peer_id);
+        while !mock
+            .peer_metadata_storage
+            .read_reputation(peer_network_id)
+            .is_below_ban_threshold()
+        {
+            mock.peer_metadata_storage
+                .report_peer_interaction(peer_network_id, PeerInteraction::Malicious);
+        }
+
+        // Peer is now banned, we should disconnect from them
+        mock.trigger_connectivity_check().await;
+        mock.expect_disconnect_success(other_peer_id, other_addr.clone())
+            .await;
+
+        // The peer should not be dialed while the ban is in place
+        mock.trigger_connectivity_check().await;
+        assert_eq!(0, mock.get_dial_queue_size().await);
+
+        // Once the ban expires, the peer is dialed again with a fresh reputation
+        mock.trigger_connectivity_check().await;
+        mock.trigger_pending_dials().await;
+        mock.expect_one_dial_success(other_peer_id, other_addr)
+            .await;
+        assert!(!mock
+            .peer_metadata_storage
+            .read_reputation(peer_network_id)
+            .is_below_ban_threshold());
+    };
+    block_on(future::join(conn_mgr.start(), test));
+}
+
 // Tests that connectivity manager retries dials and disconnects on failure.
 #[test]
 fn retry_on_failure() {
@@ -85,6 +85,23 @@ pub fn peer_connected(network_context: &NetworkContext, remote_peer_id: &PeerId,
     }
 }
 
+pub static APTOS_NETWORK_PEERS_BANNED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peers_banned",
+        "Number of peers banned due to a low reputation",
+        &["role_type", "network_id", "peer_id"]
+    )
+    .unwrap()
+});
+
+pub fn peers_banned(network_context: &NetworkContext) -> IntCounter {
+    APTOS_NETWORK_PEERS_BANNED.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+    ])
+}
+
 /// Increments the counter based on `NetworkContext`
 pub fn inc_by_with_context(
     counter: &IntCounterVec,
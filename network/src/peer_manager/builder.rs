@@ -158,10 +158,9 @@ pub struct PeerManagerBuilder {
     time_service: TimeService,
     transport_context: Option<TransportContext>,
     peer_manager_context: Option<PeerManagerContext>,
-    // TODO(philiphayes): better support multiple listening addrs
     peer_manager: Option<TransportPeerManager>,
-    // ListenAddress will be updated when the PeerManager is built
-    listen_address: NetworkAddress,
+    // ListenAddresses will be updated when the PeerManager is built
+    listen_addresses: Vec<NetworkAddress>,
 }
 
 impl PeerManagerBuilder {
@@ -170,8 +169,7 @@ impl PeerManagerBuilder {
         chain_id: ChainId,
         network_context: NetworkContext,
         time_service: TimeService,
-        // TODO(philiphayes): better support multiple listening addrs
-        listen_address: NetworkAddress,
+        listen_addresses: Vec<NetworkAddress>,
         peer_metadata_storage: Arc<PeerMetadataStorage>,
         trusted_peers: Arc<RwLock<PeerSet>>,
         authentication_mode: AuthenticationMode,
@@ -220,12 +218,20 @@ impl PeerManagerBuilder {
                 outbound_rate_limit_config,
             )),
             peer_manager: None,
-            listen_address,
+            listen_addresses,
         }
     }
 
+    /// Returns the primary (i.e., first) listen address
     pub fn listen_address(&self) -> NetworkAddress {
-        self.listen_address.clone()
+        self.listen_addresses
+            .first()
+            .cloned()
+            .expect("At least one listen address is required")
+    }
+
+    pub fn listen_addresses(&self) -> Vec<NetworkAddress> {
+        self.listen_addresses.clone()
     }
 
     pub fn connection_reqs_tx(&self) -> aptos_channel::Sender<PeerId, ConnectionRequest> {
@@ -273,8 +279,12 @@ impl PeerManagerBuilder {
             ),
         };
 
-        self.peer_manager = match self.listen_address.as_slice() {
-            [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)] => {
+        // All listeners share a single transport, so they must all be of the same kind
+        let is_tcp =
+            |addr: &NetworkAddress| matches!(addr.as_slice(), [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)]);
+        let listen_address = self.listen_address();
+        self.peer_manager = match listen_address.as_slice() {
+            [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)] if self.listen_addresses.iter().all(is_tcp) => {
                 Some(TransportPeerManager::Tcp(self.build_with_transport(
                     AptosNetTransport::new(
                         APTOS_TCP_TRANSPORT.clone(),
@@ -291,24 +301,31 @@ impl PeerManagerBuilder {
                 )))
             }
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
-            [Memory(_)] => Some(TransportPeerManager::Memory(self.build_with_transport(
-                AptosNetTransport::new(
-                    MemoryTransport,
-                    self.network_context,
-                    self.time_service.clone(),
-                    key,
-                    auth_mode,
-                    HANDSHAKE_VERSION,
-                    chain_id,
-                    protos,
-                    enable_proxy_protocol,
-                ),
-                executor,
-            ))),
+            [Memory(_)]
+                if self
+                    .listen_addresses
+                    .iter()
+                    .all(|addr| matches!(addr.as_slice(), [Memory(_)])) =>
+            {
+                Some(TransportPeerManager::Memory(self.build_with_transport(
+                    AptosNetTransport::new(
+                        MemoryTransport,
+                        self.network_context,
+                        self.time_service.clone(),
+                        key,
+                        auth_mode,
+                        HANDSHAKE_VERSION,
+                        chain_id,
+                        protos,
+                        enable_proxy_protocol,
+                    ),
+                    executor,
+                )))
+            }
             _ => panic!(
-                "{} Unsupported listen_address: '{}', expected '/memory/<port>', \
-                 '/ip4/<addr>/tcp/<port>', or '/ip6/<addr>/tcp/<port>'.",
-                self.network_context, self.listen_address
+                "{} Unsupported listen_addresses: '{:?}', expected '/memory/<port>', \
+                 '/ip4/<addr>/tcp/<port>', or '/ip6/<addr>/tcp/<port>' (all of the same kind).",
+                self.network_context, self.listen_addresses
             ),
         };
 
@@ -345,9 +362,7 @@ impl PeerManagerBuilder {
             self.time_service.clone(),
             transport,
             self.network_context,
-            // TODO(philiphayes): peer manager should take `Vec<NetworkAddress>`
-            // (which could be empty, like in client use case)
-            self.listen_address.clone(),
+            self.listen_addresses.clone(),
             pm_context.peer_metadata_storage,
             pm_context.trusted_peers,
             pm_context.pm_reqs_rx,
@@ -362,8 +377,8 @@ impl PeerManagerBuilder {
             outbound_rate_limiters,
         );
 
-        // PeerManager constructor appends a public key to the listen_address.
-        self.listen_address = peer_mgr.listen_addr().clone();
+        // PeerManager constructor appends a public key to the listen_addresses.
+        self.listen_addresses = peer_mgr.listen_addrs().to_vec();
 
         peer_mgr
     }
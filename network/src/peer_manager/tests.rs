@@ -31,7 +31,7 @@ use channel::{aptos_channel, message_queues::QueueStyle};
 use futures::{channel::oneshot, io::AsyncWriteExt, stream::StreamExt};
 use memsocket::MemorySocket;
 use netcore::transport::{
-    boxed::BoxedTransport, memory::MemoryTransport, ConnectionOrigin, TransportExt,
+    boxed::BoxedTransport, memory::MemoryTransport, ConnectionOrigin, Transport, TransportExt,
 };
 use std::{collections::HashMap, sync::Arc};
 use tokio::runtime::Handle;
@@ -89,6 +89,23 @@ fn build_test_peer_manager(
     aptos_channel::Sender<PeerId, ConnectionRequest>,
     aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerNotification>,
     conn_notifs_channel::Receiver,
+) {
+    build_test_peer_manager_with_listen_addrs(executor, peer_id, vec!["/memory/0".parse().unwrap()])
+}
+
+fn build_test_peer_manager_with_listen_addrs(
+    executor: Handle,
+    peer_id: PeerId,
+    listen_addrs: Vec<NetworkAddress>,
+) -> (
+    PeerManager<
+        BoxedTransport<Connection<MemorySocket>, impl std::error::Error + Sync + Send + 'static>,
+        MemorySocket,
+    >,
+    aptos_channel::Sender<(PeerId, ProtocolId), PeerManagerRequest>,
+    aptos_channel::Sender<PeerId, ConnectionRequest>,
+    aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerNotification>,
+    conn_notifs_channel::Receiver,
 ) {
     let (peer_manager_request_tx, peer_manager_request_rx) =
         aptos_channel::new(QueueStyle::FIFO, 1, None);
@@ -101,7 +118,7 @@ fn build_test_peer_manager(
         TimeService::mock(),
         build_test_transport(),
         NetworkContext::mock_with_peer_id(peer_id),
-        "/memory/0".parse().unwrap(),
+        listen_addrs,
         PeerMetadataStorage::test(),
         Arc::new(RwLock::new(HashMap::new())),
         peer_manager_request_rx,
@@ -638,3 +655,43 @@ fn test_dial_disconnect() {
 
     runtime.block_on(test);
 }
+
+#[test]
+fn test_multiple_listen_addresses() {
+    ::aptos_logger::Logger::init_for_testing();
+    let runtime = ::tokio::runtime::Runtime::new().unwrap();
+
+    // Create a peer manager that listens on two addresses
+    let listen_addrs: Vec<NetworkAddress> =
+        vec!["/memory/0".parse().unwrap(), "/memory/0".parse().unwrap()];
+    let (peer_manager, _request_tx, _connection_reqs_tx, _hello_rx, mut conn_status_rx) =
+        build_test_peer_manager_with_listen_addrs(
+            runtime.handle().clone(),
+            PeerId::random(),
+            listen_addrs,
+        );
+
+    // Verify a distinct listener was bound for each address
+    let listen_addrs = peer_manager.listen_addrs().to_vec();
+    assert_eq!(listen_addrs.len(), 2);
+    assert_ne!(listen_addrs[0], listen_addrs[1]);
+    runtime.spawn(peer_manager.start());
+
+    let test = async move {
+        // Verify inbound connections are accepted on every listener
+        let mut connections = vec![];
+        for listen_addr in listen_addrs {
+            let connection = MemoryTransport::default()
+                .dial(PeerId::random(), listen_addr)
+                .unwrap()
+                .await
+                .unwrap();
+            connections.push(connection);
+
+            let conn_notif = conn_status_rx.next().await.unwrap();
+            assert!(matches!(conn_notif, ConnectionNotification::NewPeer(_, _)));
+        }
+    };
+
+    runtime.block_on(test);
+}
@@ -17,7 +17,7 @@ use futures::{
     future::{BoxFuture, FutureExt},
     io::{AsyncRead, AsyncWrite},
     sink::SinkExt,
-    stream::{Fuse, FuturesUnordered, StreamExt},
+    stream::{FuturesUnordered, SelectAll, StreamExt},
 };
 use netcore::transport::{ConnectionOrigin, Transport};
 use short_hex_str::AsShortHexStr;
@@ -42,7 +42,8 @@ where
     time_service: TimeService,
     /// [`Transport`] that is used to establish connections
     transport: TTransport,
-    listener: Fuse<TTransport::Listener>,
+    /// One listener per listen address, polled together
+    listeners: SelectAll<TTransport::Listener>,
     transport_reqs_rx: channel::Receiver<TransportRequest>,
     transport_notifs_tx: channel::Sender<TransportNotification<TSocket>>,
 }
@@ -59,30 +60,37 @@ where
         network_context: NetworkContext,
         time_service: TimeService,
         transport: TTransport,
-        listen_addr: NetworkAddress,
+        listen_addrs: Vec<NetworkAddress>,
         transport_reqs_rx: channel::Receiver<TransportRequest>,
         transport_notifs_tx: channel::Sender<TransportNotification<TSocket>>,
-    ) -> (Self, NetworkAddress) {
-        let (listener, listen_addr) = transport
-            .listen_on(listen_addr)
-            .expect("Transport listen on fails");
-        debug!(
-            NetworkSchema::new(&network_context),
-            listen_address = listen_addr,
-            "{} listening on '{}'",
-            network_context,
-            listen_addr
-        );
+    ) -> (Self, Vec<NetworkAddress>) {
+        let mut listeners = SelectAll::new();
+        let mut actual_listen_addrs = Vec::with_capacity(listen_addrs.len());
+        for listen_addr in listen_addrs {
+            let (listener, listen_addr) = transport
+                .listen_on(listen_addr)
+                .expect("Transport listen on fails");
+            debug!(
+                NetworkSchema::new(&network_context),
+                listen_address = listen_addr,
+                "{} listening on '{}'",
+                network_context,
+                listen_addr
+            );
+            listeners.push(listener);
+            actual_listen_addrs.push(listen_addr);
+        }
+
         (
             Self {
                 network_context,
                 time_service,
                 transport,
-                listener: listener.fuse(),
+                listeners,
                 transport_reqs_rx,
                 transport_notifs_tx,
             },
-            listen_addr,
+            actual_listen_addrs,
         )
     }
 
@@ -102,7 +110,7 @@ where
                         pending_outbound_connections.push(fut);
                     }
                 },
-                inbound_connection = self.listener.select_next_some() => {
+                inbound_connection = self.listeners.select_next_some() => {
                     if let Some(fut) = self.upgrade_inbound_connection(inbound_connection) {
                         pending_inbound_connections.push(fut);
                     }
@@ -18,6 +18,7 @@ use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fmt,
     iter::{FromIterator, Iterator},
     ops::{BitAnd, BitOr},
@@ -171,6 +172,7 @@ impl ProtocolIdSet {
     pub fn iter(&self) -> impl Iterator<Item = ProtocolId> + '_ {
         self.0
             .iter_ones()
+            .filter_map(|idx| u8::try_from(idx).ok())
             .filter_map(|idx| bcs::from_bytes(&[idx]).ok())
     }
 
@@ -186,18 +188,18 @@ impl ProtocolIdSet {
 
     /// Returns if the protocol is set.
     pub fn contains(&self, protocol: ProtocolId) -> bool {
-        self.0.is_set(protocol as u8)
+        self.0.is_set(protocol as u16)
     }
 
     /// Insert a new protocol into the set.
     pub fn insert(&mut self, protocol: ProtocolId) {
-        self.0.set(protocol as u8)
+        self.0.set(protocol as u16)
     }
 }
 
 impl FromIterator<ProtocolId> for ProtocolIdSet {
     fn from_iter<T: IntoIterator<Item = ProtocolId>>(iter: T) -> Self {
-        Self(iter.into_iter().map(|protocol| protocol as u8).collect())
+        Self(iter.into_iter().map(|protocol| protocol as u16).collect())
     }
 }
 
@@ -148,7 +148,7 @@ fn ignore_unknown_protocols() {
     let all_known_hs = HandshakeMsg::from_supported(all_known_protos);
 
     let some_unknown_protos = ProtocolIdSet(bitvec::BitVec::from_iter([
-        ProtocolId::MempoolDirectSend as u8,
+        ProtocolId::MempoolDirectSend as u16,
         66,
         234,
     ]));
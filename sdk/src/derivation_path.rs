@@ -0,0 +1,200 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic wallets: BIP-39 mnemonic phrases, and the SLIP-0010 derivation of
+//! Ed25519 keys from the seed of a phrase.
+
+use crate::crypto::ed25519::Ed25519PrivateKey;
+use anyhow::{anyhow, bail, ensure, Result};
+use bip39::{Language, Mnemonic, Seed};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+/// The SLIP-0044 coin type of Aptos
+pub const APTOS_COIN_TYPE: u32 = 637;
+
+/// The path of the first account of a wallet, `m/44'/637'/0'/0'/0'`
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/637'/0'/0'/0'";
+
+const HARDENED_OFFSET: u32 = 1 << 31;
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// A SLIP-0010 derivation path such as `m/44'/637'/0'/0'/0'`. Ed25519 only supports hardened
+/// derivation, so every index of the path must be hardened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Derives the private key at this path from the BIP-39 `seed` of a mnemonic phrase
+    pub fn derive_private_key(&self, seed: &[u8]) -> Result<Ed25519PrivateKey> {
+        let mut node = hmac_sha512(ED25519_SEED_KEY, &[seed]);
+        for index in &self.0 {
+            let (key, chain_code) = node.split_at(32);
+            node = hmac_sha512(
+                chain_code,
+                &[&[0], key, &(index | HARDENED_OFFSET).to_be_bytes()],
+            );
+        }
+        Ed25519PrivateKey::try_from(&node[..32])
+            .map_err(|err| anyhow!("Invalid derived private key: {}", err))
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let mut segments = path.split('/');
+        ensure!(
+            segments.next() == Some("m"),
+            "Derivation path {} doesn't start with m",
+            path
+        );
+        segments
+            .map(|segment| {
+                let index = match segment
+                    .strip_suffix('\'')
+                    .or_else(|| segment.strip_suffix('H'))
+                {
+                    Some(index) => index,
+                    None => bail!(
+                        "Index {} of derivation path {} isn't hardened",
+                        segment,
+                        path
+                    ),
+                };
+                let index: u32 = index.parse().map_err(|_| {
+                    anyhow!("Invalid index {} in derivation path {}", segment, path)
+                })?;
+                ensure!(
+                    index < HARDENED_OFFSET,
+                    "Index {} of derivation path {} is too large",
+                    segment,
+                    path
+                );
+                Ok(index)
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generates an English mnemonic phrase of `word_count` words: 12, 15, 18, 21 or 24
+pub fn generate_mnemonic<R>(rng: &mut R, word_count: usize) -> Result<String>
+where
+    R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+{
+    ensure!(
+        [12, 15, 18, 21, 24].contains(&word_count),
+        "Mnemonic phrases have 12, 15, 18, 21 or 24 words, not {}",
+        word_count
+    );
+    // Each word encodes 11 bits, 32 bits of entropy for every 3 words with the checksum
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    rng.fill_bytes(&mut entropy);
+    Ok(Mnemonic::from_entropy(&entropy, Language::English)?
+        .phrase()
+        .to_owned())
+}
+
+/// Returns the BIP-39 seed of an English mnemonic phrase, without passphrase
+pub fn mnemonic_to_seed(mnemonic_phrase: &str) -> Result<Vec<u8>> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic_phrase.trim(), Language::English)?;
+    Ok(Seed::new(&mnemonic, "").as_bytes().to_vec())
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any size");
+    for bytes in data {
+        mac.update(bytes);
+    }
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ValidCryptoMaterial;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // Test vector 1 for ed25519 of SLIP-0010
+    #[test]
+    fn test_slip_0010_derivation() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        for (path, private_key) in [
+            (
+                "m",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0'/1'",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            ),
+            (
+                "m/0'/1'/2'",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            ),
+            (
+                "m/0'/1'/2'/2'",
+                "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            ),
+            (
+                "m/0'/1'/2'/2'/1000000000'",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            ),
+        ] {
+            let key = DerivationPath::from_str(path)
+                .unwrap()
+                .derive_private_key(&seed)
+                .unwrap();
+            assert_eq!(hex::encode(key.to_bytes()), private_key, "path {}", path);
+        }
+    }
+
+    #[test]
+    fn test_parse_derivation_path() {
+        let path = DerivationPath::from_str(DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(path, DerivationPath(vec![44, APTOS_COIN_TYPE, 0, 0, 0]));
+        assert_eq!(path.to_string(), DEFAULT_DERIVATION_PATH);
+        assert_eq!(
+            DerivationPath::from_str("m/44H/637H").unwrap().0,
+            vec![44, 637]
+        );
+
+        for invalid_path in ["", "44'/637'", "m/44'/637", "m/44'/x'", "m/2147483648'"] {
+            assert!(DerivationPath::from_str(invalid_path).is_err());
+        }
+    }
+
+    #[test]
+    fn test_mnemonic() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        for word_count in [12, 24] {
+            let phrase = generate_mnemonic(&mut rng, word_count).unwrap();
+            assert_eq!(phrase.split_whitespace().count(), word_count);
+            assert_eq!(
+                mnemonic_to_seed(&phrase).unwrap(),
+                mnemonic_to_seed(&format!("{}\n", phrase)).unwrap()
+            );
+        }
+        assert!(generate_mnemonic(&mut rng, 13).is_err());
+        assert!(mnemonic_to_seed("abandon abandon abandon").is_err());
+    }
+}
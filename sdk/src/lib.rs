@@ -8,6 +8,8 @@
 //! This SDK provides all the necessary components for building on top of the Aptos Blockchain. Some of the important modules are:
 //!
 //! * `crypto` - Types used for signing and verifying
+//! * `derivation_path` - Mnemonic phrases and the derivation of account keys from them
+//! * `sequence_number_manager` - Allocates sequence numbers to concurrent senders of an account
 //! * `transaction_builder` - Includes helpers for constructing transactions
 //! * `types` - Includes types for Aptos on-chain data structures
 //!
@@ -21,6 +23,14 @@ pub mod crypto {
     pub use aptos_crypto::*;
 }
 
+pub mod derivation_path;
+
+pub mod rest_client {
+    pub use aptos_rest_client::*;
+}
+
+pub mod sequence_number_manager;
+
 pub mod transaction_builder;
 
 pub mod types;
@@ -0,0 +1,542 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allocation of the sequence numbers of one account to concurrent senders.
+//!
+//! [`SequenceNumberManager`] hands out sequence numbers to tasks submitting transactions of the
+//! same account at the same time, and tracks the transactions in flight until they're committed.
+//! Sequence numbers freed by transactions mempool rejected or expired are handed out again
+//! first, so that no gap blocks the transactions after them.
+
+use crate::{
+    crypto::HashValue,
+    move_types::vm_status::StatusCode,
+    rest_client::{Client, TransactionSubmissionStatus},
+    transaction_builder::TransactionBuilder,
+    types::{
+        account_address::AccountAddress, mempool_status::MempoolStatusCode,
+        transaction::SignedTransaction, AccountKey,
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use aptos_infallible::Mutex;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The default number of transactions of an account mempool accepts at the same time
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 100;
+
+const MAX_SUBMIT_ATTEMPTS: usize = 3;
+const RESYNC_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct SequenceNumberManager {
+    client: Client,
+    address: AccountAddress,
+    in_flight_permits: Arc<Semaphore>,
+    state: Mutex<SequenceNumbers>,
+}
+
+impl SequenceNumberManager {
+    /// Creates the manager of `address`, starting from its sequence number on chain. At most
+    /// `max_in_flight` transactions are allocated a sequence number before they're committed.
+    pub async fn new(
+        client: Client,
+        address: AccountAddress,
+        max_in_flight: usize,
+    ) -> Result<Self> {
+        let account = client.get_account(address).await?.into_inner();
+        Ok(Self {
+            client,
+            address,
+            in_flight_permits: Arc::new(Semaphore::new(max_in_flight)),
+            state: Mutex::new(SequenceNumbers::new(account.sequence_number)),
+        })
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    /// The number of transactions allocated a sequence number and not known to be committed
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().in_flight.len()
+    }
+
+    /// The hashes of the submitted transactions not known to be committed, by sequence number
+    pub fn in_flight_transactions(&self) -> Vec<(u64, HashValue)> {
+        self.state
+            .lock()
+            .in_flight
+            .iter()
+            .filter_map(|(sequence_number, in_flight)| {
+                in_flight.hash.map(|hash| (*sequence_number, hash))
+            })
+            .collect()
+    }
+
+    /// Signs the transaction of `builder` with the next sequence number and submits it. The
+    /// transaction is built again with a new sequence number when mempool rejects the one it had
+    /// as stale, which the status of the batch endpoint tells apart from other rejections.
+    ///
+    /// The sequence number is only released when mempool rejects the transaction. When the
+    /// submission fails otherwise, the transaction may still have reached mempool, so it stays
+    /// in flight until [`Self::sync`] finds it committed, superseded or expired.
+    pub async fn submit(
+        &self,
+        key: &AccountKey,
+        builder: TransactionBuilder,
+    ) -> Result<SignedTransaction> {
+        let mut attempt = 1;
+        loop {
+            let sequence_number = self.allocate().await?;
+            let txn = builder
+                .clone()
+                .sender(self.address)
+                .sequence_number(sequence_number)
+                .build()
+                .sign(key.private_key(), key.public_key().clone())
+                .expect("Signing a txn can't fail")
+                .into_inner();
+
+            self.state.lock().submitted(
+                sequence_number,
+                txn.clone().committed_hash(),
+                txn.expiration_timestamp_secs(),
+            );
+            let status = self
+                .client
+                .submit_batch(std::slice::from_ref(&txn))
+                .await?
+                .into_inner()
+                .pop()
+                .ok_or_else(|| anyhow!("No submission status for transaction"))?;
+            if status.is_accepted() {
+                return Ok(txn);
+            }
+            self.state.lock().release(sequence_number);
+            if !has_stale_sequence_number(&status) || attempt == MAX_SUBMIT_ATTEMPTS {
+                bail!(
+                    "Transaction {} was rejected: {}{}",
+                    status.hash,
+                    status.mempool_status,
+                    status
+                        .vm_status
+                        .map(|vm_status| format!(" ({})", vm_status))
+                        .unwrap_or_default()
+                );
+            }
+            self.sync().await?;
+            attempt += 1;
+        }
+    }
+
+    /// Updates the transactions in flight from the account on chain: the committed or superseded
+    /// ones are done, and the expired ones free their sequence numbers. Returns the sequence number on
+    /// chain, which never goes back even if the node read from lags behind a previous one.
+    pub async fn sync(&self) -> Result<u64> {
+        let (account, state) = self.client.get_account(self.address).await?.into_parts();
+        Ok(self
+            .state
+            .lock()
+            .sync(account.sequence_number, state.timestamp_usecs / 1_000_000))
+    }
+
+    /// Allocates the next sequence number, waiting for transactions in flight to be committed
+    /// if there are already too many of them
+    async fn allocate(&self) -> Result<u64> {
+        loop {
+            if let Ok(permit) = self.in_flight_permits.clone().try_acquire_owned() {
+                return Ok(self.state.lock().allocate(permit));
+            }
+            // Too many transactions in flight, wait for some of them to be committed
+            self.sync().await?;
+            if self.in_flight_permits.available_permits() == 0 {
+                tokio::time::sleep(RESYNC_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// The sequence numbers in flight of an account, every one of them above its sequence number on
+/// chain
+#[derive(Debug)]
+struct SequenceNumbers {
+    /// The last known sequence number of the account on chain
+    on_chain: u64,
+    /// The sequence number after the highest one allocated
+    next: u64,
+    /// The sequence numbers between `on_chain` and `next` to allocate again
+    free: BTreeSet<u64>,
+    in_flight: BTreeMap<u64, InFlight>,
+}
+
+#[derive(Debug)]
+struct InFlight {
+    /// The hash of the transaction, once it's submitted
+    hash: Option<HashValue>,
+    expiration_timestamp_secs: u64,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl SequenceNumbers {
+    fn new(on_chain: u64) -> Self {
+        Self {
+            on_chain,
+            next: on_chain,
+            free: BTreeSet::new(),
+            in_flight: BTreeMap::new(),
+        }
+    }
+
+    fn allocate(&mut self, permit: OwnedSemaphorePermit) -> u64 {
+        let sequence_number = match self.free.iter().next().copied() {
+            Some(sequence_number) => {
+                self.free.remove(&sequence_number);
+                sequence_number
+            }
+            None => {
+                self.next += 1;
+                self.next - 1
+            }
+        };
+        self.in_flight.insert(
+            sequence_number,
+            InFlight {
+                hash: None,
+                // Not submitted yet, so it can't expire
+                expiration_timestamp_secs: u64::MAX,
+                _permit: permit,
+            },
+        );
+        sequence_number
+    }
+
+    fn submitted(&mut self, sequence_number: u64, hash: HashValue, expiration_timestamp_secs: u64) {
+        // The sequence number may have been committed on chain by another sender in between
+        if let Some(in_flight) = self.in_flight.get_mut(&sequence_number) {
+            in_flight.hash = Some(hash);
+            in_flight.expiration_timestamp_secs = expiration_timestamp_secs;
+        }
+    }
+
+    fn release(&mut self, sequence_number: u64) {
+        if self.in_flight.remove(&sequence_number).is_some() {
+            self.free.insert(sequence_number);
+            self.trim();
+        }
+    }
+
+    /// Syncs with the sequence number read on chain, unless it's older than the last known one,
+    /// and returns the latter
+    fn sync(&mut self, on_chain: u64, timestamp_secs: u64) -> u64 {
+        let on_chain = on_chain.max(self.on_chain);
+        self.on_chain = on_chain;
+        self.in_flight.retain(|sequence_number, in_flight| {
+            *sequence_number >= on_chain && in_flight.expiration_timestamp_secs > timestamp_secs
+        });
+        self.next = self.next.max(on_chain);
+        self.free = (on_chain..self.next)
+            .filter(|sequence_number| !self.in_flight.contains_key(sequence_number))
+            .collect();
+        self.trim();
+        on_chain
+    }
+
+    /// Gives back the free sequence numbers at the top of the allocated ones
+    fn trim(&mut self) {
+        while self.next > self.on_chain && self.free.remove(&(self.next - 1)) {
+            self.next -= 1;
+        }
+    }
+}
+
+/// Whether mempool rejected the transaction because its sequence number is already used, or too
+/// far ahead of the one of the account
+fn has_stale_sequence_number(status: &TransactionSubmissionStatus) -> bool {
+    if status.mempool_status == MempoolStatusCode::InvalidSeqNumber.to_string() {
+        return true;
+    }
+    // The VM status is the name of its status code
+    status.mempool_status == MempoolStatusCode::VmError.to_string()
+        && status.vm_status.as_ref().map_or(false, |vm_status| {
+            [
+                StatusCode::SEQUENCE_NUMBER_TOO_OLD,
+                StatusCode::SEQUENCE_NUMBER_TOO_NEW,
+            ]
+            .iter()
+            .any(|code| *vm_status == format!("{:?}", code))
+        })
+}
+
+impl std::fmt::Debug for SequenceNumberManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SequenceNumberManager")
+            .field("address", &self.address)
+            .field("state", &*self.state.lock())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rest_client::aptos_api_types::{
+            X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+        },
+        transaction_builder::TransactionFactory,
+        types::{chain_id::ChainId, mempool_status::MempoolStatus},
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use serde_json::json;
+    use url::Url;
+    use warp::{http::Response, hyper::body::Bytes, Filter};
+
+    /// A node serving the account and the batch submission endpoints. It rejects the
+    /// transactions below the sequence number of the account as too old, or every transaction
+    /// with `rejection` when set. Submissions fail without a status while `unavailable` is set.
+    #[derive(Default)]
+    struct MockNode {
+        on_chain: u64,
+        rejection: Option<MempoolStatusCode>,
+        unavailable: bool,
+        /// The sequence numbers of the transactions submitted, accepted or not
+        submitted: Vec<u64>,
+    }
+
+    impl MockNode {
+        fn submit(&mut self, txn: &SignedTransaction) -> TransactionSubmissionStatus {
+            self.submitted.push(txn.sequence_number());
+            let status = match self.rejection {
+                Some(code) => (MempoolStatus::new(code), None),
+                None if txn.sequence_number() < self.on_chain => (
+                    MempoolStatus::new(MempoolStatusCode::VmError),
+                    Some(StatusCode::SEQUENCE_NUMBER_TOO_OLD),
+                ),
+                None => (MempoolStatus::new(MempoolStatusCode::Accepted), None),
+            };
+            TransactionSubmissionStatus::new(txn.clone().committed_hash(), status)
+        }
+    }
+
+    fn serve(node: Arc<Mutex<MockNode>>) -> Url {
+        let account_node = node.clone();
+        let account =
+            warp::path!("accounts" / String)
+                .and(warp::get())
+                .map(move |_address: String| {
+                    json_response(&json!({
+                        "authentication_key": format!("0x{}", "00".repeat(32)),
+                        "sequence_number": account_node.lock().on_chain.to_string(),
+                    }))
+                });
+        let submit_batch = warp::path!("transactions" / "batch")
+            .and(warp::post())
+            .and(warp::body::bytes())
+            .map(move |body: Bytes| {
+                let txns: Vec<SignedTransaction> = bcs::from_bytes(&body).unwrap();
+                let mut node = node.lock();
+                if node.unavailable {
+                    return Response::builder()
+                        .status(warp::http::StatusCode::SERVICE_UNAVAILABLE)
+                        .body(Vec::new())
+                        .unwrap();
+                }
+                let statuses: Vec<_> = txns.iter().map(|txn| node.submit(txn)).collect();
+                json_response(&statuses)
+            });
+        let (address, future) =
+            warp::serve(account.or(submit_batch)).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(future);
+        Url::parse(&format!("http://localhost:{}/", address.port())).unwrap()
+    }
+
+    fn json_response<T: serde::Serialize>(value: &T) -> Response<Vec<u8>> {
+        Response::builder()
+            .header(X_APTOS_CHAIN_ID, ChainId::test().id().to_string())
+            .header(X_APTOS_EPOCH, "1")
+            .header(X_APTOS_LEDGER_VERSION, "1")
+            .header(X_APTOS_LEDGER_TIMESTAMP, "1000000")
+            .body(serde_json::to_vec(value).unwrap())
+            .unwrap()
+    }
+
+    async fn submit(manager: &SequenceNumberManager) -> Result<SignedTransaction> {
+        let key = AccountKey::generate(&mut StdRng::from_seed([0u8; 32]));
+        let builder = TransactionFactory::new(ChainId::test()).transfer(AccountAddress::ONE, 1);
+        manager.submit(&key, builder).await
+    }
+
+    #[tokio::test]
+    async fn test_submit_resyncs_stale_sequence_number() {
+        let node = Arc::new(Mutex::new(MockNode::default()));
+        let manager =
+            SequenceNumberManager::new(Client::new(serve(node.clone())), AccountAddress::ONE, 10)
+                .await
+                .unwrap();
+
+        // Another sender commits 5 transactions of the account
+        node.lock().on_chain = 5;
+        let txn = submit(&manager).await.unwrap();
+        assert_eq!(txn.sequence_number(), 5);
+        assert_eq!(node.lock().submitted, vec![0, 5]);
+        assert_eq!(
+            manager.in_flight_transactions(),
+            vec![(5, txn.committed_hash())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_gives_up_on_stale_sequence_numbers() {
+        let node = Arc::new(Mutex::new(MockNode {
+            rejection: Some(MempoolStatusCode::InvalidSeqNumber),
+            ..MockNode::default()
+        }));
+        let manager =
+            SequenceNumberManager::new(Client::new(serve(node.clone())), AccountAddress::ONE, 10)
+                .await
+                .unwrap();
+
+        assert!(submit(&manager).await.is_err());
+        assert_eq!(node.lock().submitted, vec![0; MAX_SUBMIT_ATTEMPTS]);
+        assert_eq!(manager.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_submit_does_not_retry_other_rejections() {
+        let node = Arc::new(Mutex::new(MockNode {
+            rejection: Some(MempoolStatusCode::MempoolIsFull),
+            ..MockNode::default()
+        }));
+        let manager =
+            SequenceNumberManager::new(Client::new(serve(node.clone())), AccountAddress::ONE, 10)
+                .await
+                .unwrap();
+
+        assert!(submit(&manager).await.is_err());
+        assert_eq!(node.lock().submitted, vec![0]);
+        assert_eq!(manager.in_flight(), 0);
+
+        // The sequence number of the rejected transaction is used again
+        node.lock().rejection = None;
+        assert_eq!(submit(&manager).await.unwrap().sequence_number(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_submit_keeps_ambiguous_failures_in_flight() {
+        let node = Arc::new(Mutex::new(MockNode {
+            unavailable: true,
+            ..MockNode::default()
+        }));
+        let manager =
+            SequenceNumberManager::new(Client::new(serve(node.clone())), AccountAddress::ONE, 10)
+                .await
+                .unwrap();
+
+        // The node may have accepted the transaction before failing, so its sequence number
+        // isn't used again
+        assert!(submit(&manager).await.is_err());
+        let in_flight = manager.in_flight_transactions();
+        assert_eq!(in_flight.len(), 1);
+        assert_eq!(in_flight[0].0, 0);
+        node.lock().unavailable = false;
+        assert_eq!(submit(&manager).await.unwrap().sequence_number(), 1);
+        assert_eq!(node.lock().submitted, vec![1]);
+
+        // Until the account moves past it on chain
+        node.lock().on_chain = 1;
+        assert_eq!(manager.sync().await.unwrap(), 1);
+        assert_eq!(
+            manager
+                .in_flight_transactions()
+                .into_iter()
+                .map(|(sequence_number, _)| sequence_number)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
+    fn allocate(sequence_numbers: &mut SequenceNumbers, semaphore: &Arc<Semaphore>) -> u64 {
+        sequence_numbers.allocate(semaphore.clone().try_acquire_owned().unwrap())
+    }
+
+    #[test]
+    fn test_allocate_and_release() {
+        let semaphore = Arc::new(Semaphore::new(10));
+        let mut sequence_numbers = SequenceNumbers::new(5);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 5);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 6);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 7);
+        assert_eq!(semaphore.available_permits(), 7);
+
+        // A released sequence number in the middle is allocated again before new ones
+        sequence_numbers.release(6);
+        assert_eq!(semaphore.available_permits(), 8);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 6);
+
+        // Releasing the last one gives it back
+        sequence_numbers.release(7);
+        assert_eq!(sequence_numbers.next, 7);
+        assert!(sequence_numbers.free.is_empty());
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 7);
+    }
+
+    #[test]
+    fn test_sync() {
+        let semaphore = Arc::new(Semaphore::new(10));
+        let mut sequence_numbers = SequenceNumbers::new(0);
+        for _ in 0..4 {
+            allocate(&mut sequence_numbers, &semaphore);
+        }
+        for sequence_number in 0..3 {
+            sequence_numbers.submitted(sequence_number, HashValue::zero(), 100);
+        }
+        sequence_numbers.submitted(3, HashValue::zero(), 200);
+
+        // 0 is committed, 1 and 2 are in flight
+        sequence_numbers.sync(1, 50);
+        assert_eq!(sequence_numbers.in_flight.len(), 3);
+        assert_eq!(sequence_numbers.in_flight[&1].hash, Some(HashValue::zero()));
+        assert_eq!(semaphore.available_permits(), 7);
+
+        // 1 and 2 expire, so they're allocated again before 4
+        sequence_numbers.sync(1, 100);
+        assert_eq!(sequence_numbers.in_flight.len(), 1);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 1);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 2);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 4);
+
+        // Another sender committed sequence numbers past the ones in flight
+        sequence_numbers.sync(10, 100);
+        assert!(sequence_numbers.in_flight.is_empty());
+        assert_eq!(semaphore.available_permits(), 10);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 10);
+
+        // A node lagging behind doesn't move the sequence number on chain back
+        assert_eq!(sequence_numbers.sync(8, 100), 10);
+        assert_eq!(sequence_numbers.on_chain, 10);
+        assert_eq!(sequence_numbers.in_flight.len(), 1);
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 11);
+    }
+
+    #[test]
+    fn test_sync_trims_expired_top() {
+        let semaphore = Arc::new(Semaphore::new(10));
+        let mut sequence_numbers = SequenceNumbers::new(0);
+        for _ in 0..3 {
+            allocate(&mut sequence_numbers, &semaphore);
+        }
+        sequence_numbers.submitted(0, HashValue::zero(), 200);
+        sequence_numbers.submitted(1, HashValue::zero(), 100);
+        sequence_numbers.submitted(2, HashValue::zero(), 100);
+
+        sequence_numbers.sync(0, 100);
+        assert_eq!(sequence_numbers.next, 1);
+        assert!(sequence_numbers.free.is_empty());
+        assert_eq!(allocate(&mut sequence_numbers, &semaphore), 1);
+    }
+}
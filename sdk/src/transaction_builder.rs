@@ -16,6 +16,7 @@ use aptos_types::transaction::{
     WriteSetPayload,
 };
 
+#[derive(Clone, Debug)]
 pub struct TransactionBuilder {
     sender: Option<AccountAddress>,
     sequence_number: Option<u64>,
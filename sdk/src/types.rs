@@ -6,12 +6,15 @@ use crate::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
         traits::Uniform,
     },
+    derivation_path::{mnemonic_to_seed, DerivationPath},
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
         transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
     },
 };
+use anyhow::Result;
+use std::str::FromStr;
 
 pub use aptos_types::*;
 
@@ -44,6 +47,19 @@ impl LocalAccount {
         Self::new(address, key, 0)
     }
 
+    /// Recovers the account at `derive_path` of the wallet of an English mnemonic phrase, e.g.
+    /// `m/44'/637'/0'/0'/0'` for its first account
+    pub fn from_derive_path(
+        derive_path: &str,
+        mnemonic_phrase: &str,
+        sequence_number: u64,
+    ) -> Result<Self> {
+        let key = AccountKey::from_derive_path(derive_path, mnemonic_phrase)?;
+        let address = key.authentication_key().derived_address();
+
+        Ok(Self::new(address, key, sequence_number))
+    }
+
     pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
         txn.sign(self.private_key(), self.public_key().clone())
             .expect("Signing a txn can't fail")
@@ -146,6 +162,12 @@ impl AccountKey {
         }
     }
 
+    pub fn from_derive_path(derive_path: &str, mnemonic_phrase: &str) -> Result<Self> {
+        let seed = mnemonic_to_seed(mnemonic_phrase)?;
+        let private_key = DerivationPath::from_str(derive_path)?.derive_private_key(&seed)?;
+        Ok(Self::from_private_key(private_key))
+    }
+
     pub fn private_key(&self) -> &Ed25519PrivateKey {
         &self.private_key
     }
@@ -164,3 +186,38 @@ impl From<Ed25519PrivateKey> for AccountKey {
         Self::from_private_key(private_key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::ValidCryptoMaterial, derivation_path::DEFAULT_DERIVATION_PATH};
+
+    // The test vector of the account derivation of the TypeScript SDK
+    #[test]
+    fn test_from_derive_path() {
+        let phrase =
+            "shoot island position soft burden budget tooth cruel issue economy destroy above";
+        let account = LocalAccount::from_derive_path(DEFAULT_DERIVATION_PATH, phrase, 3).unwrap();
+        assert_eq!(
+            hex::encode(account.private_key().to_bytes()),
+            "5d996aa76b3212142792d9130796cd2e11e3c445a93118c08414df4f66bc60ec"
+        );
+        assert_eq!(
+            hex::encode(account.public_key().to_bytes()),
+            "ea526ba1710343d953461ff68641f1b7df5f23b9042ffa2d2a798d3adb3f3d6c"
+        );
+        assert_eq!(
+            account.address(),
+            AccountAddress::from_hex_literal(
+                "0x7968dab936c1bad187c60ce4082f307d030d780e91e694ae03aef16aba73f30"
+            )
+            .unwrap()
+        );
+        assert_eq!(account.sequence_number(), 3);
+
+        assert!(LocalAccount::from_derive_path("m/44'/637'/0", phrase, 0).is_err());
+        assert!(
+            LocalAccount::from_derive_path(DEFAULT_DERIVATION_PATH, "shoot island", 0).is_err()
+        );
+    }
+}
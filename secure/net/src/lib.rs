@@ -14,7 +14,17 @@
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
 //! where a block is a length prefixed array of bytes.
-
+//!
+//! Optionally, the client and server can authenticate each other and encrypt all traffic. Each side
+//! is then configured with its own x25519 static key and pins the public key of the other side. On
+//! each new connection, the client initiates a Noise IK handshake, which the server only completes
+//! if the client's static key matches the pinned key. Afterwards, every message is encrypted with
+//! the resulting Noise session and split across as many Noise frames as necessary.
+
+use aptos_crypto::{
+    noise::{self, NoiseConfig, NoiseError, NoiseSession},
+    x25519,
+};
 use aptos_logger::{info, trace, warn, Schema};
 use aptos_secure_push_metrics::{register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
@@ -56,6 +66,8 @@ enum LogEvent {
     ConnectionSuccessful,
     ConnectionFailed,
     DisconnectedPeerOnRead,
+    HandshakeFailed,
+    HandshakeSuccessful,
     DisconnectedPeerOnWrite,
     Shutdown,
 }
@@ -89,6 +101,7 @@ static EVENT_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
 #[serde(rename_all = "snake_case")]
 enum Method {
     Connect,
+    Handshake,
     Read,
     Write,
 }
@@ -97,6 +110,7 @@ impl Method {
     fn as_str(&self) -> &'static str {
         match self {
             Method::Connect => "connect",
+            Method::Handshake => "handshake",
             Method::Read => "read",
             Method::Write => "write",
         }
@@ -142,10 +156,30 @@ pub enum Error {
     NetworkError(#[from] std::io::Error),
     #[error("No active stream")]
     NoActiveStream,
+    #[error("Noise error: {0}")]
+    NoiseError(#[from] NoiseError),
     #[error("Overflow error: {0}")]
     OverflowError(String),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("Remote peer is not authenticated, found static key: {0}")]
+    UnauthenticatedPeer(x25519::PublicKey),
+}
+
+/// The Noise IK keys used by either side of a connection: the local static key and the pinned
+/// static public key of the remote side.
+struct NoiseKeys {
+    config: NoiseConfig,
+    remote_public_key: x25519::PublicKey,
+}
+
+impl NoiseKeys {
+    fn new(private_key: x25519::PrivateKey, remote_public_key: x25519::PublicKey) -> Self {
+        Self {
+            config: NoiseConfig::new(private_key),
+            remote_public_key,
+        }
+    }
 }
 
 pub struct NetworkClient {
@@ -154,6 +188,7 @@ pub struct NetworkClient {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    noise_keys: Option<NoiseKeys>,
 }
 
 impl NetworkClient {
@@ -163,6 +198,23 @@ impl NetworkClient {
             server,
             stream: None,
             timeout_ms,
+            noise_keys: None,
+        }
+    }
+
+    /// Creates a client that authenticates and encrypts its connections with a Noise IK
+    /// handshake. The connection only succeeds if the server owns the private key corresponding
+    /// to `server_public_key`.
+    pub fn new_with_noise(
+        service: &'static str,
+        server: SocketAddr,
+        timeout_ms: u64,
+        private_key: x25519::PrivateKey,
+        server_public_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            noise_keys: Some(NoiseKeys::new(private_key, server_public_key)),
+            ..Self::new(service, server, timeout_ms)
         }
     }
 
@@ -257,7 +309,7 @@ impl NetworkClient {
 
             let stream = stream?;
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, self.server, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, self.server, self.timeout_ms);
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 self.service,
@@ -265,6 +317,30 @@ impl NetworkClient {
                 LogEvent::ConnectionSuccessful,
             )
             .remote_peer(&self.server));
+
+            if let Some(noise_keys) = &self.noise_keys {
+                self.increment_counter(Method::Handshake, MethodResult::Query);
+                if let Err(err) = stream.initiate_handshake(self.service, noise_keys) {
+                    self.increment_counter(Method::Handshake, MethodResult::Failure);
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Client,
+                        LogEvent::HandshakeFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&self.server));
+                    return Err(err);
+                }
+                self.increment_counter(Method::Handshake, MethodResult::Success);
+                info!(SecureNetLogSchema::new(
+                    self.service,
+                    NetworkMode::Client,
+                    LogEvent::HandshakeSuccessful,
+                )
+                .remote_peer(&self.server));
+            }
+
+            self.stream = Some(stream);
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
@@ -277,6 +353,7 @@ pub struct NetworkServer {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    noise_keys: Option<NoiseKeys>,
 }
 
 impl NetworkServer {
@@ -287,6 +364,23 @@ impl NetworkServer {
             listener: Some(listener.unwrap()),
             stream: None,
             timeout_ms,
+            noise_keys: None,
+        }
+    }
+
+    /// Creates a server that authenticates and encrypts its connections with a Noise IK
+    /// handshake. Clients that do not own the private key corresponding to `client_public_key`
+    /// are rejected.
+    pub fn new_with_noise(
+        service: &'static str,
+        listen: SocketAddr,
+        timeout_ms: u64,
+        private_key: x25519::PrivateKey,
+        client_public_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            noise_keys: Some(NoiseKeys::new(private_key, client_public_key)),
+            ..Self::new(service, listen, timeout_ms)
         }
     }
 
@@ -399,7 +493,33 @@ impl NetworkServer {
             .remote_peer(&stream_addr));
 
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, stream_addr, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, stream_addr, self.timeout_ms);
+
+            if let Some(noise_keys) = &self.noise_keys {
+                self.increment_counter(Method::Handshake, MethodResult::Query);
+                if let Err(err) = stream.respond_to_handshake(self.service, noise_keys) {
+                    self.increment_counter(Method::Handshake, MethodResult::Failure);
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Server,
+                        LogEvent::HandshakeFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&stream_addr));
+                    // Drop the connection so that the next call accepts a new client.
+                    let _ = stream.shutdown();
+                    return Err(err);
+                }
+                self.increment_counter(Method::Handshake, MethodResult::Success);
+                info!(SecureNetLogSchema::new(
+                    self.service,
+                    NetworkMode::Server,
+                    LogEvent::HandshakeSuccessful,
+                )
+                .remote_peer(&stream_addr));
+            }
+
+            self.stream = Some(stream);
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
@@ -411,6 +531,10 @@ struct NetworkStream {
     remote: SocketAddr,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
+    /// Once a Noise handshake completes, all messages are encrypted with this session.
+    session: Option<NoiseSession>,
+    /// Decrypted data that does not yet form a complete message.
+    plaintext_buffer: Vec<u8>,
 }
 
 impl NetworkStream {
@@ -425,12 +549,86 @@ impl NetworkStream {
             remote,
             buffer: Vec::new(),
             temp_buffer: [0; 1024],
+            session: None,
+            plaintext_buffer: Vec::new(),
         }
     }
 
-    /// Blocking read until able to successfully read an entire message
+    /// Performs the initiator side of a Noise IK handshake. This fails unless the remote side owns
+    /// the private key corresponding to the pinned public key.
+    fn initiate_handshake(
+        &mut self,
+        service: &'static str,
+        noise_keys: &NoiseKeys,
+    ) -> Result<(), Error> {
+        let mut rng = rand::rngs::OsRng;
+        let mut init_message = vec![0; noise::handshake_init_msg_len(0)];
+        let initiator_state = noise_keys.config.initiate_connection(
+            &mut rng,
+            service.as_bytes(),
+            noise_keys.remote_public_key,
+            None,
+            &mut init_message,
+        )?;
+        self.write_frame(&init_message)?;
+
+        let response = self.read_frame()?;
+        let (_, session) = noise_keys
+            .config
+            .finalize_connection(initiator_state, &response)?;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Performs the responder side of a Noise IK handshake. Initiators whose static key does not
+    /// match the pinned public key are rejected.
+    fn respond_to_handshake(
+        &mut self,
+        service: &'static str,
+        noise_keys: &NoiseKeys,
+    ) -> Result<(), Error> {
+        let init_message = self.read_frame()?;
+        let (remote_public_key, responder_state, _) = noise_keys
+            .config
+            .parse_client_init_message(service.as_bytes(), &init_message)?;
+        if remote_public_key != noise_keys.remote_public_key {
+            return Err(Error::UnauthenticatedPeer(remote_public_key));
+        }
+
+        let mut rng = rand::rngs::OsRng;
+        let mut response = vec![0; noise::handshake_resp_msg_len(0)];
+        let session =
+            noise_keys
+                .config
+                .respond_to_client(&mut rng, responder_state, None, &mut response)?;
+        self.write_frame(&response)?;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Blocking read until able to successfully read an entire message. If a Noise session has
+    /// been established, frames are decrypted until they contain an entire message.
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
-        let result = self.read_buffer();
+        if self.session.is_none() {
+            return self.read_frame();
+        }
+
+        loop {
+            let result = Self::read_buffer(&mut self.plaintext_buffer);
+            if !result.is_empty() {
+                return Ok(result);
+            }
+
+            let mut frame = self.read_frame()?;
+            let session = self.session.as_mut().ok_or(Error::NoActiveStream)?;
+            let plaintext = session.read_message_in_place(&mut frame)?;
+            self.plaintext_buffer.extend_from_slice(plaintext);
+        }
+    }
+
+    /// Blocking read until able to successfully read an entire frame from the socket
+    fn read_frame(&mut self) -> Result<Vec<u8>, Error> {
+        let result = Self::read_buffer(&mut self.buffer);
         if !result.is_empty() {
             return Ok(result);
         }
@@ -443,7 +641,7 @@ impl NetworkStream {
                 return Err(Error::RemoteStreamClosed);
             }
             self.buffer.extend(self.temp_buffer[..read].to_vec());
-            let result = self.read_buffer();
+            let result = Self::read_buffer(&mut self.buffer);
             if !result.is_empty() {
                 trace!("Found a message in the stream");
                 return Ok(result);
@@ -457,8 +655,40 @@ impl NetworkStream {
         Ok(self.stream.shutdown(Shutdown::Both)?)
     }
 
-    /// Blocking write until able to successfully send an entire message
+    /// Blocking write until able to successfully send an entire message. If a Noise session has
+    /// been established, the message is encrypted and split across as many frames as necessary.
     pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        if self.session.is_none() {
+            return self.write_frame(data);
+        }
+
+        let u32_max = u32::max_value() as usize;
+        if u32_max <= data.len() {
+            return Err(Error::DataTooLarge(data.len()));
+        }
+        let mut message = Vec::with_capacity(4 + data.len());
+        message.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        message.extend_from_slice(data);
+
+        let session = self.session.as_mut().ok_or(Error::NoActiveStream)?;
+        let frames = message
+            .chunks(noise::MAX_SIZE_NOISE_MSG - noise::AES_GCM_TAGLEN)
+            .map(|chunk| {
+                let mut frame = chunk.to_vec();
+                let auth_tag = session.write_message_in_place(&mut frame)?;
+                frame.extend_from_slice(&auth_tag);
+                Ok(frame)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for frame in frames {
+            self.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+
+    /// Blocking write until able to successfully send an entire frame to the socket
+    fn write_frame(&mut self, data: &[u8]) -> Result<(), Error> {
         let u32_max = u32::max_value() as usize;
         if u32_max <= data.len() {
             return Err(Error::DataTooLarge(data.len()));
@@ -478,22 +708,22 @@ impl NetworkStream {
     /// Data sent on a TCP socket may not necessarily be delivered at the exact time. So a read may
     /// only include a subset of what was sent. This wraps around the TCP read buffer to ensure
     /// that only full messages are received.
-    fn read_buffer(&mut self) -> Vec<u8> {
-        if self.buffer.len() < 4 {
+    fn read_buffer(buffer: &mut Vec<u8>) -> Vec<u8> {
+        if buffer.len() < 4 {
             return Vec::new();
         }
 
         let mut u32_bytes = [0; 4];
-        u32_bytes.copy_from_slice(&self.buffer[..4]);
+        u32_bytes.copy_from_slice(&buffer[..4]);
         let data_size = u32::from_le_bytes(u32_bytes) as usize;
 
-        let remaining_data = &self.buffer[4..];
+        let remaining_data = &buffer[4..];
         if remaining_data.len() < data_size {
             return Vec::new();
         }
 
         let returnable_data = remaining_data[..data_size].to_vec();
-        self.buffer = remaining_data[data_size..].to_vec();
+        *buffer = remaining_data[data_size..].to_vec();
         returnable_data
     }
 
@@ -518,11 +748,18 @@ impl NetworkStream {
 mod test {
     use super::*;
     use aptos_config::utils;
+    use aptos_crypto::Uniform;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
     /// Read, Write, Connect timeout in milliseconds.
     const TIMEOUT: u64 = 5_000;
 
+    fn noise_key_pair() -> (x25519::PrivateKey, x25519::PublicKey) {
+        let private_key = x25519::PrivateKey::generate(&mut rand::rngs::OsRng);
+        let public_key = private_key.public_key();
+        (private_key, public_key)
+    }
+
     #[test]
     fn test_ping() {
         let server_port = utils::get_available_port();
@@ -656,4 +893,73 @@ mod test {
         let result2 = server2.read().unwrap();
         assert_eq!(data2, result2);
     }
+
+    #[test]
+    fn test_noise_ping() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let (client_private_key, client_public_key) = noise_key_pair();
+        let (server_private_key, server_public_key) = noise_key_pair();
+        let mut server = NetworkServer::new_with_noise(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_private_key,
+            client_public_key,
+        );
+        let mut client = NetworkClient::new_with_noise(
+            "test",
+            server_addr,
+            TIMEOUT,
+            client_private_key,
+            server_public_key,
+        );
+
+        // The handshake requires both sides to make progress, so the server runs in its own
+        // thread and echoes back the first message it receives.
+        let server_thread = thread::spawn(move || {
+            let result = server.read().unwrap();
+            server.write(&result).unwrap();
+            result
+        });
+
+        // Larger than a single Noise frame
+        let data: Vec<u8> = (0..200_000).map(|i| i as u8).collect();
+        client.write(&data).unwrap();
+        let result = client.read().unwrap();
+        assert_eq!(data, result);
+        assert_eq!(data, server_thread.join().unwrap());
+    }
+
+    #[test]
+    fn test_noise_unauthenticated_client() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let (_, client_public_key) = noise_key_pair();
+        let (server_private_key, server_public_key) = noise_key_pair();
+        let mut server = NetworkServer::new_with_noise(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_private_key,
+            client_public_key,
+        );
+        let server_thread = thread::spawn(move || server.read());
+
+        // A client that knows the server's key, but whose own key is not pinned by the server
+        let (impostor_private_key, impostor_public_key) = noise_key_pair();
+        let mut impostor = NetworkClient::new_with_noise(
+            "test",
+            server_addr,
+            TIMEOUT,
+            impostor_private_key,
+            server_public_key,
+        );
+        impostor.write(&[0, 1, 2, 3]).unwrap_err();
+
+        match server_thread.join().unwrap() {
+            Err(Error::UnauthenticatedPeer(key)) => assert_eq!(key, impostor_public_key),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
 }
@@ -0,0 +1,320 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    from_base64, to_base64, CryptoKVStorage, Error, GetResponse, KVStorage, OnDiskStorage,
+};
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use aptos_temppath::TempPath;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::{rngs::OsRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const SALT_LENGTH: usize = 16;
+
+// The Argon2id parameters for newly created files, following the OWASP recommendations
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+// The bounds on the Argon2id parameters read from existing files, so a tampered file can neither
+// weaken the key derivation nor make it exhaust the memory or time of the node
+const ARGON2_MEMORY_KIB_RANGE: RangeInclusive<u32> = 8 * 1024..=1024 * 1024;
+const ARGON2_ITERATIONS_RANGE: RangeInclusive<u32> = 1..=16;
+const ARGON2_PARALLELISM_RANGE: RangeInclusive<u32> = 1..=16;
+
+/// Normalizes a passphrase, so the CLI and the node derive the same key from it regardless of
+/// where it is read from: the trailing line break added by most editors and `echo` is dropped.
+pub fn normalize_passphrase(passphrase: &str) -> &[u8] {
+    passphrase.trim_end_matches(&['\r', '\n'][..]).as_bytes()
+}
+
+/// EncryptedOnDiskStorage offers the same key value store as OnDiskStorage, but seals the file
+/// with AES-256-GCM. The encryption key is either derived from a passphrase via Argon2id, or read
+/// from a separate keyfile containing 32 hex encoded bytes. The key derivation parameters are kept
+/// in the clear within the file and authenticated as associated data. Like OnDiskStorage, it is
+/// intended for single threads (or must be wrapped by a Arc<RwLock<>>).
+///
+/// Each write re-encrypts the entire file under a fresh nonce and atomically replaces the file:
+/// the data is written and synced to a temporary file in the same directory, which is then
+/// renamed over the original.
+pub struct EncryptedOnDiskStorage {
+    file_path: PathBuf,
+    temp_path: TempPath,
+    time_service: TimeService,
+    cipher: Aes256Gcm,
+    key_derivation: KeyDerivation,
+}
+
+/// Describes how the encryption key of a sealed file is obtained.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+enum KeyDerivation {
+    Argon2id {
+        #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+        salt: Vec<u8>,
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+    Keyfile,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SealedFile {
+    key_derivation: KeyDerivation,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedOnDiskStorage {
+    /// Opens or creates a sealed file whose key is derived from the passphrase.
+    pub fn new_with_passphrase(file_path: PathBuf, passphrase: &[u8]) -> Result<Self, Error> {
+        let key_derivation = match Self::read_sealed_file(&file_path)? {
+            Some(sealed) => sealed.key_derivation,
+            None => {
+                let mut salt = vec![0; SALT_LENGTH];
+                OsRng.fill_bytes(&mut salt);
+                KeyDerivation::Argon2id {
+                    salt,
+                    memory_kib: ARGON2_MEMORY_KIB,
+                    iterations: ARGON2_ITERATIONS,
+                    parallelism: ARGON2_PARALLELISM,
+                }
+            }
+        };
+
+        let key = match &key_derivation {
+            KeyDerivation::Argon2id {
+                salt,
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                check_argon2_param("memory_kib", *memory_kib, ARGON2_MEMORY_KIB_RANGE)?;
+                check_argon2_param("iterations", *iterations, ARGON2_ITERATIONS_RANGE)?;
+                check_argon2_param("parallelism", *parallelism, ARGON2_PARALLELISM_RANGE)?;
+                let params = Params::new(*memory_kib, *iterations, *parallelism, Some(KEY_LENGTH))
+                    .map_err(|e| Error::InternalError(e.to_string()))?;
+                let mut key = [0; KEY_LENGTH];
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(passphrase, salt, &mut key)
+                    .map_err(|e| Error::InternalError(e.to_string()))?;
+                key
+            }
+            KeyDerivation::Keyfile => {
+                return Err(Error::InternalError(
+                    "Storage is sealed with a keyfile, not a passphrase".into(),
+                ))
+            }
+        };
+        Self::new_with_key(file_path, key, key_derivation)
+    }
+
+    /// Opens or creates a sealed file whose key is read from the keyfile.
+    pub fn new_with_keyfile(file_path: PathBuf, keyfile: &Path) -> Result<Self, Error> {
+        if let Some(sealed) = Self::read_sealed_file(&file_path)? {
+            if sealed.key_derivation != KeyDerivation::Keyfile {
+                return Err(Error::InternalError(
+                    "Storage is sealed with a passphrase, not a keyfile".into(),
+                ));
+            }
+        }
+
+        let contents = fs::read_to_string(keyfile)?;
+        let bytes = hex::decode(contents.trim())
+            .map_err(|e| Error::SerializationError(format!("Invalid keyfile: {}", e)))?;
+        if bytes.len() != KEY_LENGTH {
+            return Err(Error::SerializationError(format!(
+                "Invalid keyfile: expected {} bytes, found {}",
+                KEY_LENGTH,
+                bytes.len()
+            )));
+        }
+        let mut key = [0; KEY_LENGTH];
+        key.copy_from_slice(&bytes);
+        Self::new_with_key(file_path, key, KeyDerivation::Keyfile)
+    }
+
+    fn new_with_key(
+        file_path: PathBuf,
+        key: [u8; KEY_LENGTH],
+        key_derivation: KeyDerivation,
+    ) -> Result<Self, Error> {
+        // The parent will be one when only a filename is supplied. Therefore use the current
+        // working directory provided by PathBuf::new().
+        let file_dir = file_path
+            .parent()
+            .map_or(PathBuf::new(), |p| p.to_path_buf());
+
+        let storage = Self {
+            file_path,
+            temp_path: TempPath::new_with_temp_dir(file_dir),
+            time_service: TimeService::real(),
+            cipher: Aes256Gcm::new(Key::from_slice(&key)),
+            key_derivation,
+        };
+
+        // Seal new files right away, so the key derivation parameters are persisted. For existing
+        // files, this verifies the key.
+        let data = storage.read()?;
+        storage.write(&data)?;
+        Ok(storage)
+    }
+
+    /// Copies all data of a plaintext OnDiskStorage file into this storage. Fails without any
+    /// changes if a key exists in both.
+    pub fn migrate_from_plaintext(&mut self, plaintext_path: &Path) -> Result<(), Error> {
+        if !plaintext_path.exists() {
+            return Err(Error::InternalError(format!(
+                "Plaintext storage not found: {}",
+                plaintext_path.display()
+            )));
+        }
+
+        let plaintext = OnDiskStorage::new(plaintext_path.to_path_buf()).read()?;
+        let mut data = self.read()?;
+        if let Some(key) = plaintext.keys().find(|key| data.contains_key(*key)) {
+            return Err(Error::KeyAlreadyExists(key.clone()));
+        }
+        data.extend(plaintext);
+        self.write(&data)
+    }
+
+    fn read_sealed_file(file_path: &Path) -> Result<Option<SealedFile>, Error> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let mut file = File::open(file_path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        if contents.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    fn read(&self) -> Result<HashMap<String, Value>, Error> {
+        let sealed = match Self::read_sealed_file(&self.file_path)? {
+            Some(sealed) => sealed,
+            None => return Ok(HashMap::new()),
+        };
+        if sealed.key_derivation != self.key_derivation || sealed.nonce.len() != NONCE_LENGTH {
+            return Err(Error::InternalError(
+                "Storage was sealed with a different key".into(),
+            ));
+        }
+
+        let associated_data = serde_json::to_vec(&sealed.key_derivation)?;
+        let payload = Payload {
+            msg: &sealed.ciphertext,
+            aad: &associated_data,
+        };
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(&sealed.nonce), payload)
+            .map_err(|_| {
+                Error::InternalError(
+                    "Unable to decrypt storage, the passphrase or keyfile may be incorrect".into(),
+                )
+            })?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    fn write(&self, data: &HashMap<String, Value>) -> Result<(), Error> {
+        let plaintext = serde_json::to_vec(data)?;
+        let associated_data = serde_json::to_vec(&self.key_derivation)?;
+        let mut nonce = vec![0; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let payload = Payload {
+            msg: &plaintext,
+            aad: &associated_data,
+        };
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|e| Error::InternalError(format!("Unable to encrypt storage: {}", e)))?;
+
+        let contents = serde_json::to_vec(&SealedFile {
+            key_derivation: self.key_derivation.clone(),
+            nonce,
+            ciphertext,
+        })?;
+        let mut file = File::create(self.temp_path.path())?;
+        file.write_all(&contents)?;
+        file.sync_all()?;
+        fs::rename(&self.temp_path, &self.file_path)?;
+        sync_parent_dir(&self.file_path)
+    }
+}
+
+fn check_argon2_param(name: &str, value: u32, range: RangeInclusive<u32>) -> Result<(), Error> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(Error::InternalError(format!(
+            "Argon2id {} of {} is outside of {:?}",
+            name, value, range
+        )))
+    }
+}
+
+/// Persists the rename of a file by syncing its directory.
+#[cfg(unix)]
+fn sync_parent_dir(file_path: &Path) -> Result<(), Error> {
+    match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => Ok(File::open(parent)?.sync_all()?),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_file_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
+
+impl KVStorage for EncryptedOnDiskStorage {
+    fn available(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let mut data = self.read()?;
+        data.remove(key)
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))
+            .and_then(|value| serde_json::from_value(value).map_err(|e| e.into()))
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let mut data = self.read()?;
+        data.insert(
+            key.to_string(),
+            serde_json::to_value(&GetResponse::new(value, now))?,
+        );
+        self.write(&data)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.write(&HashMap::new())
+    }
+}
+
+impl CryptoKVStorage for EncryptedOnDiskStorage {}
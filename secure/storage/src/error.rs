@@ -63,6 +63,19 @@ impl From<aptos_vault_client::Error> for Error {
     }
 }
 
+impl From<cryptoki::error::Error> for Error {
+    fn from(error: cryptoki::error::Error) -> Self {
+        use cryptoki::error::{Error as Pkcs11Error, RvError};
+        match error {
+            Pkcs11Error::Pkcs11(RvError::PinIncorrect)
+            | Pkcs11Error::Pkcs11(RvError::PinLocked)
+            | Pkcs11Error::Pkcs11(RvError::UserNotLoggedIn)
+            | Pkcs11Error::Pkcs11(RvError::KeyUnextractable) => Self::PermissionDenied,
+            _ => Self::InternalError(format!("{}", error)),
+        }
+    }
+}
+
 impl From<aptos_github_client::Error> for Error {
     fn from(error: aptos_github_client::Error) -> Self {
         match error {
@@ -5,12 +5,14 @@
 
 mod crypto_kv_storage;
 mod crypto_storage;
+mod encrypted_on_disk;
 mod error;
 mod github;
 mod in_memory;
 mod kv_storage;
 mod namespaced;
 mod on_disk;
+mod pkcs11;
 mod policy;
 mod storage;
 mod vault;
@@ -18,12 +20,14 @@ mod vault;
 pub use crate::{
     crypto_kv_storage::CryptoKVStorage,
     crypto_storage::{CryptoStorage, PublicKeyResponse},
+    encrypted_on_disk::{normalize_passphrase, EncryptedOnDiskStorage},
     error::Error,
     github::GitHubStorage,
     in_memory::InMemoryStorage,
     kv_storage::{GetResponse, KVStorage},
     namespaced::Namespaced,
     on_disk::OnDiskStorage,
+    pkcs11::Pkcs11Storage,
     policy::{Capability, Identity, Permission, Policy},
     storage::Storage,
     vault::VaultStorage,
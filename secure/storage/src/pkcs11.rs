@@ -0,0 +1,465 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error, GetResponse, KVStorage, PublicKeyResponse};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH},
+    hash::CryptoHash,
+    PrivateKey,
+};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    error::{Error as Pkcs11Error, RvError},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, SessionFlags, UserType},
+    slot::Slot,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    convert::{TryFrom, TryInto},
+    path::Path,
+};
+
+/// The DER encoding of the Ed25519 curve OID (1.3.101.112), used as the EC params of Ed25519 keys.
+const ED25519_EC_PARAMS: [u8; 5] = [0x06, 0x03, 0x2b, 0x65, 0x70];
+
+/// The DER tag of an octet string, which wraps the EC points of Ed25519 public keys.
+const DER_OCTET_STRING_TAG: u8 = 0x04;
+
+/// The application attribute of all key/value data objects created by this storage.
+const KV_APPLICATION: &[u8] = b"aptos-secure-storage";
+
+/// Pkcs11Storage stores keys and data in a hardware security module (HSM), or any other token
+/// accessed through the PKCS#11 interface (e.g., SoftHSM for local testing).
+///
+/// Ed25519 keys are generated within the token and are marked as sensitive and non-extractable,
+/// so the private keys never leave the token: all exports fail with a permission error. Each key
+/// version is a key pair labeled with the key name and identified by its version and creation
+/// time (stored in the PKCS#11 ID attribute). Rotations retain the current and previous versions.
+///
+/// Key/value pairs are stored as PKCS#11 data objects labeled with the key name, where the value
+/// holds the serialized GetResponse.
+pub struct Pkcs11Storage {
+    pkcs11: Pkcs11,
+    slot: Slot,
+    pin: String,
+    time_service: TimeService,
+}
+
+impl Pkcs11Storage {
+    /// Loads the PKCS#11 module at `library_path` and uses the token labeled `token_label`,
+    /// logging in as the normal user with `pin`.
+    pub fn new(library_path: &Path, token_label: &str, pin: String) -> Result<Self, Error> {
+        let pkcs11 = Pkcs11::new(library_path)?;
+        match pkcs11.initialize(CInitializeArgs::OsThreads) {
+            // The module is shared by all storages within this process
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::CryptokiAlreadyInitialized)) => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut slot = None;
+        for candidate in pkcs11.get_slots_with_token()? {
+            if pkcs11.get_token_info(candidate)?.label() == token_label {
+                slot = Some(candidate);
+                break;
+            }
+        }
+        let slot = slot.ok_or_else(|| {
+            Error::InternalError(format!("PKCS#11 token not found: {}", token_label))
+        })?;
+
+        Ok(Self {
+            pkcs11,
+            slot,
+            pin,
+            time_service: TimeService::real(),
+        })
+    }
+
+    /// Opens a new read-write session on the token. Login state is shared by all sessions of an
+    /// application, hence subsequent logins may report that the user is already logged in.
+    fn session(&self) -> Result<Session, Error> {
+        let mut flags = SessionFlags::new();
+        flags.set_rw_session(true).set_serial_session(true);
+        let session = self.pkcs11.open_session_no_callback(self.slot, flags)?;
+        match session.login(UserType::User, Some(&self.pin)) {
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::UserAlreadyLoggedIn)) => Ok(session),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn find_data(&self, session: &Session, key: &str) -> Result<Vec<ObjectHandle>, Error> {
+        Ok(session.find_objects(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Token(true),
+            Attribute::Application(KV_APPLICATION.to_vec()),
+            Attribute::Label(key.as_bytes().to_vec()),
+        ])?)
+    }
+
+    /// Returns all versions of the named key, ordered from oldest to newest.
+    fn key_versions(
+        &self,
+        session: &Session,
+        name: &str,
+        class: ObjectClass,
+    ) -> Result<Vec<(KeyVersion, ObjectHandle)>, Error> {
+        let handles = session.find_objects(&[
+            Attribute::Class(class),
+            Attribute::Token(true),
+            Attribute::Label(name.as_bytes().to_vec()),
+        ])?;
+
+        let mut versions = Vec::new();
+        for handle in handles {
+            for attribute in session.get_attributes(handle, &[AttributeType::Id])? {
+                if let Attribute::Id(id) = attribute {
+                    versions.push((KeyVersion::try_from(id.as_slice())?, handle));
+                }
+            }
+        }
+        versions.sort_by_key(|(version, _)| version.version);
+        Ok(versions)
+    }
+
+    fn public_key(
+        &self,
+        session: &Session,
+        handle: ObjectHandle,
+    ) -> Result<Ed25519PublicKey, Error> {
+        let attributes = session.get_attributes(handle, &[AttributeType::EcPoint])?;
+        let ec_point = attributes
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::EcPoint(ec_point) => Some(ec_point),
+                _ => None,
+            })
+            .ok_or_else(|| Error::InternalError("Public key without EC point".into()))?;
+
+        // Tokens may return the point either raw or wrapped within a DER octet string
+        let bytes = match ec_point.as_slice() {
+            [DER_OCTET_STRING_TAG, length, bytes @ ..]
+                if *length as usize == ED25519_PUBLIC_KEY_LENGTH =>
+            {
+                bytes
+            }
+            bytes => bytes,
+        };
+        Ed25519PublicKey::try_from(bytes).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Returns the private key handle of the named key, either the latest version or the version
+    /// matching the provided public key.
+    fn private_key_handle(
+        &self,
+        session: &Session,
+        name: &str,
+        version: Option<&Ed25519PublicKey>,
+    ) -> Result<ObjectHandle, Error> {
+        let key_version = match version {
+            None => {
+                self.key_versions(session, name, ObjectClass::PUBLIC_KEY)?
+                    .pop()
+                    .ok_or_else(|| Error::KeyNotSet(name.into()))?
+                    .0
+                    .version
+            }
+            Some(version) => {
+                let mut key_version = None;
+                for (candidate, handle) in
+                    self.key_versions(session, name, ObjectClass::PUBLIC_KEY)?
+                {
+                    if &self.public_key(session, handle)? == version {
+                        key_version = Some(candidate.version);
+                    }
+                }
+                key_version
+                    .ok_or_else(|| Error::KeyVersionNotFound(name.into(), version.to_string()))?
+            }
+        };
+
+        self.key_versions(session, name, ObjectClass::PRIVATE_KEY)?
+            .into_iter()
+            .find(|(candidate, _)| candidate.version == key_version)
+            .map(|(_, handle)| handle)
+            .ok_or_else(|| Error::KeyVersionNotFound(name.into(), key_version.to_string()))
+    }
+
+    /// Generates a new key pair within the token for the given version of the named key.
+    fn generate_key(
+        &self,
+        session: &Session,
+        name: &str,
+        version: u32,
+    ) -> Result<Ed25519PublicKey, Error> {
+        let id = KeyVersion::new(version, self.time_service.now_secs()).to_bytes();
+        let label = name.as_bytes().to_vec();
+
+        let public_template = [
+            Attribute::Token(true),
+            Attribute::Verify(true),
+            Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+            Attribute::Label(label.clone()),
+            Attribute::Id(id.clone()),
+        ];
+        let private_template = [
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Label(label),
+            Attribute::Id(id),
+        ];
+        let (public_handle, _) = session.generate_key_pair(
+            &Mechanism::EccEdwardsKeyPairGen,
+            &public_template,
+            &private_template,
+        )?;
+        self.public_key(session, public_handle)
+    }
+
+    fn sign_bytes(
+        &self,
+        name: &str,
+        version: Option<&Ed25519PublicKey>,
+        bytes: &[u8],
+    ) -> Result<Ed25519Signature, Error> {
+        let session = self.session()?;
+        let handle = self.private_key_handle(&session, name, version)?;
+        let signature = session.sign(&Mechanism::Eddsa, handle, bytes)?;
+        Ed25519Signature::try_from(signature.as_slice())
+            .map_err(|e| Error::InternalError(e.to_string()))
+    }
+
+    fn signing_bytes<T: CryptoHash + Serialize>(message: &T) -> Result<Vec<u8>, Error> {
+        let mut bytes = <T::Hasher as aptos_crypto::hash::CryptoHasher>::seed().to_vec();
+        bcs::serialize_into(&mut bytes, &message).map_err(|e| {
+            Error::InternalError(format!(
+                "Serialization of signable material should not fail, yet returned Error:{}",
+                e
+            ))
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl KVStorage for Pkcs11Storage {
+    fn available(&self) -> Result<(), Error> {
+        self.session().map(|_| ())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let session = self.session()?;
+        let handle = *self
+            .find_data(&session, key)?
+            .first()
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))?;
+
+        let value = session
+            .get_attributes(handle, &[AttributeType::Value])?
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Value(value) => Some(value),
+                _ => None,
+            })
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))?;
+        serde_json::from_slice(&value).map_err(|e| e.into())
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let value = serde_json::to_vec(&GetResponse::new(value, now))?;
+
+        // Create the new object before destroying the old one, so a failure in between never
+        // loses the value.
+        let session = self.session()?;
+        let previous = self.find_data(&session, key)?;
+        session.create_object(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Application(KV_APPLICATION.to_vec()),
+            Attribute::Label(key.as_bytes().to_vec()),
+            Attribute::Value(value),
+        ])?;
+        for handle in previous {
+            session.destroy_object(handle)?;
+        }
+        Ok(())
+    }
+
+    /// Destroys all objects on the token, hence tests must use a dedicated token.
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        let session = self.session()?;
+        for handle in session.find_objects(&[Attribute::Token(true)])? {
+            session.destroy_object(handle)?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoStorage for Pkcs11Storage {
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let session = self.session()?;
+        if !self
+            .key_versions(&session, name, ObjectClass::PUBLIC_KEY)?
+            .is_empty()
+        {
+            return Err(Error::KeyAlreadyExists(name.into()));
+        }
+        self.generate_key(&session, name, 0)
+    }
+
+    fn export_private_key(&self, _name: &str) -> Result<Ed25519PrivateKey, Error> {
+        Err(Error::PermissionDenied)
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        _name: &str,
+        _version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, Error> {
+        Err(Error::PermissionDenied)
+    }
+
+    /// Imported keys are stored as sensitive and non-extractable, just like generated keys.
+    fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
+        let session = self.session()?;
+        if !self
+            .key_versions(&session, name, ObjectClass::PUBLIC_KEY)?
+            .is_empty()
+        {
+            return Err(Error::KeyAlreadyExists(name.into()));
+        }
+
+        let id = KeyVersion::new(0, self.time_service.now_secs()).to_bytes();
+        let label = name.as_bytes().to_vec();
+        let mut ec_point = vec![DER_OCTET_STRING_TAG, ED25519_PUBLIC_KEY_LENGTH as u8];
+        ec_point.extend_from_slice(&key.public_key().to_bytes());
+
+        session.create_object(&[
+            Attribute::Class(ObjectClass::PUBLIC_KEY),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Token(true),
+            Attribute::Verify(true),
+            Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+            Attribute::EcPoint(ec_point),
+            Attribute::Label(label.clone()),
+            Attribute::Id(id.clone()),
+        ])?;
+        session.create_object(&[
+            Attribute::Class(ObjectClass::PRIVATE_KEY),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+            Attribute::Value(key.to_bytes().to_vec()),
+            Attribute::Label(label),
+            Attribute::Id(id),
+        ])?;
+        Ok(())
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
+        let session = self.session()?;
+        let (version, handle) = self
+            .key_versions(&session, name, ObjectClass::PUBLIC_KEY)?
+            .pop()
+            .ok_or_else(|| Error::KeyNotSet(name.into()))?;
+        Ok(PublicKeyResponse {
+            last_update: version.created_secs,
+            public_key: self.public_key(&session, handle)?,
+        })
+    }
+
+    fn get_public_key_previous_version(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let session = self.session()?;
+        let mut versions = self.key_versions(&session, name, ObjectClass::PUBLIC_KEY)?;
+        versions.pop();
+        let (_, handle) = versions
+            .pop()
+            .ok_or_else(|| Error::KeyVersionNotFound(name.into(), "previous version".into()))?;
+        self.public_key(&session, handle)
+    }
+
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let session = self.session()?;
+        let (latest, _) = self
+            .key_versions(&session, name, ObjectClass::PUBLIC_KEY)?
+            .pop()
+            .ok_or_else(|| Error::KeyNotSet(name.into()))?;
+        let public_key = self.generate_key(&session, name, latest.version + 1)?;
+
+        // Only retain the new and the previous versions
+        for class in [ObjectClass::PUBLIC_KEY, ObjectClass::PRIVATE_KEY] {
+            for (version, handle) in self.key_versions(&session, name, class)? {
+                if version.version < latest.version {
+                    session.destroy_object(handle)?;
+                }
+            }
+        }
+        Ok(public_key)
+    }
+
+    fn sign<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        self.sign_bytes(name, None, &Self::signing_bytes(message)?)
+    }
+
+    fn sign_using_version<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        self.sign_bytes(name, Some(&version), &Self::signing_bytes(message)?)
+    }
+}
+
+/// The version and creation time of a key, stored in the PKCS#11 ID attribute of both halves of
+/// the key pair.
+struct KeyVersion {
+    version: u32,
+    created_secs: u64,
+}
+
+impl KeyVersion {
+    fn new(version: u32, created_secs: u64) -> Self {
+        Self {
+            version,
+            created_secs,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.version.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.created_secs.to_be_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for KeyVersion {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != 12 {
+            return Err(Error::SerializationError(format!(
+                "Unexpected PKCS#11 key id length: {}",
+                bytes.len()
+            )));
+        }
+        let version = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        let created_secs = u64::from_be_bytes(bytes[4..].try_into().unwrap());
+        Ok(Self::new(version, created_secs))
+    }
+}
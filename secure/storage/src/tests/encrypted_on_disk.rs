@@ -0,0 +1,100 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, EncryptedOnDiskStorage, KVStorage, OnDiskStorage, Storage};
+use aptos_temppath::TempPath;
+use std::fs;
+
+const PASSPHRASE: &[u8] = b"correct horse battery staple";
+const SECRET: &str = "a very secret value";
+
+#[test]
+fn encrypted_on_disk_with_passphrase() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let storage = EncryptedOnDiskStorage::new_with_passphrase(path_buf, PASSPHRASE).unwrap();
+    suite::execute_all_storage_tests(&mut Storage::from(storage));
+}
+
+#[test]
+fn encrypted_on_disk_with_keyfile() {
+    let keyfile = TempPath::new();
+    fs::write(keyfile.path(), hex::encode([7u8; 32])).unwrap();
+    let path_buf = TempPath::new().path().to_path_buf();
+    let storage = EncryptedOnDiskStorage::new_with_keyfile(path_buf, keyfile.path()).unwrap();
+    suite::execute_all_storage_tests(&mut Storage::from(storage));
+}
+
+#[test]
+fn test_encrypted_on_disk_reopen() {
+    let path = TempPath::new();
+    let mut storage =
+        EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), PASSPHRASE).unwrap();
+    storage.set("key", SECRET).unwrap();
+
+    // The value is not stored in plaintext
+    let contents = fs::read_to_string(path.path()).unwrap();
+    assert!(!contents.contains(SECRET));
+
+    // Reopening with the same passphrase recovers the value
+    let storage =
+        EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), PASSPHRASE).unwrap();
+    assert_eq!(storage.get::<String>("key").unwrap().value, SECRET);
+
+    // A different passphrase or a keyfile cannot open the storage
+    EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), b"wrong passphrase")
+        .unwrap_err();
+    let keyfile = TempPath::new();
+    fs::write(keyfile.path(), hex::encode([7u8; 32])).unwrap();
+    EncryptedOnDiskStorage::new_with_keyfile(path.path().to_path_buf(), keyfile.path())
+        .unwrap_err();
+}
+
+#[test]
+fn test_encrypted_on_disk_key_derivation_bounds() {
+    let path = TempPath::new();
+    EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), PASSPHRASE).unwrap();
+    let sealed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path.path()).unwrap()).unwrap();
+
+    for (param, value) in [
+        ("memory_kib", u32::MAX),
+        ("memory_kib", 1),
+        ("iterations", u32::MAX),
+        ("iterations", 0),
+        ("parallelism", u32::MAX),
+    ] {
+        let mut tampered = sealed.clone();
+        tampered["key_derivation"][param] = value.into();
+        fs::write(path.path(), serde_json::to_vec(&tampered).unwrap()).unwrap();
+        let error =
+            EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), PASSPHRASE)
+                .err()
+                .unwrap();
+        assert!(error.to_string().contains(param), "{}", error);
+    }
+}
+
+#[test]
+fn test_encrypted_on_disk_migration() {
+    let plaintext_path = TempPath::new();
+    let mut plaintext = OnDiskStorage::new(plaintext_path.path().to_path_buf());
+    plaintext.set("key", SECRET).unwrap();
+    plaintext.set("other_key", 5u64).unwrap();
+
+    let path = TempPath::new();
+    let mut storage =
+        EncryptedOnDiskStorage::new_with_passphrase(path.path().to_path_buf(), PASSPHRASE).unwrap();
+    storage
+        .migrate_from_plaintext(plaintext_path.path())
+        .unwrap();
+    assert_eq!(storage.get::<String>("key").unwrap().value, SECRET);
+    assert_eq!(
+        storage.get::<u64>("other_key").unwrap(),
+        plaintext.get::<u64>("other_key").unwrap()
+    );
+
+    // Migrating again would overwrite existing keys
+    storage
+        .migrate_from_plaintext(plaintext_path.path())
+        .unwrap_err();
+}
@@ -1,8 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod encrypted_on_disk;
 mod github;
 mod in_memory;
 mod on_disk;
+mod pkcs11;
 mod suite;
 mod vault;
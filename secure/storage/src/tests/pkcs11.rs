@@ -0,0 +1,95 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, CryptoStorage, Error, KVStorage, Namespaced, Pkcs11Storage, Storage};
+use aptos_crypto::{
+    ed25519::Ed25519PrivateKey, test_utils::TestAptosCrypto, PrivateKey, Signature, Uniform,
+};
+use std::path::PathBuf;
+
+/// The PKCS#11 module to test against, e.g., /usr/lib/softhsm/libsofthsm2.so.
+const PKCS11_LIBRARY: &str = "PKCS11_TEST_LIBRARY";
+/// The label and user PIN of the token used by the tests. All objects on the token are destroyed
+/// by the tests.
+const PKCS11_TOKEN_LABEL: &str = "aptos-test";
+const PKCS11_PIN: &str = "1234";
+
+const CRYPTO_KEY: &str = "crypto_key";
+
+/// A test for verifying Pkcs11Storage properly implements the SecureStorage API. This test
+/// depends on a local SoftHSM token, which can be created via:
+/// `softhsm2-util --init-token --free --label aptos-test --so-pin 1234 --pin 1234`
+/// and then run with `PKCS11_TEST_LIBRARY` pointing to the SoftHSM module via:
+/// `cargo test -p aptos-secure-storage pkcs11 -- --ignored`. The tests share the token, so they
+/// are run sequentially.
+#[ignore = "requires a SoftHSM token and PKCS11_TEST_LIBRARY"]
+#[test]
+fn execute_storage_tests_pkcs11() {
+    let library_path = PathBuf::from(
+        std::env::var(PKCS11_LIBRARY).expect("PKCS11_TEST_LIBRARY must point to a PKCS#11 module"),
+    );
+
+    let mut storage = Storage::from(create_pkcs11(&library_path));
+    suite::execute_non_exporting_storage_tests(&mut storage);
+
+    let mut storage = Storage::from(Namespaced::new(
+        "namespace",
+        Box::new(Storage::from(create_pkcs11(&library_path))),
+    ));
+    suite::execute_non_exporting_storage_tests(&mut storage);
+
+    test_pkcs11_non_exportable_keys(&library_path);
+}
+
+fn create_pkcs11(library_path: &std::path::Path) -> Pkcs11Storage {
+    Pkcs11Storage::new(library_path, PKCS11_TOKEN_LABEL, PKCS11_PIN.into()).unwrap()
+}
+
+/// Verifies that generated, rotated and imported keys cannot be exported, yet remain usable
+/// for signing.
+fn test_pkcs11_non_exportable_keys(library_path: &std::path::Path) {
+    let mut storage = create_pkcs11(library_path);
+    storage.reset_and_clear().unwrap();
+    let message = TestAptosCrypto("Hello, World".to_string());
+
+    let public_key = storage.create_key(CRYPTO_KEY).unwrap();
+    assert_eq!(
+        storage.export_private_key(CRYPTO_KEY),
+        Err(Error::PermissionDenied)
+    );
+
+    let rotated_public_key = storage.rotate_key(CRYPTO_KEY).unwrap();
+    assert_eq!(
+        storage.get_public_key_previous_version(CRYPTO_KEY).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        storage.export_private_key_for_version(CRYPTO_KEY, public_key),
+        Err(Error::PermissionDenied)
+    );
+    let signature = storage.sign(CRYPTO_KEY, &message).unwrap();
+    signature.verify(&message, &rotated_public_key).unwrap();
+
+    let imported_key_name = "imported_key";
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    storage
+        .import_private_key(imported_key_name, private_key.clone())
+        .unwrap();
+    assert_eq!(
+        storage
+            .get_public_key(imported_key_name)
+            .unwrap()
+            .public_key,
+        private_key.public_key()
+    );
+    assert_eq!(
+        storage.export_private_key(imported_key_name),
+        Err(Error::PermissionDenied)
+    );
+    let signature = storage.sign(imported_key_name, &message).unwrap();
+    signature
+        .verify(&message, &private_key.public_key())
+        .unwrap();
+
+    storage.reset_and_clear().unwrap();
+}
@@ -21,19 +21,24 @@ const STORAGE_TESTS: &[fn(&mut Storage)] = &[
     test_set_reset_get,
     test_create_and_get_non_existent_version,
     test_create_get_key_pair,
-    test_create_key_pair_and_perform_rotations,
     test_create_sign_rotate_sign,
     test_ensure_storage_is_available,
     test_get_non_existent,
-    test_get_public_key_previous_version,
     test_get_set,
     test_get_uncreated_key_pair,
     test_hash_value,
     test_incremental_timestamp,
-    test_import_key,
     test_verify_incorrect_value_types,
 ];
 
+/// Tests that rely on exporting private keys, which storage backends with non-exportable keys
+/// (e.g., PKCS#11) do not support.
+const EXPORT_STORAGE_TESTS: &[fn(&mut Storage)] = &[
+    test_create_key_pair_and_perform_rotations,
+    test_get_public_key_previous_version,
+    test_import_key,
+];
+
 /// Storage data constants for testing purposes.
 const CRYPTO_KEY: &str = "Private_Key";
 const U64_KEY: &str = "U64_Key";
@@ -41,6 +46,15 @@ const CRYPTO_NAME: &str = "Test_Key_Name";
 
 /// Executes all storage tests on a given storage backend.
 pub fn execute_all_storage_tests(storage: &mut Storage) {
+    storage.reset_and_clear().unwrap();
+    for test in STORAGE_TESTS.iter().chain(EXPORT_STORAGE_TESTS.iter()) {
+        test(storage);
+        storage.reset_and_clear().unwrap();
+    }
+}
+
+/// Executes all storage tests that do not export private keys on a given storage backend.
+pub fn execute_non_exporting_storage_tests(storage: &mut Storage) {
     storage.reset_and_clear().unwrap();
     for test in STORAGE_TESTS.iter() {
         test(storage);
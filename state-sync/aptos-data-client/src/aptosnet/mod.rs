@@ -69,8 +69,8 @@ const REGULAR_PEER_SAMPLE_FREQ: u64 = 3;
 /// 1. Sends requests to connected Aptos peers.
 /// 2. Does basic type conversions and error handling on the responses.
 /// 3. Routes requests to peers that advertise availability for that data.
-/// 4. Maintains peer scores based on each peer's observed quality of service
-///    and upper client reports of invalid or malicious data.
+/// 4. Reports each peer's observed quality of service and upper client reports of
+///    invalid or malicious data to the peer's network-wide reputation.
 /// 5. Selects high quality peers to send each request to.
 /// 6. Exposes a condensed data summary of our peers' data advertisements.
 ///
@@ -154,10 +154,8 @@ impl AptosNetDataClient {
             self.identify_serviceable(regular_peers, request)
         };
 
-        // Randomly select a peer to handle the request
-        serviceable_peers
-            .choose(&mut rand::thread_rng())
-            .copied()
+        // Select a low latency peer to handle the request
+        self.choose_low_latency_peer(&serviceable_peers)
             .ok_or_else(|| {
                 Error::DataIsUnavailable(
                     format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
@@ -165,6 +163,54 @@ impl AptosNetDataClient {
             })
     }
 
+    /// Selects a peer using the "power of two choices": two peers are sampled
+    /// at random and the one with the lowest measured ping latency is chosen.
+    /// This favours nearby peers without overloading a single peer. Peers that
+    /// are known to lag too far behind the most synced peer are only sampled
+    /// if no other peer is left.
+    fn choose_low_latency_peer(&self, peers: &[PeerNetworkId]) -> Option<PeerNetworkId> {
+        let peer_metadata = self.network_client.peer_metadata_storage();
+        let peers: Vec<_> = peers
+            .iter()
+            .map(|peer| {
+                let metadata = peer_metadata
+                    .read_monitoring_metadata(*peer)
+                    .unwrap_or_default();
+                (*peer, metadata)
+            })
+            .collect();
+
+        // Peers whose sync progress wasn't measured yet are given the benefit of the doubt
+        let in_sync_peers: Vec<_> = match peers
+            .iter()
+            .filter_map(|(_, metadata)| metadata.sync_progress())
+            .max()
+        {
+            Some(most_synced) => peers
+                .iter()
+                .filter(|(_, metadata)| {
+                    metadata.sync_lag(most_synced).map_or(true, |lag| {
+                        lag <= self.data_client_config.max_sync_lag_versions
+                    })
+                })
+                .copied()
+                .collect(),
+            None => vec![],
+        };
+        let candidates = if in_sync_peers.is_empty() {
+            &peers
+        } else {
+            &in_sync_peers
+        };
+
+        candidates
+            .choose_multiple(&mut rand::thread_rng(), 2)
+            .min_by_key(|(_, metadata)| {
+                metadata.average_ping_latency.unwrap_or(Duration::MAX) // Unmeasured peers are least preferred
+            })
+            .map(|(peer, _)| *peer)
+    }
+
     /// Identifies the peers in the given set of prospective peers
     /// that can service the specified request.
     fn identify_serviceable(
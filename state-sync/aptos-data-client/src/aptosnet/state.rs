@@ -12,7 +12,7 @@ use aptos_config::{
 use aptos_logger::prelude::*;
 use itertools::Itertools;
 use netcore::transport::ConnectionOrigin;
-use network::application::storage::PeerMetadataStorage;
+use network::application::{reputation::PeerInteraction, storage::PeerMetadataStorage};
 use std::{
     cmp::min,
     collections::{HashMap, HashSet},
@@ -20,19 +20,7 @@ use std::{
 };
 use storage_service_types::{StorageServerSummary, StorageServiceRequest};
 
-/// Scores for peer rankings based on preferences and behavior.
-const MAX_SCORE: f64 = 100.0;
-const MIN_SCORE: f64 = 0.0;
-const STARTING_SCORE: f64 = 50.0;
-/// Add this score on a successful response.
-const SUCCESSFUL_RESPONSE_DELTA: f64 = 1.0;
-/// Not necessarily a malicious response, but not super useful.
-const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
-/// Likely to be a malicious response.
-const MALICIOUS_MULTIPLIER: f64 = 0.8;
-/// Ignore a peer when their score dips below this threshold.
-const IGNORE_PEER_THRESHOLD: f64 = 25.0;
-
+#[derive(Clone, Copy, Debug)]
 pub(crate) enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -53,22 +41,20 @@ impl From<ResponseError> for ErrorType {
     }
 }
 
-#[derive(Debug)]
+impl From<ErrorType> for PeerInteraction {
+    fn from(error: ErrorType) -> Self {
+        match error {
+            ErrorType::NotUseful => PeerInteraction::NotUseful,
+            ErrorType::Malicious => PeerInteraction::Malicious,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 struct PeerState {
     /// The latest observed advertised data for this peer, or `None` if we
     /// haven't polled them yet.
     storage_summary: Option<StorageServerSummary>,
-    /// For now, a simplified port of the original state-sync v1 scoring system.
-    score: f64,
-}
-
-impl Default for PeerState {
-    fn default() -> Self {
-        Self {
-            storage_summary: None,
-            score: STARTING_SCORE,
-        }
-    }
 }
 
 impl PeerState {
@@ -76,29 +62,6 @@ impl PeerState {
     fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
         self.storage_summary = Some(storage_summary);
     }
-
-    /// Returns the storage summary iff the peer is not below the ignore threshold
-    fn storage_summary_if_not_ignored(&self) -> Option<&StorageServerSummary> {
-        if self.score <= IGNORE_PEER_THRESHOLD {
-            None
-        } else {
-            self.storage_summary.as_ref()
-        }
-    }
-
-    /// Updates the score of the peer according to a successful operation
-    fn update_score_success(&mut self) {
-        self.score = f64::min(self.score + SUCCESSFUL_RESPONSE_DELTA, MAX_SCORE);
-    }
-
-    /// Updates the score of the peer according to an error
-    fn update_score_error(&mut self, error: ErrorType) {
-        let multiplier = match error {
-            ErrorType::NotUseful => NOT_USEFUL_MULTIPLIER,
-            ErrorType::Malicious => MALICIOUS_MULTIPLIER,
-        };
-        self.score = f64::max(self.score * multiplier, MIN_SCORE);
-    }
 }
 
 /// Contains all of the unbanned peers' most recent [`StorageServerSummary`] data
@@ -149,22 +112,38 @@ impl PeerStates {
             return true;
         }
 
-        self.peer_to_state
-            .get(peer)
-            .and_then(PeerState::storage_summary_if_not_ignored)
+        self.storage_summary_if_not_ignored(peer)
             .map(|summary| summary.can_service(request))
             .unwrap_or(false)
     }
 
-    /// Updates the score of the peer according to a successful operation
+    /// Returns the storage summary of the peer iff its network-wide reputation isn't below the
+    /// ban threshold
+    fn storage_summary_if_not_ignored(
+        &self,
+        peer: &PeerNetworkId,
+    ) -> Option<&StorageServerSummary> {
+        if self.is_ignored(peer) {
+            None
+        } else {
+            self.peer_to_state
+                .get(peer)
+                .and_then(|peer_state| peer_state.storage_summary.as_ref())
+        }
+    }
+
+    fn is_ignored(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_metadata_storage
+            .read_reputation(*peer)
+            .is_below_ban_threshold()
+    }
+
+    /// Reports a successful operation to the network-wide reputation of the peer
     pub fn update_score_success(&mut self, peer: PeerNetworkId) {
-        let old_score = self.peer_to_state.entry(peer).or_default().score;
-        self.peer_to_state
-            .entry(peer)
-            .or_default()
-            .update_score_success();
-        let new_score = self.peer_to_state.entry(peer).or_default().score;
-        if old_score <= IGNORE_PEER_THRESHOLD && new_score > IGNORE_PEER_THRESHOLD {
+        let was_ignored = self.is_ignored(&peer);
+        self.peer_metadata_storage
+            .report_peer_interaction(peer, PeerInteraction::Success);
+        if was_ignored && !self.is_ignored(&peer) {
             info!(
                 (LogSchema::new(LogEntry::PeerStates)
                     .event(LogEvent::PeerNoLongerIgnored)
@@ -174,15 +153,12 @@ impl PeerStates {
         }
     }
 
-    /// Updates the score of the peer according to an error
+    /// Reports an error to the network-wide reputation of the peer
     pub fn update_score_error(&mut self, peer: PeerNetworkId, error: ErrorType) {
-        let old_score = self.peer_to_state.entry(peer).or_default().score;
-        self.peer_to_state
-            .entry(peer)
-            .or_default()
-            .update_score_error(error);
-        let new_score = self.peer_to_state.entry(peer).or_default().score;
-        if old_score > IGNORE_PEER_THRESHOLD && new_score <= IGNORE_PEER_THRESHOLD {
+        let was_ignored = self.is_ignored(&peer);
+        self.peer_metadata_storage
+            .report_peer_interaction(peer, error.into());
+        if !was_ignored && self.is_ignored(&peer) {
             info!(
                 (LogSchema::new(LogEntry::PeerStates)
                     .event(LogEvent::PeerIgnored)
@@ -289,8 +265,8 @@ impl PeerStates {
         // Only include likely-not-malicious peers in the data summary aggregation
         let summaries: Vec<StorageServerSummary> = self
             .peer_to_state
-            .values()
-            .filter_map(PeerState::storage_summary_if_not_ignored)
+            .keys()
+            .filter_map(|peer| self.storage_summary_if_not_ignored(peer))
             .cloned()
             .collect();
 
@@ -21,7 +21,11 @@ use futures::StreamExt;
 use maplit::hashmap;
 use netcore::transport::ConnectionOrigin;
 use network::{
-    application::{interface::MultiNetworkSender, storage::PeerMetadataStorage, types::PeerState},
+    application::{
+        interface::MultiNetworkSender,
+        storage::PeerMetadataStorage,
+        types::{PeerMonitoringMetadata, PeerState},
+    },
     peer_manager::{ConnectionRequestSender, PeerManagerRequest, PeerManagerRequestSender},
     protocols::{network::NewNetworkSender, wire::handshake::v1::ProtocolId},
     transport::ConnectionMetadata,
@@ -175,6 +179,34 @@ impl MockNetwork {
             .unwrap();
     }
 
+    /// Updates the measured ping latency of the given peer
+    fn update_ping_latency(&mut self, peer: PeerNetworkId, ping_latency: Duration) {
+        let monitoring_metadata = PeerMonitoringMetadata {
+            average_ping_latency: Some(ping_latency),
+            ..Default::default()
+        };
+        self.peer_infos
+            .update_monitoring_metadata(peer, monitoring_metadata);
+    }
+
+    /// Updates the measured ping latency and sync progress of the given peer
+    fn update_latency_and_sync_progress(
+        &mut self,
+        peer: PeerNetworkId,
+        ping_latency: Duration,
+        highest_synced_epoch: u64,
+        highest_synced_version: u64,
+    ) {
+        let monitoring_metadata = PeerMonitoringMetadata {
+            average_ping_latency: Some(ping_latency),
+            highest_synced_epoch: Some(highest_synced_epoch),
+            highest_synced_version: Some(highest_synced_version),
+            ..Default::default()
+        };
+        self.peer_infos
+            .update_monitoring_metadata(peer, monitoring_metadata);
+    }
+
     /// Get the next request sent from the client.
     async fn next_request(&mut self) -> Option<NetworkRequest> {
         match self.peer_mgr_reqs_rx.next().await {
@@ -788,6 +820,87 @@ async fn all_peer_request_selection() {
     assert!(peer_for_request == priority_peer_1 || peer_for_request == priority_peer_2);
 }
 
+#[tokio::test]
+async fn low_latency_peer_request_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    // Add two regular peers that can both service the request
+    let regular_peer_1 = mock_network.add_peer(false);
+    let regular_peer_2 = mock_network.add_peer(false);
+    let server_version_request = StorageServiceRequest::GetServerProtocolVersion;
+
+    // Measure a lower latency for the second peer
+    mock_network.update_ping_latency(regular_peer_1, Duration::from_millis(500));
+    mock_network.update_ping_latency(regular_peer_2, Duration::from_millis(50));
+
+    // Verify the lower latency peer is always selected
+    for _ in 0..10 {
+        assert_eq!(
+            client.choose_peer_for_request(&server_version_request),
+            Ok(regular_peer_2)
+        );
+    }
+
+    // Measure a lower latency for the first peer and verify it is now selected
+    mock_network.update_ping_latency(regular_peer_1, Duration::from_millis(10));
+    for _ in 0..10 {
+        assert_eq!(
+            client.choose_peer_for_request(&server_version_request),
+            Ok(regular_peer_1)
+        );
+    }
+}
+
+#[tokio::test]
+async fn lagging_peer_request_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+    let max_sync_lag_versions = AptosDataClientConfig::default().max_sync_lag_versions;
+
+    // Add two regular peers that can both service the request
+    let regular_peer_1 = mock_network.add_peer(false);
+    let regular_peer_2 = mock_network.add_peer(false);
+    let server_version_request = StorageServiceRequest::GetServerProtocolVersion;
+
+    // The first peer is closer, but lags too far behind the second peer
+    let highest_synced_version = 1_000_000;
+    mock_network.update_latency_and_sync_progress(
+        regular_peer_1,
+        Duration::from_millis(10),
+        5,
+        highest_synced_version - max_sync_lag_versions - 1,
+    );
+    mock_network.update_latency_and_sync_progress(
+        regular_peer_2,
+        Duration::from_millis(500),
+        5,
+        highest_synced_version,
+    );
+
+    // Verify the synced peer is always selected
+    for _ in 0..10 {
+        assert_eq!(
+            client.choose_peer_for_request(&server_version_request),
+            Ok(regular_peer_2)
+        );
+    }
+
+    // Let the first peer catch up to within the lag and verify the closer peer is selected
+    mock_network.update_latency_and_sync_progress(
+        regular_peer_1,
+        Duration::from_millis(10),
+        5,
+        highest_synced_version - max_sync_lag_versions,
+    );
+    for _ in 0..10 {
+        assert_eq!(
+            client.choose_peer_for_request(&server_version_request),
+            Ok(regular_peer_1)
+        );
+    }
+}
+
 #[tokio::test]
 async fn validator_peer_prioritization() {
     ::aptos_logger::Logger::init_for_testing();
@@ -1048,7 +1161,7 @@ async fn bad_peer_is_eventually_added_back() {
         .contains(&CompleteDataRange::new(0, 200).unwrap()));
 
     // Keep decreasing this peer's score by considering its responses bad.
-    // Eventually its score drops below IGNORE_PEER_THRESHOLD.
+    // Eventually its reputation drops below the ban threshold.
     for _ in 0..20 {
         let result = client.get_transactions_with_proof(200, 0, 200, false).await;
 
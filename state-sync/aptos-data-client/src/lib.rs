@@ -351,7 +351,7 @@ impl fmt::Debug for AdvertisedData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let sync_lis = (&self.synced_ledger_infos)
             .iter()
-            .map(|LedgerInfoWithSignatures::V0(ledger)| format!("{}", ledger))
+            .map(|ledger_info| format!("{}", ledger_info))
             .join(", ");
         write!(
             f,
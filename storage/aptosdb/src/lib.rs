@@ -48,7 +48,7 @@ use crate::{
     },
     pruner::{utils, Pruner, PrunerIndex},
     schema::*,
-    state_store::StateStore,
+    state_store::{StateStore, MAX_KEYS_SCANNED_PER_PAGE},
     system_store::SystemStore,
     transaction_store::TransactionStore,
 };
@@ -750,6 +750,24 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_values_by_key_prefix_paginated(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_key: Option<&StateKey>,
+        limit: usize,
+        version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        gauged_api("get_state_values_by_key_prefix_paginated", || {
+            self.state_store.get_values_by_key_prefix_paginated(
+                key_prefix,
+                start_key,
+                limit,
+                MAX_KEYS_SCANNED_PER_PAGE,
+                version,
+            )
+        })
+    }
+
     fn get_latest_ledger_info_option(&self) -> Result<Option<LedgerInfoWithSignatures>> {
         gauged_api("get_latest_ledger_info_option", || {
             Ok(self.ledger_store.get_latest_ledger_info_option())
@@ -42,6 +42,9 @@ type NodeBatch = aptos_jellyfish_merkle::NodeBatch<StateKey>;
 type StateValueBatch = aptos_jellyfish_merkle::StateValueBatch<StateKey, StateValue>;
 
 pub const MAX_VALUES_TO_FETCH_FOR_KEY_PREFIX: usize = 10_000;
+/// The maximum number of keys, deleted ones included, visited to build a page of values by key
+/// prefix.
+pub const MAX_KEYS_SCANNED_PER_PAGE: usize = 10_000;
 
 #[derive(Debug)]
 pub(crate) struct StateStore {
@@ -156,6 +159,76 @@ impl StateStore {
         key_prefix: &StateKeyPrefix,
         desired_version: Version,
     ) -> Result<HashMap<StateKey, StateValue>> {
+        let mut result = HashMap::new();
+        self.for_each_value_by_key_prefix(
+            key_prefix,
+            None,
+            desired_version,
+            |state_key, state_value| {
+                result.insert(state_key, state_value);
+                // We don't allow fetching arbitrarily large number of values to be fetched as
+                // this can potentially slowdown the DB.
+                if result.len() > MAX_VALUES_TO_FETCH_FOR_KEY_PREFIX {
+                    return Err(anyhow!(
+                        "Too many values requested for key_prefix {:?} - maximum allowed {:?}",
+                        key_prefix,
+                        MAX_VALUES_TO_FETCH_FOR_KEY_PREFIX
+                    ));
+                }
+                Ok(true)
+            },
+        )?;
+        Ok(result)
+    }
+
+    /// Returns up to `limit` key, value pairs for a particular state key prefix at desired
+    /// version, in the order of the keys and starting from `start_key` when set. Unlike
+    /// `get_values_by_key_prefix`, the values deleted at desired version are skipped, so that the
+    /// keys can be paged through, e.g. to list the items of a table.
+    ///
+    /// At most `max_keys_scanned` keys are visited, deleted ones included, so a page may hold
+    /// fewer than `limit` values. The key to start the next page from is returned along with the
+    /// values, unless all the keys were visited.
+    pub fn get_values_by_key_prefix_paginated(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_key: Option<&StateKey>,
+        limit: usize,
+        max_keys_scanned: usize,
+        desired_version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        ensure!(max_keys_scanned > 0, "max_keys_scanned must be positive");
+        let mut result = vec![];
+        let mut next_key = None;
+        let mut keys_scanned = 0;
+        self.for_each_value_by_key_prefix(
+            key_prefix,
+            start_key,
+            desired_version,
+            |state_key, state_value| {
+                if result.len() == limit || keys_scanned == max_keys_scanned {
+                    next_key = Some(state_key);
+                    return Ok(false);
+                }
+                keys_scanned += 1;
+                if state_value.maybe_bytes.is_some() {
+                    result.push((state_key, state_value));
+                }
+                Ok(true)
+            },
+        )?;
+        Ok((result, next_key))
+    }
+
+    /// Calls `f` with each state key having `key_prefix`, from `start_key` on when set, along with
+    /// its value at desired version, until `f` returns false.
+    fn for_each_value_by_key_prefix(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_key: Option<&StateKey>,
+        desired_version: Version,
+        mut f: impl FnMut(StateKey, StateValue) -> Result<bool>,
+    ) -> Result<()> {
         let mut read_opts = ReadOptions::default();
         // Without this, iterators are not guaranteed a total order of all keys, but only keys for the same prefix.
         // For example,
@@ -167,9 +240,19 @@ impl StateStore {
         // keys starting with `aptos/abc`.
         read_opts.set_total_order_seek(true);
         let mut iter = self.ledger_db.iter::<StateValueSchema>(read_opts)?;
-        let mut result = HashMap::new();
         let mut prev_key = None;
-        iter.seek(&(key_prefix))?;
+        match start_key {
+            Some(start_key) => {
+                ensure!(
+                    key_prefix.is_prefix(start_key)?,
+                    "Start key {:?} doesn't have key_prefix {:?}",
+                    start_key,
+                    key_prefix
+                );
+                iter.seek(&(start_key.clone(), desired_version))?;
+            }
+            None => iter.seek(&(key_prefix))?,
+        }
         while let Some(((state_key, version), state_value)) = iter.next().transpose()? {
             // In case the previous seek() ends on the same key with version 0.
             if Some(&state_key) == prev_key.as_ref() {
@@ -187,21 +270,14 @@ impl StateStore {
                 continue;
             }
 
-            result.insert(state_key.clone(), state_value);
-            // We don't allow fetching arbitrarily large number of values to be fetched as this can
-            // potentially slowdown the DB.
-            if result.len() > MAX_VALUES_TO_FETCH_FOR_KEY_PREFIX {
-                return Err(anyhow!(
-                    "Too many values requested for key_prefix {:?} - maximum allowed {:?}",
-                    key_prefix,
-                    MAX_VALUES_TO_FETCH_FOR_KEY_PREFIX
-                ));
+            if !f(state_key.clone(), state_value)? {
+                break;
             }
             prev_key = Some(state_key.clone());
             // Seek to the next key - this can be done by seeking to the current key with version 0
             iter.seek(&(state_key, 0))?;
         }
-        Ok(result)
+        Ok(())
     }
 
     fn expect_value_by_version(
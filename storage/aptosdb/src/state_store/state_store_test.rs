@@ -238,6 +238,91 @@ fn test_get_values_by_key_prefix() {
     assert_eq!(*key_value_map.get(&key5).unwrap(), value5_v2);
 }
 
+#[test]
+fn test_get_values_by_key_prefix_paginated() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+
+    let key1 = StateKey::table_item(1, b"key1".to_vec());
+    let key2 = StateKey::table_item(1, b"key2".to_vec());
+    let key3 = StateKey::table_item(1, b"key3".to_vec());
+    let other_table_key = StateKey::table_item(2, b"key1".to_vec());
+
+    let value1 = StateValue::from(String::from("value1").into_bytes());
+    let value2 = StateValue::from(String::from("value2").into_bytes());
+    let value3 = StateValue::from(String::from("value3").into_bytes());
+
+    put_value_set(
+        store,
+        vec![
+            (key3.clone(), value3.clone()),
+            (key1.clone(), value1.clone()),
+            (key2.clone(), value2.clone()),
+            (other_table_key, value1.clone()),
+        ],
+        0,
+    );
+    // key2 is deleted at version 1
+    put_value_set(store, vec![(key2.clone(), StateValue::empty())], 1);
+
+    let table_key_prefix = StateKeyPrefix::table_items(1);
+    let (page, next_key) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, None, 2, 10, 0)
+        .unwrap();
+    assert_eq!(
+        page,
+        vec![(key1.clone(), value1.clone()), (key2.clone(), value2)]
+    );
+    assert_eq!(next_key, Some(key3.clone()));
+    let (page, next_key) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, Some(&key2), 2, 10, 0)
+        .unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[1], (key3.clone(), value3.clone()));
+    assert_eq!(next_key, None);
+
+    // The deleted key is skipped
+    let (page, next_key) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, None, 10, 10, 1)
+        .unwrap();
+    assert_eq!(
+        page,
+        vec![
+            (key1.clone(), value1.clone()),
+            (key3.clone(), value3.clone())
+        ]
+    );
+    assert_eq!(next_key, None);
+    let (page, _) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, Some(&key2), 10, 10, 1)
+        .unwrap();
+    assert_eq!(page, vec![(key3.clone(), value3.clone())]);
+
+    // The deleted key counts towards the keys scanned, so the page ends before key3
+    let (page, next_key) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, None, 10, 2, 1)
+        .unwrap();
+    assert_eq!(page, vec![(key1, value1)]);
+    assert_eq!(next_key, Some(key3.clone()));
+    let (page, next_key) = store
+        .get_values_by_key_prefix_paginated(&table_key_prefix, Some(&key3), 10, 2, 1)
+        .unwrap();
+    assert_eq!(page, vec![(key3, value3)]);
+    assert_eq!(next_key, None);
+
+    // The start key must have the prefix
+    assert!(store
+        .get_values_by_key_prefix_paginated(
+            &table_key_prefix,
+            Some(&StateKey::table_item(2, b"key1".to_vec())),
+            10,
+            10,
+            1
+        )
+        .is_err());
+}
+
 #[test]
 fn test_retired_records() {
     let key1 = StateKey::Raw(String::from("test_key1").into_bytes());
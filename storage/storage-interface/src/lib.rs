@@ -349,6 +349,22 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns up to `limit` key, value pairs for a particular state key prefix at desired
+    /// version, in the order of the keys and starting from `start_key` when set. Deleted values are
+    /// skipped, and since the number of keys visited per call is bounded, a page may hold fewer
+    /// than `limit` values. Also returns the key to start the next page from, unless there is
+    /// none. This API can be used to page through the items of a table by passing the table
+    /// handle as the key prefix.
+    fn get_state_values_by_key_prefix_paginated(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_key: Option<&StateKey>,
+        limit: usize,
+        version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        unimplemented!()
+    }
+
     /// Returns the latest ledger info, if any.
     fn get_latest_ledger_info_option(&self) -> Result<Option<LedgerInfoWithSignatures>> {
         unimplemented!()
@@ -7,8 +7,9 @@ use forge::{ForgeConfig, Options, Result, *};
 use std::{env, num::NonZeroUsize, process, time::Duration};
 use structopt::StructOpt;
 use testcases::{
-    compatibility_test::SimpleValidatorUpgrade, fixed_tps_test::FixedTpsTest,
-    gas_price_test::NonZeroGasPrice, generate_traffic, partial_nodes_down_test::PartialNodesDown,
+    byzantine_test::ByzantineValidatorsTest, compatibility_test::SimpleValidatorUpgrade,
+    fixed_tps_test::FixedTpsTest, gas_price_test::NonZeroGasPrice, generate_traffic,
+    network_partition_test::NetworkPartitionTest, partial_nodes_down_test::PartialNodesDown,
     performance_test::PerformanceBenchmark, reconfiguration_test::ReconfigurationTest,
     state_sync_performance::StateSyncPerformance,
 };
@@ -58,7 +59,13 @@ enum OperatorCommand {
 }
 
 #[derive(StructOpt, Debug)]
-struct LocalSwarm {}
+struct LocalSwarm {
+    #[structopt(
+        long,
+        help = "Route validator traffic through proxies and run the network chaos tests"
+    )]
+    network_chaos: bool,
+}
 
 #[derive(StructOpt, Debug)]
 struct K8sSwarm {
@@ -165,13 +172,20 @@ fn main() -> Result<()> {
     match args.cli_cmd {
         // cmd input for test
         CliCommand::Test(test_cmd) => match test_cmd {
-            TestCommand::LocalSwarm(..) => run_forge(
-                local_test_suite(),
-                LocalFactory::from_workspace()?,
-                &args.options,
-                args.changelog,
-                global_emit_job_request,
-            ),
+            TestCommand::LocalSwarm(local) => {
+                let test_suite = if local.network_chaos {
+                    local_chaos_test_suite()
+                } else {
+                    local_test_suite()
+                };
+                run_forge(
+                    test_suite,
+                    LocalFactory::from_workspace()?.with_network_chaos(local.network_chaos),
+                    &args.options,
+                    args.changelog,
+                    global_emit_job_request,
+                )
+            }
             TestCommand::K8sSwarm(k8s) => {
                 let mut test_suite = k8s_test_suite();
                 if let Some(suite) = args.suite.as_ref() {
@@ -331,6 +345,18 @@ fn local_test_suite() -> ForgeConfig<'static> {
         .with_genesis_modules_bytes(cached_framework_packages::module_blobs().to_vec())
 }
 
+/// The tests injecting network chaos or Byzantine validators, which are only supported by local
+/// swarms
+fn local_chaos_test_suite() -> ForgeConfig<'static> {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(4).unwrap())
+        .with_network_tests(&[
+            &NetworkPartitionTest,
+            &ByzantineValidatorsTest { num_byzantine: 1 },
+        ])
+        .with_genesis_modules_bytes(cached_framework_packages::module_blobs().to_vec())
+}
+
 fn k8s_test_suite() -> ForgeConfig<'static> {
     ForgeConfig::default()
         .with_initial_validator_count(NonZeroUsize::new(30).unwrap())
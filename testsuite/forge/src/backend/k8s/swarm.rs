@@ -3,7 +3,8 @@
 
 use crate::{
     backend::k8s::node::K8sNode, create_k8s_client, query_sequence_numbers, remove_helm_release,
-    set_validator_image_tag, ChainInfo, FullNode, Node, Result, Swarm, Validator, Version,
+    set_validator_image_tag, ChainInfo, FullNode, Node, Result, Swarm, SwarmChaos, Validator,
+    Version,
 };
 use ::aptos_logger::*;
 use anyhow::{anyhow, bail, format_err};
@@ -148,6 +149,14 @@ impl Swarm for K8sSwarm {
         set_validator_image_tag(validator.name(), &version, &self.helm_repo)
     }
 
+    fn update_validator_config(&mut self, id: PeerId, _config: NodeConfig) -> Result<()> {
+        bail!(
+            "update_validator_config is unimplemented for the k8s swarm, validator configs can \
+            only be updated in local swarms: {}",
+            id
+        )
+    }
+
     fn full_nodes<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn FullNode> + 'a> {
         Box::new(self.fullnodes.values().map(|v| v as &'a dyn FullNode))
     }
@@ -209,6 +218,29 @@ impl Swarm for K8sSwarm {
             )
         }
     }
+
+    // Network chaos is only implemented for local swarms, which is why the chaos tests are only
+    // part of the local chaos test suite of forge-cli
+    fn inject_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        bail!(
+            "inject_chaos is unimplemented for the k8s swarm, network chaos is only supported by \
+            local swarms: {}",
+            chaos
+        )
+    }
+
+    fn remove_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        bail!(
+            "remove_chaos is unimplemented for the k8s swarm, network chaos is only supported by \
+            local swarms: {}",
+            chaos
+        )
+    }
+
+    fn remove_all_chaos(&mut self) -> Result<()> {
+        // No chaos can have been injected
+        Ok(())
+    }
 }
 
 pub(crate) fn k8s_retry_strategy() -> impl Iterator<Item = Duration> {
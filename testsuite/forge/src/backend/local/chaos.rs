@@ -0,0 +1,495 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{LocalNode, SwarmChaos};
+use anyhow::{anyhow, bail, Result};
+use aptos_config::config::{DiscoveryMethod, Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
+use aptos_infallible::RwLock;
+use aptos_logger::{debug, warn};
+use aptos_sdk::types::{
+    network_address::{NetworkAddress, Protocol},
+    PeerId,
+};
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    io::{ErrorKind, Read, Write},
+    net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// How often blocked threads check whether the network conditions or the proxy have changed
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// The delay before a lost segment is first retransmitted, doubling after each retransmission
+const RETRANSMISSION_TIMEOUT: Duration = Duration::from_millis(200);
+/// The number of retransmissions of a segment before the connection is reset
+const MAX_RETRANSMISSIONS: u32 = 6;
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// Network chaos for a local swarm. All validator network traffic is routed through in-process TCP
+/// proxies, one for each ordered pair of validators, which apply the faults currently injected.
+///
+/// Validators normally discover each other through the on-chain validator set, which contains
+/// the same addresses for everyone. Instead, each validator is configured with seed peers that
+/// point at its own proxies, so the proxy accepting a connection knows both of its endpoints.
+/// Nodes added to the swarm later on dial their seed peers through proxies as well.
+#[derive(Debug)]
+pub(crate) struct LocalNetworkChaos {
+    state: Arc<RwLock<ChaosState>>,
+    // The proxies shut down when dropped together with the swarm
+    proxies: Vec<ChaosProxy>,
+}
+
+impl LocalNetworkChaos {
+    /// Starts the proxies between the `validators` and rewrites their configs to dial each other
+    /// through them. Must be called before the validators are started.
+    pub fn new(validators: &mut HashMap<PeerId, LocalNode>) -> Result<Self> {
+        let state = Arc::new(RwLock::new(ChaosState::default()));
+
+        let targets = validators
+            .values()
+            .map(|node| {
+                let network =
+                    node.config().validator_network.as_ref().ok_or_else(|| {
+                        anyhow!("Validator {} has no validator network", node.name())
+                    })?;
+                let address = socket_address(&network.listen_address)?;
+                Ok((
+                    node.peer_id(),
+                    (address, network.identity_key().public_key()),
+                ))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let mut proxies = Vec::new();
+        for (peer_id, node) in validators.iter_mut() {
+            let mut seeds = PeerSet::new();
+            for (remote_peer_id, (address, public_key)) in &targets {
+                if remote_peer_id == peer_id {
+                    continue;
+                }
+
+                let proxy = ChaosProxy::start(*peer_id, *remote_peer_id, *address, state.clone())?;
+                let proxy_address = NetworkAddress::from_protocols(vec![
+                    Protocol::Ip4(Ipv4Addr::LOCALHOST),
+                    Protocol::Tcp(proxy.port()),
+                ])?
+                .append_prod_protos(*public_key, HANDSHAKE_VERSION);
+                seeds.insert(
+                    *remote_peer_id,
+                    Peer::from_addrs(PeerRole::Validator, vec![proxy_address]),
+                );
+                proxies.push(proxy);
+            }
+
+            let config_path = node.config_path();
+            let config = node.config_mut();
+            let network = config.validator_network.as_mut().unwrap();
+            network.discovery_method = DiscoveryMethod::None;
+            network.discovery_methods = Vec::new();
+            network.seeds = seeds;
+            config.save(config_path)?;
+        }
+
+        Ok(Self { state, proxies })
+    }
+
+    /// Routes the connections of a node added to the swarm to its seed peers through proxies, so
+    /// that the faults injected apply to its links too. Must be called before the node is started.
+    ///
+    /// A validator fullnode shares the peer id of its validator, so the link between the two is
+    /// `(peer_id, peer_id)` and can't be separated by a partition.
+    pub fn add_node(&mut self, node: &mut LocalNode) -> Result<()> {
+        let peer_id = node.peer_id();
+        let config_path = node.config_path();
+        let config = node.config_mut();
+        let networks = config
+            .validator_network
+            .iter_mut()
+            .chain(config.full_node_networks.iter_mut());
+        for network in networks {
+            for (remote_peer_id, peer) in network.seeds.iter_mut() {
+                let mut proxy_addresses = Vec::new();
+                for address in &peer.addresses {
+                    let proxy = ChaosProxy::start(
+                        peer_id,
+                        *remote_peer_id,
+                        socket_address(address)?,
+                        self.state.clone(),
+                    )?;
+                    proxy_addresses.push(proxy_address(address, proxy.port())?);
+                    self.proxies.push(proxy);
+                }
+                peer.addresses = proxy_addresses;
+            }
+        }
+        config.save(config_path)?;
+        Ok(())
+    }
+
+    pub fn inject(&self, chaos: SwarmChaos) {
+        self.state.write().chaos.push(chaos);
+    }
+
+    pub fn remove(&self, chaos: &SwarmChaos) -> Result<()> {
+        let mut state = self.state.write();
+        match state.chaos.iter().position(|c| c == chaos) {
+            Some(index) => {
+                state.chaos.remove(index);
+                Ok(())
+            }
+            None => bail!("Chaos was never injected: {}", chaos),
+        }
+    }
+
+    pub fn remove_all(&self) {
+        self.state.write().chaos.clear();
+    }
+}
+
+/// Returns `address` with its IP and port replaced by the ones of a local proxy
+fn proxy_address(address: &NetworkAddress, port: u16) -> Result<NetworkAddress> {
+    let mut protocols = vec![Protocol::Ip4(Ipv4Addr::LOCALHOST), Protocol::Tcp(port)];
+    protocols.extend(address.as_slice().iter().skip(2).cloned());
+    Ok(NetworkAddress::from_protocols(protocols)?)
+}
+
+fn socket_address(address: &NetworkAddress) -> Result<SocketAddr> {
+    match address.as_slice() {
+        [Protocol::Ip4(ip), Protocol::Tcp(port), ..] => {
+            let ip = if ip.is_unspecified() {
+                Ipv4Addr::LOCALHOST
+            } else {
+                *ip
+            };
+            Ok(SocketAddr::from((ip, *port)))
+        }
+        _ => bail!("Unsupported listen address for network chaos: {}", address),
+    }
+}
+
+/// The faults currently injected, shared by all proxies of a swarm
+#[derive(Debug, Default)]
+struct ChaosState {
+    chaos: Vec<SwarmChaos>,
+}
+
+impl ChaosState {
+    /// Combines all faults affecting the traffic sent from `from` to `to`
+    fn link(&self, from: &PeerId, to: &PeerId) -> LinkConditions {
+        let mut conditions = LinkConditions::default();
+        let link = (*from, *to);
+        for chaos in &self.chaos {
+            match chaos {
+                SwarmChaos::Partition(partition) => {
+                    conditions.partitioned |= partition.separates(from, to);
+                }
+                SwarmChaos::Delay(delay) if delay.links.contains(&link) => {
+                    conditions.latency_ms += delay.latency_ms;
+                    conditions.jitter_ms += delay.jitter_ms;
+                }
+                SwarmChaos::Loss(loss) if loss.links.contains(&link) => {
+                    conditions.loss_percentage =
+                        conditions.loss_percentage.max(loss.loss_percentage);
+                }
+                SwarmChaos::Bandwidth(bandwidth) if bandwidth.links.contains(&link) => {
+                    conditions.rate_bytes_per_sec = Some(
+                        conditions
+                            .rate_bytes_per_sec
+                            .map_or(bandwidth.rate_bytes_per_sec, |rate| {
+                                rate.min(bandwidth.rate_bytes_per_sec)
+                            }),
+                    );
+                }
+                _ => {}
+            }
+        }
+        conditions
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct LinkConditions {
+    partitioned: bool,
+    latency_ms: u64,
+    jitter_ms: u64,
+    loss_percentage: u64,
+    rate_bytes_per_sec: Option<u64>,
+}
+
+impl LinkConditions {
+    /// The delay of a segment read now, or `None` if it never gets through.
+    ///
+    /// As the proxy forwards TCP streams rather than packets, dropping bytes would corrupt the
+    /// stream instead of simulating a loss. So each transmission of the segment is dropped with
+    /// the loss probability, and like TCP, the proxy retransmits it after a timeout that doubles
+    /// with every attempt. Once the retransmissions are exhausted, TCP resets the connection.
+    fn segment_delay(&self) -> Option<Duration> {
+        let mut rng = rand::thread_rng();
+        let mut delay =
+            Duration::from_millis(self.latency_ms + rng.gen_range(0, self.jitter_ms + 1));
+        let mut timeout = RETRANSMISSION_TIMEOUT;
+        for _ in 0..=MAX_RETRANSMISSIONS {
+            if rng.gen_range(0, 100) >= self.loss_percentage {
+                return Some(delay);
+            }
+            delay += timeout;
+            timeout *= 2;
+        }
+        None
+    }
+
+    /// The time it takes to send `bytes` over the link
+    fn transmission_time(&self, bytes: usize) -> Duration {
+        match self.rate_bytes_per_sec {
+            Some(rate) => Duration::from_secs_f64(bytes as f64 / rate.max(1) as f64),
+            None => Duration::from_secs(0),
+        }
+    }
+}
+
+/// A TCP proxy for the connections `from` dials to `to`, listening on a local port
+#[derive(Debug)]
+struct ChaosProxy {
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ChaosProxy {
+    fn start(
+        from: PeerId,
+        to: PeerId,
+        target: SocketAddr,
+        state: Arc<RwLock<ChaosState>>,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let link = Link {
+            from,
+            to,
+            state,
+            shutdown: shutdown.clone(),
+        };
+        let handle = thread::Builder::new()
+            .name(format!("chaos-proxy-{}", port))
+            .spawn(move || link.accept_loop(listener, target))?;
+
+        Ok(Self {
+            port,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for ChaosProxy {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Link {
+    from: PeerId,
+    to: PeerId,
+    state: Arc<RwLock<ChaosState>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Link {
+    fn conditions(&self) -> LinkConditions {
+        self.state.read().link(&self.from, &self.to)
+    }
+
+    fn reversed(&self) -> Self {
+        Self {
+            from: self.to,
+            to: self.from,
+            state: self.state.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+
+    fn accept_loop(self, listener: TcpListener, target: SocketAddr) {
+        while !self.shutdown.load(Ordering::Relaxed) {
+            let inbound = match listener.accept() {
+                Ok((inbound, _)) => inbound,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Err(e) => {
+                    warn!(
+                        "Chaos proxy {} -> {} failed to accept: {}",
+                        self.from, self.to, e
+                    );
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            };
+
+            // Closing connections across a partition right after accepting them looks like a
+            // reset to the dialer, which keeps redialing until the partition heals
+            if self.conditions().partitioned {
+                continue;
+            }
+
+            let outbound = match TcpStream::connect_timeout(&target, CONNECT_TIMEOUT) {
+                Ok(outbound) => outbound,
+                Err(e) => {
+                    debug!(
+                        "Chaos proxy {} -> {} failed to connect: {}",
+                        self.from, self.to, e
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(e) = self.forward(inbound, outbound) {
+                warn!(
+                    "Chaos proxy {} -> {} failed to forward: {}",
+                    self.from, self.to, e
+                );
+            }
+        }
+    }
+
+    /// Forwards traffic in both directions until either side closes the connection, a partition
+    /// separates the two peers or the proxy shuts down
+    fn forward(&self, inbound: TcpStream, outbound: TcpStream) -> Result<()> {
+        inbound.set_nonblocking(false)?;
+        inbound.set_nodelay(true)?;
+        outbound.set_nodelay(true)?;
+        let connection = Arc::new(Connection {
+            inbound: inbound.try_clone()?,
+            outbound: outbound.try_clone()?,
+            closed: AtomicBool::new(false),
+        });
+
+        self.pump(
+            inbound.try_clone()?,
+            outbound.try_clone()?,
+            connection.clone(),
+        )?;
+        self.reversed().pump(outbound, inbound, connection)
+    }
+
+    /// Spawns the threads forwarding the traffic from `src` to `dst`. The reader applies the delays
+    /// as segments arrive, so that latency doesn't limit the throughput, while the writer delivers
+    /// them once due.
+    fn pump(
+        &self,
+        mut src: TcpStream,
+        mut dst: TcpStream,
+        connection: Arc<Connection>,
+    ) -> Result<()> {
+        src.set_read_timeout(Some(POLL_INTERVAL))?;
+        let (sender, receiver) = mpsc::channel::<(Instant, Vec<u8>)>();
+
+        let link = self.clone();
+        let reader_connection = connection.clone();
+        thread::Builder::new()
+            .name("chaos-proxy-reader".into())
+            .spawn(move || {
+                let mut buffer = vec![0; BUFFER_SIZE];
+                let mut last_delivery = Instant::now();
+                while !reader_connection.is_closed() && !link.shutdown.load(Ordering::Relaxed) {
+                    let conditions = link.conditions();
+                    if conditions.partitioned {
+                        reader_connection.close();
+                        return;
+                    }
+
+                    match src.read(&mut buffer) {
+                        Ok(0) => return,
+                        Ok(n) => {
+                            let delay = match conditions.segment_delay() {
+                                Some(delay) => delay,
+                                None => {
+                                    reader_connection.close();
+                                    return;
+                                }
+                            };
+                            // TCP delivers segments in order, so jitter can't reorder them
+                            let delivery = (Instant::now() + delay).max(last_delivery);
+                            last_delivery = delivery;
+                            if sender.send((delivery, buffer[..n].to_vec())).is_err() {
+                                return;
+                            }
+                        }
+                        Err(e)
+                            if e.kind() == ErrorKind::WouldBlock
+                                || e.kind() == ErrorKind::TimedOut => {}
+                        Err(_) => return,
+                    }
+                }
+                reader_connection.close();
+            })?;
+
+        let link = self.clone();
+        thread::Builder::new()
+            .name("chaos-proxy-writer".into())
+            .spawn(move || {
+                loop {
+                    match receiver.recv_timeout(POLL_INTERVAL) {
+                        Ok((delivery, bytes)) => {
+                            let now = Instant::now();
+                            if delivery > now {
+                                thread::sleep(delivery - now);
+                            }
+                            if dst.write_all(&bytes).is_err() {
+                                break;
+                            }
+                            thread::sleep(link.conditions().transmission_time(bytes.len()));
+                        }
+                        Err(mpsc::RecvTimeoutError::Timeout) => {
+                            if connection.is_closed() {
+                                break;
+                            }
+                        }
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                connection.close();
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Both ends of a proxied connection, closing it terminates all of its threads
+struct Connection {
+    inbound: TcpStream,
+    outbound: TcpStream,
+    closed: AtomicBool,
+}
+
+impl Connection {
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    fn close(&self) {
+        if !self.closed.swap(true, Ordering::Relaxed) {
+            let _ = self.inbound.shutdown(Shutdown::Both);
+            let _ = self.outbound.shutdown(Shutdown::Both);
+        }
+    }
+}
@@ -12,6 +12,7 @@ use std::{
 };
 
 mod cargo;
+mod chaos;
 mod node;
 mod swarm;
 pub use node::LocalNode;
@@ -39,15 +40,24 @@ impl LocalVersion {
 
 pub struct LocalFactory {
     versions: Arc<HashMap<Version, LocalVersion>>,
+    network_chaos: bool,
 }
 
 impl LocalFactory {
     pub fn new(versions: HashMap<Version, LocalVersion>) -> Self {
         Self {
             versions: Arc::new(versions),
+            network_chaos: false,
         }
     }
 
+    /// Launches swarms whose validators are connected through proxies, so that network faults can
+    /// be injected with `Swarm::inject_chaos`
+    pub fn with_network_chaos(mut self, network_chaos: bool) -> Self {
+        self.network_chaos = network_chaos;
+        self
+    }
+
     pub fn from_workspace() -> Result<Self> {
         let mut versions = HashMap::new();
         let new_version = cargo::get_aptos_node_binary_from_worktree().map(|(revision, bin)| {
@@ -130,7 +140,8 @@ impl LocalFactory {
         let mut builder = LocalSwarm::builder(self.versions.clone())
             .number_of_validators(number_of_validators)
             .initial_version(version.clone())
-            .min_price_per_gas_unit(min_price_per_gas_unit);
+            .min_price_per_gas_unit(min_price_per_gas_unit)
+            .network_chaos(self.network_chaos);
         if let Some(genesis_modules) = genesis_modules {
             builder = builder.genesis_modules(genesis_modules);
         }
@@ -1,9 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use super::chaos::LocalNetworkChaos;
 use crate::{
-    ChainInfo, FullNode, HealthCheckError, LocalNode, LocalVersion, Node, NodeExt, Swarm, SwarmExt,
-    Validator, Version,
+    ChainInfo, FullNode, HealthCheckError, LocalNode, LocalVersion, Node, NodeExt, Swarm,
+    SwarmChaos, SwarmExt, Validator, Version,
 };
 use anyhow::{anyhow, bail, Result};
 use aptos_config::{config::NodeConfig, keys::ConfigKey};
@@ -80,6 +81,7 @@ pub struct LocalSwarmBuilder {
     dir: Option<PathBuf>,
     genesis_modules: Option<Vec<Vec<u8>>>,
     min_price_per_gas_unit: u64,
+    network_chaos: bool,
 }
 
 impl LocalSwarmBuilder {
@@ -92,6 +94,7 @@ impl LocalSwarmBuilder {
             dir: None,
             genesis_modules: None,
             min_price_per_gas_unit: 1,
+            network_chaos: false,
         }
     }
 
@@ -125,6 +128,13 @@ impl LocalSwarmBuilder {
         self
     }
 
+    /// Routes the traffic between validators through proxies, so that network faults can be
+    /// injected with `Swarm::inject_chaos`
+    pub fn network_chaos(mut self, network_chaos: bool) -> Self {
+        self.network_chaos = network_chaos;
+        self
+    }
+
     pub fn build<R>(mut self, rng: R) -> Result<LocalSwarm>
     where
         R: ::rand::RngCore + ::rand::CryptoRng,
@@ -169,13 +179,18 @@ impl LocalSwarmBuilder {
         });
         let version = versions.get(&initial_version).unwrap();
 
-        let validators = validators
+        let mut validators = validators
             .into_iter()
             .map(|v| {
                 let node = LocalNode::new(version.to_owned(), v.name, v.directory)?;
                 Ok((node.peer_id(), node))
             })
             .collect::<Result<HashMap<_, _>>>()?;
+        let network_chaos = if self.network_chaos {
+            Some(LocalNetworkChaos::new(&mut validators)?)
+        } else {
+            None
+        };
         let root_key = ConfigKey::new(root_keys.root_key);
         let root_account = LocalAccount::new(
             aptos_sdk::types::account_config::aptos_root_address(),
@@ -194,6 +209,7 @@ impl LocalSwarmBuilder {
             root_account,
             chain_id: ChainId::test(),
             root_key,
+            network_chaos,
         })
     }
 }
@@ -210,6 +226,7 @@ pub struct LocalSwarm {
     root_account: LocalAccount,
     chain_id: ChainId,
     root_key: ConfigKey<Ed25519PrivateKey>,
+    network_chaos: Option<LocalNetworkChaos>,
 }
 
 impl LocalSwarm {
@@ -318,6 +335,9 @@ impl LocalSwarm {
 
         let peer_id = fullnode.peer_id();
         assert_eq!(peer_id, validator_peer_id);
+        if let Some(network_chaos) = self.network_chaos.as_mut() {
+            network_chaos.add_node(&mut fullnode)?;
+        }
         fullnode.start()?;
 
         self.fullnodes.insert(peer_id, fullnode);
@@ -344,6 +364,9 @@ impl LocalSwarm {
         )?;
 
         let peer_id = fullnode.peer_id();
+        if let Some(network_chaos) = self.network_chaos.as_mut() {
+            network_chaos.add_node(&mut fullnode)?;
+        }
         fullnode.start()?;
 
         self.fullnodes.insert(peer_id, fullnode);
@@ -386,6 +409,12 @@ impl LocalSwarm {
     pub fn dir(&self) -> &Path {
         self.dir.as_ref()
     }
+
+    fn network_chaos(&self) -> Result<&LocalNetworkChaos> {
+        self.network_chaos.as_ref().ok_or_else(|| {
+            anyhow!("Network chaos isn't enabled, see LocalSwarmBuilder::network_chaos")
+        })
+    }
 }
 
 impl Drop for LocalSwarm {
@@ -438,6 +467,16 @@ impl Swarm for LocalSwarm {
         validator.upgrade(version)
     }
 
+    fn update_validator_config(&mut self, id: PeerId, config: NodeConfig) -> Result<()> {
+        let validator = self
+            .validators
+            .get_mut(&id)
+            .ok_or_else(|| anyhow!("Invalid id: {}", id))?;
+        config.save(validator.config_path())?;
+        *validator.config_mut() = config;
+        Ok(())
+    }
+
     fn full_nodes<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn FullNode> + 'a> {
         Box::new(self.fullnodes.values().map(|v| v as &'a dyn FullNode))
     }
@@ -498,4 +537,20 @@ impl Swarm for LocalSwarm {
         self.dir.persist();
         self.dir.display().to_string()
     }
+
+    fn inject_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        self.network_chaos()?.inject(chaos);
+        Ok(())
+    }
+
+    fn remove_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        self.network_chaos()?.remove(&chaos)
+    }
+
+    fn remove_all_chaos(&mut self) -> Result<()> {
+        if let Some(network_chaos) = &self.network_chaos {
+            network_chaos.remove_all();
+        }
+        Ok(())
+    }
 }
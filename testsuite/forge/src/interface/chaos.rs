@@ -0,0 +1,97 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_sdk::types::PeerId;
+use std::fmt;
+
+/// A network fault which can be injected into, and later removed from, a `Swarm`.
+///
+/// Faults other than partitions apply to directed links `(from, to)`, i.e. to the traffic sent by
+/// `from` to `to`. Use [`links_between`] to affect traffic in both directions.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SwarmChaos {
+    Partition(SwarmNetworkPartition),
+    Delay(SwarmNetworkDelay),
+    Loss(SwarmNetworkLoss),
+    Bandwidth(SwarmNetworkBandwidth),
+}
+
+/// Cuts all connections between peers of different groups. Peers which aren't part of any group
+/// remain connected to everyone.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SwarmNetworkPartition {
+    pub groups: Vec<Vec<PeerId>>,
+}
+
+/// Delays all traffic sent over the links by `latency_ms`, plus a uniformly distributed jitter of
+/// up to `jitter_ms`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SwarmNetworkDelay {
+    pub links: Vec<(PeerId, PeerId)>,
+    pub latency_ms: u64,
+    pub jitter_ms: u64,
+}
+
+/// Drops `loss_percentage` percent of the packets sent over the links. Lost packets are
+/// retransmitted, and are dropped again with the same probability. Connections whose packets
+/// keep getting lost are reset, so a loss of 100% cuts the links.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SwarmNetworkLoss {
+    pub links: Vec<(PeerId, PeerId)>,
+    pub loss_percentage: u64,
+}
+
+/// Limits the throughput of each of the links to `rate_bytes_per_sec`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SwarmNetworkBandwidth {
+    pub links: Vec<(PeerId, PeerId)>,
+    pub rate_bytes_per_sec: u64,
+}
+
+/// Returns all directed links between the peers of `a` and the peers of `b`, in both directions.
+pub fn links_between(a: &[PeerId], b: &[PeerId]) -> Vec<(PeerId, PeerId)> {
+    a.iter()
+        .flat_map(|a| b.iter().flat_map(move |b| vec![(*a, *b), (*b, *a)]))
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+impl SwarmNetworkPartition {
+    /// Returns true if traffic between `a` and `b` is cut by this partition
+    pub fn separates(&self, a: &PeerId, b: &PeerId) -> bool {
+        let group_of = |peer: &PeerId| self.groups.iter().position(|g| g.contains(peer));
+        match (group_of(a), group_of(b)) {
+            (Some(group_a), Some(group_b)) => group_a != group_b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for SwarmChaos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwarmChaos::Partition(partition) => {
+                write!(f, "Partition into {} groups", partition.groups.len())
+            }
+            SwarmChaos::Delay(delay) => write!(
+                f,
+                "Delay of {}ms (jitter {}ms) on {} links",
+                delay.latency_ms,
+                delay.jitter_ms,
+                delay.links.len()
+            ),
+            SwarmChaos::Loss(loss) => write!(
+                f,
+                "Loss of {}% on {} links",
+                loss.loss_percentage,
+                loss.links.len()
+            ),
+            SwarmChaos::Bandwidth(bandwidth) => write!(
+                f,
+                "Bandwidth of {} bytes/s on {} links",
+                bandwidth.rate_bytes_per_sec,
+                bandwidth.links.len()
+            ),
+        }
+    }
+}
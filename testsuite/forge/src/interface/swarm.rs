@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ChainInfo, FullNode, NodeExt, Result, Validator, Version};
+use crate::{ChainInfo, FullNode, NodeExt, Result, SwarmChaos, Validator, Version};
 use anyhow::{anyhow, bail};
 use aptos_config::config::NodeConfig;
 use aptos_rest_client::Client as RestClient;
@@ -32,6 +32,10 @@ pub trait Swarm: Sync {
     /// Upgrade a Validator to run specified `Version`
     fn upgrade_validator(&mut self, id: PeerId, version: &Version) -> Result<()>;
 
+    /// Replaces the config of the Validator with the provided PeerId. The new config takes effect
+    /// when the Validator restarts. Only local swarms support this, other swarms return an error.
+    fn update_validator_config(&mut self, id: PeerId, config: NodeConfig) -> Result<()>;
+
     /// Returns an Iterator of references to all the FullNodes in the Swarm
     fn full_nodes<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn FullNode> + 'a>;
 
@@ -63,6 +67,17 @@ pub trait Swarm: Sync {
     fn chain_info(&mut self) -> ChainInfo<'_>;
 
     fn logs_location(&mut self) -> String;
+
+    /// Injects a network fault into the swarm. Only local swarms built with network chaos enabled
+    /// support this, other swarms return an error.
+    fn inject_chaos(&mut self, chaos: SwarmChaos) -> Result<()>;
+
+    /// Removes a previously injected network fault from the swarm. Like `inject_chaos`, only
+    /// supported by local swarms built with network chaos enabled.
+    fn remove_chaos(&mut self, chaos: SwarmChaos) -> Result<()>;
+
+    /// Removes all network faults injected into the swarm
+    fn remove_all_chaos(&mut self) -> Result<()>;
 }
 
 impl<T: ?Sized> SwarmExt for T where T: Swarm {}
@@ -1,14 +1,17 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_bitvec::BitVec;
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
     traits::{SigningKey, Uniform},
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
-    contract_event, event, state_store::state_key::StateKey, transaction, write_set,
+    contract_event, event, state_store::state_key::StateKey, transaction,
+    validator_verifier::ValidatorConsensusInfoVersions, write_set,
 };
 use move_deps::move_core_types::language_storage;
 use rand::{rngs::StdRng, SeedableRng};
@@ -37,6 +40,14 @@ fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()>
     tracer.trace_value(samples, &signature)?;
     tracer.trace_value::<MultiEd25519PublicKey>(samples, &public_key.into())?;
     tracer.trace_value::<MultiEd25519Signature>(samples, &signature.into())?;
+
+    let bls_private_key = bls12381::PrivateKey::generate(&mut rng);
+    let bls_public_key = bls12381::PublicKey::from(&bls_private_key);
+    let bls_signature = bls_private_key.sign(&message);
+
+    tracer.trace_value(samples, &bls_public_key)?;
+    tracer.trace_value(samples, &bls_signature)?;
+    tracer.trace_value(samples, &BitVec::default())?;
     Ok(())
 }
 
@@ -65,6 +76,7 @@ pub fn get_registry() -> Result<Registry> {
     tracer.trace_type::<write_set::WriteOp>(&samples)?;
 
     tracer.trace_type::<StateKey>(&samples)?;
+    tracer.trace_type::<ValidatorConsensusInfoVersions>(&samples)?;
     tracer.trace_type::<consensus::network_interface::ConsensusMsg>(&samples)?;
     tracer.trace_type::<consensus_types::block_data::BlockType>(&samples)?;
     tracer.trace_type::<consensus_types::block_retrieval::BlockRetrievalStatus>(&samples)?;
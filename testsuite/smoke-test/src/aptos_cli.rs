@@ -6,8 +6,12 @@ use aptos::{account::create::DEFAULT_FUNDED_COINS, test::CliTestFramework};
 use aptos_config::keys::ConfigKey;
 use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_faucet::FaucetArgs;
-use aptos_types::{account_config::aptos_root_address, chain_id::ChainId};
+use aptos_temppath::TempPath;
+use aptos_types::{
+    account_address::AccountAddress, account_config::aptos_root_address, chain_id::ChainId,
+};
 use forge::{LocalSwarm, Node};
+use std::{fs, path::Path};
 use tokio::task::JoinHandle;
 
 pub async fn setup_test(num_nodes: usize) -> (LocalSwarm, CliTestFramework) {
@@ -84,3 +88,90 @@ async fn test_account_flow() {
             .unwrap()
     );
 }
+
+#[tokio::test]
+async fn test_move_inspect_flow() {
+    let (_swarm, cli) = setup_test(1).await;
+    cli.wait_for_account(0).await.unwrap();
+    let account = CliTestFramework::account_id(0);
+
+    let package_dir = TempPath::new();
+    package_dir.create_as_dir().unwrap();
+    write_message_package(package_dir.path(), account, "text: vector<u8>");
+
+    let summary = cli
+        .publish_package(0, package_dir.path().to_path_buf(), false)
+        .await
+        .unwrap();
+    assert!(summary.success, "{}", summary.vm_status);
+
+    let modules = cli.list_modules(0).await.unwrap();
+    assert_eq!(modules.len(), 1);
+    assert_eq!(modules[0].name.as_str(), "Message");
+
+    let output_dir = TempPath::new();
+    output_dir.create_as_dir().unwrap();
+    let paths = cli
+        .download_module(0, "Message", output_dir.path().to_path_buf())
+        .await
+        .unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            output_dir.path().join("Message.mv"),
+            output_dir.path().join("Message.mvasm")
+        ]
+    );
+    let bytecode = fs::read(&paths[0]).unwrap();
+    let compiled = fs::read(
+        package_dir
+            .path()
+            .join("build/Inspect/bytecode_modules/Message.mv"),
+    )
+    .unwrap();
+    assert_eq!(bytecode, compiled);
+    assert!(fs::read_to_string(&paths[1])
+        .unwrap()
+        .contains("struct Message"));
+
+    // The published package can upgrade itself
+    let compatibilities = cli
+        .check_compatibility(0, package_dir.path().to_path_buf())
+        .await
+        .unwrap();
+    assert_eq!(compatibilities.len(), 1);
+    assert!(compatibilities[0].published);
+    assert!(compatibilities[0].struct_and_function_linking);
+    assert!(compatibilities[0].struct_layout);
+
+    // Changing the layout of a struct is caught before publishing
+    write_message_package(package_dir.path(), account, "text: vector<u8>, count: u64");
+    cli.check_compatibility(0, package_dir.path().to_path_buf())
+        .await
+        .unwrap_err();
+    cli.publish_package(0, package_dir.path().to_path_buf(), false)
+        .await
+        .unwrap_err();
+}
+
+/// Writes a package with a `Message` module under `account`, with the fields given
+fn write_message_package(package_dir: &Path, account: AccountAddress, fields: &str) {
+    fs::write(
+        package_dir.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Inspect\"\nversion = \"0.0.0\"\n\n[addresses]\ninspect = \"{}\"\n",
+            account.to_hex_literal()
+        ),
+    )
+    .unwrap();
+    let sources_dir = package_dir.join("sources");
+    fs::create_dir_all(&sources_dir).unwrap();
+    fs::write(
+        sources_dir.join("Message.move"),
+        format!(
+            "module inspect::Message {{\n    struct Message has key {{ {} }}\n}}\n",
+            fields
+        ),
+    )
+    .unwrap();
+}
@@ -1,12 +1,20 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::operational_tooling::launch_swarm_with_op_tool_and_backend;
-use aptos_config::config::SecureBackend;
+use crate::{
+    operational_tooling::launch_swarm_with_op_tool_and_backend,
+    smoke_test_environment::new_local_swarm_with_aptos, test_utils::check_create_mint_transfer,
+};
+use aptos_config::config::{ByzantineConfig, SecureBackend};
 use aptos_secure_storage::{KVStorage, Storage};
 use aptos_types::network_address::NetworkAddress;
-use forge::NodeExt;
-use std::{convert::TryInto, str::FromStr};
+use forge::{NodeExt, SwarmExt};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 #[ignore]
 #[tokio::test]
@@ -60,6 +68,78 @@ async fn test_consensus_observer_mode_storage_error() {
     assert_eq!(sequence_number_0, sequence_number_1);
 }
 
+#[tokio::test]
+async fn test_byzantine_validator() {
+    let mut swarm = new_local_swarm_with_aptos(4).await;
+
+    // Make the last validator misbehave in every way that still lets it vote
+    let byzantine = swarm.validators_mut().last().unwrap();
+    let byzantine_peer_id = byzantine.peer_id();
+    let mut config = byzantine.config().clone();
+    config.consensus.byzantine = ByzantineConfig {
+        equivocate_proposals: true,
+        double_vote: true,
+        withhold_votes: false,
+        stale_sync_info: true,
+        forged_sync_info: true,
+    };
+    config.save(byzantine.config_path()).unwrap();
+    byzantine.restart().await.unwrap();
+
+    // Liveness: the honest validators keep committing transactions
+    check_create_mint_transfer(&mut swarm).await;
+    swarm
+        .wait_for_all_nodes_to_catchup(Instant::now() + Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    // Safety: all validators agree on the ledger
+    let mut versions = vec![];
+    for validator in swarm.validators() {
+        let state = validator
+            .rest_client()
+            .get_ledger_information()
+            .await
+            .unwrap()
+            .into_inner();
+        versions.push(state.version);
+    }
+    let version = versions.into_iter().min().unwrap();
+    let mut accumulator_root_hashes = vec![];
+    for validator in swarm.validators() {
+        let transaction = validator
+            .rest_client()
+            .get_transaction_by_version(version)
+            .await
+            .unwrap()
+            .into_inner();
+        accumulator_root_hashes.push(
+            transaction
+                .transaction_info()
+                .unwrap()
+                .accumulator_root_hash,
+        );
+    }
+    accumulator_root_hashes.dedup();
+    assert_eq!(accumulator_root_hashes.len(), 1);
+
+    // Detection: the honest validators noticed the misbehavior
+    let mut detected = 0;
+    for validator in swarm.validators() {
+        if validator.peer_id() != byzantine_peer_id {
+            detected += validator
+                .get_metric_with_fields(
+                    "aptos_consensus_misbehavior_detected_count",
+                    HashMap::new(),
+                )
+                .await
+                .unwrap()
+                .unwrap_or(0);
+        }
+    }
+    assert!(detected > 0);
+}
+
 // TODO(https://github.com/aptos-labs/aptos-core/issues/317): add back after support update consensus config in aptos-framework
 // #[allow(dead_code)]
 // async fn test_onchain_upgrade(new_onfig: OnChainConsensusConfig) {
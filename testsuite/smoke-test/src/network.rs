@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::smoke_test_environment::new_local_swarm_with_aptos;
+use crate::{
+    smoke_test_environment::{new_local_swarm_with_aptos, new_local_swarm_with_network_chaos},
+    test_utils::{assert_balance, create_and_fund_account, transfer_coins},
+};
 use aptos::op::key::GenerateKey;
 use aptos_config::{
     config::{DiscoveryMethod, Identity, NetworkConfig, NodeConfig, PeerSet, PersistableConfig},
@@ -11,7 +14,7 @@ use aptos_crypto::{x25519, x25519::PrivateKey};
 use aptos_operational_tool::{keys::EncodingType, test_helper::OperationalTool};
 use aptos_temppath::TempPath;
 use aptos_types::network_address::{NetworkAddress, Protocol};
-use forge::{FullNode, LocalNode, NodeExt, Swarm};
+use forge::{FullNode, LocalNode, NodeExt, Swarm, SwarmChaos, SwarmExt, SwarmNetworkPartition};
 use std::{
     collections::HashMap,
     path::Path,
@@ -138,6 +141,47 @@ async fn test_connection_limiting() {
     );
 }
 
+#[tokio::test]
+async fn test_network_partition() {
+    let mut swarm = new_local_swarm_with_network_chaos(4).await;
+    let validator_peer_ids = swarm.validators().map(|v| v.peer_id()).collect::<Vec<_>>();
+    let client_1 = swarm
+        .validator(validator_peer_ids[1])
+        .unwrap()
+        .rest_client();
+    let transaction_factory = swarm.chain_info().transaction_factory();
+
+    let mut account_0 = create_and_fund_account(&mut swarm, 100).await;
+    let account_1 = create_and_fund_account(&mut swarm, 10).await;
+
+    // Partition a single validator away, the remaining three still form a quorum
+    let isolated_peer_id = validator_peer_ids[0];
+    let partition = SwarmChaos::Partition(SwarmNetworkPartition {
+        groups: vec![vec![isolated_peer_id], validator_peer_ids[1..].to_vec()],
+    });
+    swarm.inject_chaos(partition.clone()).unwrap();
+
+    transfer_coins(
+        &client_1,
+        &transaction_factory,
+        &mut account_0,
+        &account_1,
+        10,
+    )
+    .await;
+    assert_balance(&client_1, &account_0, 90).await;
+    assert_balance(&client_1, &account_1, 20).await;
+
+    // Once healed, the isolated validator catches up with the transfer
+    swarm.remove_chaos(partition).unwrap();
+    swarm
+        .wait_for_all_nodes_to_catchup(Instant::now() + Duration::from_secs(60))
+        .await
+        .unwrap();
+    let client_0 = swarm.validator(isolated_peer_id).unwrap().rest_client();
+    assert_balance(&client_0, &account_1, 20).await;
+}
+
 // Currently this test seems flaky: https://github.com/aptos-labs/aptos-core/issues/670
 #[ignore]
 #[tokio::test]
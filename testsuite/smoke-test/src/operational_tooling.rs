@@ -718,7 +718,7 @@ async fn test_fullnode_network_key_rotation() {
         .validator_config(validator_account, Some(&backend))
         .await
         .unwrap()
-        .fullnode_network_address
+        .fullnode_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, config_network_key);
@@ -728,7 +728,7 @@ async fn test_fullnode_network_key_rotation() {
         .validator_set(Some(validator_account), Some(&backend))
         .await
         .unwrap()[0]
-        .fullnode_network_address
+        .fullnode_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, info_network_key);
@@ -758,7 +758,7 @@ async fn test_network_key_rotation() {
         .validator_config(validator_account, Some(&backend))
         .await
         .unwrap()
-        .validator_network_address
+        .validator_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, config_network_key);
@@ -768,7 +768,7 @@ async fn test_network_key_rotation() {
         .validator_set(Some(validator_account), Some(&backend))
         .await
         .unwrap()[0]
-        .validator_network_address
+        .validator_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, info_network_key);
@@ -812,7 +812,7 @@ async fn test_network_key_rotation_recovery() {
         .validator_config(validator_account, Some(&backend))
         .await
         .unwrap()
-        .validator_network_address
+        .validator_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, config_network_key);
@@ -822,7 +822,7 @@ async fn test_network_key_rotation_recovery() {
         .validator_set(Some(validator_account), Some(&backend))
         .await
         .unwrap()[0]
-        .validator_network_address
+        .validator_network_addresses[0]
         .find_noise_proto()
         .unwrap();
     assert_eq!(new_network_key, info_network_key);
@@ -1038,14 +1038,13 @@ async fn test_validator_config() {
         .await
         .unwrap();
     assert_eq!(new_consensus_key, new_validator_config.consensus_public_key);
-    assert!(new_validator_config
-        .validator_network_address
+    assert!(new_validator_config.validator_network_addresses[0]
         .to_string()
         .contains(&new_network_address.to_string()));
     assert_eq!(original_validator_config.name, new_validator_config.name);
     assert_eq!(
-        original_validator_config.fullnode_network_address,
-        new_validator_config.fullnode_network_address
+        original_validator_config.fullnode_network_addresses,
+        new_validator_config.fullnode_network_addresses
     );
 }
 
@@ -1077,12 +1076,12 @@ async fn test_validator_set() {
         validator_config.consensus_public_key
     );
     assert_eq!(
-        validator_info.validator_network_address,
-        validator_config.validator_network_address
+        validator_info.validator_network_addresses,
+        validator_config.validator_network_addresses
     );
     assert_eq!(
-        validator_info.fullnode_network_address,
-        validator_config.fullnode_network_address
+        validator_info.fullnode_network_addresses,
+        validator_config.fullnode_network_addresses
     );
 
     // Fetch the entire validator set and check this account is included
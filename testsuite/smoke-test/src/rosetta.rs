@@ -35,6 +35,9 @@ pub async fn setup_test(num_nodes: usize) -> (LocalSwarm, CliTestFramework, Rose
         tls_cert_path: None,
         tls_key_path: None,
         content_length_limit: None,
+        max_view_gas_amount: None,
+        max_submit_transaction_batch_size: None,
+        auth: None,
     };
 
     // Start the server
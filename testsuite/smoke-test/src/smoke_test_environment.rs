@@ -12,10 +12,34 @@ pub async fn new_local_swarm(
 ) -> LocalSwarm {
     static FACTORY: Lazy<LocalFactory> = Lazy::new(|| LocalFactory::from_workspace().unwrap());
 
+    launch_swarm(&FACTORY, num_validators, genesis_modules).await
+}
+
+// Validators are connected through proxies, allowing network faults to be injected
+pub async fn new_local_swarm_with_network_chaos(num_validators: usize) -> LocalSwarm {
+    static FACTORY: Lazy<LocalFactory> = Lazy::new(|| {
+        LocalFactory::from_workspace()
+            .unwrap()
+            .with_network_chaos(true)
+    });
+
+    launch_swarm(
+        &FACTORY,
+        num_validators,
+        Some(cached_framework_packages::module_blobs().to_vec()),
+    )
+    .await
+}
+
+async fn launch_swarm(
+    factory: &LocalFactory,
+    num_validators: usize,
+    genesis_modules: Option<Vec<Vec<u8>>>,
+) -> LocalSwarm {
     ::aptos_logger::Logger::new().init();
-    let version = FACTORY.versions().max().unwrap();
+    let version = factory.versions().max().unwrap();
 
-    FACTORY
+    factory
         .new_swarm_with_version(
             OsRng,
             NonZeroUsize::new(num_validators).unwrap(),
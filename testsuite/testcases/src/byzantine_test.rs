@@ -0,0 +1,96 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::generate_traffic;
+use anyhow::ensure;
+use aptos_config::config::ByzantineConfig;
+use forge::{NetworkContext, NetworkTest, NodeExt, Result, SwarmExt, Test};
+use std::{collections::HashMap, time::Instant};
+use tokio::{runtime::Runtime, time::Duration};
+
+/// Turns `num_byzantine` validators Byzantine, which must be at most the `f` validators the
+/// network tolerates, and checks that the honest validators keep committing transactions
+/// (liveness) without forking (safety). Requires a local swarm, whose validators are built with
+/// the failpoints feature that enables the Byzantine behaviors.
+pub struct ByzantineValidatorsTest {
+    pub num_byzantine: usize,
+}
+
+impl Test for ByzantineValidatorsTest {
+    fn name(&self) -> &'static str {
+        "byzantine-validators"
+    }
+}
+
+impl NetworkTest for ByzantineValidatorsTest {
+    fn run<'t>(&self, ctx: &mut NetworkContext<'t>) -> Result<()> {
+        let duration = Duration::from_secs(60);
+        let all_validators = ctx
+            .swarm()
+            .validators()
+            .map(|v| v.peer_id())
+            .collect::<Vec<_>>();
+        let max_byzantine = (all_validators.len() - 1) / 3;
+        ensure!(
+            self.num_byzantine <= max_byzantine,
+            "{} validators tolerate at most {} Byzantine validators, got {}",
+            all_validators.len(),
+            max_byzantine,
+            self.num_byzantine
+        );
+        let (byzantine_nodes, honest_nodes) = all_validators.split_at(self.num_byzantine);
+
+        let runtime = Runtime::new()?;
+        for id in byzantine_nodes {
+            let mut config = ctx.swarm().validator(*id).unwrap().config().clone();
+            config.consensus.byzantine = ByzantineConfig {
+                equivocate_proposals: true,
+                double_vote: true,
+                withhold_votes: false,
+                stale_sync_info: true,
+                forged_sync_info: true,
+            };
+            ctx.swarm().update_validator_config(*id, config)?;
+            let node = ctx.swarm().validator_mut(*id).unwrap();
+            println!("Node {} is going to turn Byzantine", node.name());
+            runtime.block_on(node.restart())?;
+        }
+
+        // Liveness: the honest validators keep committing transactions
+        let txn_stat = generate_traffic(ctx, honest_nodes, duration, 1, None)?;
+        ensure!(
+            txn_stat.committed > 0,
+            "No transaction was committed with {} Byzantine validators",
+            self.num_byzantine
+        );
+        ctx.report
+            .report_txn_stats(self.name().to_string(), txn_stat, duration);
+
+        // Safety: all validators agree on the ledger
+        runtime.block_on(
+            ctx.swarm()
+                .wait_for_all_nodes_to_catchup(Instant::now() + Duration::from_secs(60)),
+        )?;
+        ctx.swarm().fork_check()?;
+
+        // The honest validators noticed the misbehavior
+        if !byzantine_nodes.is_empty() {
+            let mut detected = 0;
+            for id in honest_nodes {
+                let node = ctx.swarm().validator(*id).unwrap();
+                detected += runtime
+                    .block_on(node.get_metric_with_fields(
+                        "aptos_consensus_misbehavior_detected_count",
+                        HashMap::new(),
+                    ))?
+                    .unwrap_or(0);
+            }
+            ensure!(
+                detected > 0,
+                "The honest validators did not detect any misbehavior"
+            );
+        }
+
+        Ok(())
+    }
+}
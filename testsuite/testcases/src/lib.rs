@@ -1,9 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod byzantine_test;
 pub mod compatibility_test;
 pub mod fixed_tps_test;
 pub mod gas_price_test;
+pub mod network_partition_test;
 pub mod partial_nodes_down_test;
 pub mod performance_test;
 pub mod reconfiguration_test;
@@ -0,0 +1,57 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::generate_traffic;
+use forge::{
+    NetworkContext, NetworkTest, Result, SwarmChaos, SwarmExt, SwarmNetworkPartition, Test,
+};
+use std::time::Instant;
+use tokio::{runtime::Runtime, time::Duration};
+
+/// Partitions a minority of the validators away from the rest. The majority keeps committing
+/// transactions, and the minority catches up once the partition heals.
+pub struct NetworkPartitionTest;
+
+impl Test for NetworkPartitionTest {
+    fn name(&self) -> &'static str {
+        "network-partition"
+    }
+}
+
+impl NetworkTest for NetworkPartitionTest {
+    fn run<'t>(&self, ctx: &mut NetworkContext<'t>) -> Result<()> {
+        let duration = Duration::from_secs(60);
+        let all_validators = ctx
+            .swarm()
+            .validators()
+            .map(|v| v.peer_id())
+            .collect::<Vec<_>>();
+        let mut minority = all_validators.clone();
+        let majority = minority.split_off((all_validators.len() - 1) / 3);
+
+        let partition = SwarmChaos::Partition(SwarmNetworkPartition {
+            groups: vec![minority.clone(), majority.clone()],
+        });
+        println!(
+            "Partitioning {} of {} validators",
+            minority.len(),
+            all_validators.len()
+        );
+        ctx.swarm().inject_chaos(partition.clone())?;
+
+        // Generate some traffic
+        let txn_stat = generate_traffic(ctx, &majority, duration, 1, None)?;
+        ctx.report
+            .report_txn_stats(self.name().to_string(), txn_stat, duration);
+
+        println!("Healing the partition");
+        ctx.swarm().remove_chaos(partition)?;
+        let runtime = Runtime::new()?;
+        runtime.block_on(
+            ctx.swarm()
+                .wait_for_all_nodes_to_catchup(Instant::now() + Duration::from_secs(60)),
+        )?;
+
+        Ok(())
+    }
+}
@@ -6,9 +6,11 @@ pub mod chain_id;
 pub mod coin_store;
 pub mod core_account;
 pub mod crsn;
+pub mod table_info;
 
 pub use chain_account_info::*;
 pub use chain_id::*;
 pub use coin_store::*;
 pub use core_account::*;
 pub use crsn::*;
+pub use table_info::*;
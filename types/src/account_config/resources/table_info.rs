@@ -0,0 +1,61 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state_store::state_key::StateKey;
+use anyhow::Result;
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::TypeTag,
+    move_resource::{MoveResource, MoveStructType},
+    parser::parse_type_tag,
+};
+use serde::{Deserialize, Serialize};
+
+/// The table of the `TableInfo`s by table handle, published under the framework account at
+/// genesis
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TableInfosResource {
+    infos: TableResource,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TableResource {
+    handle: u128,
+    length: u64,
+}
+
+impl TableInfosResource {
+    /// The key of the info of the table `handle`, which is only set when the table was created
+    /// after genesis
+    pub fn info_key(&self, handle: u128) -> Result<StateKey> {
+        Ok(StateKey::table_item(
+            self.infos.handle,
+            bcs::to_bytes(&handle)?,
+        ))
+    }
+}
+
+impl MoveStructType for TableInfosResource {
+    const MODULE_NAME: &'static IdentStr = ident_str!("Table");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("TableInfos");
+}
+
+impl MoveResource for TableInfosResource {}
+
+/// The types of the keys and values of a table, recorded when the table is created
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TableInfo {
+    key_type: Vec<u8>,
+    value_type: Vec<u8>,
+}
+
+impl TableInfo {
+    pub fn key_type(&self) -> Result<TypeTag> {
+        parse_type_tag(std::str::from_utf8(&self.key_type)?)
+    }
+
+    pub fn value_type(&self) -> Result<TypeTag> {
+        parse_type_tag(std::str::from_utf8(&self.value_type)?)
+    }
+}
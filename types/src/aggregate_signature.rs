@@ -0,0 +1,94 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_address::AccountAddress;
+use aptos_bitvec::BitVec;
+use aptos_crypto::bls12381;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// This struct represents a BLS multi-signature or aggregated signature:
+/// it stores a bit mask representing the set of validators participating in the signing process
+/// and the multi-signature/aggregated signature itself, which was aggregated from these
+/// validators' partial BLS signatures.
+///
+/// The position of a validator in the bit mask is the index of its account address in the
+/// ordered list of validators of the `ValidatorVerifier` of the corresponding epoch.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AggregateSignature {
+    validator_bitmask: BitVec,
+    sig: Option<bls12381::Signature>,
+}
+
+impl AggregateSignature {
+    pub fn new(
+        validator_bitmask: BitVec,
+        aggregated_signature: Option<bls12381::Signature>,
+    ) -> Self {
+        Self {
+            validator_bitmask,
+            sig: aggregated_signature,
+        }
+    }
+
+    /// An aggregate signature without any signers, e.g. for the genesis ledger info.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn get_voters_bitvec(&self) -> &BitVec {
+        &self.validator_bitmask
+    }
+
+    /// Returns the account addresses of the signers, given the ordered list of validators of the
+    /// epoch. Positions outside of the list are ignored; the verifier rejects such bit masks.
+    pub fn get_voter_addresses(
+        &self,
+        validator_addresses: &[AccountAddress],
+    ) -> Vec<AccountAddress> {
+        self.validator_bitmask
+            .iter_ones()
+            .filter_map(|index| validator_addresses.get(index as usize).copied())
+            .collect()
+    }
+
+    pub fn get_num_voters(&self) -> usize {
+        self.validator_bitmask.count_ones() as usize
+    }
+
+    pub fn sig(&self) -> &Option<bls12381::Signature> {
+        &self.sig
+    }
+}
+
+/// Partial BLS signatures collected from individual validators, prior to aggregation.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PartialSignatures {
+    signatures: BTreeMap<AccountAddress, bls12381::Signature>,
+}
+
+impl PartialSignatures {
+    pub fn new(signatures: BTreeMap<AccountAddress, bls12381::Signature>) -> Self {
+        Self { signatures }
+    }
+
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    pub fn add_signature(&mut self, validator: AccountAddress, signature: bls12381::Signature) {
+        self.signatures.entry(validator).or_insert(signature);
+    }
+
+    pub fn remove_signature(&mut self, validator: AccountAddress) {
+        self.signatures.remove(&validator);
+    }
+
+    pub fn signatures(&self) -> &BTreeMap<AccountAddress, bls12381::Signature> {
+        &self.signatures
+    }
+}
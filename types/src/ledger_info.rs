@@ -3,6 +3,7 @@
 
 use crate::{
     account_address::AccountAddress,
+    aggregate_signature::AggregateSignature,
     block_info::{BlockInfo, Round},
     epoch_state::EpochState,
     on_chain_config::ValidatorSet,
@@ -17,7 +18,6 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter},
-    ops::{Deref, DerefMut},
 };
 
 /// This structure serves a dual purpose.
@@ -138,12 +138,14 @@ impl LedgerInfo {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum LedgerInfoWithSignatures {
     V0(LedgerInfoWithV0),
+    V1(LedgerInfoWithV1),
 }
 
 impl Display for LedgerInfoWithSignatures {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             LedgerInfoWithSignatures::V0(ledger) => write!(f, "{}", ledger),
+            LedgerInfoWithSignatures::V1(ledger) => write!(f, "{}", ledger),
         }
     }
 }
@@ -157,30 +159,65 @@ impl LedgerInfoWithSignatures {
         LedgerInfoWithSignatures::V0(LedgerInfoWithV0::new(ledger_info, signatures))
     }
 
+    /// Creates a ledger info signed by a single aggregated BLS12-381 signature.
+    pub fn new_aggregated(ledger_info: LedgerInfo, signatures: AggregateSignature) -> Self {
+        LedgerInfoWithSignatures::V1(LedgerInfoWithV1::new(ledger_info, signatures))
+    }
+
     pub fn genesis(genesis_state_root_hash: HashValue, validator_set: ValidatorSet) -> Self {
         LedgerInfoWithSignatures::V0(LedgerInfoWithV0::genesis(
             genesis_state_root_hash,
             validator_set,
         ))
     }
-}
 
-// Temporary hack to avoid massive changes, it won't work when new variant comes and needs proper
-// dispatch at that time.
-impl Deref for LedgerInfoWithSignatures {
-    type Target = LedgerInfoWithV0;
+    pub fn ledger_info(&self) -> &LedgerInfo {
+        match self {
+            LedgerInfoWithSignatures::V0(ledger) => ledger.ledger_info(),
+            LedgerInfoWithSignatures::V1(ledger) => ledger.ledger_info(),
+        }
+    }
 
-    fn deref(&self) -> &LedgerInfoWithV0 {
-        match &self {
-            LedgerInfoWithSignatures::V0(ledger) => ledger,
+    pub fn commit_info(&self) -> &BlockInfo {
+        self.ledger_info().commit_info()
+    }
+
+    /// Returns the addresses of the validators that signed the ledger info. The aggregated
+    /// signature of `V1` only records the signers' positions in `validator_addresses`, the
+    /// ordered list of validators of the epoch (see `ValidatorVerifier`).
+    pub fn get_voters(&self, validator_addresses: &[AccountAddress]) -> Vec<AccountAddress> {
+        match self {
+            LedgerInfoWithSignatures::V0(ledger) => ledger.signatures().keys().copied().collect(),
+            LedgerInfoWithSignatures::V1(ledger) => {
+                ledger.signatures().get_voter_addresses(validator_addresses)
+            }
+        }
+    }
+
+    pub fn get_num_voters(&self) -> usize {
+        match self {
+            LedgerInfoWithSignatures::V0(ledger) => ledger.signatures().len(),
+            LedgerInfoWithSignatures::V1(ledger) => ledger.signatures().get_num_voters(),
         }
     }
-}
 
-impl DerefMut for LedgerInfoWithSignatures {
-    fn deref_mut(&mut self) -> &mut LedgerInfoWithV0 {
+    pub fn verify_signatures(
+        &self,
+        validator: &ValidatorVerifier,
+    ) -> ::std::result::Result<(), VerifyError> {
         match self {
-            LedgerInfoWithSignatures::V0(ref mut ledger) => ledger,
+            LedgerInfoWithSignatures::V0(ledger) => ledger.verify_signatures(validator),
+            LedgerInfoWithSignatures::V1(ledger) => ledger.verify_signatures(validator),
+        }
+    }
+
+    pub fn check_voting_power(
+        &self,
+        validator: &ValidatorVerifier,
+    ) -> ::std::result::Result<(), VerifyError> {
+        match self {
+            LedgerInfoWithSignatures::V0(ledger) => ledger.check_voting_power(validator),
+            LedgerInfoWithSignatures::V1(ledger) => ledger.check_voting_power(validator),
         }
     }
 }
@@ -264,6 +301,61 @@ impl LedgerInfoWithV0 {
     }
 }
 
+/// A ledger info signed by a quorum of validators with a single aggregated BLS12-381 signature
+/// instead of one Ed25519 signature per validator.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LedgerInfoWithV1 {
+    ledger_info: LedgerInfo,
+    /// The signers are identified by their position in the ordered list of validators, in order
+    /// to verify the signature one needs the validator set of the given epoch.
+    signatures: AggregateSignature,
+}
+
+impl Display for LedgerInfoWithV1 {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.ledger_info)
+    }
+}
+
+impl LedgerInfoWithV1 {
+    pub fn new(ledger_info: LedgerInfo, signatures: AggregateSignature) -> Self {
+        LedgerInfoWithV1 {
+            ledger_info,
+            signatures,
+        }
+    }
+
+    pub fn ledger_info(&self) -> &LedgerInfo {
+        &self.ledger_info
+    }
+
+    pub fn commit_info(&self) -> &BlockInfo {
+        self.ledger_info.commit_info()
+    }
+
+    pub fn signatures(&self) -> &AggregateSignature {
+        &self.signatures
+    }
+
+    pub fn verify_signatures(
+        &self,
+        validator: &ValidatorVerifier,
+    ) -> ::std::result::Result<(), VerifyError> {
+        validator.verify_aggregate_signatures(self.ledger_info(), self.signatures())
+    }
+
+    pub fn check_voting_power(
+        &self,
+        validator: &ValidatorVerifier,
+    ) -> ::std::result::Result<(), VerifyError> {
+        validator.check_voting_power(
+            self.signatures
+                .get_voter_addresses(&validator.get_ordered_account_addresses())
+                .iter(),
+        )
+    }
+}
+
 //
 // Arbitrary implementation of LedgerInfoWithV0 (for fuzzing)
 //
@@ -339,4 +431,53 @@ mod tests {
             ledger_info_with_signatures_reversed_bytes
         );
     }
+
+    #[test]
+    fn test_aggregated_ledger_info() {
+        let (validator_signers, validator_verifier) =
+            crate::validator_verifier::random_validator_verifier_with_bls_keys(4, None);
+        let ledger_info = LedgerInfo::new(BlockInfo::empty(), HashValue::random());
+
+        let mut partial_signatures = crate::aggregate_signature::PartialSignatures::empty();
+        for validator in validator_signers.iter().take(3) {
+            partial_signatures.add_signature(
+                validator.author(),
+                validator.sign_bls(&ledger_info).unwrap(),
+            );
+        }
+        let aggregated_signature = validator_verifier
+            .aggregate_signatures(&partial_signatures)
+            .unwrap();
+        let ledger_info_with_signatures =
+            LedgerInfoWithSignatures::new_aggregated(ledger_info, aggregated_signature);
+        assert_eq!(ledger_info_with_signatures.get_num_voters(), 3);
+        assert_eq!(
+            ledger_info_with_signatures
+                .get_voters(&validator_verifier.get_ordered_account_addresses())
+                .len(),
+            3
+        );
+        assert_eq!(
+            ledger_info_with_signatures.verify_signatures(&validator_verifier),
+            Ok(())
+        );
+
+        let bytes = bcs::to_bytes(&ledger_info_with_signatures).unwrap();
+        let deserialized: LedgerInfoWithSignatures = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, ledger_info_with_signatures);
+
+        // The aggregated signature is bound to the ledger info it was created for.
+        let other_ledger_info = LedgerInfo::new(BlockInfo::empty(), HashValue::random());
+        let forged = LedgerInfoWithSignatures::new_aggregated(
+            other_ledger_info,
+            match ledger_info_with_signatures {
+                LedgerInfoWithSignatures::V1(ledger) => ledger.signatures().clone(),
+                LedgerInfoWithSignatures::V0(_) => unreachable!(),
+            },
+        );
+        assert_eq!(
+            forged.verify_signatures(&validator_verifier),
+            Err(VerifyError::InvalidSignature)
+        );
+    }
 }
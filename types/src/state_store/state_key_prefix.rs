@@ -17,6 +17,11 @@ impl StateKeyPrefix {
         Self { tag, bytes }
     }
 
+    /// The prefix of the keys of all the items of the table `handle`
+    pub fn table_items(handle: u128) -> Self {
+        Self::new(StateKeyTag::TableItem, handle.to_be_bytes().to_vec())
+    }
+
     /// Serializes to bytes for physical storage.
     pub fn encode(&self) -> anyhow::Result<Vec<u8>> {
         let mut out = vec![self.tag.clone() as u8];
@@ -69,4 +74,20 @@ mod tests {
         assert!(!account1_key_prefx.is_prefix(&key2).unwrap());
         assert!(!account2_key_prefx.is_prefix(&key1).unwrap());
     }
+
+    #[test]
+    fn test_table_items_key_prefix() {
+        let key1 = StateKey::table_item(1, b"state_key".to_vec());
+        let key2 = StateKey::table_item(2, b"state_key".to_vec());
+
+        let table1_key_prefix = StateKeyPrefix::table_items(1);
+
+        assert!(table1_key_prefix.is_prefix(&key1).unwrap());
+        assert!(!table1_key_prefix.is_prefix(&key2).unwrap());
+        assert!(
+            !StateKeyPrefix::from(AccountAddress::new([1u8; AccountAddress::LENGTH]))
+                .is_prefix(&key1)
+                .unwrap()
+        );
+    }
 }
@@ -1,7 +1,13 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{proof::SparseMerkleRangeProof, state_store::state_key::StateKey};
+use crate::{
+    ledger_info::LedgerInfo,
+    proof::{SparseMerkleProof, SparseMerkleRangeProof, TransactionInfoWithProof},
+    state_store::state_key::StateKey,
+    transaction::Version,
+};
+use anyhow::{ensure, Result};
 use aptos_crypto::{
     hash::{CryptoHash, CryptoHasher, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -88,6 +94,62 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// A state value at a given version, along with the proofs needed to verify it against a ledger
+/// info: the sparse merkle proof of the value (or of its absence) in the state at `version`, and
+/// the proof of the transaction info carrying the root hash of that state.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueWithProof {
+    pub version: Version,
+    pub state_value: Option<StateValue>,
+    pub proof: SparseMerkleProof,
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+}
+
+impl StateValueWithProof {
+    pub fn new(
+        version: Version,
+        state_value: Option<StateValue>,
+        proof: SparseMerkleProof,
+        transaction_info_with_proof: TransactionInfoWithProof,
+    ) -> Self {
+        Self {
+            version,
+            state_value,
+            proof,
+            transaction_info_with_proof,
+        }
+    }
+
+    /// Verifies that the state value of `state_key` at `version` is `self.state_value` in the
+    /// ledger represented by `ledger_info`.
+    pub fn verify(
+        &self,
+        ledger_info: &LedgerInfo,
+        version: Version,
+        state_key: &StateKey,
+    ) -> Result<()> {
+        ensure!(
+            self.version == version,
+            "Version ({}) is not expected ({}).",
+            self.version,
+            version,
+        );
+        self.transaction_info_with_proof
+            .verify(ledger_info, version)?;
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()?;
+        // The hash carried by the state value isn't trusted, it's recomputed from the bytes.
+        let state_value_hash = self
+            .state_value
+            .as_ref()
+            .map(|state_value| StateValue::new(state_value.maybe_bytes.clone()).hash());
+        self.proof
+            .verify_by_hash(state_root_hash, state_key.hash(), state_value_hash)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::state_store::state_value::StateValue;
@@ -695,12 +695,6 @@ impl TransactionWithProof {
     ) -> Result<()> {
         let signed_transaction = self.transaction.as_signed_user_txn()?;
 
-        ensure!(
-            self.version == version,
-            "Version ({}) is not expected ({}).",
-            self.version,
-            version,
-        );
         ensure!(
             signed_transaction.sender() == sender,
             "Sender ({}) not expected ({}).",
@@ -714,6 +708,19 @@ impl TransactionWithProof {
             sequence_number,
         );
 
+        self.verify(ledger_info, version)
+    }
+
+    /// Verifies that the transaction, and its events if present, exist in the ledger represented
+    /// by `ledger_info` at `version`.
+    pub fn verify(&self, ledger_info: &LedgerInfo, version: Version) -> Result<()> {
+        ensure!(
+            self.version == version,
+            "Version ({}) is not expected ({}).",
+            self.version,
+            version,
+        );
+
         let txn_hash = self.transaction.hash();
         ensure!(
             txn_hash == self.proof.transaction_info().transaction_hash(),
@@ -456,7 +456,10 @@ proptest! {
 
         let good_li = latest_li.ledger_info();
         let change_proof = EpochChangeProof::new(lis_with_sigs, false /* more */);
-        let sigs = latest_li.signatures();
+        let sigs = match &latest_li {
+            LedgerInfoWithSignatures::V0(ledger) => ledger.signatures(),
+            LedgerInfoWithSignatures::V1(_) => unreachable!("arb_update_proof only signs with Ed25519"),
+        };
 
         // Verifying latest ledger infos with mismatched data and signatures should fail
         let bad_li_1 = LedgerInfoWithSignatures::new(
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network_address::NetworkAddress;
-use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_crypto::{bls12381, ed25519::Ed25519PublicKey, ValidCryptoMaterial};
 use move_deps::move_core_types::{
     ident_str,
     identifier::IdentStr,
@@ -11,6 +11,7 @@ use move_deps::move_core_types::{
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 impl MoveStructType for ValidatorConfig {
     const MODULE_NAME: &'static IdentStr = ident_str!("Stake");
@@ -39,6 +40,11 @@ pub struct ValidatorConfig {
     pub validator_network_addresses: Vec<u8>,
     /// This is an bcs serialized Vec<NetworkAddress>
     pub fullnode_network_addresses: Vec<u8>,
+    /// The BLS12-381 consensus public key, empty if the validator did not register one
+    pub consensus_bls_public_key: Vec<u8>,
+    /// The proof-of-possession of `consensus_bls_public_key`. The framework can't check it, so
+    /// it's only verified when the key is used (see `bls_public_key`).
+    pub consensus_bls_proof_of_possession: Vec<u8>,
 }
 
 impl ValidatorConfig {
@@ -51,9 +57,37 @@ impl ValidatorConfig {
             consensus_public_key,
             validator_network_addresses,
             fullnode_network_addresses,
+            consensus_bls_public_key: vec![],
+            consensus_bls_proof_of_possession: vec![],
         }
     }
 
+    pub fn with_bls_public_key(
+        mut self,
+        bls_public_key: &bls12381::PublicKey,
+        proof_of_possession: &bls12381::ProofOfPossession,
+    ) -> Self {
+        self.consensus_bls_public_key = bls_public_key.to_bytes();
+        self.consensus_bls_proof_of_possession = proof_of_possession.to_bytes();
+        self
+    }
+
+    /// Returns the BLS12-381 consensus public key, if the validator registered one with a valid
+    /// proof-of-possession.
+    pub fn bls_public_key(&self) -> Option<bls12381::PublicKey> {
+        if self.consensus_bls_public_key.is_empty() {
+            return None;
+        }
+        let public_key =
+            bls12381::PublicKey::try_from(self.consensus_bls_public_key.as_slice()).ok()?;
+        let proof_of_possession = bls12381::ProofOfPossession::try_from(
+            self.consensus_bls_proof_of_possession.as_slice(),
+        )
+        .ok()?;
+        proof_of_possession.verify(&public_key).ok()?;
+        Some(public_key)
+    }
+
     pub fn fullnode_network_addresses(&self) -> Result<Vec<NetworkAddress>, bcs::Error> {
         bcs::from_bytes(&self.fullnode_network_addresses)
     }
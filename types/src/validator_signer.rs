@@ -3,6 +3,7 @@
 
 use crate::account_address::AccountAddress;
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     test_utils::TEST_SEED,
@@ -10,7 +11,7 @@ use aptos_crypto::{
 };
 use rand::{rngs::StdRng, SeedableRng};
 use serde::ser::Serialize;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::Arc};
 
 /// ValidatorSigner associates an author with public and private keys with helpers for signing and
 /// validating. This struct can be used for all signing operations including block and network
@@ -20,6 +21,7 @@ use std::convert::TryFrom;
 pub struct ValidatorSigner {
     author: AccountAddress,
     private_key: Ed25519PrivateKey,
+    bls_private_key: Option<Arc<bls12381::PrivateKey>>,
 }
 
 impl ValidatorSigner {
@@ -27,14 +29,29 @@ impl ValidatorSigner {
         ValidatorSigner {
             author,
             private_key,
+            bls_private_key: None,
         }
     }
 
+    /// Attaches a BLS12-381 consensus key, whose signature shares can be aggregated into a
+    /// single signature for quorum certificates and ledger infos.
+    pub fn with_bls_private_key(mut self, bls_private_key: bls12381::PrivateKey) -> Self {
+        self.bls_private_key = Some(Arc::new(bls_private_key));
+        self
+    }
+
     /// Constructs a signature for `message` using `private_key`.
     pub fn sign<T: Serialize + CryptoHash>(&self, message: &T) -> Ed25519Signature {
         self.private_key.sign(message)
     }
 
+    /// Constructs a BLS12-381 signature share for `message`, if this signer has a BLS12-381 key.
+    pub fn sign_bls<T: Serialize + CryptoHash>(&self, message: &T) -> Option<bls12381::Signature> {
+        self.bls_private_key
+            .as_ref()
+            .map(|private_key| private_key.sign(message))
+    }
+
     /// Returns the author associated with this signer.
     pub fn author(&self) -> AccountAddress {
         self.author
@@ -45,6 +62,13 @@ impl ValidatorSigner {
         self.private_key.public_key()
     }
 
+    /// Returns the BLS12-381 public key associated with this signer, if any.
+    pub fn bls_public_key(&self) -> Option<bls12381::PublicKey> {
+        self.bls_private_key
+            .as_ref()
+            .map(|private_key| bls12381::PublicKey::from(private_key.as_ref()))
+    }
+
     /// Returns the private key associated with this signer. Only available for testing purposes.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn private_key(&self) -> &Ed25519PrivateKey {
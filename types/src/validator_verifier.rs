@@ -1,14 +1,20 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, on_chain_config::ValidatorSet};
+use crate::{
+    account_address::AccountAddress,
+    aggregate_signature::{AggregateSignature, PartialSignatures},
+    on_chain_config::ValidatorSet,
+};
+use aptos_bitvec::BitVec;
 use aptos_crypto::{
+    bls12381,
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     Signature, VerifyingKey,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt};
+use std::{collections::BTreeMap, convert::TryFrom, fmt};
 use thiserror::Error;
 
 #[cfg(any(test, feature = "fuzzing"))]
@@ -45,13 +51,30 @@ pub enum VerifyError {
     InvalidSignature,
     #[error("Inconsistent Block Info")]
     InconsistentBlockInfo,
+    #[error("Invalid bitvec from the multi-signature")]
+    /// The bit mask of an aggregate signature refers to validators outside of the validator set.
+    InvalidBitVec,
+    #[error("Failed to aggregate signatures")]
+    FailedToAggregateSignature,
+    #[error("Failed to aggregate public keys")]
+    FailedToAggregatePubKey,
+    #[error("Author has no BLS12-381 public key")]
+    /// The author is known, but did not register a BLS12-381 consensus key.
+    MissingBlsPublicKey,
 }
 
 /// Helper struct to manage validator information for validation
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(
+    from = "ValidatorConsensusInfoVersions",
+    into = "ValidatorConsensusInfoVersions"
+)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct ValidatorConsensusInfo {
     public_key: Ed25519PublicKey,
+    // Optional BLS12-381 consensus key, whose proof-of-possession has been verified.
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(value = "None"))]
+    bls_public_key: Option<bls12381::PublicKey>,
     voting_power: u64,
 }
 
@@ -59,9 +82,80 @@ impl ValidatorConsensusInfo {
     pub fn new(public_key: Ed25519PublicKey, voting_power: u64) -> Self {
         ValidatorConsensusInfo {
             public_key,
+            bls_public_key: None,
             voting_power,
         }
     }
+
+    /// Creates a validator info with a BLS12-381 consensus key. The key is only accepted with a
+    /// valid proof-of-possession, which protects signature aggregation from rogue-key attacks.
+    pub fn new_with_bls_public_key(
+        public_key: Ed25519PublicKey,
+        bls_public_key: bls12381::PublicKey,
+        proof_of_possession: &bls12381::ProofOfPossession,
+        voting_power: u64,
+    ) -> anyhow::Result<Self> {
+        proof_of_possession.verify(&bls_public_key)?;
+        Ok(ValidatorConsensusInfo {
+            public_key,
+            bls_public_key: Some(bls_public_key),
+            voting_power,
+        })
+    }
+}
+
+/// The serialized form of `ValidatorConsensusInfo`, which is part of every `EpochState`. Each
+/// set of consensus keys is a version of its own, so a validator without a BLS12-381 key is
+/// never read as one with a key (or the other way around), and new keys can be added as new
+/// versions.
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "ValidatorConsensusInfo")]
+pub enum ValidatorConsensusInfoVersions {
+    V0 {
+        public_key: Ed25519PublicKey,
+        voting_power: u64,
+    },
+    V1 {
+        public_key: Ed25519PublicKey,
+        bls_public_key: bls12381::PublicKey,
+        voting_power: u64,
+    },
+}
+
+impl From<ValidatorConsensusInfoVersions> for ValidatorConsensusInfo {
+    fn from(versions: ValidatorConsensusInfoVersions) -> Self {
+        match versions {
+            ValidatorConsensusInfoVersions::V0 {
+                public_key,
+                voting_power,
+            } => ValidatorConsensusInfo::new(public_key, voting_power),
+            ValidatorConsensusInfoVersions::V1 {
+                public_key,
+                bls_public_key,
+                voting_power,
+            } => ValidatorConsensusInfo {
+                public_key,
+                bls_public_key: Some(bls_public_key),
+                voting_power,
+            },
+        }
+    }
+}
+
+impl From<ValidatorConsensusInfo> for ValidatorConsensusInfoVersions {
+    fn from(info: ValidatorConsensusInfo) -> Self {
+        match info.bls_public_key {
+            None => ValidatorConsensusInfoVersions::V0 {
+                public_key: info.public_key,
+                voting_power: info.voting_power,
+            },
+            Some(bls_public_key) => ValidatorConsensusInfoVersions::V1 {
+                public_key: info.public_key,
+                bls_public_key,
+                voting_power: info.voting_power,
+            },
+        }
+    }
 }
 
 /// Supports validation of signatures for known authors with individual voting powers. This struct
@@ -204,6 +298,93 @@ impl ValidatorVerifier {
         Ok(())
     }
 
+    /// Verify the correctness of a BLS12-381 signature share of a message by a known author.
+    pub fn verify_bls<T: Serialize + CryptoHash>(
+        &self,
+        author: AccountAddress,
+        message: &T,
+        signature: &bls12381::Signature,
+    ) -> std::result::Result<(), VerifyError> {
+        match self.address_to_validator_info.get(&author) {
+            Some(ValidatorConsensusInfo {
+                bls_public_key: Some(public_key),
+                ..
+            }) => public_key
+                .verify_struct_signature(message, signature)
+                .map_err(|_| VerifyError::InvalidSignature),
+            Some(_) => Err(VerifyError::MissingBlsPublicKey),
+            None => Err(VerifyError::UnknownAuthor),
+        }
+    }
+
+    /// Aggregates the given BLS12-381 signature shares into a single signature. The signers are
+    /// recorded in a bit mask, indexed by their position in the ordered list of validators.
+    /// The shares are expected to have been verified individually (see `verify_bls`).
+    pub fn aggregate_signatures(
+        &self,
+        partial_signatures: &PartialSignatures,
+    ) -> std::result::Result<AggregateSignature, VerifyError> {
+        let signatures = partial_signatures.signatures();
+        if signatures
+            .keys()
+            .any(|author| !self.address_to_validator_info.contains_key(author))
+        {
+            return Err(VerifyError::UnknownAuthor);
+        }
+        let mut validator_bitmask = BitVec::default();
+        let mut shares = Vec::with_capacity(signatures.len());
+        for (index, address) in self.get_ordered_account_addresses_iter().enumerate() {
+            if let Some(signature) = signatures.get(&address) {
+                let index = u16::try_from(index).map_err(|_| VerifyError::InvalidBitVec)?;
+                validator_bitmask.set(index);
+                shares.push(signature.clone());
+            }
+        }
+        let aggregated_signature = bls12381::Signature::aggregate(shares)
+            .map_err(|_| VerifyError::FailedToAggregateSignature)?;
+        Ok(AggregateSignature::new(
+            validator_bitmask,
+            Some(aggregated_signature),
+        ))
+    }
+
+    /// Verifies an aggregated BLS12-381 signature: the signers in the bit mask must be known
+    /// validators with a BLS12-381 public key and hold at least quorum voting power.
+    pub fn verify_aggregate_signatures<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        aggregated_signature: &AggregateSignature,
+    ) -> std::result::Result<(), VerifyError> {
+        self.check_num_of_voters(aggregated_signature.get_voters_bitvec())?;
+        let authors =
+            aggregated_signature.get_voter_addresses(&self.get_ordered_account_addresses());
+        self.check_voting_power(authors.iter())?;
+
+        let public_keys = authors
+            .iter()
+            .map(|author| {
+                self.get_bls_public_key(author)
+                    .ok_or(VerifyError::MissingBlsPublicKey)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let aggregated_key = bls12381::PublicKey::aggregate(public_keys.iter().collect())
+            .map_err(|_| VerifyError::FailedToAggregatePubKey)?;
+        match aggregated_signature.sig() {
+            Some(signature) => aggregated_key
+                .verify_struct_signature(message, signature)
+                .map_err(|_| VerifyError::InvalidSignature),
+            None => Err(VerifyError::InvalidSignature),
+        }
+    }
+
+    /// Ensure the bit mask of an aggregate signature does not refer to non-existent validators.
+    fn check_num_of_voters(&self, bitvec: &BitVec) -> std::result::Result<(), VerifyError> {
+        match bitvec.last_set_bit() {
+            Some(last_bit) if last_bit as usize >= self.len() => Err(VerifyError::InvalidBitVec),
+            _ => Ok(()),
+        }
+    }
+
     /// Ensure there are not more than the maximum expected signatures (all possible signatures).
     fn check_num_of_signatures(
         &self,
@@ -250,6 +431,24 @@ impl ValidatorVerifier {
             .map(|validator_info| validator_info.public_key.clone())
     }
 
+    /// Returns the BLS12-381 public key for this address, if the validator registered one.
+    pub fn get_bls_public_key(&self, author: &AccountAddress) -> Option<bls12381::PublicKey> {
+        self.address_to_validator_info
+            .get(author)
+            .and_then(|validator_info| validator_info.bls_public_key.clone())
+    }
+
+    /// Returns true if the signatures of this validator set can be aggregated, i.e., every
+    /// validator has a BLS12-381 public key and fits into an aggregate signature's bit mask.
+    pub fn supports_aggregate_signatures(&self) -> bool {
+        !self.is_empty()
+            && self.len() <= u16::MAX as usize + 1
+            && self
+                .address_to_validator_info
+                .values()
+                .all(|validator_info| validator_info.bls_public_key.is_some())
+    }
+
     /// Returns the voting power for this address.
     pub fn get_voting_power(&self, author: &AccountAddress) -> Option<u64> {
         self.address_to_validator_info
@@ -264,6 +463,12 @@ impl ValidatorVerifier {
         self.address_to_validator_info.keys().copied()
     }
 
+    /// Returns an ordered list of account addresses, the positions in an aggregate signature's
+    /// bit mask refer to.
+    pub fn get_ordered_account_addresses(&self) -> Vec<AccountAddress> {
+        self.get_ordered_account_addresses_iter().collect()
+    }
+
     /// Returns the number of authors to be validated.
     pub fn len(&self) -> usize {
         self.address_to_validator_info.len()
@@ -307,10 +512,12 @@ impl From<&ValidatorSet> for ValidatorVerifier {
             |mut map, validator| {
                 map.insert(
                     *validator.account_address(),
-                    ValidatorConsensusInfo::new(
-                        validator.consensus_public_key().clone(),
-                        validator.consensus_voting_power(),
-                    ),
+                    ValidatorConsensusInfo {
+                        public_key: validator.consensus_public_key().clone(),
+                        // Keys without a valid proof-of-possession are ignored
+                        bls_public_key: validator.config().bls_public_key(),
+                        voting_power: validator.consensus_voting_power(),
+                    },
                 );
                 map
             },
@@ -375,11 +582,66 @@ pub fn random_validator_verifier(
     )
 }
 
+/// Same as `random_validator_verifier`, but every validator additionally holds a BLS12-381
+/// consensus key, so that the resulting verifier supports aggregate signatures.
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn random_validator_verifier_with_bls_keys(
+    count: usize,
+    custom_voting_power_quorum: Option<u64>,
+) -> (
+    Vec<crate::validator_signer::ValidatorSigner>,
+    ValidatorVerifier,
+) {
+    use aptos_crypto::Uniform;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut signers = Vec::new();
+    let mut account_address_to_validator_info = BTreeMap::new();
+    for i in 0..count {
+        // Unlike `[i as u8; 32]`, the seed is unique for validator sets larger than 256
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&(i as u64).to_le_bytes());
+        let mut rng = StdRng::from_seed(seed);
+        let bls_private_key = bls12381::PrivateKey::generate(&mut rng);
+        let bls_public_key = bls12381::PublicKey::from(&bls_private_key);
+        let proof_of_possession =
+            bls12381::ProofOfPossession::create_with_pubkey(&bls_private_key, &bls_public_key);
+        let random_signer = crate::validator_signer::ValidatorSigner::random(seed)
+            .with_bls_private_key(bls_private_key);
+        account_address_to_validator_info.insert(
+            random_signer.author(),
+            ValidatorConsensusInfo::new_with_bls_public_key(
+                random_signer.public_key(),
+                bls_public_key,
+                &proof_of_possession,
+                1,
+            )
+            .expect("Unable to create testing validator info"),
+        );
+        signers.push(random_signer);
+    }
+    (
+        signers,
+        match custom_voting_power_quorum {
+            Some(custom_voting_power_quorum) => ValidatorVerifier::new_with_quorum_voting_power(
+                account_address_to_validator_info,
+                custom_voting_power_quorum,
+            )
+            .expect("Unable to create testing validator verifier"),
+            None => ValidatorVerifier::new(account_address_to_validator_info),
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::validator_signer::ValidatorSigner;
-    use aptos_crypto::test_utils::{TestAptosCrypto, TEST_SEED};
+    use aptos_crypto::{
+        test_utils::{TestAptosCrypto, TEST_SEED},
+        Uniform,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
     use std::collections::BTreeMap;
 
     #[test]
@@ -656,4 +918,219 @@ mod tests {
             Err(VerifyError::UnknownAuthor)
         );
     }
+
+    #[test]
+    fn test_aggregate_signatures() {
+        let (validator_signers, validator_verifier) =
+            random_validator_verifier_with_bls_keys(4, None);
+        assert!(validator_verifier.supports_aggregate_signatures());
+        let dummy_struct = TestAptosCrypto("Hello, World".to_string());
+
+        // Signature shares of 3 out of 4 validators reach the quorum of 3.
+        let mut partial_signatures = PartialSignatures::empty();
+        for signer in validator_signers.iter().take(3) {
+            let signature = signer.sign_bls(&dummy_struct).unwrap();
+            assert_eq!(
+                validator_verifier.verify_bls(signer.author(), &dummy_struct, &signature),
+                Ok(())
+            );
+            partial_signatures.add_signature(signer.author(), signature);
+        }
+        let aggregated_signature = validator_verifier
+            .aggregate_signatures(&partial_signatures)
+            .unwrap();
+        assert_eq!(aggregated_signature.get_num_voters(), 3);
+        let mut voters = aggregated_signature
+            .get_voter_addresses(&validator_verifier.get_ordered_account_addresses());
+        let mut signers: Vec<_> = validator_signers
+            .iter()
+            .take(3)
+            .map(|signer| signer.author())
+            .collect();
+        voters.sort();
+        signers.sort();
+        assert_eq!(voters, signers);
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&dummy_struct, &aggregated_signature),
+            Ok(())
+        );
+
+        // The aggregated signature does not verify for a different message.
+        let other_struct = TestAptosCrypto("Goodbye, World".to_string());
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&other_struct, &aggregated_signature),
+            Err(VerifyError::InvalidSignature)
+        );
+
+        // Claiming an additional signer invalidates the signature.
+        let mut bitvec = aggregated_signature.get_voters_bitvec().clone();
+        for i in 0..4 {
+            bitvec.set(i);
+        }
+        let forged = AggregateSignature::new(bitvec, aggregated_signature.sig().clone());
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&dummy_struct, &forged),
+            Err(VerifyError::InvalidSignature)
+        );
+
+        // Signers outside of the validator set are rejected.
+        let mut bitvec = aggregated_signature.get_voters_bitvec().clone();
+        bitvec.set(4);
+        let forged = AggregateSignature::new(bitvec, aggregated_signature.sig().clone());
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&dummy_struct, &forged),
+            Err(VerifyError::InvalidBitVec)
+        );
+
+        // 2 signers do not reach the quorum.
+        partial_signatures.remove_signature(validator_signers[0].author());
+        let aggregated_signature = validator_verifier
+            .aggregate_signatures(&partial_signatures)
+            .unwrap();
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&dummy_struct, &aggregated_signature),
+            Err(VerifyError::TooLittleVotingPower {
+                voting_power: 2,
+                quorum_voting_power: 3
+            })
+        );
+
+        // Shares of unknown authors cannot be aggregated.
+        let unknown_signer = random_validator_verifier_with_bls_keys(5, None)
+            .0
+            .pop()
+            .unwrap();
+        partial_signatures.add_signature(
+            unknown_signer.author(),
+            unknown_signer.sign_bls(&dummy_struct).unwrap(),
+        );
+        assert_eq!(
+            validator_verifier.aggregate_signatures(&partial_signatures),
+            Err(VerifyError::UnknownAuthor)
+        );
+    }
+
+    #[test]
+    fn test_aggregate_signatures_of_large_validator_set() {
+        let (validator_signers, validator_verifier) =
+            random_validator_verifier_with_bls_keys(300, None);
+        assert!(validator_verifier.supports_aggregate_signatures());
+        let dummy_struct = TestAptosCrypto("Hello, World".to_string());
+
+        // Every validator signs, so positions beyond 255 are part of the bit mask
+        let mut partial_signatures = PartialSignatures::empty();
+        for signer in &validator_signers {
+            partial_signatures
+                .add_signature(signer.author(), signer.sign_bls(&dummy_struct).unwrap());
+        }
+        let aggregated_signature = validator_verifier
+            .aggregate_signatures(&partial_signatures)
+            .unwrap();
+        assert_eq!(aggregated_signature.get_num_voters(), 300);
+        assert_eq!(
+            aggregated_signature.get_voters_bitvec().last_set_bit(),
+            Some(299)
+        );
+        assert_eq!(
+            validator_verifier.verify_aggregate_signatures(&dummy_struct, &aggregated_signature),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_bls_keys_are_optional() {
+        let (validator_signers, validator_verifier) = random_validator_verifier(4, None, false);
+        assert!(!validator_verifier.supports_aggregate_signatures());
+        assert_eq!(
+            validator_verifier.get_bls_public_key(&validator_signers[0].author()),
+            None
+        );
+        assert!(validator_signers[0]
+            .sign_bls(&TestAptosCrypto("Hello, World".to_string()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_bls_public_key_requires_proof_of_possession() {
+        let signer = ValidatorSigner::random([0u8; 32]);
+        let mut rng = StdRng::from_seed(TEST_SEED);
+        let bls_private_key = bls12381::PrivateKey::generate(&mut rng);
+        let other_private_key = bls12381::PrivateKey::generate(&mut rng);
+        let proof_of_possession = bls12381::ProofOfPossession::create(&other_private_key);
+        assert!(ValidatorConsensusInfo::new_with_bls_public_key(
+            signer.public_key(),
+            bls12381::PublicKey::from(&bls_private_key),
+            &proof_of_possession,
+            1,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validator_consensus_info_versions() {
+        let (_, validator_verifier) = random_validator_verifier(1, None, false);
+        let (_, bls_validator_verifier) = random_validator_verifier_with_bls_keys(1, None);
+        for (verifier, version) in [(validator_verifier, 0), (bls_validator_verifier, 1)] {
+            let info = verifier.address_to_validator_info.values().next().unwrap();
+            let bytes = bcs::to_bytes(info).unwrap();
+            assert_eq!(bytes[0], version);
+            assert_eq!(
+                &bcs::from_bytes::<ValidatorConsensusInfo>(&bytes).unwrap(),
+                info
+            );
+            assert_eq!(
+                bcs::from_bytes::<ValidatorVerifier>(&bcs::to_bytes(&verifier).unwrap()).unwrap(),
+                verifier
+            );
+        }
+    }
+
+    #[test]
+    fn test_bls_public_keys_from_validator_set() {
+        use crate::{validator_config::ValidatorConfig, validator_info::ValidatorInfo};
+
+        let mut rng = StdRng::from_seed(TEST_SEED);
+        let bls_private_key = bls12381::PrivateKey::generate(&mut rng);
+        let bls_public_key = bls12381::PublicKey::from(&bls_private_key);
+        let signers: Vec<_> = (0..3).map(|i| ValidatorSigner::random([i; 32])).collect();
+        let config =
+            |signer: &ValidatorSigner| ValidatorConfig::new(signer.public_key(), vec![], vec![]);
+
+        // A registered key, a key with the proof-of-possession of another key and no key
+        let mut validator_infos = vec![
+            ValidatorInfo::new(
+                signers[0].author(),
+                1,
+                config(&signers[0]).with_bls_public_key(
+                    &bls_public_key,
+                    &bls12381::ProofOfPossession::create(&bls_private_key),
+                ),
+            ),
+            ValidatorInfo::new(
+                signers[1].author(),
+                1,
+                config(&signers[1]).with_bls_public_key(
+                    &bls_public_key,
+                    &bls12381::ProofOfPossession::create(&bls12381::PrivateKey::generate(&mut rng)),
+                ),
+            ),
+            ValidatorInfo::new(signers[2].author(), 1, config(&signers[2])),
+        ];
+        validator_infos.sort_by_key(|info| *info.account_address());
+        let validator_set = ValidatorSet::new(validator_infos);
+        let validator_verifier = ValidatorVerifier::from(&validator_set);
+        assert_eq!(
+            validator_verifier.get_bls_public_key(&signers[0].author()),
+            Some(bls_public_key)
+        );
+        assert_eq!(
+            validator_verifier.get_bls_public_key(&signers[1].author()),
+            None
+        );
+        assert_eq!(
+            validator_verifier.get_bls_public_key(&signers[2].author()),
+            None
+        );
+        assert!(!validator_verifier.supports_aggregate_signatures());
+    }
 }